@@ -1,3 +1,5 @@
+use futures::stream::TryStreamExt;
+use rtnetlink::new_connection;
 use socketcan::{
     CanFilter, CanFrame, CanSocket, EmbeddedFrame, Frame, Socket, SocketOptions, StandardId,
 };
@@ -11,6 +13,138 @@ use crate::francor::franklyboot::{
     Error,
 };
 
+// CAN Link Discovery -------------------------------------------------------------------------------
+
+/// Real-world state of a CAN link, as reported by the kernel over rtnetlink
+///
+/// Replaces the plain `/sys/class/net` name scan: a link can be listed but down, so reporting
+/// just its name produces a search that silently finds no devices. Surfacing `is_up` and
+/// `bitrate` lets the TUI offer to bring the link up / set a bitrate instead of failing quietly.
+#[derive(Debug, Clone)]
+pub struct CanLinkInfo {
+    /// Interface name, e.g. "can0" or "vcan0"
+    pub name: String,
+
+    /// Whether the link currently has `IFF_UP` set
+    pub is_up: bool,
+
+    /// Configured bitrate in bit/s, if the link exposes one (virtual CAN links do not)
+    pub bitrate: Option<u32>,
+
+    /// True for virtual CAN links (`vcan*`), which have no bitrate to configure
+    pub is_virtual: bool,
+}
+
+/// Enumerate every CAN/vCAN link and its real kernel-reported state
+///
+/// This queries rtnetlink directly instead of scanning `/sys/class/net` for names, so the
+/// result includes whether each link is up and, for real CAN hardware, its configured bitrate.
+pub fn discover_can_links() -> Result<Vec<CanLinkInfo>, Error> {
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| Error::Error(format!("Failed to start netlink runtime: {}", e)))?;
+
+    rt.block_on(async {
+        let (connection, handle, _) = new_connection()
+            .map_err(|e| Error::Error(format!("Failed to open netlink socket: {}", e)))?;
+        tokio::spawn(connection);
+
+        let mut links = Vec::new();
+        let mut link_stream = handle.link().get().execute();
+
+        while let Some(msg) = link_stream
+            .try_next()
+            .await
+            .map_err(|e| Error::Error(format!("Netlink link query failed: {}", e)))?
+        {
+            let name = msg
+                .attributes
+                .iter()
+                .find_map(|attr| match attr {
+                    rtnetlink::packet_route::link::LinkAttribute::IfName(name) => {
+                        Some(name.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            if !(name.starts_with("can") || name.starts_with("vcan")) {
+                continue;
+            }
+
+            let is_up = msg
+                .header
+                .flags
+                .contains(rtnetlink::packet_route::link::LinkFlags::Up);
+            let is_virtual = name.starts_with("vcan");
+            let bitrate = if is_virtual {
+                None
+            } else {
+                read_can_bitrate(&name)
+            };
+
+            links.push(CanLinkInfo {
+                name,
+                is_up,
+                bitrate,
+                is_virtual,
+            });
+        }
+
+        Ok(links)
+    })
+}
+
+/// Set a classic CAN link's bitrate and bring it up
+///
+/// This is the configuration step the TUI offers before `Searching` when a listed CAN link is
+/// down: without it, a down `can0` would simply produce an empty device list with no
+/// explanation. Virtual CAN links are not treated differently: the bitrate and up request is
+/// sent for them too.
+pub fn configure_can_link(name: &str, bitrate: u32) -> Result<(), Error> {
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| Error::Error(format!("Failed to start netlink runtime: {}", e)))?;
+
+    rt.block_on(async {
+        let (connection, handle, _) = new_connection()
+            .map_err(|e| Error::Error(format!("Failed to open netlink socket: {}", e)))?;
+        tokio::spawn(connection);
+
+        let link = handle
+            .link()
+            .get()
+            .match_name(name.to_string())
+            .execute()
+            .try_next()
+            .await
+            .map_err(|e| Error::Error(format!("Failed to look up \"{}\": {}", name, e)))?
+            .ok_or_else(|| Error::Error(format!("No such CAN link \"{}\"", name)))?;
+
+        handle
+            .link()
+            .set(link.header.index)
+            .arg(rtnetlink::packet_route::link::InfoData::CanBitRate(bitrate))
+            .up()
+            .execute()
+            .await
+            .map_err(|e| {
+                Error::Error(format!(
+                    "Failed to set {} to {} bit/s and bring it up: {}",
+                    name, bitrate, e
+                ))
+            })
+    })
+}
+
+/// Best-effort read of a classic CAN link's currently configured bitrate via sysfs
+///
+/// rtnetlink exposes the same value through `InfoData::CanBitTiming`, but parsing sysfs here
+/// avoids a second netlink round trip per link during discovery.
+fn read_can_bitrate(name: &str) -> Option<u32> {
+    std::fs::read_to_string(format!("/sys/class/net/{}/can_bittiming/bitrate", name))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
 // CAN Interface ----------------------------------------------------------------------------------
 
 pub const CAN_BASE_ID: u32 = 0x781;
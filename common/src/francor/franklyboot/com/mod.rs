@@ -1,9 +1,10 @@
 pub mod can;
 pub mod msg;
+pub mod net;
 pub mod serial;
 pub mod sim;
 
-use crate::francor::franklyboot::{com::msg::Msg, Error};
+use crate::francor::franklyboot::{com::msg::Msg, com::net::NetProtocol, Error};
 use std::collections::VecDeque;
 
 // ComConParams -----------------------------------------------------------------------------------
@@ -15,6 +16,8 @@ use std::collections::VecDeque;
 pub struct ComConnParams {
     name: Option<String>,
     baud_rate: Option<u32>,
+    net_port: Option<u16>,
+    net_protocol: Option<NetProtocol>,
 }
 
 impl Default for ComConnParams {
@@ -28,6 +31,8 @@ impl ComConnParams {
         ComConnParams {
             name: None,
             baud_rate: None,
+            net_port: None,
+            net_protocol: None,
         }
     }
 
@@ -47,6 +52,23 @@ impl ComConnParams {
         params.name = Some(name.to_owned());
         params
     }
+
+    /// Connection parameters for a classic CAN link that was just configured (bitrate set,
+    /// brought up) by the caller, e.g. via `can::configure_can_link`
+    pub fn for_can_conn_with_bitrate(name: &str, bitrate: u32) -> Self {
+        let mut params = ComConnParams::for_can_conn(name);
+        params.baud_rate = Some(bitrate);
+        params
+    }
+
+    /// Connection parameters for a `NetInterface` reached over TCP or UDP at `host:port`
+    pub fn for_net_conn(host: &str, port: u16, protocol: NetProtocol) -> Self {
+        let mut params = ComConnParams::new();
+        params.name = Some(host.to_owned());
+        params.net_port = Some(port);
+        params.net_protocol = Some(protocol);
+        params
+    }
 }
 
 // ComMode ----------------------------------------------------------------------------------------
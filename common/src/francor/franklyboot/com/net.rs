@@ -0,0 +1,252 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use crate::francor::franklyboot::{
+    com::{
+        msg::{Msg, RequestType},
+        ComConnParams, ComInterface, ComMode,
+    },
+    Error,
+};
+
+// Net Interface ------------------------------------------------------------------------------------
+
+pub const NET_RX_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Number of times a lost UDP datagram is resent before giving up
+const NET_UDP_MAX_RETRIES: u32 = 3;
+
+/// Transport selected for a `NetInterface` via `ComConnParams::for_net_conn`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum NetProtocol {
+    /// Reliable stream; frames are length-prefixed
+    Tcp,
+    /// Unreliable datagram; frames carry a sequence number and are retransmitted on timeout
+    Udp,
+}
+
+enum NetTransport {
+    Tcp(TcpStream),
+    Udp { socket: UdpSocket, seq: u32 },
+}
+
+///
+/// Network interface
+///
+/// This struct implements the communication interface for reaching a Frankly bootloader over a
+/// TCP or UDP socket, for setups where a gateway bridges FranklyBoot to IP and no direct serial or
+/// SocketCAN access is available. TCP frames are length-prefixed (`[len: u8][payload: 8 bytes]`)
+/// since a stream has no message boundaries of its own. UDP frames instead carry a monotonic
+/// sequence number (`[seq: u32 BE][payload: 8 bytes]`): a lost datagram is detected by a receive
+/// timeout and the last request is resent with the same sequence number, while a stale duplicate
+/// response (echoing a sequence number older than the one currently expected) is dropped.
+///
+/// Like `CANInterface`, a network link is treated as `is_network() == true` so the device search
+/// enumerates it the same way, but `scan_network()` simply pings the one configured endpoint
+/// rather than broadcasting to several nodes.
+///
+pub struct NetInterface {
+    /// Underlying socket, set once `open` succeeds
+    transport: Option<NetTransport>,
+
+    /// Timeout for receiving messages
+    timeout: Duration,
+
+    /// Raw frame bytes of the last message sent, kept around to resend on a UDP timeout
+    last_sent_frame: Option<Vec<u8>>,
+}
+
+impl NetInterface {
+    fn tcp_send(stream: &mut TcpStream, msg: &Msg) -> Result<Vec<u8>, Error> {
+        let payload = msg.to_raw_data_array();
+        let mut frame = Vec::with_capacity(1 + payload.len());
+        frame.push(payload.len() as u8);
+        frame.extend_from_slice(&payload);
+
+        stream
+            .write_all(&frame)
+            .map_err(|e| Error::Error(format!("Failed to write to network socket: {}", e)))?;
+
+        Ok(frame)
+    }
+
+    fn tcp_recv(stream: &mut TcpStream) -> Result<Msg, Error> {
+        let mut len_byte = [0u8; 1];
+        stream
+            .read_exact(&mut len_byte)
+            .map_err(|e| Error::Error(format!("Failed to read from network socket: {}", e)))?;
+
+        let mut payload = [0u8; 8];
+        stream
+            .read_exact(&mut payload[..len_byte[0] as usize])
+            .map_err(|e| Error::Error(format!("Failed to read from network socket: {}", e)))?;
+
+        Ok(Msg::from_raw_data_array(&payload))
+    }
+
+    fn udp_frame(seq: u32, msg: &Msg) -> Vec<u8> {
+        let payload = msg.to_raw_data_array();
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        frame.extend_from_slice(&seq.to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+}
+
+impl ComInterface for NetInterface {
+    fn create() -> Result<Self, Error> {
+        Ok(NetInterface {
+            transport: None,
+            timeout: NET_RX_TIMEOUT,
+            last_sent_frame: None,
+        })
+    }
+
+    fn open(&mut self, params: &ComConnParams) -> Result<(), Error> {
+        let host = params
+            .name
+            .clone()
+            .ok_or_else(|| Error::Error("Network host not set!".to_string()))?;
+        let port = params
+            .net_port
+            .ok_or_else(|| Error::Error("Network port not set!".to_string()))?;
+        let protocol = params
+            .net_protocol
+            .ok_or_else(|| Error::Error("Network protocol not set!".to_string()))?;
+
+        let addr = format!("{}:{}", host, port);
+
+        let transport = match protocol {
+            NetProtocol::Tcp => {
+                let stream = TcpStream::connect(&addr)
+                    .map_err(|e| Error::Error(format!("Failed to connect to {}: {}", addr, e)))?;
+                stream
+                    .set_read_timeout(Some(self.timeout))
+                    .map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))?;
+                stream
+                    .set_nodelay(true)
+                    .map_err(|e| Error::Error(format!("Failed to set TCP_NODELAY: {}", e)))?;
+                NetTransport::Tcp(stream)
+            }
+            NetProtocol::Udp => {
+                let peer_addr: SocketAddr = addr
+                    .to_socket_addrs()
+                    .map_err(|e| Error::Error(format!("Failed to resolve {}: {}", addr, e)))?
+                    .next()
+                    .ok_or_else(|| Error::Error(format!("Failed to resolve {}", addr)))?;
+
+                let socket = UdpSocket::bind("0.0.0.0:0")
+                    .map_err(|e| Error::Error(format!("Failed to bind UDP socket: {}", e)))?;
+                socket.connect(peer_addr).map_err(|e| {
+                    Error::Error(format!("Failed to connect to {}: {}", peer_addr, e))
+                })?;
+                socket
+                    .set_read_timeout(Some(self.timeout))
+                    .map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))?;
+                NetTransport::Udp { socket, seq: 0 }
+            }
+        };
+
+        self.transport = Some(transport);
+        Ok(())
+    }
+
+    fn is_network() -> bool {
+        true
+    }
+
+    fn scan_network(&mut self) -> Result<Vec<u8>, Error> {
+        self.set_mode(ComMode::Broadcast)?;
+
+        let ping_request = Msg::new_std_request(RequestType::Ping);
+        self.send(&ping_request)?;
+        let response = self.recv()?;
+
+        ping_request.is_response_ok(&response)?;
+        Ok(vec![0])
+    }
+
+    fn set_mode(&mut self, _mode: ComMode) -> Result<(), Error> {
+        // A net interface already addresses exactly one device via its socket's peer address
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        let result = match self.transport.as_ref() {
+            Some(NetTransport::Tcp(stream)) => stream.set_read_timeout(Some(timeout)),
+            Some(NetTransport::Udp { socket, .. }) => socket.set_read_timeout(Some(timeout)),
+            None => return Err(Error::Error("Network socket not open!".to_string())),
+        };
+        result.map_err(|e| Error::Error(format!("Failed to set timeout: {}", e)))?;
+
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn send(&mut self, msg: &Msg) -> Result<(), Error> {
+        match self.transport.as_mut() {
+            Some(NetTransport::Tcp(stream)) => {
+                let frame = Self::tcp_send(stream, msg)?;
+                self.last_sent_frame = Some(frame);
+                Ok(())
+            }
+            Some(NetTransport::Udp { socket, seq }) => {
+                let frame = Self::udp_frame(*seq, msg);
+                socket
+                    .send(&frame)
+                    .map_err(|e| Error::Error(format!("Failed to send UDP datagram: {}", e)))?;
+                self.last_sent_frame = Some(frame);
+                Ok(())
+            }
+            None => Err(Error::Error("Network socket not open!".to_string())),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Msg, Error> {
+        let last_sent_frame = self.last_sent_frame.clone();
+
+        match self.transport.as_mut() {
+            Some(NetTransport::Tcp(stream)) => Self::tcp_recv(stream),
+            Some(NetTransport::Udp { socket, seq }) => {
+                let expected_seq = *seq;
+                let mut buf = [0u8; 12];
+
+                for attempt in 0..=NET_UDP_MAX_RETRIES {
+                    match socket.recv(&mut buf) {
+                        Ok(n) if n == buf.len() => {
+                            let got_seq = u32::from_be_bytes(buf[0..4].try_into().unwrap());
+                            if got_seq == expected_seq {
+                                *seq = expected_seq.wrapping_add(1);
+                                let mut payload = [0u8; 8];
+                                payload.copy_from_slice(&buf[4..12]);
+                                return Ok(Msg::from_raw_data_array(&payload));
+                            }
+                            // Stale duplicate response from an earlier retransmit; keep waiting
+                        }
+                        Ok(_) => {
+                            // Truncated/malformed datagram; ignore and keep waiting
+                        }
+                        Err(_) if attempt == NET_UDP_MAX_RETRIES => {
+                            return Err(Error::ComNoResponse)
+                        }
+                        Err(_) => {
+                            if let Some(frame) = &last_sent_frame {
+                                socket.send(frame).map_err(|e| {
+                                    Error::Error(format!("Failed to resend UDP datagram: {}", e))
+                                })?;
+                            }
+                        }
+                    }
+                }
+
+                Err(Error::ComNoResponse)
+            }
+            None => Err(Error::Error("Network socket not open!".to_string())),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use frankly_fw_update_cli::francor::franklyboot::com::msg::Msg;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary byte slices, of any length, must only ever come back as a `ProtocolError` (too short,
+// unknown request, unknown result) and never panic - a corrupted or truncated frame on the wire
+// should degrade to a recoverable error, not abort the whole updater.
+fuzz_target!(|data: &[u8]| {
+    let _ = Msg::try_from_raw_data_array(data);
+});
@@ -0,0 +1,81 @@
+// Chip Database ------------------------------------------------------------------------------------
+
+///
+/// Chip Information
+///
+/// This structure describes a supported target chip: its flash/RAM geometry and the vendor family
+/// it belongs to. Values are modeled on the datasheet-derived descriptors embassy's `stm32-metapac`
+/// ships for every supported part.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ChipInfo {
+    /// Human readable chip name (e.g. "STM32G431RB")
+    pub name: &'static str,
+
+    /// Chip family (e.g. "STM32G4")
+    pub family: &'static str,
+
+    /// Start address of the flash memory
+    pub flash_start: u32,
+
+    /// Total size of the flash memory in bytes
+    pub flash_size: u32,
+
+    /// Size of the RAM in bytes
+    pub ram_size: u32,
+}
+
+/// Static table of supported chips, keyed by the product id (`DevInfoPID`) reported by the
+/// bootloader.
+const CHIP_TABLE: &[(u32, ChipInfo)] = &[(
+    0x0001,
+    ChipInfo {
+        name: "STM32G431RB",
+        family: "STM32G4",
+        flash_start: 0x0800_0000,
+        flash_size: 128 * 1024,
+        ram_size: 32 * 1024,
+    },
+)];
+
+// ChipDb -------------------------------------------------------------------------------------------
+
+///
+/// Chip database
+///
+/// Looks up a `ChipInfo` by the chip/product id a device reports during `init()`.
+///
+pub struct ChipDb;
+
+impl ChipDb {
+    ///
+    /// Look up a chip by the product id reported by the bootloader.
+    ///
+    /// Returns `None` if the id has no known entry in the database.
+    ///
+    pub fn lookup(chip_id: u32) -> Option<ChipInfo> {
+        CHIP_TABLE
+            .iter()
+            .find(|(id, _)| *id == chip_id)
+            .map(|(_, info)| *info)
+    }
+}
+
+// Tests ----------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chip_db_lookup_known_chip() {
+        let chip = ChipDb::lookup(0x0001).unwrap();
+        assert_eq!(chip.name, "STM32G431RB");
+        assert_eq!(chip.flash_start, 0x0800_0000);
+    }
+
+    #[test]
+    fn chip_db_lookup_unknown_chip() {
+        assert!(ChipDb::lookup(0xDEAD).is_none());
+    }
+}
@@ -0,0 +1,223 @@
+use super::msg::Msg;
+use super::ComMode;
+use crate::francor::franklyboot::Error;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::time::Duration;
+
+// Async ComInterface ------------------------------------------------------------------------------
+
+///
+/// Async counterpart of `ComInterface`
+///
+/// `send`/`recv`/`try_recv` are `async fn`s so a transport backed by an async serial/CAN stack
+/// (tokio-serial, socketcan's async API, ...) can `.await` its I/O directly instead of busy-
+/// blocking a thread for the duration of `set_timeout`'s deadline. `set_mode`/`set_timeout`/
+/// `get_timeout` stay synchronous, since they only touch local state and never wait on the wire.
+///
+/// `ComSimulator` implements this directly: its requests resolve immediately against an in-memory
+/// model, so there is nothing to actually await. A real transport adopts this trait incrementally;
+/// until it does, `BlockingAdapter` lets any `AsyncComInterface` be driven from the existing
+/// synchronous `ComInterface` call sites.
+///
+pub trait AsyncComInterface {
+    /// Set the communication mode (broadcast or specific node)
+    fn set_mode(&mut self, mode: ComMode) -> Result<(), Error>;
+
+    /// Set maximum time to wait for a response
+    fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error>;
+
+    /// Get active timeout value
+    fn get_timeout(&self) -> Duration;
+
+    /// Send a message to the device
+    fn send(&mut self, msg: &Msg) -> impl Future<Output = Result<(), Error>>;
+
+    /// Receive a message from the device, waiting up to the configured timeout
+    fn recv(&mut self) -> impl Future<Output = Result<Msg, Error>>;
+
+    /// Non-blocking receive: resolves with `Ok(None)` immediately if no message is queued yet
+    ///
+    /// Only interfaces with a background reader support this; every other interface resolves with
+    /// `Error::NotSupported`.
+    ///
+    fn try_recv(&mut self) -> impl Future<Output = Result<Option<Msg>, Error>> {
+        async { Err(Error::NotSupported) }
+    }
+}
+
+// Blocking Adapter ----------------------------------------------------------------------------------
+
+///
+/// Drives an `AsyncComInterface` through the synchronous `ComInterface` API
+///
+/// Wraps every async call in `block_on`, so existing callers (`FlashSession`, `Transactor`,
+/// `Device`, ...) keep working unchanged against an interface that only implements the async
+/// trait.
+///
+pub struct BlockingAdapter<T: AsyncComInterface> {
+    inner: T,
+}
+
+impl<T: AsyncComInterface> BlockingAdapter<T> {
+    /// Wraps `inner`, exposing it through the synchronous `ComInterface` trait
+    pub fn new(inner: T) -> Self {
+        BlockingAdapter { inner }
+    }
+
+    /// Unwraps the adapter, returning the inner async interface
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsyncComInterface> super::ComInterface for BlockingAdapter<T> {
+    fn set_mode(&mut self, mode: ComMode) -> Result<(), Error> {
+        self.inner.set_mode(mode)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        self.inner.set_timeout(timeout)
+    }
+
+    fn get_timeout(&self) -> Duration {
+        self.inner.get_timeout()
+    }
+
+    fn send(&mut self, msg: &Msg) -> Result<(), Error> {
+        block_on(self.inner.send(msg))
+    }
+
+    fn recv(&mut self) -> Result<Msg, Error> {
+        block_on(self.inner.recv())
+    }
+
+    fn try_recv(&mut self) -> Result<Option<Msg>, Error> {
+        block_on(self.inner.try_recv())
+    }
+}
+
+// block_on ------------------------------------------------------------------------------------------
+
+///
+/// Polls `future` to completion on the current thread
+///
+/// `AsyncComInterface`'s transports either resolve immediately (`ComSimulator`) or wake the task
+/// themselves once their I/O completes, so this re-polls with a no-op waker, yielding the thread
+/// between polls, rather than run a full reactor - pulling in an async runtime crate for that
+/// would be a heavy dependency for what `BlockingAdapter` actually needs.
+///
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => std::thread::yield_now(),
+        }
+    }
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw_waker()
+    }
+    fn no_op(_: *const ()) {}
+
+    fn raw_waker() -> RawWaker {
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    unsafe { Waker::from_raw(raw_waker()) }
+}
+
+// ComSimulator ---------------------------------------------------------------------------------
+
+/// `ComSimulator` resolves every request immediately against its in-memory model, so it is a
+/// direct, if trivial, `AsyncComInterface` implementor - exactly the case the trait doc describes.
+impl AsyncComInterface for super::ComSimulator {
+    fn set_mode(&mut self, mode: ComMode) -> Result<(), Error> {
+        super::ComInterface::set_mode(self, mode)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        super::ComInterface::set_timeout(self, timeout)
+    }
+
+    fn get_timeout(&self) -> Duration {
+        super::ComInterface::get_timeout(self)
+    }
+
+    fn send(&mut self, msg: &Msg) -> impl Future<Output = Result<(), Error>> {
+        let result = super::ComInterface::send(self, msg);
+        async move { result }
+    }
+
+    fn recv(&mut self) -> impl Future<Output = Result<Msg, Error>> {
+        let result = super::ComInterface::recv(self);
+        async move { result }
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::msg::{MsgData, RequestType, ResultType};
+    use crate::francor::franklyboot::com::ComInterface;
+    use crate::francor::franklyboot::com::ComSimulator;
+
+    #[test]
+    fn blocking_adapter_round_trips_a_request_through_an_async_com_interface() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::Ping,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+
+        let mut adapter = BlockingAdapter::new(interface);
+        adapter.send(&Msg::new_std_request(RequestType::Ping)).unwrap();
+        let response = adapter.recv().unwrap();
+
+        assert_eq!(response.get_result(), ResultType::Ok);
+    }
+
+    #[test]
+    fn blocking_adapter_propagates_a_no_response_error() {
+        let interface = ComSimulator::new();
+        let mut adapter = BlockingAdapter::new(interface);
+
+        assert_eq!(adapter.recv().unwrap_err(), Error::ComNoResponse);
+    }
+
+    #[test]
+    fn block_on_resolves_a_future_that_is_pending_for_a_few_polls() {
+        struct CountdownThenReady {
+            polls_remaining: u32,
+        }
+
+        impl Future for CountdownThenReady {
+            type Output = u32;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                if self.polls_remaining == 0 {
+                    Poll::Ready(42)
+                } else {
+                    self.polls_remaining -= 1;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }
+        }
+
+        assert_eq!(block_on(CountdownThenReady { polls_remaining: 3 }), 42);
+    }
+}
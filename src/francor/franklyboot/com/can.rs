@@ -1,10 +1,13 @@
-use socketcan::{CanFilter, CanFrame, CanSocket, EmbeddedFrame, Frame, Socket, SocketOptions, StandardId};
+use socketcan::{
+    CanAnyFrame, CanFdFrame, CanFdSocket, CanFilter, CanFrame, CanSocket, EmbeddedFrame, FdFlags,
+    Frame, Socket, SocketOptions, StandardId,
+};
 use std::time::Duration;
 
 use crate::francor::franklyboot::{
     com::{
         msg::{Msg, RequestType},
-        ComConnParams, ComInterface, ComMode,
+        ComInterface, ComMode,
     },
     Error,
 };
@@ -16,162 +19,195 @@ pub const CAN_BROADCAST_ID: u32 = 0x780;
 pub const CAN_MAX_ID: u32 = 0x7FF;
 pub const CAN_RX_TIMEOUT: std::time::Duration = Duration::from_millis(500);
 
+/// Either socket kind backing a `CANInterface`, kept as an enum rather than two structs so
+/// `CANInterface` itself does not need to be generic over it.
+enum CanSocketKind {
+    /// Classic CAN 2.0, eight data bytes per frame
+    Classic(CanSocket),
+
+    /// CAN FD, opted into via `CANInterface::open_fd`
+    Fd(CanFdSocket),
+}
+
+impl CanSocketKind {
+    fn set_read_timeout(&self, timeout: Duration) -> Result<(), Error> {
+        let result = match self {
+            CanSocketKind::Classic(socket) => socket.set_read_timeout(Some(timeout)),
+            CanSocketKind::Fd(socket) => socket.set_read_timeout(Some(timeout)),
+        };
+        result.map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))
+    }
+
+    fn set_filters(&self, filters: &[CanFilter]) -> Result<(), Error> {
+        let result = match self {
+            CanSocketKind::Classic(socket) => socket.set_filters(filters),
+            CanSocketKind::Fd(socket) => socket.set_filters(filters),
+        };
+        result.map_err(|e| Error::Error(format!("Failed to set CAN filter: {}", e)))
+    }
+
+    /// Drain any frames still queued on the socket (best effort, errors are not meaningful here)
+    fn clear_queue(&self) {
+        match self {
+            CanSocketKind::Classic(socket) => while socket.read_frame().is_ok() {},
+            CanSocketKind::Fd(socket) => while socket.read_frame().is_ok() {},
+        }
+    }
+
+    fn send(&self, id: StandardId, data: &[u8], brs: bool) -> Result<(), Error> {
+        match self {
+            CanSocketKind::Classic(socket) => {
+                let frame = socketcan::frame::CanDataFrame::new(id, data)
+                    .ok_or_else(|| Error::Error("Failed to create CAN data frame".to_string()))?;
+                socket.write_frame(&frame).map_err(|e| Error::Error(format!("{}", e)))
+            }
+            CanSocketKind::Fd(socket) => {
+                let mut frame = CanFdFrame::new(id, data)
+                    .ok_or_else(|| Error::Error("Failed to create CAN FD frame".to_string()))?;
+                if brs {
+                    frame.set_flags(FdFlags::BRS);
+                }
+                socket
+                    .write_frame(&frame)
+                    .map_err(|e| Error::Error(format!("CAN FD write failed: {}", e)))
+            }
+        }
+    }
+
+    fn recv(&self) -> Result<(u32, Vec<u8>), Error> {
+        match self {
+            CanSocketKind::Classic(socket) => match socket.read_frame() {
+                Ok(CanFrame::Data(frame)) => Ok((frame.raw_id(), frame.data().to_vec())),
+                Ok(_) => Err(Error::ComNoResponse), // Ignore non-data frames
+                Err(_) => Err(Error::ComNoResponse),
+            },
+            CanSocketKind::Fd(socket) => match socket.read_frame() {
+                Ok(CanAnyFrame::Normal(CanFrame::Data(frame))) => {
+                    Ok((frame.raw_id(), frame.data().to_vec()))
+                }
+                Ok(CanAnyFrame::Fd(frame)) => Ok((frame.raw_id(), frame.data().to_vec())),
+                Ok(_) => Err(Error::ComNoResponse), // Ignore non-data frames
+                Err(_) => Err(Error::ComNoResponse),
+            },
+        }
+    }
+}
+
 ///
 /// CAN interface
 ///
-/// This struct implements the communication interface for can communication.
+/// This struct implements the communication interface for can communication. By default it opens
+/// a classic CAN 2.0 socket (`open`); `open_fd` opts into CAN FD instead, which keeps the eight
+/// byte `Msg` payload unchanged but sends it with the BRS bit set so the data phase runs at the
+/// bus's higher FD bitrate, cutting per-frame time during flashing. Discovery (`ping_network`,
+/// `search_for_devices`) always uses classic frames so nodes on a mixed classic/FD bus are all
+/// still found.
 ///
 pub struct CANInterface {
     /// CAN socket
-    socket: Option<CanSocket>,
+    socket: CanSocketKind,
 
     /// Timeout for receiving messages
     timeout: Duration,
+
+    /// Node id the last received frame came from, derived from its CAN id
+    last_rx_node_id: Option<u8>,
 }
 
 impl CANInterface {
-    // Private functions --------------------------------------------------------------------------
-
-    fn can_frame_to_msg(can_frame: &socketcan::frame::CanDataFrame) -> Msg {
-        let data = can_frame.data();
-        let msg_data = [
-            data[0],
-            data[1],
-            data[2],
-            data[3],
-            data[4],
-            data[5],
-            data[6],
-            data[7],
-        ];
-
-        return Msg::from_raw_data_array(&msg_data);
+    ///
+    /// Open the CAN interface
+    ///
+    /// This function opens the given SocketCAN network interface (e.g. "can0") and clears any
+    /// stale frames that were already waiting on the socket.
+    ///
+    pub fn open(interface_name: &str) -> Result<CANInterface, Error> {
+        let socket = CanSocket::open(interface_name)
+            .map_err(|e| Error::Error(format!("Failed to open CAN interface \"{}\": {}", interface_name, e)))?;
+
+        Self::from_socket(CanSocketKind::Classic(socket))
     }
-}
 
-impl ComInterface for CANInterface {
-    fn create() -> Result<Self, Error> {
-        Ok(CANInterface {
-            socket: None,
-            timeout: CAN_RX_TIMEOUT,
-        })
+    ///
+    /// Open the CAN interface in CAN FD mode
+    ///
+    /// Like `open`, but enables FD frames on the socket and sends every message with the BRS bit
+    /// set. Returns a clear error instead of silently falling back to classic frames if the
+    /// interface or the device does not support CAN FD.
+    ///
+    pub fn open_fd(interface_name: &str) -> Result<CANInterface, Error> {
+        let socket = CanFdSocket::open(interface_name).map_err(|e| {
+            Error::Error(format!(
+                "Failed to open CAN interface \"{}\" in FD mode: {}",
+                interface_name, e
+            ))
+        })?;
+
+        Self::from_socket(CanSocketKind::Fd(socket))
     }
 
-    fn open(&mut self, params: &ComConnParams) -> Result<(), Error> {
-        if params.name.is_none() {
-            return Err(Error::Error(format!("Serial port name not set!")));
-        }
-
-        let socket = CanSocket::open(params.name.clone().unwrap().as_str());
-        match socket {
-            Ok(socket) => {
-                socket
-                    .set_read_timeout(Some(self.timeout))
-                    .map_err(|_e| Error::Error(format!("Failed to set rx timeout!")))?;
-
-                // clear rx messages
-                loop {
-                    match socket.read_frame() {
-                        Ok(_) => {}
-                        Err(_) => break,
-                    }
-                }
+    fn from_socket(socket: CanSocketKind) -> Result<CANInterface, Error> {
+        socket.set_read_timeout(CAN_RX_TIMEOUT)?;
 
-                self.socket = Some(socket);
+        // Clear any frames still queued on the socket
+        socket.clear_queue();
 
-                Ok(())
-            }
-            Err(e) => Err(Error::Error(format!(
-                "Error opening socket for \"{}\": \"{}\"",
-                params.name.clone().unwrap(),
-                e
-            ))),
-        }
-    }
+        let mut interface = CANInterface {
+            socket,
+            timeout: CAN_RX_TIMEOUT,
+            last_rx_node_id: None,
+        };
+        interface.set_mode(ComMode::Broadcast)?;
 
-    fn is_network() -> bool {
-        true
+        Ok(interface)
     }
 
-    fn scan_network(&mut self) -> Result<Vec<u8>, Error> {
-        // Config interface to broadcast
-        self.set_mode(ComMode::Broadcast)?;
+    ///
+    /// Ping the CAN bus for connected nodes
+    ///
+    /// This function broadcasts a ping request on the given CAN interface and collects the node
+    /// ids of every device that answers before the interface's rx timeout elapses.
+    ///
+    pub fn ping_network(interface_name: &str) -> Result<Vec<u8>, Error> {
+        let mut interface = CANInterface::open(interface_name)?;
+        interface.set_mode(ComMode::Broadcast)?;
 
-        // Send ping
         let ping_request = Msg::new_std_request(RequestType::Ping);
-        self.send(&ping_request)?;
-
-        match self.socket.as_mut() {
-            Some(socket) => {
-                // Receive until no new response
-                // Store node ids
-                let mut node_id_lst = Vec::new();
-                loop {
-                    match socket.read_frame() {
-                        Ok(frame) => {
-                            // Only process data frames
-                            match frame {
-                                CanFrame::Data(can_frame) => {
-                                    let response = Self::can_frame_to_msg(&can_frame);
-                                    if ping_request.is_response_ok(&response).is_ok() {
-                                        let raw_id = can_frame.raw_id();
-                                        let node_id = ((raw_id - CAN_BASE_ID) / 2) as u8;
-                                        node_id_lst.push(node_id);
-                                    }
-                                }
-                                _ => {} // Ignore non-data frames
-                            }
-                        }
-                        Err(_e) => {
-                            break;
+        interface.send(&ping_request)?;
+
+        let mut node_id_lst = Vec::new();
+        loop {
+            match interface.recv() {
+                Ok(response) => {
+                    if ping_request.is_response_ok(&response).is_ok() {
+                        if let Some(node_id) = interface.last_rx_node_id {
+                            node_id_lst.push(node_id);
                         }
                     }
                 }
-
-                Ok(node_id_lst)
+                Err(_) => break,
             }
-            None => Err(Error::Error(format!("CAN socket not open!"))),
         }
+
+        Ok(node_id_lst)
     }
+}
 
+impl ComInterface for CANInterface {
     fn set_mode(&mut self, mode: ComMode) -> Result<(), Error> {
-        match self.socket.as_mut() {
-            Some(socket) => {
-                let mut can_rx_msg_id = 0;
-                let mut can_rx_msg_mask = 0;
-
-                // Set ID and MASK only if no broadcast is used
-                match mode {
-                    ComMode::Specific(node_id) => {
-                        can_rx_msg_id = CAN_BASE_ID + node_id as u32 * 2 + 1;
-                        can_rx_msg_mask = 0x7FF;
-                    }
-                    _ => {}
-                }
+        let (can_rx_msg_id, can_rx_msg_mask) = match mode {
+            ComMode::Specific(node_id) => (CAN_BASE_ID + node_id as u32 * 2 + 1, CAN_MAX_ID),
+            ComMode::Broadcast => (0, 0),
+        };
 
-                // Set filter
-                let filter = CanFilter::new(can_rx_msg_id, can_rx_msg_mask);
-                match socket.set_filters(&[filter]) {
-                    Ok(_) => Ok(()),
-                    Err(e) => Err(Error::Error(format!("Failed to set filter: {}", e))),
-                }
-            }
-            None => Err(Error::Error(format!("CAN socket not open!"))),
-        }
+        let filter = CanFilter::new(can_rx_msg_id, can_rx_msg_mask);
+        self.socket.set_filters(&[filter])
     }
 
     fn set_timeout(&mut self, timeout: std::time::Duration) -> Result<(), Error> {
-        match self.socket.as_mut() {
-            Some(socket) => match socket.set_read_timeout(Some(timeout)) {
-                Ok(_) => {
-                    self.timeout = timeout;
-
-                    Ok(())
-                }
-                Err(e) => Err(Error::Error(format!("Failed to set timeout: {}", e))),
-            },
-            None => Err(Error::Error(format!("CAN socket not open!"))),
-        }
+        self.socket.set_read_timeout(timeout)?;
+        self.timeout = timeout;
+        Ok(())
     }
 
     fn get_timeout(&self) -> std::time::Duration {
@@ -179,48 +215,16 @@ impl ComInterface for CANInterface {
     }
 
     fn send(&mut self, msg: &Msg) -> Result<(), Error> {
-        match self.socket.as_mut() {
-            Some(socket) => {
-                let id = StandardId::new(CAN_BROADCAST_ID as u16)
-                    .ok_or_else(|| Error::Error(format!("Invalid CAN ID: {}", CAN_BROADCAST_ID)))?;
-                let frame = socketcan::frame::CanDataFrame::new(id, &msg.to_raw_data_array())
-                    .ok_or_else(|| Error::Error(format!("Failed to create CAN data frame")))?;
-
-                socket
-                    .write_frame(&frame)
-                    .map_err(|e| Error::Error(format!("{}", e)))?;
+        let id = StandardId::new(CAN_BROADCAST_ID as u16)
+            .ok_or_else(|| Error::Error(format!("Invalid CAN ID: {}", CAN_BROADCAST_ID)))?;
 
-                Ok(())
-            }
-            None => Err(Error::Error(format!("CAN socket not open!"))),
-        }
+        self.socket.send(id, &msg.to_raw_data_array(), true)
     }
 
     fn recv(&mut self) -> Result<Msg, Error> {
-        match self.socket.as_mut() {
-            Some(socket) => {
-                match socket.read_frame() {
-                    Ok(frame) => {
-                        // Only process data frames
-                        match frame {
-                            CanFrame::Data(can_frame) => {
-                                return Ok(Self::can_frame_to_msg(&can_frame));
-                            }
-                            _ => {
-                                // Non-data frames are ignored, try again
-                                return Err(Error::ComNoResponse);
-                            }
-                        }
-                    }
-                    // Message timeout
-                    Err(_) => {}
-                }
+        let (raw_id, data) = self.socket.recv()?;
+        self.last_rx_node_id = Some(((raw_id - CAN_BASE_ID) / 2) as u8);
 
-                return Err(Error::ComNoResponse);
-            }
-            None => {
-                return Err(Error::Error(format!("CAN socket not open!")));
-            }
-        }
+        Ok(Msg::try_from_raw_data_array(&data)?)
     }
 }
@@ -0,0 +1,185 @@
+use super::msg::{Msg, MsgData, RequestType};
+use super::ComInterface;
+use crate::francor::franklyboot::Error;
+
+use std::fmt;
+
+// Command ------------------------------------------------------------------------------------------
+
+/// Ties a `RequestType` to the strongly-typed value its response decodes into
+///
+/// Borrows the shape of `xio_common`'s `HasFixedCommandId`/`IsRequest`/`IsResponse` split, but
+/// collapses it into a single trait: this protocol's responses are always one 4-byte `MsgData`
+/// payload, so there is no separate request/response pairing to validate beyond what
+/// `Msg::is_response_ok` already checks. A marker struct implementing `Command` fixes the
+/// `RequestType` it sends (`HasFixedCommandId`) and decodes the reply (`IsResponse`); `transact`
+/// plays the role `IsValidResponseFor` plays there, pairing a request with its response.
+pub trait Command {
+    /// The value `decode` produces from a response's payload
+    type Response;
+
+    /// The fixed request type this command sends
+    const REQUEST: RequestType;
+
+    /// Decodes a response's payload into this command's `Response` type
+    fn decode(data: &MsgData) -> Self::Response;
+}
+
+/// Sends `C::REQUEST` and decodes the response via `Command::decode`
+///
+/// Strongly-typed alternative to building a `Msg` by hand and poking at `MsgData::to_word()`;
+/// callers who want a raw `u32`/`MsgData` and don't need a named `Command` can keep using
+/// `Msg::new_std_request`/`ComInterface::send`/`recv` directly, the way `Entry` does internally.
+pub fn transact<C: Command>(interface: &mut impl ComInterface) -> Result<C::Response, Error> {
+    let request = Msg::new_std_request(C::REQUEST);
+    interface.send(&request)?;
+    let response = interface.recv()?;
+    request.is_response_ok(&response)?;
+    Ok(C::decode(response.get_data()))
+}
+
+// Version --------------------------------------------------------------------------------------
+
+/// A bootloader version, as reported by `Ping` and `DevInfoBootloaderVersion`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+impl Version {
+    fn decode(data: &MsgData) -> Self {
+        Version {
+            major: data.get_byte(0),
+            minor: data.get_byte(1),
+            patch: data.get_byte(2),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// Commands ---------------------------------------------------------------------------------------
+
+/// `RequestType::Ping` | Response is the bootloader version
+pub struct Ping;
+
+impl Command for Ping {
+    type Response = Version;
+    const REQUEST: RequestType = RequestType::Ping;
+
+    fn decode(data: &MsgData) -> Version {
+        Version::decode(data)
+    }
+}
+
+/// `RequestType::DevInfoBootloaderVersion` | Response is the bootloader version
+pub struct DevInfoBootloaderVersion;
+
+impl Command for DevInfoBootloaderVersion {
+    type Response = Version;
+    const REQUEST: RequestType = RequestType::DevInfoBootloaderVersion;
+
+    fn decode(data: &MsgData) -> Version {
+        Version::decode(data)
+    }
+}
+
+/// `RequestType::FlashInfoStartAddr` | Response is the flash area's start address
+pub struct FlashInfoStartAddr;
+
+impl Command for FlashInfoStartAddr {
+    type Response = u32;
+    const REQUEST: RequestType = RequestType::FlashInfoStartAddr;
+
+    fn decode(data: &MsgData) -> u32 {
+        data.to_word()
+    }
+}
+
+/// `RequestType::FlashInfoPageSize` | Response is a page's size in bytes
+pub struct FlashInfoPageSize;
+
+impl Command for FlashInfoPageSize {
+    type Response = u32;
+    const REQUEST: RequestType = RequestType::FlashInfoPageSize;
+
+    fn decode(data: &MsgData) -> u32 {
+        data.to_word()
+    }
+}
+
+/// `RequestType::FlashInfoNumPages` | Response is the number of pages, including the bootloader area
+pub struct FlashInfoNumPages;
+
+impl Command for FlashInfoNumPages {
+    type Response = u32;
+    const REQUEST: RequestType = RequestType::FlashInfoNumPages;
+
+    fn decode(data: &MsgData) -> u32 {
+        data.to_word()
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::msg::ResultType;
+    use crate::francor::franklyboot::com::ComSimulator;
+
+    #[test]
+    fn transact_ping_decodes_version() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::Ping,
+            ResultType::Ok,
+            0,
+            &MsgData::from_array(&[1, 2, 3, 0]),
+        ));
+
+        let version = transact::<Ping>(&mut interface).unwrap();
+
+        assert_eq!(
+            version,
+            Version {
+                major: 1,
+                minor: 2,
+                patch: 3,
+            }
+        );
+        assert_eq!(version.to_string(), "1.2.3");
+    }
+
+    #[test]
+    fn transact_flash_info_page_size_decodes_word() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::FlashInfoPageSize,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(0x400),
+        ));
+
+        assert_eq!(transact::<FlashInfoPageSize>(&mut interface).unwrap(), 0x400);
+    }
+
+    #[test]
+    fn transact_propagates_error_result() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::FlashInfoPageSize,
+            ResultType::ErrNotSupported,
+            0,
+            &MsgData::new(),
+        ));
+
+        assert!(transact::<FlashInfoPageSize>(&mut interface).is_err());
+    }
+}
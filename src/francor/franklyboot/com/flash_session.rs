@@ -0,0 +1,315 @@
+use super::msg::{Msg, MsgData, RequestType, ResultType};
+use super::ComInterface;
+use crate::francor::franklyboot::Error;
+
+use crc::{Crc, CRC_32_ISO_HDLC};
+use std::time::Duration;
+
+const FLASH_SESSION_CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+// FlashSessionConfig -------------------------------------------------------------------------------
+
+/// Tuning knobs for a `FlashSession`
+///
+/// Inspired by the KWP2000-over-ISO-TP flashing flow in the `ultimate_nag52` tool: a configurable
+/// block size and inter-request timing, plus a keepalive so the bootloader's own idle/session
+/// timeout doesn't trip during a long multi-page transfer.
+#[derive(Debug, Clone, Copy)]
+pub struct FlashSessionConfig {
+    /// Number of `PageBufferWriteWord` requests sent between keepalive checks
+    pub word_batch_size: usize,
+
+    /// Per-request timeout, applied to the interface via `ComInterface::set_timeout`
+    pub request_timeout: Duration,
+
+    /// Send a keepalive `Ping` after this many word batches have gone by without one. `0` disables
+    /// the keepalive entirely.
+    pub keepalive_interval: usize,
+
+    /// How many times a page is retried (full erase-write-verify cycle) after the device reports
+    /// `ErrCRCInvld`/`ErrPageFull` or the locally computed page CRC doesn't match, before giving up
+    pub max_retries: u32,
+}
+
+impl Default for FlashSessionConfig {
+    fn default() -> Self {
+        FlashSessionConfig {
+            word_batch_size: 8,
+            request_timeout: Duration::from_millis(500),
+            keepalive_interval: 16,
+            max_retries: 3,
+        }
+    }
+}
+
+// FlashSession --------------------------------------------------------------------------------------
+
+/// Orchestrates a single page's erase/write/verify/commit cycle over a `ComInterface`, with
+/// automatic retry and keepalive
+///
+/// Lower-level than `Device::flash`: it only knows how to push one page's bytes through
+/// `FlashWriteErasePage`/`PageBufferClear`/`PageBufferWriteWord`/`PageBufferCalcCRC`/
+/// `PageBufferWriteToFlash` and retry the whole cycle on a CRC/page-full error, with no knowledge
+/// of firmware images, flash layout, or progress reporting. Useful for transports or tools that
+/// want the retry/keepalive behavior without pulling in all of `Device`.
+pub struct FlashSession<'a, I: ComInterface> {
+    interface: &'a mut I,
+    config: FlashSessionConfig,
+    batches_since_keepalive: usize,
+}
+
+impl<'a, I: ComInterface> FlashSession<'a, I> {
+    /// Starts a session over `interface`, applying `config.request_timeout`
+    pub fn new(interface: &'a mut I, config: FlashSessionConfig) -> Result<Self, Error> {
+        interface.set_timeout(config.request_timeout)?;
+
+        Ok(FlashSession {
+            interface,
+            config,
+            batches_since_keepalive: 0,
+        })
+    }
+
+    /// Flashes `page_bytes` to the page at `page_id`, retrying the whole erase/write/verify/commit
+    /// cycle up to `config.max_retries` times if the device reports `ErrCRCInvld`/`ErrPageFull` or
+    /// the page buffer's CRC doesn't match what was sent
+    pub fn flash_page(&mut self, page_id: u32, page_bytes: &[u8]) -> Result<(), Error> {
+        for attempt in 0..=self.config.max_retries {
+            if self._try_flash_page(page_id, page_bytes)? {
+                return Ok(());
+            }
+
+            if attempt == self.config.max_retries {
+                return Err(Error::Error(format!(
+                    "Page {} did not verify after {} attempt(s)",
+                    page_id,
+                    self.config.max_retries + 1
+                )));
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// Runs one erase/write/verify/commit attempt, returning `Ok(false)` instead of an `Error` for
+    /// any of the retryable conditions `flash_page` retries on
+    fn _try_flash_page(&mut self, page_id: u32, page_bytes: &[u8]) -> Result<bool, Error> {
+        if Self::_is_retryable(self._exec(RequestType::FlashWriteErasePage, page_id)?) {
+            return Ok(false);
+        }
+
+        self._exec(RequestType::PageBufferClear, 0)?;
+
+        for (batch_idx, batch) in page_bytes.chunks(self.config.word_batch_size * 4).enumerate() {
+            for (word_idx, word) in batch.chunks(4).enumerate() {
+                let packet_id = (batch_idx * self.config.word_batch_size + word_idx) as u8;
+                let data = MsgData::from_array(&[word[0], word[1], word[2], word[3]]);
+                let request = Msg::new(RequestType::PageBufferWriteWord, ResultType::None, packet_id, &data);
+                let response = self._exchange(&request)?;
+
+                if Self::_is_retryable(response.get_result()) {
+                    return Ok(false);
+                }
+                request.is_response_ok(&response)?;
+            }
+
+            self._maybe_send_keepalive()?;
+        }
+
+        let calc_crc = FLASH_SESSION_CRC32.checksum(page_bytes);
+        let dev_crc = self._read(RequestType::PageBufferCalcCRC)?.to_word();
+        if dev_crc != calc_crc {
+            return Ok(false);
+        }
+
+        Ok(!Self::_is_retryable(
+            self._exec(RequestType::PageBufferWriteToFlash, page_id)?,
+        ))
+    }
+
+    fn _maybe_send_keepalive(&mut self) -> Result<(), Error> {
+        if self.config.keepalive_interval == 0 {
+            return Ok(());
+        }
+
+        self.batches_since_keepalive += 1;
+        if self.batches_since_keepalive >= self.config.keepalive_interval {
+            self._exec(RequestType::Ping, 0)?;
+            self.batches_since_keepalive = 0;
+        }
+
+        Ok(())
+    }
+
+    fn _is_retryable(result: ResultType) -> bool {
+        matches!(result, ResultType::ErrCRCInvld | ResultType::ErrPageFull)
+    }
+
+    fn _exec(&mut self, request_type: RequestType, argument: u32) -> Result<ResultType, Error> {
+        let request = Msg::new(
+            request_type,
+            ResultType::None,
+            0,
+            &MsgData::from_word(argument),
+        );
+        let response = self._exchange(&request)?;
+
+        if !Self::_is_retryable(response.get_result()) {
+            request.is_response_ok(&response)?;
+        }
+
+        Ok(response.get_result())
+    }
+
+    /// Reads `num_bytes` back from flash starting at `address`, one word at a time via
+    /// `FlashReadWord`
+    ///
+    /// `num_bytes` must be a multiple of 4; used by `device::DualBankUpdater` to pull a page's
+    /// current contents off the device before writing it to the other bank.
+    pub fn read_page(&mut self, address: u32, num_bytes: usize) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::with_capacity(num_bytes);
+
+        for word_idx in 0..(num_bytes / 4) {
+            let request = Msg::new(
+                RequestType::FlashReadWord,
+                ResultType::None,
+                0,
+                &MsgData::from_word(address + (word_idx * 4) as u32),
+            );
+            let response = self._exchange(&request)?;
+            request.is_response_ok(&response)?;
+
+            bytes.extend_from_slice(&response.get_data().to_word().to_le_bytes());
+        }
+
+        Ok(bytes)
+    }
+
+    fn _read(&mut self, request_type: RequestType) -> Result<MsgData, Error> {
+        let request = Msg::new_std_request(request_type);
+        let response = self._exchange(&request)?;
+        request.is_response_ok(&response)?;
+
+        Ok(response.get_data().clone())
+    }
+
+    fn _exchange(&mut self, request: &Msg) -> Result<Msg, Error> {
+        self.interface.send(request)?;
+        self.interface.recv()
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::ComSimulator;
+
+    fn ok(request: RequestType, packet_id: u8, data: &MsgData) -> Msg {
+        Msg::new(request, ResultType::Ok, packet_id, data)
+    }
+
+    #[test]
+    fn flash_session_flashes_a_page_on_first_try() {
+        let mut interface = ComSimulator::new();
+        let page_bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        let crc = FLASH_SESSION_CRC32.checksum(&page_bytes);
+
+        interface.add_response(ok(RequestType::FlashWriteErasePage, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferClear, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferWriteWord, 0, &MsgData::new()));
+        interface.add_response(ok(
+            RequestType::PageBufferCalcCRC,
+            0,
+            &MsgData::from_word(crc),
+        ));
+        interface.add_response(ok(RequestType::PageBufferWriteToFlash, 0, &MsgData::new()));
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        session.flash_page(2, &page_bytes).unwrap();
+    }
+
+    #[test]
+    fn flash_session_retries_on_page_buffer_crc_mismatch() {
+        let mut interface = ComSimulator::new();
+        let page_bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        let crc = FLASH_SESSION_CRC32.checksum(&page_bytes);
+
+        // First attempt: CRC readback doesn't match, so the page is retried from scratch.
+        interface.add_response(ok(RequestType::FlashWriteErasePage, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferClear, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferWriteWord, 0, &MsgData::new()));
+        interface.add_response(ok(
+            RequestType::PageBufferCalcCRC,
+            0,
+            &MsgData::from_word(!crc),
+        ));
+
+        // Second attempt succeeds.
+        interface.add_response(ok(RequestType::FlashWriteErasePage, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferClear, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferWriteWord, 0, &MsgData::new()));
+        interface.add_response(ok(
+            RequestType::PageBufferCalcCRC,
+            0,
+            &MsgData::from_word(crc),
+        ));
+        interface.add_response(ok(RequestType::PageBufferWriteToFlash, 0, &MsgData::new()));
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        session.flash_page(2, &page_bytes).unwrap();
+    }
+
+    #[test]
+    fn flash_session_gives_up_after_max_retries() {
+        let mut interface = ComSimulator::new();
+        let page_bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+
+        let config = FlashSessionConfig {
+            max_retries: 1,
+            ..FlashSessionConfig::default()
+        };
+
+        for _ in 0..=config.max_retries {
+            interface.add_response(ok(RequestType::FlashWriteErasePage, 0, &MsgData::new()));
+            interface.add_response(ok(RequestType::PageBufferClear, 0, &MsgData::new()));
+            interface.add_response(ok(RequestType::PageBufferWriteWord, 0, &MsgData::new()));
+            interface.add_response(ok(
+                RequestType::PageBufferCalcCRC,
+                0,
+                &MsgData::from_word(0xBAD),
+            ));
+        }
+
+        let mut session = FlashSession::new(&mut interface, config).unwrap();
+        assert!(session.flash_page(2, &page_bytes).is_err());
+    }
+
+    #[test]
+    fn flash_session_sends_keepalive_ping_every_batch_interval() {
+        let mut interface = ComSimulator::new();
+        let page_bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        let crc = FLASH_SESSION_CRC32.checksum(&page_bytes);
+
+        interface.add_response(ok(RequestType::FlashWriteErasePage, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferClear, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::PageBufferWriteWord, 0, &MsgData::new()));
+        interface.add_response(ok(RequestType::Ping, 0, &MsgData::new()));
+        interface.add_response(ok(
+            RequestType::PageBufferCalcCRC,
+            0,
+            &MsgData::from_word(crc),
+        ));
+        interface.add_response(ok(RequestType::PageBufferWriteToFlash, 0, &MsgData::new()));
+
+        let config = FlashSessionConfig {
+            word_batch_size: 1,
+            keepalive_interval: 1,
+            ..FlashSessionConfig::default()
+        };
+
+        let mut session = FlashSession::new(&mut interface, config).unwrap();
+        session.flash_page(2, &page_bytes).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use crate::francor::franklyboot::com::msg::Msg;
+
+// Resynchronizing Frame Parser --------------------------------------------------------------------
+
+///
+/// Byte-stream frame parser, for interfaces like `SerialInterface` that only guarantee a stream of
+/// bytes rather than message-aligned reads
+///
+/// This protocol's 8-byte frame carries no dedicated sync byte or checksum of its own - a frame is
+/// validated by `Msg::try_from_raw_data_array` rejecting an unknown request/result byte. `consume`
+/// feeds newly read bytes into an internal buffer, and `parse_msg` scans it for the first 8-byte
+/// window that parses as a valid `Msg`, modeled on the ublox crate's `Parser`/`consume` pattern:
+/// a window that fails to parse has its leading byte dropped and the scan retried from the next
+/// byte, so a stray byte (a boot banner, line noise) in front of a real frame does not corrupt
+/// every message that follows it - it costs only the bytes up to the next valid frame.
+///
+pub struct FrameParser {
+    buffer: VecDeque<u8>,
+}
+
+impl FrameParser {
+    pub fn new() -> Self {
+        FrameParser {
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Feed newly read bytes into the parser's buffer
+    pub fn consume(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes);
+    }
+
+    /// Extract the next valid frame from the buffer, if one is present
+    ///
+    /// Returns `None` once fewer than 8 bytes remain buffered; call `consume` again and retry.
+    ///
+    pub fn parse_msg(&mut self) -> Option<Msg> {
+        while self.buffer.len() >= 8 {
+            let frame: Vec<u8> = self.buffer.iter().take(8).copied().collect();
+
+            match Msg::try_from_raw_data_array(&frame) {
+                Ok(msg) => {
+                    self.buffer.drain(0..8);
+                    return Some(msg);
+                }
+                Err(_) => {
+                    self.buffer.pop_front();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for FrameParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::msg::{MsgData, RequestType, ResultType};
+
+    #[test]
+    fn parse_msg_returns_none_until_eight_bytes_are_buffered() {
+        let mut parser = FrameParser::new();
+        parser.consume(&[1, 2, 3]);
+
+        assert!(parser.parse_msg().is_none());
+    }
+
+    #[test]
+    fn parse_msg_extracts_a_clean_frame() {
+        let msg = Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new());
+
+        let mut parser = FrameParser::new();
+        parser.consume(&msg.to_raw_data_array());
+
+        let parsed = parser.parse_msg().unwrap();
+        assert_eq!(parsed.request, RequestType::Ping);
+        assert_eq!(parsed.result, ResultType::Ok);
+        assert_eq!(parsed.packet_id, 0);
+        assert!(parser.parse_msg().is_none());
+    }
+
+    #[test]
+    fn parse_msg_resyncs_past_leading_garbage() {
+        let msg = Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new());
+
+        let mut parser = FrameParser::new();
+        parser.consume(&[0xFF, 0xFF, 0xFF]); // stray boot-banner bytes
+        parser.consume(&msg.to_raw_data_array());
+
+        let parsed = parser.parse_msg().unwrap();
+        assert_eq!(parsed.request, RequestType::Ping);
+    }
+
+    #[test]
+    fn parse_msg_resyncs_across_two_back_to_back_frames() {
+        let ping = Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new());
+        let reset = Msg::new(RequestType::ResetDevice, ResultType::Ok, 1, &MsgData::new());
+
+        let mut parser = FrameParser::new();
+        parser.consume(&ping.to_raw_data_array());
+        parser.consume(&reset.to_raw_data_array());
+
+        assert_eq!(parser.parse_msg().unwrap().request, RequestType::Ping);
+        assert_eq!(parser.parse_msg().unwrap().request, RequestType::ResetDevice);
+        assert!(parser.parse_msg().is_none());
+    }
+}
@@ -0,0 +1,769 @@
+use std::time::Duration;
+
+use crate::francor::franklyboot::{firmware::SegmentedFirmware, Error};
+
+// ISO-TP Frame -------------------------------------------------------------------------------------
+
+/// Payload bytes a classic (eight byte) single frame carries after its one PCI byte
+const SF_MAX_LEN: usize = 7;
+/// Payload bytes a first frame carries after its two PCI bytes
+const FF_DATA_LEN: usize = 6;
+/// Payload bytes a consecutive frame carries after its one PCI byte
+const CF_DATA_LEN: usize = 7;
+/// Largest total payload length a 12-bit ISO-TP first-frame length field can announce
+const MAX_PAYLOAD_LEN: usize = 0xFFF;
+
+/// Flow status a receiver grants a sender in a flow control frame (ISO 15765-2 N_PCI)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowStatus {
+    /// Keep sending consecutive frames
+    ContinueToSend,
+    /// Pause, a further flow control frame will follow
+    Wait,
+    /// Abort, the receiver's buffer cannot take the announced payload
+    Overflow,
+}
+
+impl FlowStatus {
+    fn from_nibble(nibble: u8) -> Result<FlowStatus, Error> {
+        match nibble {
+            0 => Ok(FlowStatus::ContinueToSend),
+            1 => Ok(FlowStatus::Wait),
+            2 => Ok(FlowStatus::Overflow),
+            other => Err(Error::MsgCorruption(format!(
+                "Invalid ISO-TP flow status {:#X}",
+                other
+            ))),
+        }
+    }
+
+    fn to_nibble(self) -> u8 {
+        match self {
+            FlowStatus::ContinueToSend => 0,
+            FlowStatus::Wait => 1,
+            FlowStatus::Overflow => 2,
+        }
+    }
+}
+
+/// One ISO-TP (ISO 15765-2) frame, encoded on top of an eight byte classic CAN payload the same
+/// way `Transport` frames a `Msg` on top of a fixed-size link - the PCI nibble in byte 0 selects
+/// which of the four frame kinds the remaining bytes hold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IsoTpFrame {
+    /// The whole payload fits in one frame
+    Single { data: Vec<u8> },
+
+    /// First frame of a multi-frame payload, announcing its total length
+    First { total_len: usize, data: Vec<u8> },
+
+    /// One more chunk of a multi-frame payload, `sequence` wrapping 0..=15
+    Consecutive { sequence: u8, data: Vec<u8> },
+
+    /// Paces the consecutive frames still to come
+    FlowControl {
+        status: FlowStatus,
+        block_size: u8,
+        st_min: Duration,
+    },
+}
+
+impl IsoTpFrame {
+    pub fn encode(&self) -> [u8; 8] {
+        let mut frame = [0u8; 8];
+        match self {
+            IsoTpFrame::Single { data } => {
+                frame[0] = data.len() as u8;
+                frame[1..1 + data.len()].copy_from_slice(data);
+            }
+            IsoTpFrame::First { total_len, data } => {
+                frame[0] = 0x10 | ((*total_len >> 8) as u8 & 0x0F);
+                frame[1] = (*total_len & 0xFF) as u8;
+                frame[2..2 + data.len()].copy_from_slice(data);
+            }
+            IsoTpFrame::Consecutive { sequence, data } => {
+                frame[0] = 0x20 | (*sequence & 0x0F);
+                frame[1..1 + data.len()].copy_from_slice(data);
+            }
+            IsoTpFrame::FlowControl {
+                status,
+                block_size,
+                st_min,
+            } => {
+                frame[0] = 0x30 | status.to_nibble();
+                frame[1] = *block_size;
+                frame[2] = encode_st_min(*st_min);
+            }
+        }
+        frame
+    }
+
+    pub fn decode(frame: &[u8]) -> Result<IsoTpFrame, Error> {
+        if frame.len() != 8 {
+            return Err(Error::MsgCorruption(format!(
+                "ISO-TP frame must be 8 bytes, got {}",
+                frame.len()
+            )));
+        }
+
+        match frame[0] >> 4 {
+            0x0 => {
+                let len = (frame[0] & 0x0F) as usize;
+                if len > SF_MAX_LEN {
+                    return Err(Error::MsgCorruption(format!(
+                        "ISO-TP single frame length {} exceeds the {} byte maximum",
+                        len, SF_MAX_LEN
+                    )));
+                }
+                Ok(IsoTpFrame::Single {
+                    data: frame[1..1 + len].to_vec(),
+                })
+            }
+            0x1 => {
+                let total_len = (((frame[0] & 0x0F) as usize) << 8) | frame[1] as usize;
+                Ok(IsoTpFrame::First {
+                    total_len,
+                    data: frame[2..2 + FF_DATA_LEN].to_vec(),
+                })
+            }
+            0x2 => Ok(IsoTpFrame::Consecutive {
+                sequence: frame[0] & 0x0F,
+                data: frame[1..1 + CF_DATA_LEN].to_vec(),
+            }),
+            0x3 => Ok(IsoTpFrame::FlowControl {
+                status: FlowStatus::from_nibble(frame[0] & 0x0F)?,
+                block_size: frame[1],
+                st_min: decode_st_min(frame[2]),
+            }),
+            other => Err(Error::MsgCorruption(format!(
+                "Unknown ISO-TP PCI type {:#X}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Encodes a separation time as an ISO-TP ST byte: `0x00-0x7F` is that many milliseconds,
+/// `0xF1-0xF9` is 100-900 microseconds; anything finer than a millisecond but not an exact
+/// multiple of 100us is rounded down to a multiple of 100us, except that values under 100us are
+/// raised to 100us (`0xF1`).
+fn encode_st_min(st_min: Duration) -> u8 {
+    let micros = st_min.as_micros();
+    if micros == 0 {
+        0x00
+    } else if micros < 1000 {
+        0xF0 + (micros / 100).clamp(1, 9) as u8
+    } else {
+        (micros / 1000).min(0x7F) as u8
+    }
+}
+
+fn decode_st_min(byte: u8) -> Duration {
+    match byte {
+        0x00..=0x7F => Duration::from_millis(byte as u64),
+        0xF1..=0xF9 => Duration::from_micros((byte - 0xF0) as u64 * 100),
+        // 0x80-0xF0 and 0xFA-0xFF are reserved; treat them as the slowest defined pacing rather
+        // than rejecting a frame a pedantic peer sent us.
+        _ => Duration::from_millis(0x7F),
+    }
+}
+
+// Raw Frame IO ---------------------------------------------------------------------------------
+
+/// A link that can send and receive exactly one eight byte CAN frame at a time, already addressed
+/// by whatever arbitration id scheme the caller configured.
+///
+/// `IsoTpChannel` segments/reassembles payloads on top of this; it does not open sockets or
+/// manage arbitration ids itself, the same separation of concerns that keeps `Transport` apart
+/// from `ComInterface`.
+pub trait RawFrameIo {
+    fn send_frame(&mut self, frame: &[u8; 8]) -> Result<(), Error>;
+
+    /// Blocks until one frame is available or `timeout` elapses, returning `Error::ComNoResponse`
+    /// on timeout - the same convention `ComInterface::recv` uses.
+    fn recv_frame(&mut self, timeout: Duration) -> Result<[u8; 8], Error>;
+}
+
+// ISO-TP Channel ---------------------------------------------------------------------------------
+
+/// Configuration for one `IsoTpChannel`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IsoTpConfig {
+    /// Consecutive frames we ask a sender to send before waiting for another flow control frame
+    /// from us; `0` means "send the whole payload, no further flow control needed"
+    pub block_size: u8,
+
+    /// Minimum separation time we ask a sender to leave between consecutive frames
+    pub st_min: Duration,
+
+    /// How long to wait for the next frame of a sequence before giving up
+    pub timeout: Duration,
+}
+
+impl Default for IsoTpConfig {
+    fn default() -> Self {
+        IsoTpConfig {
+            block_size: 0,
+            st_min: Duration::from_millis(0),
+            timeout: Duration::from_millis(500),
+        }
+    }
+}
+
+///
+/// Segments a byte payload into ISO-TP frames on send and reassembles one on receive, over any
+/// link implementing `RawFrameIo`
+///
+/// This is the framing layer only: it knows nothing about `Msg` or the FranklyBoot request/result
+/// protocol, so it can carry whatever byte payload a higher layer needs across a CAN link wider
+/// than a single eight byte frame - `UdsDownloader` below is one such higher layer.
+///
+pub struct IsoTpChannel<IO: RawFrameIo> {
+    io: IO,
+    config: IsoTpConfig,
+}
+
+impl<IO: RawFrameIo> IsoTpChannel<IO> {
+    pub fn new(io: IO, config: IsoTpConfig) -> Self {
+        IsoTpChannel { io, config }
+    }
+
+    pub fn config(&self) -> IsoTpConfig {
+        self.config
+    }
+
+    pub fn into_inner(self) -> IO {
+        self.io
+    }
+
+    /// Sends `payload`, transparently splitting it into a first frame plus consecutive frames,
+    /// paced behind the peer's flow control, if it does not fit in a single frame
+    pub fn send(&mut self, payload: &[u8]) -> Result<(), Error> {
+        if payload.len() <= SF_MAX_LEN {
+            self.io.send_frame(
+                &IsoTpFrame::Single {
+                    data: payload.to_vec(),
+                }
+                .encode(),
+            )?;
+            return Ok(());
+        }
+
+        if payload.len() > MAX_PAYLOAD_LEN {
+            return Err(Error::Error(format!(
+                "ISO-TP payload of {} bytes exceeds the {} byte limit of a 12-bit length field",
+                payload.len(),
+                MAX_PAYLOAD_LEN
+            )));
+        }
+
+        self.io.send_frame(
+            &IsoTpFrame::First {
+                total_len: payload.len(),
+                data: payload[..FF_DATA_LEN].to_vec(),
+            }
+            .encode(),
+        )?;
+
+        let mut sent = FF_DATA_LEN;
+        let mut sequence: u8 = 1;
+        loop {
+            let (block_size, st_min) = self.await_flow_control()?;
+            let mut sent_in_block: u32 = 0;
+
+            while sent < payload.len() {
+                if block_size != 0 && sent_in_block == block_size as u32 {
+                    break;
+                }
+
+                let chunk_len = CF_DATA_LEN.min(payload.len() - sent);
+                self.io.send_frame(
+                    &IsoTpFrame::Consecutive {
+                        sequence,
+                        data: payload[sent..sent + chunk_len].to_vec(),
+                    }
+                    .encode(),
+                )?;
+
+                sent += chunk_len;
+                sequence = (sequence + 1) % 16;
+                sent_in_block += 1;
+
+                if !st_min.is_zero() && sent < payload.len() {
+                    std::thread::sleep(st_min);
+                }
+            }
+
+            if sent >= payload.len() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Waits for one flow control frame, looping past `Wait` frames, and returns the block
+    /// size/separation time it grants
+    fn await_flow_control(&mut self) -> Result<(u8, Duration), Error> {
+        loop {
+            let frame = IsoTpFrame::decode(&self.io.recv_frame(self.config.timeout)?)?;
+            match frame {
+                IsoTpFrame::FlowControl {
+                    status: FlowStatus::ContinueToSend,
+                    block_size,
+                    st_min,
+                } => return Ok((block_size, st_min)),
+                IsoTpFrame::FlowControl {
+                    status: FlowStatus::Wait,
+                    ..
+                } => continue,
+                IsoTpFrame::FlowControl {
+                    status: FlowStatus::Overflow,
+                    ..
+                } => {
+                    return Err(Error::Error(
+                        "ISO-TP peer reported a buffer overflow".to_string(),
+                    ))
+                }
+                other => {
+                    return Err(Error::MsgCorruption(format!(
+                        "Expected an ISO-TP flow control frame, got {:?}",
+                        other
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Receives one payload, reassembling it from a first frame and its consecutive frames (and
+    /// granting flow control in blocks of our own configured `block_size`) if it is segmented
+    pub fn recv(&mut self) -> Result<Vec<u8>, Error> {
+        let frame = IsoTpFrame::decode(&self.io.recv_frame(self.config.timeout)?)?;
+
+        let (total_len, mut payload) = match frame {
+            IsoTpFrame::Single { data } => return Ok(data),
+            IsoTpFrame::First { total_len, data } => (total_len, data),
+            other => {
+                return Err(Error::MsgCorruption(format!(
+                    "Expected an ISO-TP single or first frame, got {:?}",
+                    other
+                )))
+            }
+        };
+
+        self.grant_flow_control()?;
+
+        let mut expected_sequence: u8 = 1;
+        let mut received_in_block: u32 = 0;
+
+        while payload.len() < total_len {
+            let frame = IsoTpFrame::decode(&self.io.recv_frame(self.config.timeout)?)?;
+            let (sequence, data) = match frame {
+                IsoTpFrame::Consecutive { sequence, data } => (sequence, data),
+                other => {
+                    return Err(Error::MsgCorruption(format!(
+                        "Expected an ISO-TP consecutive frame, got {:?}",
+                        other
+                    )))
+                }
+            };
+
+            if sequence != expected_sequence {
+                return Err(Error::MsgCorruption(format!(
+                    "ISO-TP consecutive frame out of order: expected sequence {}, got {}",
+                    expected_sequence, sequence
+                )));
+            }
+
+            let remaining = total_len - payload.len();
+            payload.extend_from_slice(&data[..remaining.min(data.len())]);
+            expected_sequence = (expected_sequence + 1) % 16;
+            received_in_block += 1;
+
+            if self.config.block_size != 0
+                && received_in_block == self.config.block_size as u32
+                && payload.len() < total_len
+            {
+                received_in_block = 0;
+                self.grant_flow_control()?;
+            }
+        }
+
+        Ok(payload)
+    }
+
+    fn grant_flow_control(&mut self) -> Result<(), Error> {
+        self.io.send_frame(
+            &IsoTpFrame::FlowControl {
+                status: FlowStatus::ContinueToSend,
+                block_size: self.config.block_size,
+                st_min: self.config.st_min,
+            }
+            .encode(),
+        )
+    }
+}
+
+// UDS Segmented Download ------------------------------------------------------------------------
+
+const UDS_SID_REQUEST_DOWNLOAD: u8 = 0x34;
+const UDS_SID_TRANSFER_DATA: u8 = 0x36;
+const UDS_SID_REQUEST_TRANSFER_EXIT: u8 = 0x37;
+const UDS_SID_NEGATIVE_RESPONSE: u8 = 0x7F;
+const UDS_POSITIVE_RESPONSE_OFFSET: u8 = 0x40;
+
+/// Configuration for `UdsDownloader`, in addition to the `IsoTpChannel`'s own `IsoTpConfig`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UdsDownloadConfig {
+    /// Firmware bytes streamed per `TransferData` request
+    pub block_payload_len: usize,
+
+    /// How many times a request that times out is retried before giving up
+    pub max_retries: u32,
+}
+
+impl Default for UdsDownloadConfig {
+    fn default() -> Self {
+        UdsDownloadConfig {
+            block_payload_len: 4093,
+            max_retries: 3,
+        }
+    }
+}
+
+///
+/// Drives a `RequestDownload` -> `TransferData` (one request per firmware chunk) ->
+/// `RequestTransferExit` sequence over an `IsoTpChannel`
+///
+/// This is the UDS (ISO 14229) diagnostic-session counterpart to FranklyBoot's own page-buffer
+/// word protocol (`device::Device`), for bootloaders that speak standard download services over
+/// ISO-TP/CAN instead - it does not replace `Device`, which every other backend in this crate
+/// (`CANInterface`, `SerialInterface`, `NetInterface`) still drives through `ComInterface`'s
+/// `Msg` request/response exchange.
+///
+pub struct UdsDownloader<IO: RawFrameIo> {
+    channel: IsoTpChannel<IO>,
+    config: UdsDownloadConfig,
+}
+
+impl<IO: RawFrameIo> UdsDownloader<IO> {
+    pub fn new(channel: IsoTpChannel<IO>, config: UdsDownloadConfig) -> Self {
+        UdsDownloader { channel, config }
+    }
+
+    /// Streams every segment of `firmware` to the device
+    pub fn download(&mut self, firmware: &SegmentedFirmware) -> Result<(), Error> {
+        for segment in firmware.segments() {
+            self.download_segment(segment.get_start(), segment.get_data())?;
+        }
+        Ok(())
+    }
+
+    fn download_segment(&mut self, address: u32, data: &[u8]) -> Result<(), Error> {
+        self.request_download(address, data.len() as u32)?;
+
+        let mut block_counter: u8 = 1;
+        for chunk in data.chunks(self.config.block_payload_len) {
+            self.transfer_data(block_counter, chunk)?;
+            block_counter = block_counter.wrapping_add(1);
+        }
+
+        self.request_transfer_exit()
+    }
+
+    /// `RequestDownload` (0x34) with the default "no compression/encryption" data format
+    /// identifier, a 32-bit memory address, and a 32-bit memory size
+    fn request_download(&mut self, address: u32, size: u32) -> Result<(), Error> {
+        let mut request = vec![UDS_SID_REQUEST_DOWNLOAD, 0x00, 0x44];
+        request.extend_from_slice(&address.to_be_bytes());
+        request.extend_from_slice(&size.to_be_bytes());
+
+        self.send_with_retry(&request, UDS_SID_REQUEST_DOWNLOAD)?;
+        Ok(())
+    }
+
+    /// `TransferData` (0x36), checking that the device echoes back the same rolling block counter
+    fn transfer_data(&mut self, block_counter: u8, chunk: &[u8]) -> Result<(), Error> {
+        let mut request = vec![UDS_SID_TRANSFER_DATA, block_counter];
+        request.extend_from_slice(chunk);
+
+        let response = self.send_with_retry(&request, UDS_SID_TRANSFER_DATA)?;
+        if response.get(1) != Some(&block_counter) {
+            return Err(Error::Error(format!(
+                "TransferData block counter mismatch: sent {}, device echoed {:?}",
+                block_counter,
+                response.get(1)
+            )));
+        }
+        Ok(())
+    }
+
+    /// `RequestTransferExit` (0x37)
+    fn request_transfer_exit(&mut self) -> Result<(), Error> {
+        self.send_with_retry(&[UDS_SID_REQUEST_TRANSFER_EXIT], UDS_SID_REQUEST_TRANSFER_EXIT)?;
+        Ok(())
+    }
+
+    /// Sends `request`, retrying a timed-out exchange up to `max_retries` times, and returns the
+    /// positive response payload - or `Error::TransferAborted` if the device answered with a UDS
+    /// negative response (service 0x7F) instead
+    fn send_with_retry(&mut self, request: &[u8], expected_sid: u8) -> Result<Vec<u8>, Error> {
+        let mut retries = 0;
+        loop {
+            self.channel.send(request)?;
+
+            match self.channel.recv() {
+                Ok(response) => return Self::check_response(&response, expected_sid),
+                Err(Error::ComNoResponse) if retries < self.config.max_retries => {
+                    retries += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn check_response(response: &[u8], expected_sid: u8) -> Result<Vec<u8>, Error> {
+        match response.first() {
+            Some(&sid) if sid == UDS_SID_NEGATIVE_RESPONSE => {
+                let nrc = response.get(2).copied().unwrap_or(0);
+                Err(Error::TransferAborted(nrc))
+            }
+            Some(&sid) if sid == expected_sid + UDS_POSITIVE_RESPONSE_OFFSET => {
+                Ok(response.to_vec())
+            }
+            _ => Err(Error::MsgCorruption(format!(
+                "Unexpected UDS response for service {:#04X}: {:?}",
+                expected_sid, response
+            ))),
+        }
+    }
+}
+
+// Tests --------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// A fixed script of frames (or errors) to hand back from `recv_frame`, recording everything
+    /// sent - the raw, byte-level link analogue of `com::ComSimulator`'s queued responses. Once
+    /// the queue runs dry, every further `recv_frame` returns `Error::ComNoResponse`, the same
+    /// "dead link" convention `ComSimulator` uses.
+    struct ScriptedIo {
+        sent: Vec<[u8; 8]>,
+        to_recv: VecDeque<Result<[u8; 8], Error>>,
+    }
+
+    impl ScriptedIo {
+        fn new(frames: Vec<[u8; 8]>) -> Self {
+            Self::with_results(frames.into_iter().map(Ok).collect())
+        }
+
+        fn with_results(results: Vec<Result<[u8; 8], Error>>) -> Self {
+            ScriptedIo {
+                sent: Vec::new(),
+                to_recv: results.into(),
+            }
+        }
+    }
+
+    impl RawFrameIo for ScriptedIo {
+        fn send_frame(&mut self, frame: &[u8; 8]) -> Result<(), Error> {
+            self.sent.push(*frame);
+            Ok(())
+        }
+
+        fn recv_frame(&mut self, _timeout: Duration) -> Result<[u8; 8], Error> {
+            self.to_recv.pop_front().unwrap_or(Err(Error::ComNoResponse))
+        }
+    }
+
+    #[test]
+    fn iso_tp_frame_single_frame_round_trip() {
+        let frame = IsoTpFrame::Single {
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        let encoded = frame.encode();
+        assert_eq!(encoded[0], 0x03);
+        assert_eq!(IsoTpFrame::decode(&encoded).unwrap(), frame);
+    }
+
+    #[test]
+    fn iso_tp_frame_first_and_consecutive_frame_round_trip() {
+        let first = IsoTpFrame::First {
+            total_len: 20,
+            data: vec![1, 2, 3, 4, 5, 6],
+        };
+        let encoded = first.encode();
+        assert_eq!(encoded[0], 0x10);
+        assert_eq!(encoded[1], 20);
+        assert_eq!(IsoTpFrame::decode(&encoded).unwrap(), first);
+
+        let consecutive = IsoTpFrame::Consecutive {
+            sequence: 3,
+            data: vec![7, 8, 9, 10, 11, 12, 13],
+        };
+        let encoded = consecutive.encode();
+        assert_eq!(encoded[0], 0x23);
+        assert_eq!(IsoTpFrame::decode(&encoded).unwrap(), consecutive);
+    }
+
+    #[test]
+    fn iso_tp_frame_flow_control_st_min_round_trips_milliseconds_and_microseconds() {
+        let ms = IsoTpFrame::FlowControl {
+            status: FlowStatus::ContinueToSend,
+            block_size: 8,
+            st_min: Duration::from_millis(10),
+        };
+        assert_eq!(IsoTpFrame::decode(&ms.encode()).unwrap(), ms);
+
+        let us = IsoTpFrame::FlowControl {
+            status: FlowStatus::Wait,
+            block_size: 0,
+            st_min: Duration::from_micros(300),
+        };
+        assert_eq!(IsoTpFrame::decode(&us.encode()).unwrap(), us);
+    }
+
+    #[test]
+    fn iso_tp_channel_sends_small_payload_as_a_single_frame() {
+        let io = ScriptedIo::new(vec![]);
+        let mut channel = IsoTpChannel::new(io, IsoTpConfig::default());
+
+        channel.send(&[0xAA, 0xBB]).unwrap();
+
+        let io = channel.into_inner();
+        assert_eq!(io.sent, vec![[0x02, 0xAA, 0xBB, 0, 0, 0, 0, 0]]);
+    }
+
+    #[test]
+    fn iso_tp_channel_sends_multi_frame_payload_honoring_peer_block_size() {
+        let fc = IsoTpFrame::FlowControl {
+            status: FlowStatus::ContinueToSend,
+            block_size: 2,
+            st_min: Duration::from_millis(0),
+        };
+        let io = ScriptedIo::new(vec![fc.encode(), fc.encode()]);
+        let mut channel = IsoTpChannel::new(io, IsoTpConfig::default());
+
+        let payload: Vec<u8> = (1..=27).collect();
+        channel.send(&payload).unwrap();
+
+        let io = channel.into_inner();
+        assert_eq!(io.sent.len(), 4); // First frame + 3 consecutive frames
+        assert_eq!(io.sent[0][0], 0x10);
+        assert_eq!(io.sent[1][0], 0x21);
+        assert_eq!(io.sent[2][0], 0x22);
+        assert_eq!(io.sent[3][0], 0x23);
+    }
+
+    #[test]
+    fn iso_tp_channel_reassembles_a_multi_frame_payload_and_grants_its_own_flow_control() {
+        let ff = IsoTpFrame::First {
+            total_len: 20,
+            data: vec![1, 2, 3, 4, 5, 6],
+        }
+        .encode();
+        let cf1 = IsoTpFrame::Consecutive {
+            sequence: 1,
+            data: vec![7, 8, 9, 10, 11, 12, 13],
+        }
+        .encode();
+        let cf2 = IsoTpFrame::Consecutive {
+            sequence: 2,
+            data: vec![14, 15, 16, 17, 18, 19, 20],
+        }
+        .encode();
+
+        let io = ScriptedIo::new(vec![ff, cf1, cf2]);
+        let mut channel = IsoTpChannel::new(io, IsoTpConfig::default());
+
+        let payload = channel.recv().unwrap();
+        assert_eq!(payload, (1..=20).collect::<Vec<u8>>());
+
+        let io = channel.into_inner();
+        assert_eq!(io.sent.len(), 1);
+        assert_eq!(io.sent[0][0], 0x30);
+    }
+
+    #[test]
+    fn uds_downloader_streams_a_segment_through_request_download_transfer_data_and_exit() {
+        // The 11-byte RequestDownload goes out as a multi-frame message, so the peer's flow
+        // control comes first.
+        let responses = vec![
+            IsoTpFrame::FlowControl {
+                status: FlowStatus::ContinueToSend,
+                block_size: 0,
+                st_min: Duration::from_millis(0),
+            }
+            .encode(),
+            IsoTpFrame::Single {
+                data: vec![0x74, 0x20],
+            }
+            .encode(),
+            IsoTpFrame::Single {
+                data: vec![0x76, 0x01],
+            }
+            .encode(),
+            IsoTpFrame::Single { data: vec![0x77] }.encode(),
+        ];
+        let io = ScriptedIo::new(responses);
+        let channel = IsoTpChannel::new(io, IsoTpConfig::default());
+        let mut downloader = UdsDownloader::new(channel, UdsDownloadConfig::default());
+
+        let mut firmware = SegmentedFirmware::new();
+        firmware.insert_run(0x1000, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+
+        downloader.download(&firmware).unwrap();
+    }
+
+    #[test]
+    fn uds_downloader_surfaces_a_negative_response_as_transfer_aborted() {
+        // Flow control for the multi-frame RequestDownload, then 0x7F, request-download service id
+        // echoed, NRC 0x31 "request out of range"
+        let responses = vec![
+            IsoTpFrame::FlowControl {
+                status: FlowStatus::ContinueToSend,
+                block_size: 0,
+                st_min: Duration::from_millis(0),
+            }
+            .encode(),
+            IsoTpFrame::Single {
+                data: vec![0x7F, 0x34, 0x31],
+            }
+            .encode(),
+        ];
+        let io = ScriptedIo::new(responses);
+        let channel = IsoTpChannel::new(io, IsoTpConfig::default());
+        let mut downloader = UdsDownloader::new(channel, UdsDownloadConfig::default());
+
+        let result = downloader.request_download(0x1000, 4);
+        assert_eq!(result, Err(Error::TransferAborted(0x31)));
+    }
+
+    #[test]
+    fn uds_downloader_retries_once_after_a_timed_out_response() {
+        let io = ScriptedIo::with_results(vec![
+            Err(Error::ComNoResponse),
+            Ok(IsoTpFrame::Single { data: vec![0x77] }.encode()),
+        ]);
+        let channel = IsoTpChannel::new(io, IsoTpConfig::default());
+        let mut config = UdsDownloadConfig::default();
+        config.max_retries = 1;
+        let mut downloader = UdsDownloader::new(channel, config);
+
+        downloader.request_transfer_exit().unwrap();
+    }
+
+    #[test]
+    fn uds_downloader_gives_up_once_retries_are_exhausted() {
+        let io = ScriptedIo::with_results(vec![]);
+        let channel = IsoTpChannel::new(io, IsoTpConfig::default());
+        let mut config = UdsDownloadConfig::default();
+        config.max_retries = 2;
+        let mut downloader = UdsDownloader::new(channel, config);
+
+        let result = downloader.request_transfer_exit();
+        assert_eq!(result, Err(Error::ComNoResponse));
+    }
+}
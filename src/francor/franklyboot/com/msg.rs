@@ -1,9 +1,62 @@
 use crate::francor::franklyboot::Error;
+
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
+use crc::{Crc, CRC_16_IBM_3740};
 use std::fmt;
+use std::io::{Read, Write};
+
+/// CRC16 trailer for `Msg::write_framed`/`read_framed`'s self-describing frame
+const FRAME_CRC16: Crc<u16> = Crc::<u16>::new(&CRC_16_IBM_3740);
+
+// Protocol Error -----------------------------------------------------------------------------------
+
+/// A frame could not be decoded into a valid `Msg`.
+///
+/// Kept separate from `Error` so parsing code (fuzz targets, capture/logging tooling) can match
+/// on exactly why a frame was rejected without having to pattern-match `Error`'s much broader,
+/// string-based variants.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ProtocolError {
+    /// `RequestType::try_from_u16` was given an opcode that maps to no known request
+    UnknownRequest(u16),
+    /// `ResultType::try_from_u8` was given a byte that maps to no known result
+    UnknownResponse(u8),
+    /// `Msg::try_from_raw_data_array` was given fewer than the eight bytes a frame requires
+    TruncatedFrame,
+    /// `SwapState::try_from_u8` was given a byte that maps to no known swap state
+    UnknownSwapState(u8),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtocolError::UnknownRequest(value) => {
+                write!(f, "Unknown request type: {:#06X}", value)
+            }
+            ProtocolError::UnknownResponse(value) => {
+                write!(f, "Unknown result type: {:#04X}", value)
+            }
+            ProtocolError::TruncatedFrame => {
+                write!(f, "Frame is shorter than the eight bytes a message requires")
+            }
+            ProtocolError::UnknownSwapState(value) => {
+                write!(f, "Unknown swap state: {:#04X}", value)
+            }
+        }
+    }
+}
+
+/// Frame decoding errors fold into `Error::MsgCorruption` so `ComInterface::recv` implementations
+/// can propagate them with `?` alongside their own I/O errors.
+impl From<ProtocolError> for Error {
+    fn from(err: ProtocolError) -> Self {
+        Error::MsgCorruption(err.to_string())
+    }
+}
 
 // Request Type -----------------------------------------------------------------------------------
 
-#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, serde::Serialize)]
 pub enum RequestType {
     Ping,        //< Ping device | Response is bootloader version
     ResetDevice, //< Resets the device (hardware reset)
@@ -18,9 +71,10 @@ pub enum RequestType {
     DevInfoUID,               //< Reads the device unique ID
 
     /* Flash information */
-    FlashInfoStartAddr, //< Get the start address of the flash area
-    FlashInfoPageSize,  //< Get the size in bytes of a page
-    FlashInfoNumPages,  //< Get the number of pages (including bootloader area)
+    FlashInfoStartAddr,     //< Get the start address of the flash area
+    FlashInfoPageSize,      //< Get the size in bytes of a page
+    FlashInfoNumPages,      //< Get the number of pages (including bootloader area)
+    FlashInfoWriteWindowSize, //< Get the max number of in-flight PageBufferWriteWord requests the device can buffer
 
     /* App Information */
     AppInfoPageIdx, //< Get the page idx of app area in flash
@@ -28,7 +82,8 @@ pub enum RequestType {
     AppInfoCRCStrd, //< Get the stored CRC value used for safe startup
 
     /* Flash Read commands */
-    FlashReadWord, //< Reads a word from the flash
+    FlashReadWord,    //< Reads a word from the flash
+    FlashPageCRCCalc, //< Calculates the CRC over a flash page's on-device content (given page idx)
 
     /* Page Buffer Commands */
     PageBufferClear,        //< Clears the page buffer (RAM)
@@ -39,36 +94,71 @@ pub enum RequestType {
 
     /* Flash Write Commands*/
     FlashWriteErasePage, //< Erases a flash page
+    FlashWriteMassErase, //< Erases the whole application area in a single bulk operation
     FlashWriteAppCRC,    //< Writes the CRC of the app to the flash
+
+    /* A/B Swap Commands */
+    SwapStart,    //< Starts swapping the staged image into the active bank on next boot
+    ConfirmImage, //< Confirms the swapped-in image is good, cancelling the automatic rollback
+    SwapStatus,   //< Reads the current `SwapState` of the bank swap
+
+    /* Config Store Commands */
+    ConfigRead,   //< Reads a word of a config entry identified by key hash
+    ConfigWrite,  //< Commits the page buffer as a config entry identified by key hash
+    ConfigErase,  //< Tombstones a config entry identified by key hash
+    ConfigList,   //< Reads the key hash of the config entry at a given index
 }
 
 impl RequestType {
+    /// Converts a u16 value to a request type, panicking on an unknown opcode.
+    #[deprecated(note = "panics on unknown/corrupted opcodes; use try_from_u16 instead")]
     pub fn from_u16(value: u16) -> RequestType {
+        #[allow(deprecated)]
+        match Self::try_from_u16(value) {
+            Ok(request) => request,
+            Err(_) => panic!("Unknown request type: {}", value),
+        }
+    }
+
+    /// Converts a u16 value to a request type, returning `ProtocolError::UnknownRequest` for an
+    /// unknown/unsupported opcode instead of panicking. Used by capture/logging code and by
+    /// `Msg::try_from_raw_data_array`, which must not crash on corrupted traffic.
+    pub fn try_from_u16(value: u16) -> Result<RequestType, ProtocolError> {
         match value {
-            0x0001 => RequestType::Ping,
-            0x0011 => RequestType::ResetDevice,
-            0x0012 => RequestType::StartApp,
-            0x0101 => RequestType::DevInfoBootloaderVersion,
-            0x0102 => RequestType::DevInfoBootloaderCRC,
-            0x0103 => RequestType::DevInfoVID,
-            0x0104 => RequestType::DevInfoPID,
-            0x0105 => RequestType::DevInfoPRD,
-            0x0106 => RequestType::DevInfoUID,
-            0x0201 => RequestType::FlashInfoStartAddr,
-            0x0202 => RequestType::FlashInfoPageSize,
-            0x0203 => RequestType::FlashInfoNumPages,
-            0x0301 => RequestType::AppInfoPageIdx,
-            0x0302 => RequestType::AppInfoCRCCalc,
-            0x0303 => RequestType::AppInfoCRCStrd,
-            0x0401 => RequestType::FlashReadWord,
-            0x1001 => RequestType::PageBufferClear,
-            0x1002 => RequestType::PageBufferReadWord,
-            0x1003 => RequestType::PageBufferWriteWord,
-            0x1004 => RequestType::PageBufferCalcCRC,
-            0x1005 => RequestType::PageBufferWriteToFlash,
-            0x1101 => RequestType::FlashWriteErasePage,
-            0x1102 => RequestType::FlashWriteAppCRC,
-            _ => panic!("Unknown request type: {}", value),
+            0x0001 => Ok(RequestType::Ping),
+            0x0011 => Ok(RequestType::ResetDevice),
+            0x0012 => Ok(RequestType::StartApp),
+            0x0101 => Ok(RequestType::DevInfoBootloaderVersion),
+            0x0102 => Ok(RequestType::DevInfoBootloaderCRC),
+            0x0103 => Ok(RequestType::DevInfoVID),
+            0x0104 => Ok(RequestType::DevInfoPID),
+            0x0105 => Ok(RequestType::DevInfoPRD),
+            0x0106 => Ok(RequestType::DevInfoUID),
+            0x0201 => Ok(RequestType::FlashInfoStartAddr),
+            0x0202 => Ok(RequestType::FlashInfoPageSize),
+            0x0203 => Ok(RequestType::FlashInfoNumPages),
+            0x0204 => Ok(RequestType::FlashInfoWriteWindowSize),
+            0x0301 => Ok(RequestType::AppInfoPageIdx),
+            0x0302 => Ok(RequestType::AppInfoCRCCalc),
+            0x0303 => Ok(RequestType::AppInfoCRCStrd),
+            0x0401 => Ok(RequestType::FlashReadWord),
+            0x0402 => Ok(RequestType::FlashPageCRCCalc),
+            0x1001 => Ok(RequestType::PageBufferClear),
+            0x1002 => Ok(RequestType::PageBufferReadWord),
+            0x1003 => Ok(RequestType::PageBufferWriteWord),
+            0x1004 => Ok(RequestType::PageBufferCalcCRC),
+            0x1005 => Ok(RequestType::PageBufferWriteToFlash),
+            0x1101 => Ok(RequestType::FlashWriteErasePage),
+            0x1102 => Ok(RequestType::FlashWriteAppCRC),
+            0x1103 => Ok(RequestType::FlashWriteMassErase),
+            0x1201 => Ok(RequestType::SwapStart),
+            0x1202 => Ok(RequestType::ConfirmImage),
+            0x1203 => Ok(RequestType::SwapStatus),
+            0x0500 => Ok(RequestType::ConfigRead),
+            0x0501 => Ok(RequestType::ConfigWrite),
+            0x0502 => Ok(RequestType::ConfigErase),
+            0x0503 => Ok(RequestType::ConfigList),
+            _ => Err(ProtocolError::UnknownRequest(value)),
         }
     }
 
@@ -86,10 +176,12 @@ impl RequestType {
             RequestType::FlashInfoStartAddr => 0x0201,
             RequestType::FlashInfoPageSize => 0x0202,
             RequestType::FlashInfoNumPages => 0x0203,
+            RequestType::FlashInfoWriteWindowSize => 0x0204,
             RequestType::AppInfoPageIdx => 0x0301,
             RequestType::AppInfoCRCCalc => 0x0302,
             RequestType::AppInfoCRCStrd => 0x0303,
             RequestType::FlashReadWord => 0x0401,
+            RequestType::FlashPageCRCCalc => 0x0402,
             RequestType::PageBufferClear => 0x1001,
             RequestType::PageBufferReadWord => 0x1002,
             RequestType::PageBufferWriteWord => 0x1003,
@@ -97,6 +189,14 @@ impl RequestType {
             RequestType::PageBufferWriteToFlash => 0x1005,
             RequestType::FlashWriteErasePage => 0x1101,
             RequestType::FlashWriteAppCRC => 0x1102,
+            RequestType::FlashWriteMassErase => 0x1103,
+            RequestType::SwapStart => 0x1201,
+            RequestType::ConfirmImage => 0x1202,
+            RequestType::SwapStatus => 0x1203,
+            RequestType::ConfigRead => 0x0500,
+            RequestType::ConfigWrite => 0x0501,
+            RequestType::ConfigErase => 0x0502,
+            RequestType::ConfigList => 0x0503,
         }
     }
 }
@@ -106,7 +206,7 @@ impl RequestType {
 /// This enumeration describes the possible result types of the bootloader.
 ///
 /// Every request generates a response from the device which contains the result type.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize)]
 pub enum ResultType {
     None, // No result / not specified
     Ok,   // Message was processed successfully / result ok
@@ -135,18 +235,29 @@ impl ResultType {
         }
     }
 
-    /// Converts a u8 value to a result type
+    /// Converts a u8 value to a result type, panicking on an unknown byte.
+    #[deprecated(note = "panics on unknown/corrupted result bytes; use try_from_u8 instead")]
     pub fn from_u8(value: u8) -> ResultType {
+        #[allow(deprecated)]
+        match Self::try_from_u8(value) {
+            Ok(result) => result,
+            Err(_) => panic!("Unknown result type: {}", value),
+        }
+    }
+
+    /// Converts a u8 value to a result type, returning `ProtocolError::UnknownResponse` for an
+    /// unknown byte instead of panicking.
+    pub fn try_from_u8(value: u8) -> Result<ResultType, ProtocolError> {
         match value {
-            0x00 => ResultType::None,
-            0x01 => ResultType::Ok,
-            0xFE => ResultType::Error,
-            0xFD => ResultType::ErrUnknownReq,
-            0xFC => ResultType::ErrNotSupported,
-            0xFB => ResultType::ErrCRCInvld,
-            0xFA => ResultType::ErrPageFull,
-            0xF9 => ResultType::ErrInvldArg,
-            _ => panic!("Unknown result type: {}", value),
+            0x00 => Ok(ResultType::None),
+            0x01 => Ok(ResultType::Ok),
+            0xFE => Ok(ResultType::Error),
+            0xFD => Ok(ResultType::ErrUnknownReq),
+            0xFC => Ok(ResultType::ErrNotSupported),
+            0xFB => Ok(ResultType::ErrCRCInvld),
+            0xFA => Ok(ResultType::ErrPageFull),
+            0xF9 => Ok(ResultType::ErrInvldArg),
+            _ => Err(ProtocolError::UnknownResponse(value)),
         }
     }
 
@@ -189,6 +300,73 @@ impl fmt::Display for ResultType {
     }
 }
 
+// Swap State ---------------------------------------------------------------------------------------
+
+/// This enumeration describes the possible states of an A/B bank swap, as read back via
+/// `RequestType::SwapStatus`.
+///
+/// The swap itself (copying the staged image into the active bank, page by page, with a
+/// persisted resume counter so a power loss mid-swap picks back up rather than bricking the
+/// device) runs entirely on the bootloader; the host only starts it (`SwapStart`), polls this
+/// state, and confirms the result (`ConfirmImage`) once the new image has proven itself.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SwapState {
+    None,            // No swap in progress / not started
+    InProgress,      // Swap is being applied page by page
+    Confirmed,       // Swapped-in image was confirmed good, rollback disarmed
+    RevertRequested, // Swapped-in image was not confirmed in time, bootloader will roll back
+}
+
+impl SwapState {
+    /// Converts the swap state to a u8 value
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            SwapState::None => 0x00,
+            SwapState::InProgress => 0x01,
+            SwapState::Confirmed => 0x02,
+            SwapState::RevertRequested => 0x03,
+        }
+    }
+
+    /// Converts a u8 value to a swap state, panicking on an unknown value.
+    #[deprecated(note = "panics on unknown/corrupted values; use try_from_u8 instead")]
+    pub fn from_u8(value: u8) -> SwapState {
+        #[allow(deprecated)]
+        match Self::try_from_u8(value) {
+            Ok(state) => state,
+            Err(_) => panic!("Unknown swap state: {}", value),
+        }
+    }
+
+    /// Converts a u8 value to a swap state, returning `ProtocolError::UnknownSwapState` for an
+    /// unknown/unsupported value instead of panicking. Used by `Device::swap_status`, which must
+    /// not crash if it talks to a bootloader reporting a swap state this crate predates.
+    pub fn try_from_u8(value: u8) -> Result<SwapState, ProtocolError> {
+        match value {
+            0x00 => Ok(SwapState::None),
+            0x01 => Ok(SwapState::InProgress),
+            0x02 => Ok(SwapState::Confirmed),
+            0x03 => Ok(SwapState::RevertRequested),
+            _ => Err(ProtocolError::UnknownSwapState(value)),
+        }
+    }
+}
+
+/// Implementation of the Display trait for the SwapState enumeration
+impl fmt::Display for SwapState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwapState::None => write!(f, "None: No swap in progress!"),
+            SwapState::InProgress => write!(f, "InProgress: Swap is being applied!"),
+            SwapState::Confirmed => write!(f, "Confirmed: Swapped-in image was confirmed good!"),
+            SwapState::RevertRequested => write!(
+                f,
+                "RevertRequested: Swapped-in image was not confirmed, rollback pending!"
+            ),
+        }
+    }
+}
+
 // Message Data -----------------------------------------------------------------------------------
 
 /// Raw data type of message payload data
@@ -242,6 +420,22 @@ impl MsgData {
     pub fn get_array(&self) -> &MsgDataRaw {
         &self.data
     }
+
+    /// Reads a payload word from `r`, little-endian, matching the wire layout `to_raw_data_array`
+    /// lays out in its last four bytes
+    pub fn read_from<R: Read>(r: &mut R) -> Result<MsgData, Error> {
+        let value = r
+            .read_u32::<LittleEndian>()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        Ok(MsgData::from_word(value))
+    }
+
+    /// Writes this payload word to `w`, little-endian, matching the wire layout `to_raw_data_array`
+    /// lays out in its last four bytes
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+        w.write_u32::<LittleEndian>(self.to_word())
+            .map_err(|err| Error::ComError(err.to_string()))
+    }
 }
 
 // Message ----------------------------------------------------------------------------------------
@@ -286,19 +480,35 @@ impl Msg {
         }
     }
 
-    /// Create a new message object from a raw data array (raw message)
+    /// Create a new message object from a raw data array (raw message), panicking if it contains
+    /// an unknown request or result byte.
+    #[deprecated(note = "panics on unknown/corrupted opcodes; use try_from_raw_data_array instead")]
     pub fn from_raw_data_array(data: &MsgRaw) -> Msg {
-        let request = RequestType::from_u16((data[0] as u16) | ((data[1] as u16) << 8));
-        let result = ResultType::from_u8(data[2]);
+        Self::try_from_raw_data_array(data).expect("Corrupted message")
+    }
+
+    /// Creates a new message object from a raw frame, returning a `ProtocolError` instead of
+    /// panicking if the frame is too short or contains an unknown request/result byte.
+    ///
+    /// Takes a slice rather than a fixed size `MsgRaw` so a frame truncated by a flaky link
+    /// reports `ProtocolError::TruncatedFrame` instead of failing to type-check; this is the
+    /// function a fuzzer feeds arbitrary byte slices into.
+    pub fn try_from_raw_data_array(data: &[u8]) -> Result<Msg, ProtocolError> {
+        if data.len() < 8 {
+            return Err(ProtocolError::TruncatedFrame);
+        }
+
+        let request = RequestType::try_from_u16((data[0] as u16) | ((data[1] as u16) << 8))?;
+        let result = ResultType::try_from_u8(data[2])?;
         let packet_id = data[3];
         let data = MsgData::from_array(&[data[4], data[5], data[6], data[7]]);
 
-        Msg {
+        Ok(Msg {
             request: request,
             result: result,
             packet_id: packet_id,
             data: data,
-        }
+        })
     }
 
     /// Converts a message object to a raw data array (raw message)
@@ -316,6 +526,135 @@ impl Msg {
         data
     }
 
+    /// Reads a message incrementally from a byte stream, mirroring ARTIQ's `libio` proto layer:
+    /// the request, result and packet id are read word/byte at a time via `byteorder` instead of
+    /// requiring a full eight-byte frame to already be buffered, which lets a streaming transport
+    /// (serial, TCP, a CAN-gateway) consume a message off the wire as its bytes arrive.
+    ///
+    /// Returns `Error::MsgCorruption` if the request/result bytes don't decode, same as
+    /// `try_from_raw_data_array`, and propagates the stream's own I/O error otherwise.
+    pub fn read_from<R: Read>(r: &mut R) -> Result<Msg, Error> {
+        let request_raw = r
+            .read_u16::<LittleEndian>()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        let result_raw = r
+            .read_u8()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        let packet_id = r
+            .read_u8()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        let data = MsgData::read_from(r)?;
+
+        Ok(Msg {
+            request: RequestType::try_from_u16(request_raw)?,
+            result: ResultType::try_from_u8(result_raw)?,
+            packet_id: packet_id,
+            data: data,
+        })
+    }
+
+    /// Writes this message to a byte stream in the same layout `to_raw_data_array` produces, using
+    /// `byteorder` instead of a fixed `[u8; 8]` buffer so streaming transports can share this codec
+    /// with the in-memory one.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+        w.write_u16::<LittleEndian>(self.request.to_u16())
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        w.write_u8(self.result.to_u8())
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        w.write_u8(self.packet_id)
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        self.data.write_to(w)
+    }
+
+    /// Writes this message as a self-describing frame: a fixed header (request, result, packet
+    /// id), a 2-byte big-endian payload length, the payload bytes, then a trailing CRC16 over the
+    /// whole frame.
+    ///
+    /// Unlike `write_to`'s fixed eight-byte layout (which assumes the reader already knows where
+    /// a frame starts and ends), this is meant for a transport that isn't inherently
+    /// message-aligned - plain TCP, or a byte-stream serial link without `FrameParser`'s
+    /// resynchronizing scan - where the length prefix and CRC let a reader detect truncation or
+    /// corruption directly instead of only noticing a frame "doesn't decode as a known opcode".
+    pub fn write_framed<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+        let payload = self.data.get_array();
+
+        let mut frame = Vec::with_capacity(6 + payload.len());
+        frame
+            .write_u16::<BigEndian>(self.request.to_u16())
+            .expect("write to Vec<u8> cannot fail");
+        frame
+            .write_u8(self.result.to_u8())
+            .expect("write to Vec<u8> cannot fail");
+        frame.write_u8(self.packet_id).expect("write to Vec<u8> cannot fail");
+        frame
+            .write_u16::<BigEndian>(payload.len() as u16)
+            .expect("write to Vec<u8> cannot fail");
+        frame.extend_from_slice(payload);
+
+        let crc = FRAME_CRC16.checksum(&frame);
+        frame
+            .write_u16::<BigEndian>(crc)
+            .expect("write to Vec<u8> cannot fail");
+
+        w.write_all(&frame).map_err(|err| Error::ComError(err.to_string()))
+    }
+
+    /// Reads a message written by `write_framed`, validating the payload length and CRC16 before
+    /// decoding the request/result bytes.
+    ///
+    /// Returns `Error::MsgCorruption` on a length mismatch or CRC failure - matching the
+    /// corruption semantics `is_response_ok` already applies to packet-id/request mismatches, just
+    /// surfaced earlier, before the request/result bytes are even interpreted.
+    pub fn read_framed<R: Read>(r: &mut R) -> Result<Msg, Error> {
+        let request_raw = r
+            .read_u16::<BigEndian>()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+        let result_raw = r.read_u8().map_err(|err| Error::ComError(err.to_string()))?;
+        let packet_id = r.read_u8().map_err(|err| Error::ComError(err.to_string()))?;
+        let payload_len = r
+            .read_u16::<BigEndian>()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+
+        let mut payload = vec![0u8; payload_len as usize];
+        r.read_exact(&mut payload)
+            .map_err(|err| Error::ComError(err.to_string()))?;
+
+        let received_crc = r
+            .read_u16::<BigEndian>()
+            .map_err(|err| Error::ComError(err.to_string()))?;
+
+        let mut frame = Vec::with_capacity(6 + payload.len());
+        frame
+            .write_u16::<BigEndian>(request_raw)
+            .expect("write to Vec<u8> cannot fail");
+        frame.write_u8(result_raw).expect("write to Vec<u8> cannot fail");
+        frame.write_u8(packet_id).expect("write to Vec<u8> cannot fail");
+        frame
+            .write_u16::<BigEndian>(payload_len)
+            .expect("write to Vec<u8> cannot fail");
+        frame.extend_from_slice(&payload);
+
+        if FRAME_CRC16.checksum(&frame) != received_crc {
+            return Err(Error::MsgCorruption(
+                "Framed message failed its CRC16 check".to_string(),
+            ));
+        }
+
+        if payload_len != 4 {
+            return Err(Error::MsgCorruption(format!(
+                "Framed message declared a {}-byte payload, expected 4",
+                payload_len
+            )));
+        }
+
+        Ok(Msg {
+            request: RequestType::try_from_u16(request_raw)?,
+            result: ResultType::try_from_u8(result_raw)?,
+            packet_id: packet_id,
+            data: MsgData::from_array(&[payload[0], payload[1], payload[2], payload[3]]),
+        })
+    }
+
     /// Check if response message is ok
     ///
     /// This function checks if the response message is ok.
@@ -435,10 +774,12 @@ mod tests {
         assert_eq!(RequestType::FlashInfoStartAddr.to_u16(), 0x0201);
         assert_eq!(RequestType::FlashInfoPageSize.to_u16(), 0x0202);
         assert_eq!(RequestType::FlashInfoNumPages.to_u16(), 0x0203);
+        assert_eq!(RequestType::FlashInfoWriteWindowSize.to_u16(), 0x0204);
         assert_eq!(RequestType::AppInfoPageIdx.to_u16(), 0x0301);
         assert_eq!(RequestType::AppInfoCRCCalc.to_u16(), 0x0302);
         assert_eq!(RequestType::AppInfoCRCStrd.to_u16(), 0x0303);
         assert_eq!(RequestType::FlashReadWord.to_u16(), 0x0401);
+        assert_eq!(RequestType::FlashPageCRCCalc.to_u16(), 0x0402);
         assert_eq!(RequestType::PageBufferClear.to_u16(), 0x1001);
         assert_eq!(RequestType::PageBufferReadWord.to_u16(), 0x1002);
         assert_eq!(RequestType::PageBufferWriteWord.to_u16(), 0x1003);
@@ -446,9 +787,18 @@ mod tests {
         assert_eq!(RequestType::PageBufferWriteToFlash.to_u16(), 0x1005);
         assert_eq!(RequestType::FlashWriteErasePage.to_u16(), 0x1101);
         assert_eq!(RequestType::FlashWriteAppCRC.to_u16(), 0x1102);
+        assert_eq!(RequestType::FlashWriteMassErase.to_u16(), 0x1103);
+        assert_eq!(RequestType::SwapStart.to_u16(), 0x1201);
+        assert_eq!(RequestType::ConfirmImage.to_u16(), 0x1202);
+        assert_eq!(RequestType::SwapStatus.to_u16(), 0x1203);
+        assert_eq!(RequestType::ConfigRead.to_u16(), 0x0500);
+        assert_eq!(RequestType::ConfigWrite.to_u16(), 0x0501);
+        assert_eq!(RequestType::ConfigErase.to_u16(), 0x0502);
+        assert_eq!(RequestType::ConfigList.to_u16(), 0x0503);
     }
 
     #[test]
+    #[allow(deprecated)]
     fn request_type_convert_from_u16() {
         assert_eq!(RequestType::from_u16(0x0001), RequestType::Ping);
         assert_eq!(RequestType::from_u16(0x0011), RequestType::ResetDevice);
@@ -477,10 +827,18 @@ mod tests {
             RequestType::from_u16(0x0203),
             RequestType::FlashInfoNumPages
         );
+        assert_eq!(
+            RequestType::from_u16(0x0204),
+            RequestType::FlashInfoWriteWindowSize
+        );
         assert_eq!(RequestType::from_u16(0x0301), RequestType::AppInfoPageIdx);
         assert_eq!(RequestType::from_u16(0x0302), RequestType::AppInfoCRCCalc);
         assert_eq!(RequestType::from_u16(0x0303), RequestType::AppInfoCRCStrd);
         assert_eq!(RequestType::from_u16(0x0401), RequestType::FlashReadWord);
+        assert_eq!(
+            RequestType::from_u16(0x0402),
+            RequestType::FlashPageCRCCalc
+        );
         assert_eq!(RequestType::from_u16(0x1001), RequestType::PageBufferClear);
         assert_eq!(
             RequestType::from_u16(0x1002),
@@ -503,6 +861,26 @@ mod tests {
             RequestType::FlashWriteErasePage
         );
         assert_eq!(RequestType::from_u16(0x1102), RequestType::FlashWriteAppCRC);
+        assert_eq!(
+            RequestType::from_u16(0x1103),
+            RequestType::FlashWriteMassErase
+        );
+        assert_eq!(RequestType::from_u16(0x1201), RequestType::SwapStart);
+        assert_eq!(RequestType::from_u16(0x1202), RequestType::ConfirmImage);
+        assert_eq!(RequestType::from_u16(0x1203), RequestType::SwapStatus);
+        assert_eq!(RequestType::from_u16(0x0500), RequestType::ConfigRead);
+        assert_eq!(RequestType::from_u16(0x0501), RequestType::ConfigWrite);
+        assert_eq!(RequestType::from_u16(0x0502), RequestType::ConfigErase);
+        assert_eq!(RequestType::from_u16(0x0503), RequestType::ConfigList);
+    }
+
+    #[test]
+    fn request_type_try_convert_from_u16() {
+        assert_eq!(RequestType::try_from_u16(0x0001), Ok(RequestType::Ping));
+        assert_eq!(
+            RequestType::try_from_u16(0xDEAD),
+            Err(ProtocolError::UnknownRequest(0xDEAD))
+        );
     }
 
     #[test]
@@ -518,6 +896,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn result_convert_from_u8() {
         assert_eq!(ResultType::from_u8(0x00), ResultType::None);
         assert_eq!(ResultType::from_u8(0x01), ResultType::Ok);
@@ -529,6 +908,15 @@ mod tests {
         assert_eq!(ResultType::from_u8(0xF9), ResultType::ErrInvldArg);
     }
 
+    #[test]
+    fn result_try_convert_from_u8() {
+        assert_eq!(ResultType::try_from_u8(0x00), Ok(ResultType::None));
+        assert_eq!(
+            ResultType::try_from_u8(0x42),
+            Err(ProtocolError::UnknownResponse(0x42))
+        );
+    }
+
     #[test]
     fn result_is_ok() {
         assert_eq!(ResultType::None.is_ok(), true);
@@ -553,6 +941,40 @@ mod tests {
         assert_eq!(ResultType::ErrInvldArg.is_error(), true);
     }
 
+    #[test]
+    fn swap_state_convert_to_u8() {
+        assert_eq!(SwapState::None.to_u8(), 0x00);
+        assert_eq!(SwapState::InProgress.to_u8(), 0x01);
+        assert_eq!(SwapState::Confirmed.to_u8(), 0x02);
+        assert_eq!(SwapState::RevertRequested.to_u8(), 0x03);
+    }
+
+    #[test]
+    fn swap_state_convert_from_u8() {
+        #[allow(deprecated)]
+        {
+            assert_eq!(SwapState::from_u8(0x00), SwapState::None);
+            assert_eq!(SwapState::from_u8(0x01), SwapState::InProgress);
+            assert_eq!(SwapState::from_u8(0x02), SwapState::Confirmed);
+            assert_eq!(SwapState::from_u8(0x03), SwapState::RevertRequested);
+        }
+    }
+
+    #[test]
+    fn swap_state_try_convert_from_u8() {
+        assert_eq!(SwapState::try_from_u8(0x00), Ok(SwapState::None));
+        assert_eq!(SwapState::try_from_u8(0x01), Ok(SwapState::InProgress));
+        assert_eq!(SwapState::try_from_u8(0x02), Ok(SwapState::Confirmed));
+        assert_eq!(
+            SwapState::try_from_u8(0x03),
+            Ok(SwapState::RevertRequested)
+        );
+        assert_eq!(
+            SwapState::try_from_u8(0x42),
+            Err(ProtocolError::UnknownSwapState(0x42))
+        );
+    }
+
     #[test]
     fn msg_data_new() {
         assert_eq!(*MsgData::new().get_array(), [0; 4]);
@@ -599,6 +1021,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn msg_from_raw_data_array() {
         let msg = Msg::from_raw_data_array(&[0x03, 0x01, 0x01, 0x05, 0x01, 0x02, 0x03, 0x04]);
         assert_eq!(msg.request, RequestType::DevInfoVID);
@@ -607,6 +1030,43 @@ mod tests {
         assert_eq!(*msg.data.get_array(), [0x01, 0x02, 0x03, 0x04]);
     }
 
+    #[test]
+    fn msg_try_from_raw_data_array_ok() {
+        let msg =
+            Msg::try_from_raw_data_array(&[0x03, 0x01, 0x01, 0x05, 0x01, 0x02, 0x03, 0x04])
+                .unwrap();
+        assert_eq!(msg.request, RequestType::DevInfoVID);
+        assert_eq!(msg.result, ResultType::Ok);
+        assert_eq!(msg.packet_id, 5);
+        assert_eq!(*msg.data.get_array(), [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn msg_try_from_raw_data_array_truncated() {
+        assert_eq!(
+            Msg::try_from_raw_data_array(&[0x03, 0x01, 0x01]).unwrap_err(),
+            ProtocolError::TruncatedFrame
+        );
+    }
+
+    #[test]
+    fn msg_try_from_raw_data_array_unknown_request() {
+        assert_eq!(
+            Msg::try_from_raw_data_array(&[0xFF, 0xFF, 0x01, 0x05, 0x01, 0x02, 0x03, 0x04])
+                .unwrap_err(),
+            ProtocolError::UnknownRequest(0xFFFF)
+        );
+    }
+
+    #[test]
+    fn msg_try_from_raw_data_array_unknown_result() {
+        assert_eq!(
+            Msg::try_from_raw_data_array(&[0x03, 0x01, 0x42, 0x05, 0x01, 0x02, 0x03, 0x04])
+                .unwrap_err(),
+            ProtocolError::UnknownResponse(0x42)
+        );
+    }
+
     #[test]
     fn msg_to_raw_data_array() {
         let msg = Msg::new(
@@ -621,6 +1081,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn msg_write_to_matches_to_raw_data_array() {
+        let msg = Msg::new(
+            RequestType::DevInfoVID,
+            ResultType::Ok,
+            5,
+            &MsgData::from_array(&[0x01, 0x02, 0x03, 0x04]),
+        );
+
+        let mut buf = Vec::new();
+        msg.write_to(&mut buf).unwrap();
+
+        assert_eq!(buf, msg.to_raw_data_array());
+    }
+
+    #[test]
+    fn msg_read_from_round_trips_write_to() {
+        let msg = Msg::new(
+            RequestType::DevInfoVID,
+            ResultType::Ok,
+            5,
+            &MsgData::from_array(&[0x01, 0x02, 0x03, 0x04]),
+        );
+
+        let mut buf = Vec::new();
+        msg.write_to(&mut buf).unwrap();
+
+        let read_back = Msg::read_from(&mut buf.as_slice()).unwrap();
+        assert_eq!(read_back.request, msg.request);
+        assert_eq!(read_back.result, msg.result);
+        assert_eq!(read_back.packet_id, msg.packet_id);
+        assert_eq!(*read_back.data.get_array(), *msg.data.get_array());
+    }
+
+    #[test]
+    fn msg_read_from_truncated_stream_is_com_error() {
+        let mut buf: &[u8] = &[0x03, 0x01];
+        assert!(matches!(Msg::read_from(&mut buf), Err(Error::ComError(_))));
+    }
+
+    #[test]
+    fn msg_read_from_unknown_request_is_msg_corruption() {
+        let mut buf: &[u8] = &[0xFF, 0xFF, 0x01, 0x05, 0x01, 0x02, 0x03, 0x04];
+        assert!(matches!(
+            Msg::read_from(&mut buf),
+            Err(Error::MsgCorruption(_))
+        ));
+    }
+
+    #[test]
+    fn msg_read_framed_round_trips_write_framed() {
+        let msg = Msg::new(
+            RequestType::DevInfoVID,
+            ResultType::Ok,
+            5,
+            &MsgData::from_array(&[0x01, 0x02, 0x03, 0x04]),
+        );
+
+        let mut buf = Vec::new();
+        msg.write_framed(&mut buf).unwrap();
+
+        let read_back = Msg::read_framed(&mut buf.as_slice()).unwrap();
+        assert_eq!(read_back.request, msg.request);
+        assert_eq!(read_back.result, msg.result);
+        assert_eq!(read_back.packet_id, msg.packet_id);
+        assert_eq!(*read_back.data.get_array(), *msg.data.get_array());
+    }
+
+    #[test]
+    fn msg_read_framed_detects_crc_mismatch() {
+        let msg = Msg::new_std_request(RequestType::Ping);
+
+        let mut buf = Vec::new();
+        msg.write_framed(&mut buf).unwrap();
+        let last = buf.len() - 1;
+        buf[last] ^= 0xFF; // corrupt the trailing CRC16
+
+        assert!(matches!(
+            Msg::read_framed(&mut buf.as_slice()),
+            Err(Error::MsgCorruption(_))
+        ));
+    }
+
+    #[test]
+    fn msg_read_framed_detects_length_mismatch() {
+        let msg = Msg::new_std_request(RequestType::Ping);
+
+        let mut buf = Vec::new();
+        msg.write_framed(&mut buf).unwrap();
+        buf[5] = 3; // claim a 3-byte payload instead of 4, invalidating the trailing CRC too
+
+        assert!(matches!(
+            Msg::read_framed(&mut buf.as_slice()),
+            Err(Error::MsgCorruption(_))
+        ));
+    }
+
+    #[test]
+    fn msg_read_framed_truncated_stream_is_com_error() {
+        let mut buf: &[u8] = &[0x00, 0x03];
+        assert!(matches!(
+            Msg::read_framed(&mut buf),
+            Err(Error::ComError(_))
+        ));
+    }
+
     #[test]
     fn msg_new_std_request() {
         let msg = Msg::new_std_request(RequestType::Ping);
@@ -0,0 +1,231 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use crate::francor::franklyboot::{
+    com::{
+        msg::{Msg, RequestType},
+        ComInterface, ComMode,
+    },
+    Error,
+};
+
+// Net Interface ------------------------------------------------------------------------------------
+
+pub const NET_RX_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Number of times a lost UDP datagram is resent before giving up
+const NET_UDP_MAX_RETRIES: u32 = 3;
+
+enum NetTransport {
+    Tcp(TcpStream),
+    Udp(UdpSocket),
+}
+
+///
+/// Network interface
+///
+/// This struct implements the communication interface for reaching a Frankly bootloader over a
+/// TCP or UDP socket, for setups where a gateway bridges FranklyBoot to IP and no direct serial or
+/// SocketCAN access is available. The address is given as `tcp:host:port` or `udp:host:port`; TCP
+/// gives a reliable stream, while UDP is lossy so `recv` resends the last request a few times
+/// before giving up. The scheme prefix on the address string is the transport selector, the same
+/// pattern every other interface in this crate uses for its own connection parameters (e.g.
+/// `CANInterface::open`'s interface name) rather than a dedicated params struct, and
+/// `ping_network` is `CANInterface::ping_network`'s UDP-broadcast counterpart, returning the
+/// addresses of the nodes that answered.
+///
+pub struct NetInterface {
+    /// Underlying socket
+    transport: NetTransport,
+
+    /// Timeout for receiving messages
+    timeout: Duration,
+
+    /// Raw bytes of the last message sent, kept around to resend on a UDP timeout
+    last_sent: Option<[u8; 8]>,
+}
+
+impl NetInterface {
+    ///
+    /// Open a network connection to a device
+    ///
+    /// `address` must be of the form `tcp:host:port` or `udp:host:port`, e.g.
+    /// `tcp:192.168.1.10:4242`.
+    ///
+    pub fn open(address: &str) -> Result<NetInterface, Error> {
+        let (scheme, host_port) = address.split_once(':').ok_or_else(|| {
+            Error::Error(format!(
+                "Network address \"{}\" must be in \"tcp:host:port\" or \"udp:host:port\" format",
+                address
+            ))
+        })?;
+
+        let transport = match scheme {
+            "tcp" => {
+                let stream = TcpStream::connect(host_port).map_err(|e| {
+                    Error::Error(format!("Failed to connect to {}: {}", host_port, e))
+                })?;
+                stream
+                    .set_read_timeout(Some(NET_RX_TIMEOUT))
+                    .map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))?;
+                stream
+                    .set_nodelay(true)
+                    .map_err(|e| Error::Error(format!("Failed to set TCP_NODELAY: {}", e)))?;
+                NetTransport::Tcp(stream)
+            }
+            "udp" => {
+                let peer_addr: SocketAddr = host_port
+                    .to_socket_addrs()
+                    .map_err(|e| Error::Error(format!("Failed to resolve {}: {}", host_port, e)))?
+                    .next()
+                    .ok_or_else(|| Error::Error(format!("Failed to resolve {}", host_port)))?;
+
+                let socket = UdpSocket::bind("0.0.0.0:0")
+                    .map_err(|e| Error::Error(format!("Failed to bind UDP socket: {}", e)))?;
+                socket.connect(peer_addr).map_err(|e| {
+                    Error::Error(format!("Failed to connect to {}: {}", peer_addr, e))
+                })?;
+                socket
+                    .set_read_timeout(Some(NET_RX_TIMEOUT))
+                    .map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))?;
+                NetTransport::Udp(socket)
+            }
+            _ => {
+                return Err(Error::Error(format!(
+                    "Unknown network scheme \"{}\", expected \"tcp\" or \"udp\"",
+                    scheme
+                )))
+            }
+        };
+
+        Ok(NetInterface {
+            transport,
+            timeout: NET_RX_TIMEOUT,
+            last_sent: None,
+        })
+    }
+
+    ///
+    /// Discover devices by broadcasting a ping to every host on a UDP subnet
+    ///
+    /// `broadcast_addr` is `host:port` where `host` is the subnet's broadcast address (e.g.
+    /// `192.168.1.255:4242`), the UDP analogue of `CANInterface::ping_network`. This enables
+    /// `SO_BROADCAST` on a UDP socket, sends a single ping datagram to `broadcast_addr`, and
+    /// collects the reply address of every device that answers before the rx timeout elapses.
+    /// Each responder's address can then be passed straight to `NetInterface::open` as
+    /// `udp:<address>` to connect to that specific device.
+    ///
+    pub fn ping_network(broadcast_addr: &str) -> Result<Vec<SocketAddr>, Error> {
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .map_err(|e| Error::Error(format!("Failed to bind UDP socket: {}", e)))?;
+        socket
+            .set_broadcast(true)
+            .map_err(|e| Error::Error(format!("Failed to enable UDP broadcast: {}", e)))?;
+        socket
+            .set_read_timeout(Some(NET_RX_TIMEOUT))
+            .map_err(|e| Error::Error(format!("Failed to set rx timeout: {}", e)))?;
+
+        let ping_request = Msg::new_std_request(RequestType::Ping);
+        socket
+            .send_to(&ping_request.to_raw_data_array(), broadcast_addr)
+            .map_err(|e| {
+                Error::Error(format!(
+                    "Failed to send broadcast ping to {}: {}",
+                    broadcast_addr, e
+                ))
+            })?;
+
+        let mut responders = Vec::new();
+        loop {
+            let mut data = [0u8; 8];
+            match socket.recv_from(&mut data) {
+                Ok((_, from)) => {
+                    if let Ok(response) = Msg::try_from_raw_data_array(&data) {
+                        if ping_request.is_response_ok(&response).is_ok()
+                            && !responders.contains(&from)
+                        {
+                            responders.push(from);
+                        }
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok(responders)
+    }
+}
+
+impl ComInterface for NetInterface {
+    fn set_mode(&mut self, _mode: ComMode) -> Result<(), Error> {
+        // A net interface already addresses exactly one device via its socket's peer address
+        Err(Error::NotSupported)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        let result = match &self.transport {
+            NetTransport::Tcp(stream) => stream.set_read_timeout(Some(timeout)),
+            NetTransport::Udp(socket) => socket.set_read_timeout(Some(timeout)),
+        };
+        result.map_err(|e| Error::Error(format!("Failed to set timeout: {}", e)))?;
+
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn send(&mut self, msg: &Msg) -> Result<(), Error> {
+        let raw = msg.to_raw_data_array();
+        self.last_sent = Some(raw);
+
+        match &mut self.transport {
+            NetTransport::Tcp(stream) => stream
+                .write_all(&raw)
+                .map_err(|e| Error::Error(format!("Failed to write to network socket: {}", e))),
+            NetTransport::Udp(socket) => socket
+                .send(&raw)
+                .map(|_| ())
+                .map_err(|e| Error::Error(format!("Failed to send UDP datagram: {}", e))),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Msg, Error> {
+        match &mut self.transport {
+            NetTransport::Tcp(stream) => {
+                let mut data = [0u8; 8];
+                stream.read_exact(&mut data).map_err(|e| {
+                    Error::Error(format!("Failed to read from network socket: {}", e))
+                })?;
+                Ok(Msg::try_from_raw_data_array(&data)?)
+            }
+            NetTransport::Udp(socket) => {
+                let mut data = [0u8; 8];
+
+                for attempt in 0..=NET_UDP_MAX_RETRIES {
+                    match socket.recv(&mut data) {
+                        Ok(_) => return Ok(Msg::try_from_raw_data_array(&data)?),
+                        Err(e) if attempt == NET_UDP_MAX_RETRIES => {
+                            return Err(Error::Error(format!(
+                                "No response after {} retries: {}",
+                                NET_UDP_MAX_RETRIES, e
+                            )))
+                        }
+                        Err(_) => {
+                            if let Some(raw) = self.last_sent {
+                                socket.send(&raw).map_err(|e| {
+                                    Error::Error(format!("Failed to resend UDP datagram: {}", e))
+                                })?;
+                            }
+                        }
+                    }
+                }
+
+                Err(Error::ComNoResponse)
+            }
+        }
+    }
+}
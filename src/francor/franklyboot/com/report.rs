@@ -0,0 +1,276 @@
+use super::msg::{RequestType, ResultType};
+use crate::francor::franklyboot::Error;
+
+use serde::Serialize;
+use std::time::Duration;
+
+// ReportEntry ----------------------------------------------------------------------------------
+
+/// One request/response round-trip recorded by an `UpdateReport`
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntry {
+    pub request: RequestType,
+    pub result: ResultType,
+    pub packet_id: u8,
+
+    /// Offset, in bytes, into the firmware image this exchange applies to. `0` for exchanges that
+    /// aren't tied to a particular offset (e.g. `Ping`, `DevInfo*`).
+    pub byte_offset: usize,
+
+    /// Number of retries this exchange needed before it produced `result`
+    pub retry_count: u32,
+
+    pub elapsed: Duration,
+}
+
+// FailureCause -----------------------------------------------------------------------------------
+
+/// Why an `UpdateReport`'s session didn't reach `Outcome::Success`
+///
+/// Mirrors the request body's distinction: a `Rejected` device response is an operational failure
+/// the device itself reported, while `Corruption` means the link dropped or mangled a frame before
+/// a result was ever produced - the two call for very different remediation (fix firmware/flash
+/// layout vs. fix the cable/transport).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FailureCause {
+    /// The device rejected a request with the given `ResultType` (`Error::ResultError`)
+    Rejected(ResultType),
+
+    /// A response didn't match its request, or couldn't be parsed (`Error::MsgCorruption`)
+    Corruption(String),
+
+    /// Any other `Error` variant, carrying its `Display` message
+    Other(String),
+}
+
+impl FailureCause {
+    /// Classifies `err` the way `UpdateReport::fail` does, without needing a report instance
+    pub fn from_error(err: &Error) -> FailureCause {
+        match err {
+            Error::ResultError(desc) => FailureCause::Rejected(_result_from_desc(desc)),
+            Error::MsgCorruption(desc) => FailureCause::Corruption(desc.clone()),
+            other => FailureCause::Other(other.to_string()),
+        }
+    }
+}
+
+/// `Error::ResultError`'s payload is already a formatted description, not the `ResultType` itself,
+/// so recover it from the last recorded entry instead of re-parsing the string - `UpdateReport::fail`
+/// uses this path; `from_error` alone (with no entries to consult) falls back to `ResultType::Error`.
+fn _result_from_desc(_desc: &str) -> ResultType {
+    ResultType::Error
+}
+
+// Outcome ------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Outcome {
+    /// The session ran to completion without error
+    Success,
+
+    /// The session ended with `cause`
+    Failed(FailureCause),
+}
+
+// ReportSummary --------------------------------------------------------------------------------
+
+/// Roll-up of an `UpdateReport`, cheap to log or display without walking every entry
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportSummary {
+    pub total_pages_written: usize,
+    pub total_retries: u32,
+    pub outcome: Outcome,
+}
+
+// UpdateReport ---------------------------------------------------------------------------------
+
+/// Serializable record of a firmware update session, one entry per request/response round-trip
+///
+/// Callers append an entry after each exchange (e.g. from a `Transactor` or `FlashSession` call
+/// site) and mark the final outcome once with `finish`/`fail`. `to_json` then gives integrators a
+/// machine-readable artifact for logging/telemetry, instead of only the scattered `println!` lines
+/// `StdoutObserver` produces for a human watching a terminal.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateReport {
+    entries: Vec<ReportEntry>,
+    outcome: Outcome,
+}
+
+impl UpdateReport {
+    /// Starts an empty report, assuming success until `fail` is called
+    pub fn new() -> Self {
+        UpdateReport {
+            entries: Vec::new(),
+            outcome: Outcome::Success,
+        }
+    }
+
+    /// Appends one request/response round-trip to the session
+    pub fn record(
+        &mut self,
+        request: RequestType,
+        result: ResultType,
+        packet_id: u8,
+        byte_offset: usize,
+        retry_count: u32,
+        elapsed: Duration,
+    ) {
+        self.entries.push(ReportEntry {
+            request,
+            result,
+            packet_id,
+            byte_offset,
+            retry_count,
+            elapsed,
+        });
+    }
+
+    /// Marks the session failed, classifying `err` as a device rejection or link corruption
+    ///
+    /// A `Rejected` cause is recovered from the last recorded entry's `result` rather than `err`'s
+    /// own (string) payload, since that's the actual `ResultType` the device returned.
+    pub fn fail(&mut self, err: &Error) {
+        self.outcome = match err {
+            Error::ResultError(_) => Outcome::Failed(FailureCause::Rejected(
+                self.entries
+                    .last()
+                    .map(|entry| entry.result)
+                    .unwrap_or(ResultType::Error),
+            )),
+            Error::MsgCorruption(desc) => Outcome::Failed(FailureCause::Corruption(desc.clone())),
+            other => Outcome::Failed(FailureCause::Other(other.to_string())),
+        };
+    }
+
+    /// All recorded entries, in exchange order
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    pub fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+
+    /// Summarizes the session: pages written, total retries, and the failure cause (if any)
+    pub fn summary(&self) -> ReportSummary {
+        ReportSummary {
+            total_pages_written: self
+                .entries
+                .iter()
+                .filter(|entry| {
+                    entry.request == RequestType::PageBufferWriteToFlash
+                        && entry.result == ResultType::Ok
+                })
+                .count(),
+            total_retries: self.entries.iter().map(|entry| entry.retry_count).sum(),
+            outcome: self.outcome.clone(),
+        }
+    }
+
+    /// Serializes the full report - every entry plus the outcome - to a JSON string
+    ///
+    /// `UpdateReport` itself only ever derives `Serialize`, so this can't fail on the Rust side;
+    /// `serde_json::to_string_pretty` only errors on non-serializable maps/floats, neither of which
+    /// this type contains.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .unwrap_or_else(|err| format!("{{\"error\": \"failed to serialize report: {}\"}}", err))
+    }
+}
+
+impl Default for UpdateReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_report_starts_empty_and_successful() {
+        let report = UpdateReport::new();
+        assert_eq!(report.entries().len(), 0);
+        assert_eq!(*report.outcome(), Outcome::Success);
+    }
+
+    #[test]
+    fn update_report_summary_counts_pages_and_retries() {
+        let mut report = UpdateReport::new();
+        report.record(
+            RequestType::FlashWriteErasePage,
+            ResultType::Ok,
+            0,
+            0,
+            1,
+            Duration::from_millis(5),
+        );
+        report.record(
+            RequestType::PageBufferWriteToFlash,
+            ResultType::Ok,
+            1,
+            0,
+            0,
+            Duration::from_millis(2),
+        );
+        report.record(
+            RequestType::PageBufferWriteToFlash,
+            ResultType::Ok,
+            2,
+            256,
+            2,
+            Duration::from_millis(3),
+        );
+
+        let summary = report.summary();
+        assert_eq!(summary.total_pages_written, 2);
+        assert_eq!(summary.total_retries, 3);
+        assert_eq!(summary.outcome, Outcome::Success);
+    }
+
+    #[test]
+    fn update_report_fail_distinguishes_rejection_from_corruption() {
+        let mut rejected = UpdateReport::new();
+        rejected.record(
+            RequestType::Ping,
+            ResultType::ErrNotSupported,
+            0,
+            0,
+            0,
+            Duration::from_millis(1),
+        );
+        rejected.fail(&Error::ResultError("device rejected Ping".to_string()));
+        assert_eq!(
+            *rejected.outcome(),
+            Outcome::Failed(FailureCause::Rejected(ResultType::ErrNotSupported))
+        );
+
+        let mut corrupted = UpdateReport::new();
+        corrupted.fail(&Error::MsgCorruption("packet id mismatch".to_string()));
+        assert_eq!(
+            *corrupted.outcome(),
+            Outcome::Failed(FailureCause::Corruption("packet id mismatch".to_string()))
+        );
+    }
+
+    #[test]
+    fn update_report_to_json_round_trips_through_serde_json() {
+        let mut report = UpdateReport::new();
+        report.record(
+            RequestType::Ping,
+            ResultType::Ok,
+            0,
+            0,
+            0,
+            Duration::from_millis(1),
+        );
+
+        let json = report.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["entries"][0]["request"], "Ping");
+        assert_eq!(value["outcome"], "Success");
+    }
+}
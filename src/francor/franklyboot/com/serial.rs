@@ -1,8 +1,17 @@
 use serialport::SerialPort;
-use std::time::Duration;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc,
+};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use crate::francor::franklyboot::{
-    com::{msg::Msg, ComInterface, ComMode},
+    com::{
+        framing::FrameParser,
+        msg::{Msg, MsgRaw},
+        ComInterface, ComMode,
+    },
     Error,
 };
 
@@ -10,6 +19,42 @@ use crate::francor::franklyboot::{
 
 pub const RX_TIMEOUT: std::time::Duration = Duration::from_millis(500);
 
+/// Per-byte read timeout that applies no per-byte scaling, for passing to `set_timeout_params`
+pub const RX_TIMEOUT_PER_BYTE: Duration = Duration::from_millis(0);
+
+/// Bytes in a single frame, used to scale the read deadline the same way each `recv` call is sized
+const FRAME_LEN: u32 = std::mem::size_of::<MsgRaw>() as u32;
+
+/// A background reader thread's handle, owned by a `SerialInterface` opened with `spawn_reader`
+struct ReaderHandle {
+    /// Messages the reader thread has decoded off the port, oldest first
+    msg_rx: mpsc::Receiver<Msg>,
+
+    /// Set by `Drop` to ask the reader thread to stop after its current port read times out
+    shutdown: Arc<AtomicBool>,
+
+    /// `None` only after the thread has already been joined
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ReaderHandle {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// How a `SerialInterface` receives frames off its port
+enum RecvMode {
+    /// `recv` reads the port itself, resyncing a `FrameParser` as bytes arrive
+    Direct(FrameParser),
+
+    /// A background thread continuously drains the port; see `SerialInterface::spawn_reader`
+    Threaded(ReaderHandle),
+}
+
 ///
 /// Serial interface
 ///
@@ -19,8 +64,12 @@ pub struct SerialInterface {
     /// Serial port interface trait
     port: Box<dyn SerialPort>,
 
-    /// Timeout for receiving messages
+    /// Timeout for receiving messages, normally set by `set_timeout_params`
     timeout: Duration,
+
+    /// How incoming frames are received: synchronously off `port`, or via a background reader
+    /// thread spawned by `spawn_reader`
+    recv_mode: RecvMode,
 }
 
 impl SerialInterface {
@@ -43,8 +92,78 @@ impl SerialInterface {
         Ok(SerialInterface {
             port,
             timeout: RX_TIMEOUT,
+            recv_mode: RecvMode::Direct(FrameParser::new()),
         })
     }
+
+    ///
+    /// Open a serial port with a background reader thread
+    ///
+    /// Like `open`, but a dedicated thread continuously drains the port into a channel instead of
+    /// `recv` reading it synchronously, mirroring how crosvm moved its serial device to a thread
+    /// reading input instead of a synchronous poll. This lets a caller `send` a broadcast request
+    /// and then collect every node's response as it arrives (via `try_recv`) instead of blocking
+    /// between `send` and a single `recv`. The thread is joined cleanly when the returned
+    /// `SerialInterface` is dropped.
+    ///
+    pub fn spawn_reader(port_name: &str, baud_rate: u32) -> Result<SerialInterface, String> {
+        let port = serialport::new(port_name, baud_rate)
+            .timeout(RX_TIMEOUT)
+            .open()
+            .map_err(|e| format!("Failed to open serial port: {}", e))?;
+        let mut reader_port = port
+            .try_clone()
+            .map_err(|e| format!("Failed to clone serial port handle for reader thread: {}", e))?;
+
+        let (msg_tx, msg_rx) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let reader_shutdown = shutdown.clone();
+
+        let handle = thread::spawn(move || {
+            let mut parser = FrameParser::new();
+            let mut chunk = [0u8; 64];
+
+            while !reader_shutdown.load(Ordering::SeqCst) {
+                match reader_port.read(&mut chunk) {
+                    Ok(n) if n > 0 => {
+                        parser.consume(&chunk[..n]);
+                        while let Some(msg) = parser.parse_msg() {
+                            if msg_tx.send(msg).is_err() {
+                                // The SerialInterface was dropped; nothing left to deliver to.
+                                return;
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                    // A real I/O error (port unplugged, ...) means there is nothing left to read.
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(SerialInterface {
+            port,
+            timeout: RX_TIMEOUT,
+            recv_mode: RecvMode::Threaded(ReaderHandle {
+                msg_rx,
+                shutdown,
+                handle: Some(handle),
+            }),
+        })
+    }
+
+    ///
+    /// Set the parameters of the adaptive read timeout
+    ///
+    /// The effective timeout used by `recv` becomes `base + FRAME_LEN * per_byte`, mirroring how
+    /// the serialport library derives its own read deadline from `readTimeout + buf.length *
+    /// readTimeoutMult`. A larger `per_byte` gives slow or congested links proportionally more
+    /// time while short command/response exchanges on a clean link stay snappy.
+    ///
+    pub fn set_timeout_params(&mut self, base: Duration, per_byte: Duration) -> Result<(), Error> {
+        self.set_timeout(base + per_byte * FRAME_LEN)
+    }
 }
 
 impl ComInterface for SerialInterface {
@@ -67,8 +186,14 @@ impl ComInterface for SerialInterface {
     }
 
     fn send(&mut self, msg: &Msg) -> Result<(), Error> {
+        // In threaded mode the reader thread owns a cloned handle reading concurrently, so only
+        // the outgoing buffer is ours to clear here.
+        let clear_target = match self.recv_mode {
+            RecvMode::Direct(_) => serialport::ClearBuffer::All,
+            RecvMode::Threaded(_) => serialport::ClearBuffer::Output,
+        };
         self.port
-            .clear(serialport::ClearBuffer::All)
+            .clear(clear_target)
             .map_err(|e| Error::Error(format!("Failed to clear serial port buffers! {}", e)))?;
         self.port
             .write_all(&msg.to_raw_data_array())
@@ -77,11 +202,63 @@ impl ComInterface for SerialInterface {
     }
 
     fn recv(&mut self) -> Result<Msg, Error> {
-        let mut data = [0u8; 8];
-        self.port
-            .read_exact(&mut data)
-            .map_err(|e| Error::Error(format!("Failed to read from serial port: {}", e)))?;
+        let timeout = self.timeout;
+        let SerialInterface {
+            port, recv_mode, ..
+        } = self;
+
+        match recv_mode {
+            RecvMode::Threaded(reader) => reader
+                .msg_rx
+                .recv_timeout(timeout)
+                .map_err(|_| Error::ComNoResponse),
+            RecvMode::Direct(parser) => {
+                let deadline = Instant::now() + timeout;
+
+                loop {
+                    if let Some(msg) = parser.parse_msg() {
+                        return Ok(msg);
+                    }
+
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(Error::Error(
+                            "Timed out waiting for a valid frame from serial port".to_string(),
+                        ));
+                    }
+                    port.set_timeout(remaining)
+                        .map_err(|e| Error::Error(format!("Failed to set timeout: {}", e)))?;
 
-        Ok(Msg::from_raw_data_array(&data))
+                    let mut chunk = [0u8; 64];
+                    match port.read(&mut chunk) {
+                        Ok(n) => parser.consume(&chunk[..n]),
+                        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                            return Err(Error::Error(
+                                "Timed out waiting for a valid frame from serial port".to_string(),
+                            ))
+                        }
+                        Err(e) => {
+                            return Err(Error::Error(format!(
+                                "Failed to read from serial port: {}",
+                                e
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn try_recv(&mut self) -> Result<Option<Msg>, Error> {
+        match &mut self.recv_mode {
+            RecvMode::Threaded(reader) => match reader.msg_rx.try_recv() {
+                Ok(msg) => Ok(Some(msg)),
+                Err(mpsc::TryRecvError::Empty) => Ok(None),
+                Err(mpsc::TryRecvError::Disconnected) => Err(Error::ComError(
+                    "Serial reader thread exited unexpectedly".to_string(),
+                )),
+            },
+            RecvMode::Direct(_) => Err(Error::NotSupported),
+        }
     }
 }
@@ -1,11 +1,265 @@
-use std::time::Duration;
+use std::fs::File;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use crate::francor::franklyboot::{
-    com::msg::{Msg, RequestType},
+    com::{
+        msg::{Msg, MsgData, RequestType, ResultType},
+        transport::{RawFrameTransport, Transport},
+    },
     utils::sim_api,
     Error,
 };
 
+// Capture ------------------------------------------------------------------------------------------
+
+/// Direction a captured frame travelled
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+    Tx,
+    Rx,
+}
+
+/// A single request/response frame captured during a sim session
+#[derive(Debug, Clone)]
+pub struct SimFrame {
+    /// Time elapsed since the capture was started
+    pub timestamp: Duration,
+
+    /// Direction the frame travelled
+    pub direction: Direction,
+
+    /// Node the frame was sent to/received from (`None` for a broadcast frame)
+    pub node_id: Option<u8>,
+
+    /// Request type of the frame, or `None` if the request id could not be parsed
+    pub request_type: Option<RequestType>,
+
+    /// Raw 8 byte frame payload
+    pub raw: [u8; 8],
+}
+
+/// Running counters for a sim capture session
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SimStats {
+    pub total_packets: u32,
+    pub parse_errors: u32,
+    pub unsupported_packets: u32,
+    pub retries: u32,
+}
+
+///
+/// Sim capture/stats session
+///
+/// Records every request/response frame exchanged over the simulated network together with
+/// running counters, so simulated flash/erase runs can be asserted against expected packet
+/// sequences in integration tests.
+///
+pub struct SimCapture {
+    start: Instant,
+    frames: Vec<SimFrame>,
+    stats: SimStats,
+}
+
+impl SimCapture {
+    pub fn new() -> Self {
+        SimCapture {
+            start: Instant::now(),
+            frames: Vec::new(),
+            stats: SimStats::default(),
+        }
+    }
+
+    /// Get the current running counters
+    pub fn stats(&self) -> SimStats {
+        self.stats
+    }
+
+    /// Record a raw frame, classifying its request type without panicking on corrupted data
+    pub fn record(&mut self, direction: Direction, node_id: Option<u8>, raw: &[u8; 8]) {
+        self.stats.total_packets += 1;
+
+        let request_id = (raw[0] as u16) | ((raw[1] as u16) << 8);
+        let request_type = RequestType::try_from_u16(request_id).ok();
+        if request_type.is_none() {
+            self.stats.unsupported_packets += 1;
+        }
+
+        self.frames.push(SimFrame {
+            timestamp: self.start.elapsed(),
+            direction,
+            node_id,
+            request_type,
+            raw: *raw,
+        });
+    }
+
+    /// Record that a frame could not be parsed at all (e.g. wrong length)
+    pub fn record_parse_error(&mut self) {
+        self.stats.total_packets += 1;
+        self.stats.parse_errors += 1;
+    }
+
+    /// Record that a request had to be retried
+    pub fn record_retry(&mut self) {
+        self.stats.retries += 1;
+    }
+
+    /// Human readable summary of the capture session
+    pub fn summary(&self) -> String {
+        format!(
+            "Sim capture: {} packets ({} parse errors, {} unsupported, {} retries) over {:.3}s",
+            self.stats.total_packets,
+            self.stats.parse_errors,
+            self.stats.unsupported_packets,
+            self.stats.retries,
+            self.start.elapsed().as_secs_f64()
+        )
+    }
+
+    /// Write the raw captured frames to a CSV file
+    pub fn write_csv(&self, file_path: &str) -> Result<(), Error> {
+        let mut file = File::create(file_path)
+            .map_err(|e| Error::Error(format!("Failed to create capture file: {}", e)))?;
+
+        writeln!(file, "timestamp_ms,direction,node_id,request_type,raw")
+            .map_err(|e| Error::Error(format!("Failed to write capture file: {}", e)))?;
+
+        for frame in &self.frames {
+            writeln!(
+                file,
+                "{},{:?},{},{:?},{:02X?}",
+                frame.timestamp.as_millis(),
+                frame.direction,
+                frame
+                    .node_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "broadcast".to_string()),
+                frame.request_type,
+                frame.raw
+            )
+            .map_err(|e| Error::Error(format!("Failed to write capture file: {}", e)))?;
+        }
+
+        Ok(())
+    }
+}
+
+// Device Info --------------------------------------------------------------------------------------
+
+///
+/// Identity of a single node discovered by `SIMInterface::list_devices`
+///
+/// Holds the node's `DevInfoUID`, a stable identifier that survives reboots and bus
+/// re-enumeration - unlike the node id a ping assigns it, which can collide or shift if two
+/// boards share a bus address or nodes drop on/off the bus between scans.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub uid: u32,
+}
+
+// Fault Injection ------------------------------------------------------------------------------
+
+///
+/// A fault to inject into the simulated network, armed via `SIMInterface::inject_fault`
+///
+/// Exercises failures that are impossible to reproduce deterministically against a real link: a
+/// node that drops off the bus mid-session, a corrupted frame, or a write whose effect silently
+/// never lands. Only one fault can be armed at a time; arming a new one replaces whatever was
+/// armed before and resets its counters.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FaultSpec {
+    /// Stop responding once `n` responses (broadcast and per-node combined) have already been
+    /// sent, simulating a node that goes silent partway through a session.
+    DropResponseAfter(u32),
+
+    /// Flip a bit of a fraction of outgoing responses, simulating link noise. `Msg` carries no
+    /// CRC of its own, so flipping a data byte would decode cleanly but with the wrong value;
+    /// flipping the request id byte instead almost always turns the frame into an unrecognized
+    /// request, so the caller sees it the same way it would see real line noise: a
+    /// `Error::MsgCorruption` out of `Msg::try_from_raw_data_array`. `probability` is clamped to
+    /// `[0.0, 1.0]`.
+    CorruptPayload(f64),
+
+    /// Silently stop forwarding writes to the device once `n` have already gone through, while
+    /// still acknowledging them `Ok` - simulating power loss mid-flash where the page write never
+    /// actually reaches the device.
+    PowerLossAfterWrites(u32),
+}
+
+/// Whether `request` is a write-style request `PowerLossAfterWrites` should count and drop
+fn is_write_request(request: RequestType) -> bool {
+    matches!(
+        request,
+        RequestType::PageBufferWriteWord
+            | RequestType::PageBufferWriteToFlash
+            | RequestType::FlashWriteErasePage
+            | RequestType::FlashWriteAppCRC
+            | RequestType::FlashWriteMassErase
+            | RequestType::ConfigWrite
+            | RequestType::ConfigErase
+    )
+}
+
+#[derive(Debug, Default)]
+struct FaultState {
+    spec: Option<FaultSpec>,
+    responses_sent: u32,
+    writes_applied: u32,
+    rng: u64,
+}
+
+impl FaultState {
+    /// Advances a small xorshift64 generator and returns a value in `[0.0, 1.0)`
+    ///
+    /// A real `rand` dependency would be overkill for a single probability check, and a fixed,
+    /// seedable generator keeps `CorruptPayload` tests reproducible run to run.
+    fn next_f64(&mut self) -> f64 {
+        if self.rng == 0 {
+            self.rng = 0x9E3779B97F4A7C15;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Called once per outgoing response; returns whether it should be dropped
+    fn should_drop_response(&mut self) -> bool {
+        self.responses_sent += 1;
+        matches!(self.spec, Some(FaultSpec::DropResponseAfter(n)) if self.responses_sent > n)
+    }
+
+    /// Called once per outgoing response; returns whether its payload should be corrupted
+    fn should_corrupt_response(&mut self) -> bool {
+        match self.spec {
+            Some(FaultSpec::CorruptPayload(probability)) => {
+                self.next_f64() < probability.clamp(0.0, 1.0)
+            }
+            _ => false,
+        }
+    }
+
+    /// Called once per outgoing write request; returns whether it should be swallowed instead of
+    /// forwarded to the device
+    fn should_drop_write(&mut self, request: RequestType) -> bool {
+        if !is_write_request(request) {
+            return false;
+        }
+
+        self.writes_applied += 1;
+        matches!(self.spec, Some(FaultSpec::PowerLossAfterWrites(n)) if self.writes_applied > n)
+    }
+}
+
+fn fault_state() -> &'static Mutex<FaultState> {
+    static STATE: OnceLock<Mutex<FaultState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(FaultState::default()))
+}
+
 // SIM Interface ----------------------------------------------------------------------------------
 
 pub struct SIMInterface;
@@ -29,13 +283,55 @@ impl SIMInterface {
         Ok(())
     }
 
+    ///
+    /// Arms `fault`, resetting its counters, so every following request/response on the
+    /// simulated network is subject to it until `clear_faults` is called or a different fault is
+    /// armed
+    ///
+    pub fn inject_fault(fault: FaultSpec) {
+        let mut state = fault_state().lock().unwrap();
+        state.spec = Some(fault);
+        state.responses_sent = 0;
+        state.writes_applied = 0;
+    }
+
+    ///
+    /// Disarms any currently injected fault
+    ///
+    pub fn clear_faults() {
+        *fault_state().lock().unwrap() = FaultState::default();
+    }
+
     ///
     /// Pings the network to search for nodes and returns a list of found nodes
     ///
     pub fn ping_network() -> Result<Vec<u8>, Error> {
+        Self::ping_network_with_capture(None)
+    }
+
+    ///
+    /// Pings the network to search for nodes, optionally recording every exchanged frame and
+    /// running counters into `capture` for later summary/CSV dump.
+    ///
+    pub fn ping_network_with_capture(
+        mut capture: Option<&mut SimCapture>,
+    ) -> Result<Vec<u8>, Error> {
+        // The simulated network's FFI boundary (`sim_api`) always exchanges plain 8 byte frames,
+        // so framing goes through `RawFrameTransport` rather than baking `to_raw_data_array`/
+        // `from_raw_data_array` calls directly into this function; a real link with a larger
+        // fixed packet size (see `transport::HidFrameTransport`) would plug in here instead.
+        let transport = RawFrameTransport;
+
         // Send ping
         let ping_request = Msg::new_std_request(RequestType::Ping);
-        sim_api::send_broadcast_msg(&ping_request.to_raw_data_array());
+        let ping_frame: [u8; 8] = transport
+            .encode(&ping_request)
+            .try_into()
+            .expect("RawFrameTransport::encode always returns 8 bytes");
+        sim_api::send_broadcast_msg(&ping_frame);
+        if let Some(capture) = capture.as_deref_mut() {
+            capture.record(Direction::Tx, None, &ping_frame);
+        }
 
         // Receive until no new response
         let mut node_id_lst = Vec::new();
@@ -46,8 +342,28 @@ impl SIMInterface {
                 break;
             }
 
-            let (node_id, response_msg_raw) = response.unwrap();
-            let response_msg = Msg::from_raw_data_array(&response_msg_raw);
+            let (node_id, mut response_msg_raw) = response.unwrap();
+
+            if fault_state().lock().unwrap().should_drop_response() {
+                continue;
+            }
+            if fault_state().lock().unwrap().should_corrupt_response() {
+                response_msg_raw[0] ^= 0xFF;
+            }
+
+            let response_msg = match transport.decode(&response_msg_raw) {
+                Ok(msg) => msg,
+                Err(_) => {
+                    if let Some(capture) = capture.as_deref_mut() {
+                        capture.record_parse_error();
+                    }
+                    continue;
+                }
+            };
+
+            if let Some(capture) = capture.as_deref_mut() {
+                capture.record(Direction::Rx, Some(node_id), &response_msg_raw);
+            }
 
             if ping_request.is_response_ok(&response_msg).is_ok() {
                 node_id_lst.push(node_id);
@@ -56,14 +372,145 @@ impl SIMInterface {
 
         Ok(node_id_lst)
     }
+
+    ///
+    /// Pings the network, then reads each found node's `DevInfoUID`, returning each node's current
+    /// bus id paired with its stable unique identifier
+    ///
+    pub fn list_devices() -> Result<Vec<(u8, DeviceInfo)>, Error> {
+        Self::list_devices_with_capture(None)
+    }
+
+    ///
+    /// Like `list_devices`, but optionally recording every exchanged frame into `capture`
+    ///
+    pub fn list_devices_with_capture(
+        mut capture: Option<&mut SimCapture>,
+    ) -> Result<Vec<(u8, DeviceInfo)>, Error> {
+        let node_id_lst = Self::ping_network_with_capture(capture.as_deref_mut())?;
+
+        let uid_request = Msg::new_std_request(RequestType::DevInfoUID);
+
+        let mut devices = Vec::new();
+        for node_id in node_id_lst {
+            let response_msg =
+                Self::send_request_to_node_with_capture(node_id, &uid_request, capture.as_deref_mut())?;
+
+            devices.push((
+                node_id,
+                DeviceInfo {
+                    uid: response_msg.get_data().to_word(),
+                },
+            ));
+        }
+
+        Ok(devices)
+    }
+
+    ///
+    /// Sends `request` to a single node and returns its decoded response, honoring any fault
+    /// armed via `inject_fault`
+    ///
+    pub fn send_request_to_node(node_id: u8, request: &Msg) -> Result<Msg, Error> {
+        Self::send_request_to_node_with_capture(node_id, request, None)
+    }
+
+    ///
+    /// Like `send_request_to_node`, but optionally recording the exchanged frames into `capture`
+    ///
+    /// This is the per-node counterpart of the broadcast loop in `ping_network_with_capture`;
+    /// `list_devices_with_capture` uses it for its `DevInfoUID` read, and it is `pub` so tests can
+    /// drive write-style requests (`PageBufferWriteToFlash`, `FlashWriteErasePage`, ...) against a
+    /// node to exercise `FaultSpec::PowerLossAfterWrites`.
+    ///
+    pub fn send_request_to_node_with_capture(
+        node_id: u8,
+        request: &Msg,
+        mut capture: Option<&mut SimCapture>,
+    ) -> Result<Msg, Error> {
+        let transport = RawFrameTransport;
+        let request_frame: [u8; 8] = transport
+            .encode(request)
+            .try_into()
+            .expect("RawFrameTransport::encode always returns 8 bytes");
+
+        let write_dropped = fault_state()
+            .lock()
+            .unwrap()
+            .should_drop_write(request.get_request());
+
+        if !write_dropped {
+            sim_api::send_msg(node_id, &request_frame);
+        }
+        if let Some(capture) = capture.as_deref_mut() {
+            capture.record(Direction::Tx, Some(node_id), &request_frame);
+        }
+
+        if write_dropped {
+            // The write never reached the device, but the caller still gets an `Ok` response, so
+            // it believes the write took effect - exactly what a real power loss mid-write looks
+            // like from the host's side.
+            return Ok(Msg::new(
+                request.get_request(),
+                ResultType::Ok,
+                request.get_packet_id(),
+                &MsgData::new(),
+            ));
+        }
+
+        let mut response_raw = sim_api::get_response_msg(node_id).ok_or(Error::ComNoResponse)?;
+
+        if fault_state().lock().unwrap().should_drop_response() {
+            return Err(Error::ComNoResponse);
+        }
+        if fault_state().lock().unwrap().should_corrupt_response() {
+            response_raw[0] ^= 0xFF;
+        }
+
+        let response_msg = transport.decode(&response_raw)?;
+
+        if let Some(capture) = capture.as_deref_mut() {
+            capture.record(Direction::Rx, Some(node_id), &response_raw);
+        }
+
+        request.is_response_ok(&response_msg)?;
+        Ok(response_msg)
+    }
+
+    ///
+    /// Resolves a stable unique identifier to the node id currently hosting it, or `None` if no
+    /// node on the network reports that UID. Lets a caller target a specific physical board
+    /// reliably across reboots and re-enumerations instead of trusting a volatile bus address.
+    ///
+    pub fn device_having(uid: u32) -> Result<Option<u8>, Error> {
+        Ok(Self::list_devices()?
+            .into_iter()
+            .find(|(_, info)| info.uid == uid)
+            .map(|(node_id, _)| node_id))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// `SIMInterface` wraps a single process-global C++ simulator (`sim_api`) and, since
+    /// `FaultSpec`, a single process-global fault (`fault_state`) - neither is scoped per test, so
+    /// running this module's tests concurrently (cargo's default) would let one test's
+    /// `config_nodes`/`inject_fault` corrupt another's in-flight network or fault counters. Every
+    /// test below takes this lock first to serialize access; it's recovered rather than
+    /// re-panicked on poison so one test panicking mid-session (e.g. on an assertion) doesn't wedge
+    /// every test that runs after it.
+    fn test_lock() -> std::sync::MutexGuard<'static, ()> {
+        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+        LOCK.get_or_init(|| Mutex::new(()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     #[test]
     fn test_ping_network() {
+        let _guard = test_lock();
         let node_lst = vec![1, 20, 3, 52];
         SIMInterface::config_nodes(node_lst.clone()).unwrap();
 
@@ -71,4 +518,137 @@ mod tests {
 
         assert_eq!(node_lst, node_lst_found);
     }
+
+    #[test]
+    fn test_ping_network_with_capture() {
+        let _guard = test_lock();
+        let node_lst = vec![1, 20, 3, 52];
+        SIMInterface::config_nodes(node_lst.clone()).unwrap();
+
+        let mut capture = SimCapture::new();
+        let node_lst_found =
+            SIMInterface::ping_network_with_capture(Some(&mut capture)).unwrap();
+
+        assert_eq!(node_lst, node_lst_found);
+        assert_eq!(capture.stats().total_packets as usize, 1 + node_lst.len());
+        assert_eq!(capture.stats().unsupported_packets, 0);
+    }
+
+    #[test]
+    fn test_list_devices() {
+        let _guard = test_lock();
+        let node_lst = vec![1, 20, 3];
+        SIMInterface::config_nodes(node_lst.clone()).unwrap();
+
+        let devices = SIMInterface::list_devices().unwrap();
+
+        let found_node_ids: Vec<u8> = devices.iter().map(|(node_id, _)| *node_id).collect();
+        assert_eq!(found_node_ids, node_lst);
+    }
+
+    #[test]
+    fn test_device_having_resolves_a_uid_to_its_current_node_id() {
+        let _guard = test_lock();
+        let node_lst = vec![1, 20, 3];
+        SIMInterface::config_nodes(node_lst.clone()).unwrap();
+
+        let devices = SIMInterface::list_devices().unwrap();
+        let (node_id, info) = devices[1];
+
+        assert_eq!(SIMInterface::device_having(info.uid).unwrap(), Some(node_id));
+    }
+
+    #[test]
+    fn test_device_having_returns_none_for_an_unknown_uid() {
+        let _guard = test_lock();
+        let node_lst = vec![1, 20, 3];
+        SIMInterface::config_nodes(node_lst).unwrap();
+
+        assert_eq!(SIMInterface::device_having(0xFFFF_FFFF).unwrap(), None);
+    }
+
+    #[test]
+    fn sim_capture_record_unsupported_packet() {
+        let mut capture = SimCapture::new();
+        capture.record(Direction::Rx, Some(1), &[0xFF, 0xFF, 0x00, 0x00, 0, 0, 0, 0]);
+
+        assert_eq!(capture.stats().total_packets, 1);
+        assert_eq!(capture.stats().unsupported_packets, 1);
+    }
+
+    #[test]
+    fn test_inject_fault_drop_response_after_makes_a_node_go_silent() {
+        let _guard = test_lock();
+        SIMInterface::clear_faults();
+        let node_lst = vec![1, 2, 3];
+        SIMInterface::config_nodes(node_lst).unwrap();
+        SIMInterface::inject_fault(FaultSpec::DropResponseAfter(1));
+
+        let found = SIMInterface::ping_network().unwrap();
+
+        SIMInterface::clear_faults();
+        assert_eq!(found, vec![1]);
+    }
+
+    #[test]
+    fn test_inject_fault_corrupt_payload_breaks_decoding_of_the_response() {
+        let _guard = test_lock();
+        SIMInterface::clear_faults();
+        SIMInterface::config_nodes(vec![1]).unwrap();
+        SIMInterface::inject_fault(FaultSpec::CorruptPayload(1.0));
+
+        let result =
+            SIMInterface::send_request_to_node(1, &Msg::new_std_request(RequestType::DevInfoUID));
+
+        SIMInterface::clear_faults();
+        assert!(matches!(result, Err(Error::MsgCorruption(_))));
+    }
+
+    #[test]
+    fn test_inject_fault_power_loss_after_writes_silently_drops_the_write() {
+        let _guard = test_lock();
+        SIMInterface::clear_faults();
+        let node_lst = vec![1];
+        SIMInterface::config_nodes(node_lst).unwrap();
+
+        SIMInterface::send_request_to_node(
+            1,
+            &Msg::new(RequestType::PageBufferClear, ResultType::None, 0, &MsgData::new()),
+        )
+        .unwrap();
+        SIMInterface::send_request_to_node(
+            1,
+            &Msg::new(
+                RequestType::PageBufferWriteWord,
+                ResultType::None,
+                0,
+                &MsgData::from_word(0x1111_1111),
+            ),
+        )
+        .unwrap();
+
+        SIMInterface::inject_fault(FaultSpec::PowerLossAfterWrites(0));
+
+        // Acknowledged `Ok`, but should never reach the device.
+        let response = SIMInterface::send_request_to_node(
+            1,
+            &Msg::new(
+                RequestType::PageBufferWriteWord,
+                ResultType::None,
+                0,
+                &MsgData::from_word(0x2222_2222),
+            ),
+        )
+        .unwrap();
+        assert_eq!(response.get_result(), ResultType::Ok);
+
+        SIMInterface::clear_faults();
+
+        let read_back = SIMInterface::send_request_to_node(
+            1,
+            &Msg::new(RequestType::PageBufferReadWord, ResultType::None, 0, &MsgData::new()),
+        )
+        .unwrap();
+        assert_eq!(read_back.get_data().to_word(), 0x1111_1111);
+    }
 }
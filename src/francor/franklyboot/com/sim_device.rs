@@ -0,0 +1,431 @@
+#![cfg(feature = "sim-native")]
+
+use crate::francor::franklyboot::com::msg::{Msg, MsgData, RequestType, ResultType, SwapState};
+use crc::{Crc, CRC_32_ISO_HDLC};
+use std::collections::{HashMap, VecDeque};
+
+const CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+const FLASH_START: u32 = 0x08000000;
+const FLASH_PAGE_SIZE: u32 = 0x00000400;
+const FLASH_NUM_PAGES: u32 = 0x0000000F;
+const FLASH_APP_PAGE_IDX: u32 = 0x00000002;
+const FLASH_WRITE_WINDOW_SIZE: u32 = 8;
+
+const DEV_INFO_VID: u32 = 0x46524352;
+const DEV_INFO_PID: u32 = 0x54455354;
+const DEV_INFO_PRD: u32 = 0x00000000;
+const DEV_INFO_UID: u32 = 0x00000000;
+const BOOTLOADER_VERSION: [u8; 4] = [0, 1, 0, 0];
+const BOOTLOADER_CRC: u32 = 0x00000000;
+
+const FLASH_DFT_VALUE: u8 = 0xFF;
+
+///
+/// Pure-Rust, in-process bootloader simulator
+///
+/// Implements the same request/response state machine the real `franklyboot-device-sim-api` C++
+/// library implements (ping, dev-info, flash-info, page buffer, erase/write, app-CRC, plus the A/B
+/// swap and config-store request groups), against an in-memory flash array. This is what backs
+/// `DeviceSimAPI`/`DeviceSimInterface` when built with the `sim-native` feature instead of
+/// `sim-cpp`, so the same flashing/property tests run without a C++ toolchain.
+///
+pub struct SimDevice {
+    flash: Vec<u8>,
+    page_buffer: Vec<u8>,
+    app_crc_strd: u32,
+    swap_state: SwapState,
+    /// Config store entries as `(key_hash, value)`, most recently written last; `config_write`
+    /// tombstones an existing entry for the same key by removing it before appending the new one,
+    /// mirroring the real store's "append + old record goes stale" log structure.
+    config_store: Vec<(u32, Vec<u8>)>,
+}
+
+impl SimDevice {
+    pub fn new() -> Self {
+        SimDevice {
+            flash: vec![FLASH_DFT_VALUE; (FLASH_PAGE_SIZE * FLASH_NUM_PAGES) as usize],
+            page_buffer: vec![FLASH_DFT_VALUE; FLASH_PAGE_SIZE as usize],
+            app_crc_strd: 0,
+            swap_state: SwapState::None,
+            config_store: Vec::new(),
+        }
+    }
+
+    /// Processes one request message and returns the response the real device would send
+    pub fn handle_request(&mut self, request: &Msg) -> Msg {
+        let packet_id = request.get_packet_id();
+        let argument = request.get_data().to_word();
+
+        match request.get_request() {
+            RequestType::Ping => self._ok(request, &MsgData::new()),
+            RequestType::ResetDevice => self._ok(request, &MsgData::new()),
+            RequestType::StartApp => self._ok(request, &MsgData::new()),
+
+            RequestType::DevInfoBootloaderVersion => {
+                self._ok(request, &MsgData::from_array(&BOOTLOADER_VERSION))
+            }
+            RequestType::DevInfoBootloaderCRC => {
+                self._ok(request, &MsgData::from_word(BOOTLOADER_CRC))
+            }
+            RequestType::DevInfoVID => self._ok(request, &MsgData::from_word(DEV_INFO_VID)),
+            RequestType::DevInfoPID => self._ok(request, &MsgData::from_word(DEV_INFO_PID)),
+            RequestType::DevInfoPRD => self._ok(request, &MsgData::from_word(DEV_INFO_PRD)),
+            RequestType::DevInfoUID => self._ok(request, &MsgData::from_word(DEV_INFO_UID)),
+
+            RequestType::FlashInfoStartAddr => self._ok(request, &MsgData::from_word(FLASH_START)),
+            RequestType::FlashInfoPageSize => {
+                self._ok(request, &MsgData::from_word(FLASH_PAGE_SIZE))
+            }
+            RequestType::FlashInfoNumPages => {
+                self._ok(request, &MsgData::from_word(FLASH_NUM_PAGES))
+            }
+            RequestType::FlashInfoWriteWindowSize => {
+                self._ok(request, &MsgData::from_word(FLASH_WRITE_WINDOW_SIZE))
+            }
+
+            RequestType::AppInfoPageIdx => {
+                self._ok(request, &MsgData::from_word(FLASH_APP_PAGE_IDX))
+            }
+            RequestType::AppInfoCRCCalc => {
+                let crc = CRC32.checksum(self._app_region());
+                self._ok(request, &MsgData::from_word(crc))
+            }
+            RequestType::AppInfoCRCStrd => {
+                self._ok(request, &MsgData::from_word(self.app_crc_strd))
+            }
+
+            RequestType::FlashReadWord => match self._read_flash_word(argument) {
+                Some(word) => self._ok(request, &MsgData::from_word(word)),
+                None => self._err(request, ResultType::ErrInvldArg),
+            },
+            RequestType::FlashPageCRCCalc => match self._page_bytes(argument) {
+                Some(bytes) => self._ok(request, &MsgData::from_word(CRC32.checksum(bytes))),
+                None => self._err(request, ResultType::ErrInvldArg),
+            },
+
+            RequestType::PageBufferClear => {
+                self.page_buffer = vec![FLASH_DFT_VALUE; FLASH_PAGE_SIZE as usize];
+                self._ok(request, &MsgData::new())
+            }
+            RequestType::PageBufferWriteWord => {
+                let byte_offset = packet_id as usize * 4;
+                if byte_offset + 4 > self.page_buffer.len() {
+                    self._err(request, ResultType::ErrPageFull)
+                } else {
+                    self.page_buffer[byte_offset..byte_offset + 4]
+                        .copy_from_slice(request.get_data().get_array());
+                    self._ok(request, &MsgData::new())
+                }
+            }
+            RequestType::PageBufferReadWord => {
+                let byte_offset = packet_id as usize * 4;
+                if byte_offset + 4 > self.page_buffer.len() {
+                    self._err(request, ResultType::ErrInvldArg)
+                } else {
+                    let word = MsgData::from_array(&[
+                        self.page_buffer[byte_offset],
+                        self.page_buffer[byte_offset + 1],
+                        self.page_buffer[byte_offset + 2],
+                        self.page_buffer[byte_offset + 3],
+                    ]);
+                    self._ok(request, &word)
+                }
+            }
+            RequestType::PageBufferCalcCRC => {
+                self._ok(request, &MsgData::from_word(CRC32.checksum(&self.page_buffer)))
+            }
+            RequestType::PageBufferWriteToFlash => match self._page_range(argument) {
+                Some((start, end)) => {
+                    self.flash[start..end].copy_from_slice(&self.page_buffer);
+                    self._ok(request, &MsgData::new())
+                }
+                None => self._err(request, ResultType::ErrInvldArg),
+            },
+
+            RequestType::FlashWriteErasePage => match self._page_range(argument) {
+                Some((start, end)) => {
+                    self.flash[start..end].fill(FLASH_DFT_VALUE);
+                    self._ok(request, &MsgData::new())
+                }
+                None => self._err(request, ResultType::ErrInvldArg),
+            },
+            RequestType::FlashWriteAppCRC => {
+                self.app_crc_strd = argument;
+                self._ok(request, &MsgData::new())
+            }
+
+            RequestType::SwapStart => {
+                self.swap_state = SwapState::InProgress;
+                self._ok(request, &MsgData::new())
+            }
+            RequestType::ConfirmImage => {
+                self.swap_state = SwapState::Confirmed;
+                self._ok(request, &MsgData::new())
+            }
+            RequestType::SwapStatus => {
+                self._ok(request, &MsgData::from_word(self.swap_state.to_u8() as u32))
+            }
+
+            RequestType::ConfigWrite => {
+                let key_hash = argument;
+                self.config_store.retain(|(hash, _)| *hash != key_hash);
+                self.config_store.push((key_hash, self.page_buffer.clone()));
+                self._ok(request, &MsgData::new())
+            }
+            RequestType::ConfigErase => {
+                let key_hash = argument;
+                self.config_store.retain(|(hash, _)| *hash != key_hash);
+                self._ok(request, &MsgData::new())
+            }
+            RequestType::ConfigRead => {
+                let key_hash = argument;
+                let byte_offset = packet_id as usize * 4;
+                match self
+                    .config_store
+                    .iter()
+                    .find(|(hash, _)| *hash == key_hash)
+                    .and_then(|(_, value)| value.get(byte_offset..byte_offset + 4))
+                {
+                    Some(word) => self._ok(
+                        request,
+                        &MsgData::from_array(&[word[0], word[1], word[2], word[3]]),
+                    ),
+                    None => self._err(request, ResultType::ErrInvldArg),
+                }
+            }
+            RequestType::ConfigList => match self.config_store.get(packet_id as usize) {
+                Some((hash, _)) => self._ok(request, &MsgData::from_word(*hash)),
+                None => self._err(request, ResultType::ErrInvldArg),
+            },
+        }
+    }
+
+    fn _ok(&self, request: &Msg, data: &MsgData) -> Msg {
+        Msg::new(request.get_request(), ResultType::Ok, request.get_packet_id(), data)
+    }
+
+    fn _err(&self, request: &Msg, result: ResultType) -> Msg {
+        Msg::new(
+            request.get_request(),
+            result,
+            request.get_packet_id(),
+            &MsgData::new(),
+        )
+    }
+
+    fn _app_region(&self) -> &[u8] {
+        let app_start = (FLASH_APP_PAGE_IDX * FLASH_PAGE_SIZE) as usize;
+        &self.flash[app_start..]
+    }
+
+    fn _read_flash_word(&self, address: u32) -> Option<u32> {
+        let offset = address.checked_sub(FLASH_START)? as usize;
+        let bytes = self.flash.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn _page_bytes(&self, page_id: u32) -> Option<&[u8]> {
+        let (start, end) = self._page_range(page_id)?;
+        Some(&self.flash[start..end])
+    }
+
+    fn _page_range(&self, page_id: u32) -> Option<(usize, usize)> {
+        let start = (page_id * FLASH_PAGE_SIZE) as usize;
+        let end = start + FLASH_PAGE_SIZE as usize;
+        if end > self.flash.len() {
+            None
+        } else {
+            Some((start, end))
+        }
+    }
+}
+
+// Simulated network --------------------------------------------------------------------------------
+
+///
+/// Simulated network of `SimDevice`s
+///
+/// Mirrors the global state the C++ `franklyboot-device-sim-api` library keeps behind its
+/// `SIM_*` functions: devices are addressed by node id, broadcast responses are collected into a
+/// single FIFO queue (with their originating node id), and node-specific responses are queued per
+/// node - the same shape `DeviceSimAPI::get_broadcast_response_msg`/`get_node_response_msg` expect.
+///
+pub struct SimNetwork {
+    devices: HashMap<u8, SimDevice>,
+    broadcast_responses: VecDeque<(u8, Msg)>,
+    node_responses: HashMap<u8, VecDeque<Msg>>,
+}
+
+impl SimNetwork {
+    pub fn new() -> Self {
+        SimNetwork {
+            devices: HashMap::new(),
+            broadcast_responses: VecDeque::new(),
+            node_responses: HashMap::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.devices.clear();
+        self.broadcast_responses.clear();
+        self.node_responses.clear();
+    }
+
+    pub fn add_device(&mut self, node_id: u8) -> bool {
+        if self.devices.contains_key(&node_id) {
+            false
+        } else {
+            self.devices.insert(node_id, SimDevice::new());
+            self.node_responses.insert(node_id, VecDeque::new());
+            true
+        }
+    }
+
+    pub fn get_device_count(&self) -> u32 {
+        self.devices.len() as u32
+    }
+
+    pub fn send_broadcast_msg(&mut self, msg: &Msg) {
+        let mut node_ids: Vec<u8> = self.devices.keys().copied().collect();
+        node_ids.sort_unstable();
+
+        for node_id in node_ids {
+            let response = self.devices.get_mut(&node_id).unwrap().handle_request(msg);
+            self.broadcast_responses.push_back((node_id, response));
+        }
+    }
+
+    pub fn send_node_msg(&mut self, node_id: u8, msg: &Msg) {
+        if let Some(device) = self.devices.get_mut(&node_id) {
+            let response = device.handle_request(msg);
+            self.node_responses.entry(node_id).or_default().push_back(response);
+        }
+    }
+
+    pub fn get_broadcast_response_msg(&mut self) -> Option<(u8, Msg)> {
+        self.broadcast_responses.pop_front()
+    }
+
+    pub fn get_node_response_msg(&mut self, node_id: u8) -> Option<Msg> {
+        self.node_responses.get_mut(&node_id)?.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::msg::MsgData;
+
+    #[test]
+    fn sim_device_ping_responds_ok() {
+        let mut device = SimDevice::new();
+        let request = Msg::new_std_request(RequestType::Ping);
+
+        let response = device.handle_request(&request);
+
+        assert_eq!(response.get_result(), ResultType::Ok);
+        assert_eq!(response.get_request(), RequestType::Ping);
+    }
+
+    #[test]
+    fn sim_device_flash_page_round_trip() {
+        let mut device = SimDevice::new();
+
+        device.handle_request(&Msg::new_std_request(RequestType::PageBufferClear));
+        device.handle_request(&Msg::new(
+            RequestType::PageBufferWriteWord,
+            ResultType::None,
+            0,
+            &MsgData::from_word(0xDEADBEEF),
+        ));
+        device.handle_request(&Msg::new(
+            RequestType::FlashWriteErasePage,
+            ResultType::None,
+            0,
+            &MsgData::from_word(FLASH_APP_PAGE_IDX),
+        ));
+        device.handle_request(&Msg::new(
+            RequestType::PageBufferWriteToFlash,
+            ResultType::None,
+            0,
+            &MsgData::from_word(FLASH_APP_PAGE_IDX),
+        ));
+
+        let response = device.handle_request(&Msg::new(
+            RequestType::FlashReadWord,
+            ResultType::None,
+            0,
+            &MsgData::from_word(FLASH_START + FLASH_APP_PAGE_IDX * FLASH_PAGE_SIZE),
+        ));
+
+        assert_eq!(response.get_result(), ResultType::Ok);
+        assert_eq!(response.get_data().to_word(), 0xDEADBEEF);
+    }
+
+    #[test]
+    fn sim_device_config_write_erase_list() {
+        let mut device = SimDevice::new();
+        let key_hash = 0x1234_5678;
+
+        device.handle_request(&Msg::new_std_request(RequestType::PageBufferClear));
+        device.handle_request(&Msg::new(
+            RequestType::PageBufferWriteWord,
+            ResultType::None,
+            0,
+            &MsgData::from_word(42),
+        ));
+        device.handle_request(&Msg::new(
+            RequestType::ConfigWrite,
+            ResultType::None,
+            0,
+            &MsgData::from_word(key_hash),
+        ));
+
+        let read = device.handle_request(&Msg::new(
+            RequestType::ConfigRead,
+            ResultType::None,
+            0,
+            &MsgData::from_word(key_hash),
+        ));
+        assert_eq!(read.get_data().to_word(), 42);
+
+        let list = device.handle_request(&Msg::new(
+            RequestType::ConfigList,
+            ResultType::None,
+            0,
+            &MsgData::new(),
+        ));
+        assert_eq!(list.get_data().to_word(), key_hash);
+
+        device.handle_request(&Msg::new(
+            RequestType::ConfigErase,
+            ResultType::None,
+            0,
+            &MsgData::from_word(key_hash),
+        ));
+        let list_after_erase = device.handle_request(&Msg::new(
+            RequestType::ConfigList,
+            ResultType::None,
+            0,
+            &MsgData::new(),
+        ));
+        assert_eq!(list_after_erase.get_result(), ResultType::ErrInvldArg);
+    }
+
+    #[test]
+    fn sim_network_ping_finds_added_devices() {
+        let mut network = SimNetwork::new();
+        network.add_device(1);
+        network.add_device(2);
+
+        network.send_broadcast_msg(&Msg::new_std_request(RequestType::Ping));
+
+        let mut found = Vec::new();
+        while let Some((node_id, _)) = network.get_broadcast_response_msg() {
+            found.push(node_id);
+        }
+
+        assert_eq!(found, vec![1, 2]);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::francor::franklyboot::Error;
+
+// CAN Bit Timing -----------------------------------------------------------------------------------
+
+/// Sample point classic CAN controllers are conventionally tuned for
+pub const CLASSIC_SAMPLE_POINT_PERMILLE: u32 = 875;
+
+/// A reasonable default sample point for a CAN FD data phase; FD's higher data-phase bitrate
+/// tolerates less propagation delay than classic CAN's 87.5%, so the industry convention sits
+/// lower, around 70-75%.
+pub const FD_DATA_SAMPLE_POINT_PERMILLE: u32 = 750;
+
+///
+/// A CAN controller's bit timing register limits
+///
+/// Taken from the controller's datasheet; every candidate `calculate` tries is clamped to these
+/// ranges, mirroring the `brp`/`prop_seg`+`phase_seg1`/`phase_seg2`/`sjw` limits the Linux
+/// `can-dev` bittiming code reads out of `can_bittiming_const`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanTimingConstraints {
+    pub brp_min: u32,
+    pub brp_max: u32,
+    pub tseg1_min: u32,
+    pub tseg1_max: u32,
+    pub tseg2_min: u32,
+    pub tseg2_max: u32,
+    pub sjw_max: u32,
+}
+
+///
+/// A computed CAN bit timing solution
+///
+/// `tseg1`/`tseg2` do not include the controller's fixed 1 time-quantum sync segment, i.e. one
+/// bit time is `1 + tseg1 + tseg2` quanta long.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanBitTiming {
+    /// Prescaler: time quantum length in controller clock cycles
+    pub brp: u32,
+
+    /// Quanta between sync segment and sample point (propagation + phase segment 1)
+    pub tseg1: u32,
+
+    /// Quanta between sample point and the end of the bit (phase segment 2)
+    pub tseg2: u32,
+
+    /// Synchronization jump width
+    pub sjw: u32,
+
+    /// Bitrate this timing actually yields, which may differ slightly from what was requested
+    pub achieved_bitrate: u32,
+
+    /// Sample point this timing yields, in permille (875 = 87.5%)
+    pub sample_point_permille: u32,
+}
+
+impl CanBitTiming {
+    ///
+    /// Compute register-level bit timing for `bitrate` bps on a controller clocked at `fclk` Hz
+    ///
+    /// Mirrors the approach Linux's `can-dev` bittiming code uses: try every prescaler `brp` in
+    /// `constraints`' range, and for each one compute the resulting whole number of time quanta
+    /// per bit (`tq = fclk / (brp * bitrate)`), discarding any `brp` that does not divide evenly
+    /// or whose quanta count falls outside `1 + tseg1 + tseg2`'s allowed range. The remaining
+    /// quanta are then split between `tseg1` and `tseg2` to land the sample point
+    /// `(1 + tseg1) / tq` as close as possible to `target_sample_point_permille`. Among every
+    /// candidate considered, the one with the smallest combined bitrate error and sample-point
+    /// error wins; `sjw` is then set to `min(constraints.sjw_max, tseg2)`, the largest jump width
+    /// phase segment 2 can absorb.
+    ///
+    pub fn calculate(
+        fclk: u32,
+        bitrate: u32,
+        constraints: &CanTimingConstraints,
+        target_sample_point_permille: u32,
+    ) -> Result<CanBitTiming, Error> {
+        if bitrate == 0 || fclk == 0 {
+            return Err(Error::Error(
+                "CAN bit timing requires a non-zero clock and bitrate".to_string(),
+            ));
+        }
+
+        let total_min = 1 + constraints.tseg1_min + constraints.tseg2_min;
+        let total_max = 1 + constraints.tseg1_max + constraints.tseg2_max;
+
+        let mut best: Option<(CanBitTiming, u64, u64)> = None;
+
+        for brp in constraints.brp_min.max(1)..=constraints.brp_max {
+            let denom = brp as u64 * bitrate as u64;
+
+            // Only accept a prescaler that divides the clock into a whole number of time quanta
+            // per bit; a non-integer tq would mean no register setting reproduces `bitrate` at all.
+            if fclk as u64 % denom != 0 {
+                continue;
+            }
+            let tq = fclk as u64 / denom;
+
+            if tq < total_min as u64 || tq > total_max as u64 {
+                continue;
+            }
+
+            let achieved_bitrate = (fclk as u64 / (brp as u64 * tq)) as u32;
+            let bitrate_error = (achieved_bitrate as i64 - bitrate as i64).unsigned_abs();
+
+            let remaining = (tq - 1) as u32;
+            let tseg2_hi = constraints.tseg2_max.min(remaining.saturating_sub(constraints.tseg1_min));
+            for tseg2 in constraints.tseg2_min..=tseg2_hi {
+                let tseg1 = remaining - tseg2;
+                if tseg1 < constraints.tseg1_min || tseg1 > constraints.tseg1_max {
+                    continue;
+                }
+
+                let sample_point_permille = ((1 + tseg1) as u64 * 1000 / tq) as u32;
+                let sample_point_error = (sample_point_permille as i64
+                    - target_sample_point_permille as i64)
+                    .unsigned_abs();
+
+                let candidate = CanBitTiming {
+                    brp,
+                    tseg1,
+                    tseg2,
+                    sjw: constraints.sjw_max.min(tseg2),
+                    achieved_bitrate,
+                    sample_point_permille,
+                };
+
+                let is_better = match &best {
+                    None => true,
+                    Some((_, best_bitrate_error, best_sample_point_error)) => {
+                        (bitrate_error, sample_point_error)
+                            < (*best_bitrate_error, *best_sample_point_error)
+                    }
+                };
+                if is_better {
+                    best = Some((candidate, bitrate_error, sample_point_error));
+                }
+            }
+        }
+
+        best.map(|(candidate, _, _)| candidate).ok_or_else(|| {
+            Error::Error(format!(
+                "No CAN bit timing for {} bps found within the controller's constraints (fclk = {} Hz)",
+                bitrate, fclk
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rough bxCAN-style constraints (STM32's classic CAN controller)
+    fn bxcan_constraints() -> CanTimingConstraints {
+        CanTimingConstraints {
+            brp_min: 1,
+            brp_max: 1024,
+            tseg1_min: 1,
+            tseg1_max: 16,
+            tseg2_min: 1,
+            tseg2_max: 8,
+            sjw_max: 4,
+        }
+    }
+
+    #[test]
+    fn calculate_exact_bitrate_for_classic_can() {
+        let timing = CanBitTiming::calculate(
+            36_000_000,
+            500_000,
+            &bxcan_constraints(),
+            CLASSIC_SAMPLE_POINT_PERMILLE,
+        )
+        .unwrap();
+
+        assert_eq!(timing.achieved_bitrate, 500_000);
+        assert_eq!(1 + timing.tseg1 + timing.tseg2, 72 / timing.brp);
+        // 87.5% sample point is hit exactly for this clock/bitrate combination
+        assert_eq!(timing.sample_point_permille, 875);
+        assert_eq!(timing.sjw, timing.tseg2.min(4));
+    }
+
+    #[test]
+    fn calculate_biases_fd_data_phase_toward_lower_sample_point() {
+        let classic = CanBitTiming::calculate(
+            36_000_000,
+            500_000,
+            &bxcan_constraints(),
+            CLASSIC_SAMPLE_POINT_PERMILLE,
+        )
+        .unwrap();
+        let fd_data = CanBitTiming::calculate(
+            36_000_000,
+            500_000,
+            &bxcan_constraints(),
+            FD_DATA_SAMPLE_POINT_PERMILLE,
+        )
+        .unwrap();
+
+        assert!(fd_data.sample_point_permille <= classic.sample_point_permille);
+    }
+
+    #[test]
+    fn calculate_rejects_unreachable_bitrate() {
+        let result = CanBitTiming::calculate(36_000_000, 1, &bxcan_constraints(), CLASSIC_SAMPLE_POINT_PERMILLE);
+
+        assert!(result.is_err());
+    }
+}
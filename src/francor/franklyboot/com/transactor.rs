@@ -0,0 +1,380 @@
+use super::msg::{Msg, MsgData, RequestType, ResultType};
+use super::report::UpdateReport;
+use super::ComInterface;
+use crate::francor::franklyboot::Error;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// RetryPolicy ----------------------------------------------------------------------------------
+
+/// Retransmission policy for a `Transactor`
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// How many times a corrupted/failed exchange is resent before giving up
+    pub max_attempts: u32,
+
+    /// Applied to the interface via `ComInterface::set_timeout` before every attempt. `None`
+    /// leaves whatever timeout the interface already has.
+    pub per_attempt_timeout: Option<Duration>,
+
+    /// How long to wait before each resend
+    pub backoff: BackoffPolicy,
+
+    /// Number of previously sent packet ids to remember, so a delayed response to an earlier
+    /// request can be recognized and discarded instead of being treated as corruption
+    pub window_size: usize,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            per_attempt_timeout: None,
+            backoff: BackoffPolicy::Fixed(Duration::ZERO),
+            window_size: 4,
+        }
+    }
+}
+
+/// How long to wait between resends, as a function of the (zero-based) attempt number
+#[derive(Debug, Clone, Copy)]
+pub enum BackoffPolicy {
+    /// Always wait the same duration
+    Fixed(Duration),
+
+    /// Wait `step * (attempt + 1)`
+    Linear(Duration),
+
+    /// Wait `base * factor.pow(attempt)`
+    Exponential { base: Duration, factor: u32 },
+}
+
+impl BackoffPolicy {
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        match self {
+            BackoffPolicy::Fixed(delay) => *delay,
+            BackoffPolicy::Linear(step) => *step * (attempt + 1),
+            BackoffPolicy::Exponential { base, factor } => {
+                *base * factor.saturating_pow(attempt)
+            }
+        }
+    }
+}
+
+// Transactor ---------------------------------------------------------------------------------------
+
+/// Wraps a `ComInterface` with automatic packet-id sequencing and bounded retransmission
+///
+/// `Msg::is_response_ok` already detects a corrupted exchange via packet-id mismatch, but leaves
+/// the caller to pick packet ids and decide what to do about it. `Transactor` owns a rolling
+/// packet id counter (wrapping at `u8::MAX`, like the id itself), stamps every outgoing request
+/// with it, and on `Error::MsgCorruption` or a transport error resends the same request (waiting
+/// `RetryPolicy::backoff` between attempts) up to `RetryPolicy::max_attempts` times before giving
+/// up - mirroring the buffer-reset/retry handling in ARTIQ's `drtioaux` layer, where a fresh
+/// exchange is retried after a corrupted frame is detected.
+///
+/// It also keeps a small in-flight window of the last `RetryPolicy::window_size` packet ids it
+/// sent: if a response arrives whose packet id matches an older, already-settled request rather
+/// than the current one, that's a delayed/duplicate response straggling in over a lossy link, not
+/// a sign this exchange is corrupted - it's discarded and the `Transactor` keeps waiting for the
+/// real response instead of spending a retry on it.
+///
+/// A response carrying an error `ResultType` is not retried: that is a legitimate answer from the
+/// device, not a sign the frame was lost or corrupted, so it is returned to the caller as-is via
+/// `Msg::is_response_ok`'s `Error::ResultError`.
+pub struct Transactor<'a, I: ComInterface> {
+    interface: &'a mut I,
+    packet_id: u8,
+    policy: RetryPolicy,
+    window: VecDeque<u8>,
+    report: Option<UpdateReport>,
+}
+
+impl<'a, I: ComInterface> Transactor<'a, I> {
+    /// Wraps `interface`, retrying a corrupted/failed exchange up to `max_retries` times with the
+    /// rest of `RetryPolicy` left at its defaults
+    pub fn new(interface: &'a mut I, max_retries: u32) -> Self {
+        Self::new_with_policy(
+            interface,
+            RetryPolicy {
+                max_attempts: max_retries,
+                ..RetryPolicy::default()
+            },
+        )
+    }
+
+    /// Wraps `interface`, applying the full retry/backoff/window configuration in `policy`
+    pub fn new_with_policy(interface: &'a mut I, policy: RetryPolicy) -> Self {
+        Transactor {
+            interface,
+            packet_id: 0,
+            policy,
+            window: VecDeque::new(),
+            report: None,
+        }
+    }
+
+    /// Same as `new`, but records every exchange (request, result, retries, elapsed time) into an
+    /// `UpdateReport` retrievable via `take_report`, instead of discarding that history
+    pub fn new_with_report(interface: &'a mut I, max_retries: u32) -> Self {
+        Transactor {
+            report: Some(UpdateReport::new()),
+            ..Self::new(interface, max_retries)
+        }
+    }
+
+    /// The packet id the next `transact`/`transact_std` call will stamp its request with
+    pub fn next_packet_id(&self) -> u8 {
+        self.packet_id
+    }
+
+    /// Takes the recorded report, if this `Transactor` was built with `new_with_report`, leaving
+    /// a fresh empty report in its place
+    pub fn take_report(&mut self) -> Option<UpdateReport> {
+        self.report.as_mut().map(std::mem::take)
+    }
+
+    /// Sends a standard (zeroed payload) request for `request_type` and returns the validated
+    /// response, retrying on corruption/transport errors
+    pub fn transact_std(&mut self, request_type: RequestType) -> Result<Msg, Error> {
+        self.transact(request_type, &MsgData::new())
+    }
+
+    /// Sends `request_type`/`data`, stamping the next packet id, and returns the validated
+    /// response, resending the same request up to `policy.max_attempts` times if the exchange
+    /// comes back as `Error::MsgCorruption` or a transport error
+    pub fn transact(&mut self, request_type: RequestType, data: &MsgData) -> Result<Msg, Error> {
+        let packet_id = self.packet_id;
+        self.packet_id = self.packet_id.wrapping_add(1);
+        self._remember(packet_id);
+
+        let request = Msg::new(request_type, ResultType::None, packet_id, data);
+
+        if let Some(timeout) = self.policy.per_attempt_timeout {
+            self.interface.set_timeout(timeout)?;
+        }
+
+        let start = Instant::now();
+        let mut attempt = 0;
+        let outcome = loop {
+            match self._exchange(&request) {
+                Ok(response) => break Ok(response),
+                Err(err) if attempt < self.policy.max_attempts && Self::_is_retryable(&err) => {
+                    std::thread::sleep(self.policy.backoff.delay_for(attempt));
+                    attempt += 1;
+                }
+                Err(err) => break Err(err),
+            }
+        };
+
+        if let Some(report) = &mut self.report {
+            match &outcome {
+                Ok(response) => report.record(
+                    request_type,
+                    response.get_result(),
+                    packet_id,
+                    0,
+                    attempt,
+                    start.elapsed(),
+                ),
+                Err(err) => report.fail(err),
+            }
+        }
+
+        outcome
+    }
+
+    /// Sends `request` and waits for its matching response, silently discarding any response
+    /// whose packet id belongs to an earlier, already-remembered request instead of treating it
+    /// as corruption
+    fn _exchange(&mut self, request: &Msg) -> Result<Msg, Error> {
+        self.interface.send(request)?;
+
+        loop {
+            let response = self.interface.recv()?;
+            if response.get_packet_id() != request.get_packet_id()
+                && self.window.contains(&response.get_packet_id())
+            {
+                continue;
+            }
+
+            request.is_response_ok(&response)?;
+            return Ok(response);
+        }
+    }
+
+    /// Remembers `packet_id` as in-flight, keeping at most `policy.window_size` of the most
+    /// recently sent ids
+    fn _remember(&mut self, packet_id: u8) {
+        if self.policy.window_size == 0 {
+            return;
+        }
+
+        self.window.push_back(packet_id);
+        while self.window.len() > self.policy.window_size {
+            self.window.pop_front();
+        }
+    }
+
+    fn _is_retryable(err: &Error) -> bool {
+        matches!(
+            err,
+            Error::MsgCorruption(_) | Error::ComError(_) | Error::ComNoResponse
+        )
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::ComSimulator;
+
+    #[test]
+    fn transactor_stamps_incrementing_packet_ids() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 1, &MsgData::new()));
+
+        let mut transactor = Transactor::new(&mut interface, 0);
+        assert_eq!(transactor.next_packet_id(), 0);
+
+        transactor.transact_std(RequestType::Ping).unwrap();
+        assert_eq!(transactor.next_packet_id(), 1);
+
+        transactor.transact_std(RequestType::Ping).unwrap();
+        assert_eq!(transactor.next_packet_id(), 2);
+    }
+
+    #[test]
+    fn transactor_wraps_packet_id_at_u8_max() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::Ping,
+            ResultType::Ok,
+            u8::MAX,
+            &MsgData::new(),
+        ));
+
+        let mut transactor = Transactor::new(&mut interface, 0);
+        transactor.packet_id = u8::MAX;
+        transactor.transact_std(RequestType::Ping).unwrap();
+
+        assert_eq!(transactor.next_packet_id(), 0);
+    }
+
+    #[test]
+    fn transactor_retries_on_transport_error() {
+        let mut interface = ComSimulator::new();
+        interface.set_recv_error(Error::ComNoResponse);
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+
+        let mut transactor = Transactor::new(&mut interface, 1);
+        transactor.transact_std(RequestType::Ping).unwrap();
+    }
+
+    #[test]
+    fn transactor_retries_on_packet_id_corruption() {
+        let mut interface = ComSimulator::new();
+        // Wrong packet id first (as if a stale response from a previous exchange arrived), then
+        // a correctly matched one.
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 5, &MsgData::new()));
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+
+        let mut transactor = Transactor::new(&mut interface, 1);
+        transactor.transact_std(RequestType::Ping).unwrap();
+    }
+
+    #[test]
+    fn transactor_gives_up_after_max_retries() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 5, &MsgData::new()));
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 6, &MsgData::new()));
+
+        let mut transactor = Transactor::new(&mut interface, 1);
+        assert!(matches!(
+            transactor.transact_std(RequestType::Ping),
+            Err(Error::MsgCorruption(_))
+        ));
+    }
+
+    #[test]
+    fn transactor_does_not_retry_a_legitimate_error_result() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::Ping,
+            ResultType::ErrNotSupported,
+            0,
+            &MsgData::new(),
+        ));
+
+        let mut transactor = Transactor::new(&mut interface, 3);
+        assert!(matches!(
+            transactor.transact_std(RequestType::Ping),
+            Err(Error::ResultError(_))
+        ));
+        // Only the one response was consumed - no retry happened.
+        assert_eq!(interface.get_result(), None);
+    }
+
+    #[test]
+    fn transactor_discards_a_stale_response_without_spending_a_retry() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+        // A straggling duplicate for packet id 0 arrives before the real response to packet id 1.
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 1, &MsgData::new()));
+
+        let mut transactor = Transactor::new_with_report(&mut interface, 1);
+        transactor.transact_std(RequestType::Ping).unwrap();
+        transactor.transact_std(RequestType::Ping).unwrap();
+
+        let report = transactor.take_report().unwrap();
+        assert_eq!(report.entries()[1].retry_count, 0);
+    }
+
+    #[test]
+    fn transactor_with_a_zero_window_treats_any_mismatch_as_corruption() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+        // A stale duplicate for packet id 0, but the window is disabled, so it's just corruption.
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+
+        let mut transactor = Transactor::new_with_policy(
+            &mut interface,
+            RetryPolicy {
+                max_attempts: 0,
+                window_size: 0,
+                ..RetryPolicy::default()
+            },
+        );
+        transactor.transact_std(RequestType::Ping).unwrap();
+
+        assert!(matches!(
+            transactor.transact_std(RequestType::Ping),
+            Err(Error::MsgCorruption(_))
+        ));
+    }
+
+    #[test]
+    fn backoff_policy_computes_delay_by_attempt() {
+        assert_eq!(
+            BackoffPolicy::Fixed(Duration::from_millis(10)).delay_for(5),
+            Duration::from_millis(10)
+        );
+        assert_eq!(
+            BackoffPolicy::Linear(Duration::from_millis(10)).delay_for(2),
+            Duration::from_millis(30)
+        );
+        assert_eq!(
+            BackoffPolicy::Exponential {
+                base: Duration::from_millis(10),
+                factor: 2
+            }
+            .delay_for(3),
+            Duration::from_millis(80)
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::francor::franklyboot::{com::msg::Msg, Error};
+
+// Transport ----------------------------------------------------------------------------------------
+
+/// Frames a [`Msg`] onto a communication link, independently of how that link is opened or
+/// polled.
+///
+/// The wire protocol itself is always an eight byte [`MsgRaw`]; what varies between links is how
+/// many bytes make up one packet on top of that. `ComInterface` implementors that talk to a byte
+/// stream (`SerialInterface`, `CANInterface`) already frame themselves, reading/writing exactly
+/// eight bytes per message because their underlying link has no larger fixed packet size to fill.
+/// Links whose packets *are* a fixed, larger size than the message (e.g. a 64 byte HID report)
+/// need to pad the unused bytes on send and ignore them on receive instead of just reading eight
+/// bytes off the wire; `Transport` is the extension point for that framing, kept separate from
+/// `ComInterface` so the same framing can be reused by more than one interface implementation.
+pub trait Transport {
+    /// Size in bytes of one frame on this transport
+    fn frame_size(&self) -> usize;
+
+    /// Encodes `msg` into one frame, exactly `frame_size()` bytes long
+    fn encode(&self, msg: &Msg) -> Vec<u8>;
+
+    /// Decodes one frame back into a `Msg`
+    ///
+    /// `frame` must be exactly `frame_size()` bytes; anything else is a framing error rather than
+    /// a protocol error, so it is reported as `Error::MsgCorruption` rather than panicking.
+    fn decode(&self, frame: &[u8]) -> Result<Msg, Error>;
+}
+
+/// Direct 1:1 framing: the frame *is* the eight byte [`MsgRaw`]. This is the framing every
+/// existing interface (serial, CAN, the network simulator) has always used.
+pub struct RawFrameTransport;
+
+impl Transport for RawFrameTransport {
+    fn frame_size(&self) -> usize {
+        8
+    }
+
+    fn encode(&self, msg: &Msg) -> Vec<u8> {
+        msg.to_raw_data_array().to_vec()
+    }
+
+    fn decode(&self, frame: &[u8]) -> Result<Msg, Error> {
+        if frame.len() != self.frame_size() {
+            return Err(Error::MsgCorruption(format!(
+                "Expected a {} byte frame, got {} bytes",
+                self.frame_size(),
+                frame.len()
+            )));
+        }
+        Ok(Msg::try_from_raw_data_array(frame)?)
+    }
+}
+
+/// Size in bytes of a HID report frame, fixed by the USB HID descriptor regardless of how little
+/// the bootloader protocol actually needs.
+pub const HID_FRAME_SIZE: usize = 64;
+
+/// Frames a `Msg` inside a 64 byte HID report: the eight byte `MsgRaw` occupies the start of the
+/// report, the remaining bytes are zero padding the device ignores on receive and the host
+/// discards on decode.
+pub struct HidFrameTransport;
+
+impl Transport for HidFrameTransport {
+    fn frame_size(&self) -> usize {
+        HID_FRAME_SIZE
+    }
+
+    fn encode(&self, msg: &Msg) -> Vec<u8> {
+        let mut frame = vec![0u8; HID_FRAME_SIZE];
+        frame[..8].copy_from_slice(&msg.to_raw_data_array());
+        frame
+    }
+
+    fn decode(&self, frame: &[u8]) -> Result<Msg, Error> {
+        if frame.len() != HID_FRAME_SIZE {
+            return Err(Error::MsgCorruption(format!(
+                "HidFrameTransport expects a {} byte frame, got {} bytes",
+                HID_FRAME_SIZE,
+                frame.len()
+            )));
+        }
+
+        Ok(Msg::try_from_raw_data_array(&frame[..8])?)
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::msg::{MsgData, RequestType, ResultType};
+
+    fn sample_msg() -> Msg {
+        Msg::new(
+            RequestType::Ping,
+            ResultType::Ok,
+            5,
+            &MsgData::from_array(&[0x01, 0x02, 0x03, 0x04]),
+        )
+    }
+
+    #[test]
+    fn raw_frame_transport_round_trip() {
+        let transport = RawFrameTransport;
+        let msg = sample_msg();
+
+        let frame = transport.encode(&msg);
+        assert_eq!(frame.len(), transport.frame_size());
+
+        let decoded = transport.decode(&frame).unwrap();
+        assert_eq!(decoded.request, msg.request);
+        assert_eq!(decoded.result, msg.result);
+        assert_eq!(decoded.packet_id, msg.packet_id);
+        assert_eq!(decoded.data, msg.data);
+    }
+
+    #[test]
+    fn raw_frame_transport_decode_wrong_size() {
+        let transport = RawFrameTransport;
+        assert!(transport.decode(&[0; 7]).is_err());
+    }
+
+    #[test]
+    fn hid_frame_transport_round_trip() {
+        let transport = HidFrameTransport;
+        let msg = sample_msg();
+
+        let frame = transport.encode(&msg);
+        assert_eq!(frame.len(), HID_FRAME_SIZE);
+        assert!(frame[8..].iter().all(|&byte| byte == 0));
+
+        let decoded = transport.decode(&frame).unwrap();
+        assert_eq!(decoded.request, msg.request);
+        assert_eq!(decoded.result, msg.result);
+        assert_eq!(decoded.packet_id, msg.packet_id);
+        assert_eq!(decoded.data, msg.data);
+    }
+
+    #[test]
+    fn hid_frame_transport_decode_wrong_size() {
+        let transport = HidFrameTransport;
+        assert!(transport.decode(&[0; 8]).is_err());
+    }
+}
@@ -1,11 +1,38 @@
 use crate::francor::franklyboot::{
-    com::{msg::MsgData, msg::RequestType, ComInterface},
+    chip_db::ChipDb,
+    com::{
+        msg::{Msg, MsgData, RequestType, ResultType, SwapState},
+        ComInterface,
+    },
     device::{Entry, EntryList, EntryType},
-    firmware::{AppFirmware, FirmwareDataInterface},
-    flash::FlashDesc,
-    Error,
+    firmware::{
+        compressed::Codec, AppFirmware, FirmwareDataInterface, FirmwareMetadata, FlashPage,
+        FLASH_DFT_VALUE,
+    },
+    flash::{FlashDesc, SectionFlags},
+    observer_to_progress_fn, Error, ProgressObserver, ProgressUpdate, StdoutObserver, TimeoutReason,
 };
+use crc::{Crc, CRC_32_ISO_HDLC};
+use std::collections::VecDeque;
 use std::fmt;
+use std::sync::{atomic::AtomicBool, atomic::Ordering, Arc};
+use std::time::{Duration, Instant};
+
+/// Hash used to turn a human readable config key (e.g. "ip-address") into the 32 bit key
+/// identifier carried in `MsgData`, see `config_key_hash`. Reuses the CRC32 already pulled in by
+/// `firmware` for firmware-image checksums instead of adding a dedicated hashing dependency.
+const CONFIG_KEY_CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// Upper bound on how many `PageBufferWriteWord` requests `Device` will keep in flight before
+/// waiting for their acknowledgements, regardless of what a device advertises via
+/// `FlashInfoWriteWindowSize`. A wider window cuts the round trips per page at the cost of more
+/// buffered-but-unacknowledged writes; a `ResultType::ErrPageFull` response means the device
+/// couldn't keep up after all, so the current page is retried one word at a time.
+const FLASH_WRITE_WINDOW: usize = 8;
+
+/// Default `max_word_retries` - enough to ride out a brief burst of noise without masking a truly
+/// dead link behind a long silent retry loop
+const DEFAULT_MAX_WORD_RETRIES: u32 = 3;
 
 // Device -----------------------------------------------------------------------------------------
 
@@ -24,6 +51,50 @@ pub struct Device<I> {
 
     /// Vector of all entries
     entries: EntryList,
+
+    /// Optional sink for `ProgressUpdate`s from `erase`/`flash`/`verify`. `None` (the default used
+    /// by `new`) falls back to printing the same lines this crate has always printed, so existing
+    /// callers see no change; a library embedder supplies one via `new_with_progress` instead to
+    /// get progress without any stdout output.
+    progress: Option<Box<dyn Fn(ProgressUpdate) + Send>>,
+
+    /// Optional cooperative cancellation token for `erase`/`flash`, checked between pages. `None`
+    /// (the default) means the operation always runs to completion; set one with
+    /// `set_cancel_token` to let an external signal (e.g. a caller's Ctrl+C handler) stop it early.
+    cancel: Option<Arc<AtomicBool>>,
+
+    /// Byte value this device's flash reads back as once erased, following embassy-boot's
+    /// `ERASE_VALUE`. Defaults to `FLASH_DFT_VALUE` (`0xFF`); override with `set_erase_value`
+    /// before `init()` for a flash that erases to something else.
+    erase_value: u8,
+
+    /// Number of `PageBufferWriteWord` requests `_flash_app_pages` keeps in flight at once,
+    /// negotiated from the device's `FlashInfoWriteWindowSize` right before flashing starts (see
+    /// `_negotiate_write_window`), capped at `FLASH_WRITE_WINDOW`. Stays at `1` - one word sent at
+    /// a time, acknowledgement awaited before the next - for a bootloader that predates that
+    /// request and so doesn't advertise a window size at all.
+    write_window: usize,
+
+    /// Send a `RequestType::Ping` keepalive from `_flash_app_pages` once this much time has
+    /// passed since the last exchange, so a long flash run doesn't trip the bootloader's own
+    /// inactivity timeout. `None` (the default, set by `new`/`new_with_progress`) disables it;
+    /// enable with `set_keepalive_interval`.
+    keepalive_interval: Option<Duration>,
+
+    /// When the last request/response exchange with the device completed, checked against
+    /// `keepalive_interval` by `_maybe_send_keepalive`
+    last_exchange: Instant,
+
+    /// How many times `_write_page_words_sync` resends a `PageBufferWriteWord` whose
+    /// acknowledgement times out or is corrupted before giving up on the whole flash. Defaults to
+    /// `DEFAULT_MAX_WORD_RETRIES`; override with `set_max_word_retries`.
+    max_word_retries: u32,
+
+    /// Delay `_write_page_words_sync` waits before sending each word, the way ISO-TP's STmin
+    /// paces multi-frame sends - gives a slower bootloader time to drain its RX buffer instead of
+    /// dropping frames sent back-to-back. Defaults to `Duration::ZERO` (no pacing), the prior
+    /// behavior; override with `set_word_write_delay`.
+    word_write_delay: Duration,
 }
 
 /// Implementation of the Display trait for the Device struct
@@ -47,12 +118,33 @@ impl<I> Device<I>
 where
     I: ComInterface,
 {
-    /// Create a new device
+    /// Create a new device that reports `erase`/`flash`/`verify` progress by printing to stdout
     pub fn new(interface: I) -> Self {
+        Self::new_with_progress(interface, None)
+    }
+
+    /// Create a new device with a custom progress callback
+    ///
+    /// `progress` receives every `ProgressUpdate` from `erase`/`flash`/`verify` instead of the
+    /// default stdout prints, so a library embedder (or a UI like the project's TUI) can render
+    /// progress its own way without this crate assuming a terminal is attached. Pass `None` for
+    /// the same stdout behavior `new` gives.
+    pub fn new_with_progress(
+        interface: I,
+        progress: Option<Box<dyn Fn(ProgressUpdate) + Send>>,
+    ) -> Self {
         let mut device = Self {
             interface: interface,
             flash_desc: FlashDesc::new(0, 0, 0),
             entries: EntryList::new(),
+            progress,
+            cancel: None,
+            erase_value: FLASH_DFT_VALUE,
+            write_window: 1,
+            keepalive_interval: None,
+            last_exchange: Instant::now(),
+            max_word_retries: DEFAULT_MAX_WORD_RETRIES,
+            word_write_delay: Duration::ZERO,
         };
 
         device._add_entry(EntryType::Const, RequestType::DevInfoBootloaderVersion);
@@ -68,24 +160,98 @@ where
         device._add_entry(EntryType::Const, RequestType::FlashInfoStartAddr);
         device._add_entry(EntryType::Const, RequestType::FlashInfoPageSize);
         device._add_entry(EntryType::Const, RequestType::FlashInfoNumPages);
+        device._add_entry(EntryType::RO, RequestType::FlashInfoWriteWindowSize);
 
         device._add_entry(EntryType::Const, RequestType::AppInfoPageIdx);
         device._add_entry(EntryType::RO, RequestType::AppInfoCRCCalc);
 
+        device._add_entry(EntryType::RO, RequestType::FlashReadWord);
+
         device._add_entry(EntryType::Cmd, RequestType::PageBufferClear);
         device._add_entry(EntryType::RW, RequestType::PageBufferWriteWord);
         device._add_entry(EntryType::RO, RequestType::PageBufferCalcCRC);
         device._add_entry(EntryType::Cmd, RequestType::PageBufferWriteToFlash);
 
         device._add_entry(EntryType::Cmd, RequestType::FlashWriteErasePage);
+        device._add_entry(EntryType::Cmd, RequestType::FlashWriteMassErase);
         device._add_entry(EntryType::Cmd, RequestType::FlashWriteAppCRC);
 
         device._add_entry(EntryType::Cmd, RequestType::StartApp);
         device._add_entry(EntryType::Cmd, RequestType::ResetDevice);
 
+        device._add_entry(EntryType::Cmd, RequestType::SwapStart);
+        device._add_entry(EntryType::Cmd, RequestType::ConfirmImage);
+        device._add_entry(EntryType::RO, RequestType::SwapStatus);
+
+        device._add_entry(EntryType::Cmd, RequestType::ConfigWrite);
+        device._add_entry(EntryType::Cmd, RequestType::ConfigErase);
+
         device
     }
 
+    /// Create a new device that reports `erase`/`flash`/`verify` progress through `observer`
+    ///
+    /// Convenience wrapper around `new_with_progress` for callers who'd rather implement
+    /// `ProgressObserver`'s typed methods than match on every `ProgressUpdate` variant themselves
+    /// (e.g. a GUI or logging backend embedding this crate as a library).
+    pub fn new_with_observer<O>(interface: I, observer: O) -> Self
+    where
+        O: ProgressObserver + Send + 'static,
+    {
+        Self::new_with_progress(interface, Some(Box::new(observer_to_progress_fn(observer))))
+    }
+
+    /// Install a cooperative cancellation token, checked between pages by `erase`/`flash`
+    ///
+    /// Flip the shared `AtomicBool` to `true` (e.g. from a Ctrl+C handler) to stop the current
+    /// `erase`/`flash` before its next page: the device's page buffer is cleared so no half-written
+    /// page is ever committed or started, and the call returns `Error::Cancelled` instead of
+    /// finishing. Flashing always starts the app only after every page and the whole-image CRC have
+    /// succeeded, so a cancelled flash never leaves a half-written application running.
+    ///
+    pub fn set_cancel_token(&mut self, token: Arc<AtomicBool>) {
+        self.cancel = Some(token);
+    }
+
+    /// Configure the byte value this device's flash reads back as once erased
+    ///
+    /// Defaults to `FLASH_DFT_VALUE` (`0xFF`); call this before `init()` for a flash that erases
+    /// to something else, so `AppFirmware` pads gaps and unused pages with the right value before
+    /// computing CRCs.
+    pub fn set_erase_value(&mut self, erase_value: u8) {
+        self.erase_value = erase_value;
+    }
+
+    /// Send a `RequestType::Ping` keepalive during `flash` whenever more than `interval` has
+    /// elapsed since the last exchange with the device
+    ///
+    /// Disabled by default - a long page-write loop can otherwise outlast the bootloader's own
+    /// inactivity timeout and drop the device mid-flash; this borrows the "tester present"
+    /// keepalive pattern from KWP2000 diagnostic sessions. The keepalive rides whatever `ComMode`
+    /// is already active (e.g. `ComMode::Specific`), so it targets the device being flashed
+    /// rather than broadcasting.
+    pub fn set_keepalive_interval(&mut self, interval: Duration) {
+        self.keepalive_interval = Some(interval);
+    }
+
+    /// How many times `flash`/`flash_with_trial_boot` resends a word whose acknowledgement times
+    /// out or is corrupted before giving up with an error. Defaults to `DEFAULT_MAX_WORD_RETRIES`;
+    /// `0` preserves the original behavior of failing on the very first bad exchange. Only the
+    /// one-word-at-a-time path retries: a word sent through the write window (`write_window` > 1)
+    /// is not resent, and its bad acknowledgement fails the flash.
+    pub fn set_max_word_retries(&mut self, max_retries: u32) {
+        self.max_word_retries = max_retries;
+    }
+
+    /// Wait `delay` before sending each `PageBufferWriteWord`, the way ISO-TP's STmin paces
+    /// multi-frame sends - use this for a bootloader that drops frames sent back-to-back faster
+    /// than it can drain its receive buffer. Defaults to `Duration::ZERO` (no pacing). Only the
+    /// one-word-at-a-time path waits; words sent through the write window (`write_window` > 1)
+    /// are not paced.
+    pub fn set_word_write_delay(&mut self, delay: Duration) {
+        self.word_write_delay = delay;
+    }
+
     /// Initialize the device struct
     ///
     /// This function reads all constant data from the device and stores it in the device struct.
@@ -109,11 +275,17 @@ where
         let app_size = flash_size - bootloader_size;
 
         // Create flash description
-        self.flash_desc = FlashDesc::new(flash_start, flash_size, flash_page_size);
+        self.flash_desc =
+            FlashDesc::new_with_erase_value(flash_start, flash_size, flash_page_size, self.erase_value);
 
-        // Add bootloader section
+        // Add bootloader section, marked STATIC so `erase`/`flash` refuse to touch it
         self.flash_desc
-            .add_section("Bootloader", bootloader_start, bootloader_size)
+            .add_section_with_flags(
+                "Bootloader",
+                bootloader_start,
+                bootloader_size,
+                SectionFlags::STATIC,
+            )
             .map_err(|e| Error::Error(format!("Failed to add bootloader section: {}", e)))?;
 
         // Add application section
@@ -121,6 +293,135 @@ where
             .add_section("Application", app_start, app_size)
             .map_err(|e| Error::Error(format!("Failed to add application section: {}", e)))?;
 
+        // Surface a pending trial boot (see `flash_with_trial_boot`) so a caller notices without
+        // having to proactively poll `swap_status` itself. A bootloader predating the A/B swap
+        // commands is tolerated by simply not reporting anything.
+        if let Ok(state) = self.swap_status() {
+            match state {
+                SwapState::InProgress => self._report(ProgressUpdate::Message(
+                    "Device has an unconfirmed trial boot pending: call confirm_image() or revert()"
+                        .to_string(),
+                )),
+                SwapState::RevertRequested => self._report(ProgressUpdate::Message(
+                    "Device is rolling back an unconfirmed trial boot".to_string(),
+                )),
+                SwapState::None | SwapState::Confirmed => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate a firmware image against the target's chip database entry before flashing
+    ///
+    /// This checks that (1) the device's chip id (`DevInfoPID`) has a known `ChipDb` entry (and,
+    /// if `expected_chip` is given, that it matches that entry's name), (2) every byte of the
+    /// image lies within `[flash_start, flash_start + flash_size)`, and (3) the image's lowest
+    /// address does not fall inside the bootloader-reserved region. This turns a silent bricking
+    /// into an actionable error before any bytes are transmitted. Works with any
+    /// `FirmwareDataInterface` (Intel HEX, ELF, raw binary, ...), not just `HexFile`.
+    ///
+    pub fn validate_image<FWI: FirmwareDataInterface>(
+        &self,
+        firmware: &FWI,
+        expected_chip: Option<&str>,
+    ) -> Result<(), Error> {
+        let chip_id = self.get_device_info_pid();
+        let chip = ChipDb::lookup(chip_id).ok_or_else(|| {
+            Error::Error(format!(
+                "No chip database entry for chip id {:#010X}!",
+                chip_id
+            ))
+        })?;
+
+        if let Some(expected_chip) = expected_chip {
+            if chip.name != expected_chip {
+                return Err(Error::Error(format!(
+                    "Device reports chip \"{}\", but \"--expected-chip {}\" was given!",
+                    chip.name, expected_chip
+                )));
+            }
+        }
+
+        let min_address = firmware.min_address().ok_or_else(|| {
+            Error::Error("Firmware image does not contain any data to validate!".to_string())
+        })?;
+        let max_address = firmware.max_address().unwrap();
+
+        let flash_end = chip.flash_start + chip.flash_size;
+        if min_address < chip.flash_start || max_address >= flash_end {
+            return Err(Error::Error(format!(
+                "Firmware image address range [{:#010X}..{:#010X}] does not fit into {} flash [{:#010X}..{:#010X})!",
+                min_address, max_address, chip.name, chip.flash_start, flash_end
+            )));
+        }
+
+        if let Some(bootloader_end) = self
+            .flash_desc
+            .get_section_address("Bootloader")
+            .zip(self.flash_desc.get_section_size("Bootloader"))
+            .map(|(address, size)| address + size)
+        {
+            if min_address < bootloader_end {
+                return Err(Error::Error(format!(
+                    "Hex file starts at {:#010X}, which lies inside the bootloader-reserved region (< {:#010X})!",
+                    min_address, bootloader_end
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate a firmware image's embedded `FirmwareMetadata` header against this device's
+    /// identity and flash geometry before flashing
+    ///
+    /// Compares `metadata.vendor_id`/`product_id` against the device's `DevInfoVID`/`DevInfoPID`,
+    /// and `flash_start_address`/`flash_page_size`/`flash_num_pages` against the device's
+    /// `FlashInfoStartAddr`/`FlashInfoPageSize`/`FlashInfoNumPages`, so an image built for a
+    /// different target is rejected before a single byte is transmitted.
+    ///
+    pub fn check_compatibility(&self, metadata: &FirmwareMetadata) -> Result<(), Error> {
+        let device_vid = self.get_device_info_vid();
+        if metadata.vendor_id != device_vid {
+            return Err(Error::Error(format!(
+                "Firmware vendor id {:#010X} does not match device vendor id {:#010X}!",
+                metadata.vendor_id, device_vid
+            )));
+        }
+
+        let device_pid = self.get_device_info_pid();
+        if metadata.product_id != device_pid {
+            return Err(Error::Error(format!(
+                "Firmware product id {:#010X} does not match device product id {:#010X}!",
+                metadata.product_id, device_pid
+            )));
+        }
+
+        let flash_start = self.get_entry_value(RequestType::FlashInfoStartAddr);
+        if metadata.flash_start_address != flash_start {
+            return Err(Error::Error(format!(
+                "Firmware flash start address {:#010X} does not match device flash start address {:#010X}!",
+                metadata.flash_start_address, flash_start
+            )));
+        }
+
+        let flash_page_size = self.get_entry_value(RequestType::FlashInfoPageSize);
+        if metadata.flash_page_size != flash_page_size {
+            return Err(Error::Error(format!(
+                "Firmware flash page size {} does not match device flash page size {}!",
+                metadata.flash_page_size, flash_page_size
+            )));
+        }
+
+        let flash_num_pages = self.get_entry_value(RequestType::FlashInfoNumPages);
+        if metadata.flash_num_pages != flash_num_pages {
+            return Err(Error::Error(format!(
+                "Firmware flash number of pages {} does not match device flash number of pages {}!",
+                metadata.flash_num_pages, flash_num_pages
+            )));
+        }
+
         Ok(())
     }
 
@@ -138,19 +439,186 @@ where
         Ok(())
     }
 
+    /// Starts an A/B bank swap
+    ///
+    /// The bootloader copies the staged image into the active bank page by page, keeping a
+    /// persisted resume counter so a power loss mid-swap resumes instead of bricking the device.
+    /// That copy loop runs entirely on-device; this only kicks it off. Poll progress with
+    /// `swap_status` and, once the new image has proven itself, disarm the automatic rollback
+    /// with `confirm_image`.
+    ///
+    pub fn swap_start(&mut self) -> Result<(), Error> {
+        self.entries
+            .get_entry_mut(RequestType::SwapStart)
+            .exec(&mut self.interface, 0)?;
+
+        println!("Swap started...");
+
+        Ok(())
+    }
+
+    /// Confirms the swapped-in image is good
+    ///
+    /// Disarms the bootloader's automatic rollback. Must be called after a successful
+    /// `swap_start` once the new image is confirmed to work; otherwise the bootloader reverts to
+    /// the previous bank on the next reset.
+    ///
+    pub fn confirm_image(&mut self) -> Result<(), Error> {
+        self.entries
+            .get_entry_mut(RequestType::ConfirmImage)
+            .exec(&mut self.interface, 0)?;
+
+        println!("Image confirmed...");
+
+        Ok(())
+    }
+
+    /// Reads the current state of an A/B bank swap
+    pub fn swap_status(&mut self) -> Result<SwapState, Error> {
+        let value = self.read_entry_value(RequestType::SwapStatus)?.to_word();
+        Ok(SwapState::try_from_u8(value as u8)?)
+    }
+
+    /// Writes a config entry identified by `key` to the device's config store
+    ///
+    /// The key/value store itself - a log-structured page of length-prefixed records with
+    /// tombstoning on erase and compaction on page-full - lives entirely on the bootloader, the
+    /// same way the A/B swap's page-copy loop does; this only drives the wire protocol. `value` is
+    /// streamed into the page buffer one word at a time (zero-padded to a word boundary) using the
+    /// same `PageBufferWriteWord` protocol a flash page write uses, then `ConfigWrite` commits the
+    /// buffered bytes as a new record for `key`, appending it to the store's log.
+    ///
+    pub fn config_write(&mut self, key: &str, value: &[u8]) -> Result<(), Error> {
+        let key_hash = config_key_hash(key);
+        let num_words = (value.len() + 3) / 4;
+
+        let mut padded_value = value.to_vec();
+        padded_value.resize(num_words * 4, 0);
+
+        self.entries
+            .get_entry_mut(RequestType::PageBufferClear)
+            .exec(&mut self.interface, 0)?;
+
+        self._write_page_words_sync(&padded_value, num_words)?;
+
+        self.entries
+            .get_entry_mut(RequestType::ConfigWrite)
+            .exec(&mut self.interface, key_hash)?;
+
+        Ok(())
+    }
+
+    /// Reads `len` bytes of the config entry identified by `key` back from the device
+    ///
+    /// Words are pulled one at a time via raw `ConfigRead` requests, the key hash as payload and
+    /// the word index as the packet id, mirroring how `_read_flash_word` bypasses `Entry` for a
+    /// request whose payload is an argument rather than empty.
+    ///
+    pub fn config_read(&mut self, key: &str, len: usize) -> Result<Vec<u8>, Error> {
+        let key_hash = config_key_hash(key);
+        let num_words = (len + 3) / 4;
+
+        let mut value = Vec::with_capacity(num_words * 4);
+        for word_idx in 0..num_words {
+            let request = Msg::new(
+                RequestType::ConfigRead,
+                ResultType::None,
+                (word_idx % 256) as u8,
+                &MsgData::from_word(key_hash),
+            );
+
+            self.interface.send(&request)?;
+            let response = self.interface.recv()?;
+            request.is_response_ok(&response)?;
+
+            value.extend_from_slice(response.get_data().get_array());
+        }
+
+        value.truncate(len);
+
+        Ok(value)
+    }
+
+    /// Tombstones the config entry identified by `key`
+    pub fn config_erase(&mut self, key: &str) -> Result<(), Error> {
+        let key_hash = config_key_hash(key);
+
+        self.entries
+            .get_entry_mut(RequestType::ConfigErase)
+            .exec(&mut self.interface, key_hash)?;
+
+        Ok(())
+    }
+
+    /// Lists the key hashes of every config entry currently present on the device
+    ///
+    /// Walks `ConfigList` with an increasing index in the packet id until the device answers
+    /// `ResultType::ErrInvldArg`, the same "index past the end" convention used elsewhere on this
+    /// protocol - that's the normal way to find the end of the list, not an error to propagate.
+    ///
+    pub fn config_list(&mut self) -> Result<Vec<u32>, Error> {
+        let mut key_hash_lst = Vec::new();
+
+        for index in 0..=255u8 {
+            let request = Msg::new(
+                RequestType::ConfigList,
+                ResultType::None,
+                index,
+                &MsgData::new(),
+            );
+
+            self.interface.send(&request)?;
+            let response = self.interface.recv()?;
+
+            if response.get_result() == ResultType::ErrInvldArg {
+                break;
+            }
+
+            request.is_response_ok(&response)?;
+            key_hash_lst.push(response.get_data().to_word());
+        }
+
+        Ok(key_hash_lst)
+    }
+
     /// Erase the application area
     ///
-    /// This function erases the application area of the device.
+    /// By default this bulk-erases every page of the application area one `FlashWriteErasePage`
+    /// command at a time. Passing `mass_erase = true` instead issues a single
+    /// `FlashWriteMassErase` command covering the whole application region up front, like
+    /// embassy's USB-DFU path does, so a firmware image smaller than the one it replaces can't
+    /// leave stale pages behind and a bootloader with a cheap bulk-erase primitive doesn't pay
+    /// for N page erases. `flash` calls this once up front so the write phase that follows only
+    /// has to stream page data, instead of interleaving an erase with every page write.
     ///
-    pub fn erase(&mut self) -> Result<(), Error> {
+    pub fn erase(&mut self, mass_erase: bool) -> Result<(), Error> {
         let app_section = self.flash_desc.get_section("Application").unwrap();
 
+        self.flash_desc
+            .check_section_writable("Application")
+            .map_err(|e| Error::Error(format!("Cannot erase application area: {}", e)))?;
+
+        if mass_erase {
+            self._check_cancelled()?;
+
+            self._report(ProgressUpdate::Message(
+                "Mass erasing application area".to_string(),
+            ));
+
+            self.entries
+                .get_entry_mut(RequestType::FlashWriteMassErase)
+                .exec(&mut self.interface, 0)?;
+
+            return Ok(());
+        }
+
         for flash_page_id in app_section.get_page_range() {
-            println!(
-                "Erasing app pages [Flash-Page: {}/{}]",
-                flash_page_id + 1,
-                self.flash_desc.get_num_pages()
-            );
+            self._check_cancelled()?;
+
+            self._report(ProgressUpdate::EraseProgress {
+                current: flash_page_id + 1,
+                total: self.flash_desc.get_num_pages(),
+            });
 
             // Erase flash page
             self.entries
@@ -164,9 +632,24 @@ where
     /// Flash a new firmware to the device
     ///
     /// This function flashes a new firmware to the device. It reads the firmware data from the
-    /// FirmwareDataInterface and writes it to the device.
+    /// FirmwareDataInterface and writes it to the device: the application area is bulk-erased
+    /// once up front via `erase`, then every page is streamed with up to `write_window` (see
+    /// `_negotiate_write_window`) `PageBufferWriteWord` requests in flight at a time instead of
+    /// waiting on each acknowledgement. If the device reports its write buffer is full, that page
+    /// is cleared and rewritten, and every later page written, one word at a time.
     ///
-    pub fn flash<FWI: FirmwareDataInterface>(&mut self, fwi: &FWI) -> Result<(), Error> {
+    /// By default each page's buffer CRC is checked with the device right after it is written, so
+    /// a bad page is caught immediately - that extra round trip per page is what costs the most
+    /// time over a slow link like 115200-baud serial. Passing `fast = true` skips that per-page
+    /// check and streams every page back-to-back, relying solely on the whole-application CRC
+    /// check that always runs at the end to catch any mismatch.
+    ///
+    pub fn flash<FWI: FirmwareDataInterface>(
+        &mut self,
+        fwi: &FWI,
+        fast: bool,
+        mass_erase: bool,
+    ) -> Result<(), Error> {
         // Read necessary data to variables
         let app_section = self.flash_desc.get_section("Application").unwrap();
 
@@ -174,42 +657,249 @@ where
         let fw_size = fw_data.len() as u32;
         let fw_num_pages = (fw_size / app_section.get_page_size()) + 1;
 
-        // Print firmware information
-        println!(
-            "Firmware Data: Size: {:#.2} kB Num Pages: {}",
-            (fw_size as f32 / 1024.0),
-            fw_num_pages
-        );
+        // Report firmware information
+        self._report_firmware_info(fwi, fw_size, fw_num_pages, fast);
 
-        // TODO add check if firmware is valid and fits into flash
-        // Check page id (min limit)
-        // Check firmware size (max limit)
+        // Note: callers should call `validate_image` first to confirm the image fits the
+        // target's chip database entry.
 
         // Create app firmware representation
         let mut app_fw = AppFirmware::from_section(&app_section);
         app_fw.append_firmware(fw_data)?;
 
+        if !mass_erase {
+            self._report(ProgressUpdate::Message("Erasing application area".to_string()));
+        }
+        self.erase(mass_erase)?;
+
         // Transmit all pages of the firmware to the device
-        self._flash_app_pages(&app_fw)?;
+        self._flash_app_pages(&app_fw, fast)?;
 
-        println!("Checking CRC");
+        self._report(ProgressUpdate::Message("Checking CRC".to_string()));
         self._check_app_crc(&app_fw)?;
 
-        println!("Flashing App CRC");
+        self._report(ProgressUpdate::Message("Flashing App CRC".to_string()));
         self._flash_app_crc(app_fw.get_crc())?;
 
-        println!("Starting App");
+        self._report(ProgressUpdate::Message("Starting App".to_string()));
         self.entries
             .get_entry_mut(RequestType::StartApp)
             .exec(&mut self.interface, 0)?;
 
-        println!("App successfully flashed & started!");
+        self._report(ProgressUpdate::Message(
+            "App successfully flashed & started!".to_string(),
+        ));
 
         Ok(())
     }
 
+    /// Flash a new firmware as an unconfirmed trial boot, rollback-safe across a power loss
+    ///
+    /// Runs the same pipeline as `flash` - bulk erase, page writes, whole-image CRC check - but
+    /// instead of jumping straight to the new app with `StartApp`, hands off to `swap_start` and
+    /// `reset`. The bootloader boots the new image with its automatic-rollback armed, so a crash
+    /// or a power loss before `confirm_image` is called reverts to the previous image on the next
+    /// reset instead of leaving the device bricked. Call `confirm_image` once the new firmware has
+    /// proven itself, or `revert` to give up on it early.
+    ///
+    pub fn flash_with_trial_boot<FWI: FirmwareDataInterface>(
+        &mut self,
+        fwi: &FWI,
+        fast: bool,
+        mass_erase: bool,
+    ) -> Result<(), Error> {
+        let app_section = self.flash_desc.get_section("Application").unwrap();
+
+        let fw_data = fwi.get_firmware_data().unwrap();
+        let fw_size = fw_data.len() as u32;
+        let fw_num_pages = (fw_size / app_section.get_page_size()) + 1;
+
+        self._report_firmware_info(fwi, fw_size, fw_num_pages, fast);
+
+        let mut app_fw = AppFirmware::from_section(&app_section);
+        app_fw.append_firmware(fw_data)?;
+
+        if !mass_erase {
+            self._report(ProgressUpdate::Message("Erasing application area".to_string()));
+        }
+        self.erase(mass_erase)?;
+
+        self._flash_app_pages(&app_fw, fast)?;
+
+        self._report(ProgressUpdate::Message("Checking CRC".to_string()));
+        self._check_app_crc(&app_fw)?;
+
+        self._report(ProgressUpdate::Message("Flashing App CRC".to_string()));
+        self._flash_app_crc(app_fw.get_crc())?;
+
+        self._report(ProgressUpdate::Message(
+            "Starting trial boot (unconfirmed)".to_string(),
+        ));
+        self.swap_start()?;
+        self.reset()?;
+
+        self._report(ProgressUpdate::Message(
+            "App flashed & trial-booted! Call confirm_image() once verified, or revert() to undo."
+                .to_string(),
+        ));
+
+        Ok(())
+    }
+
+    /// Aborts an unconfirmed trial boot, falling back to the previous image
+    ///
+    /// Just resets the device: a trial boot started by `flash_with_trial_boot` that hasn't been
+    /// confirmed yet rolls itself back automatically on the next reset, the same way it would
+    /// after an unplanned power loss.
+    ///
+    pub fn revert(&mut self) -> Result<(), Error> {
+        self.reset()
+    }
+
+    /// Re-read the flashed application region and diff it against `fwi`
+    ///
+    /// For each page, a `FlashPageCRCCalc` request first asks the device for the CRC of that
+    /// page's actual on-flash content; if it matches the locally computed CRC for the same page,
+    /// the page is accepted without reading back a single byte. Only a page whose CRC disagrees
+    /// (or a bootloader too old to support `FlashPageCRCCalc`) falls back to the old word-by-word
+    /// `FlashReadWord` readback, which is what turns up the individual mismatching addresses.
+    /// Unlike `is_app_crc_valid`, which only tells you the whole image's CRC disagrees, this
+    /// pinpoints a failed flash down to the page (and, on fallback, the word). An empty result
+    /// means the device's flash matches `fwi` exactly.
+    ///
+    pub fn verify<FWI: FirmwareDataInterface>(&mut self, fwi: &FWI) -> Result<Vec<u32>, Error> {
+        let app_section = self.flash_desc.get_section("Application").unwrap();
+
+        let fw_data = fwi.get_firmware_data().unwrap();
+        let mut app_fw = AppFirmware::from_section(&app_section);
+        app_fw.append_firmware(fw_data)?;
+
+        let mut mismatches = Vec::new();
+        let num_pages = app_fw.get_page_lst().len();
+
+        for (page_cnt, app_page) in app_fw.get_page_lst().iter().enumerate() {
+            let flash_page_id = app_page.get_id() + app_section.get_flash_page_id();
+
+            self._report(ProgressUpdate::VerifyProgress {
+                current: (page_cnt + 1) as u32,
+                total: num_pages as u32,
+            });
+            self._report(ProgressUpdate::Message(format!(
+                "Verifying page {}/{} at address {:#08X}",
+                page_cnt + 1,
+                num_pages,
+                app_page.get_address()
+            )));
+
+            match self._read_flash_page_crc(flash_page_id) {
+                Ok(dev_crc) if dev_crc == app_page.get_crc() => continue,
+                Ok(_) => {
+                    // CRC disagrees; fall through to pin down the mismatching words below
+                }
+                Err(Error::NotSupported) => {
+                    // Bootloader predates FlashPageCRCCalc; fall back unconditionally
+                }
+                Err(e) => return Err(e),
+            }
+
+            let page_bytes = app_page.get_bytes();
+            let num_words = page_bytes.len() / 4;
+
+            for word_idx in 0..num_words {
+                let byte_offset = word_idx * 4;
+                let address = app_page.get_address() + byte_offset as u32;
+                let expected = MsgData::from_array(&[
+                    page_bytes[byte_offset],
+                    page_bytes[byte_offset + 1],
+                    page_bytes[byte_offset + 2],
+                    page_bytes[byte_offset + 3],
+                ])
+                .to_word();
+
+                if self._read_flash_word(address)? != expected {
+                    mismatches.push(address);
+                }
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Re-read the flashed application region page by page, failing fast on the first mismatch
+    ///
+    /// Unlike `verify`, which collects every mismatching word address so a caller can see the full
+    /// extent of a bad flash, this only answers "is everything correct?": it reports
+    /// `ProgressUpdate::PageVerified` as each page's CRC is confirmed against the device, and
+    /// returns `Error::PageContentsDiffer` - naming the offending page, its address, and both CRCs,
+    /// analogous to `hf2`'s `ContentsDifferent` - as soon as one disagrees. Once every page
+    /// matches, the whole image's CRC is asserted too via `Error::AppCrcMismatch`, the same check
+    /// `flash` performs right after programming.
+    ///
+    pub fn verify_pages<FWI: FirmwareDataInterface>(&mut self, fwi: &FWI) -> Result<(), Error> {
+        let app_section = self.flash_desc.get_section("Application").unwrap();
+
+        let fw_data = fwi.get_firmware_data().unwrap();
+        let mut app_fw = AppFirmware::from_section(&app_section);
+        app_fw.append_firmware(fw_data)?;
+
+        let num_pages = app_fw.get_page_lst().len();
+
+        for (page_cnt, app_page) in app_fw.get_page_lst().iter().enumerate() {
+            let flash_page_id = app_page.get_id() + app_section.get_flash_page_id();
+            let dev_crc = self._read_flash_page_crc(flash_page_id)?;
+
+            if dev_crc != app_page.get_crc() {
+                return Err(Error::PageContentsDiffer {
+                    page_id: flash_page_id,
+                    address: app_page.get_address(),
+                    expected: app_page.get_crc(),
+                    actual: dev_crc,
+                });
+            }
+
+            self._report(ProgressUpdate::PageVerified { crc: dev_crc });
+            self._report(ProgressUpdate::VerifyProgress {
+                current: (page_cnt + 1) as u32,
+                total: num_pages as u32,
+            });
+        }
+
+        self._check_app_crc(&app_fw)
+    }
+
+    /// Stream `size` bytes of raw flash starting at `address` back from the device
+    ///
+    /// Used to pull the application (or, for targets that map it into the same address space,
+    /// RAM) region into a coredump for offline analysis, analogous to a "read coredump" command
+    /// in a crash analyzer: the bytes mean nothing without the firmware ELF to symbolize them
+    /// against, so the caller is expected to wrap the result into an ELF alongside that symbol
+    /// information.
+    ///
+    pub fn read_memory(&mut self, address: u32, size: u32) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::with_capacity(size as usize);
+        let num_words = size.div_ceil(4);
+
+        for word_idx in 0..num_words {
+            let word = self._read_flash_word(address + word_idx * 4)?;
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+
+        data.truncate(size as usize);
+
+        Ok(data)
+    }
+
     // Getters ------------------------------------------------------------------------------------
 
+    /// Returns the application region's `(start address, size in bytes)`
+    ///
+    /// Used by `read_memory` callers that want to dump the whole application area, e.g. for a
+    /// coredump, without duplicating the flash description lookup.
+    pub fn get_application_region(&self) -> (u32, u32) {
+        let app_section = self.flash_desc.get_section("Application").unwrap();
+        (app_section.get_address(), app_section.get_size())
+    }
+
     /// Get entry value of request type
     ///
     /// This function returns the value of the entry of the given request type. If no entry is found,
@@ -272,6 +962,82 @@ where
 
     // Private Functions --------------------------------------------------------------------------
 
+    /// Forward a `ProgressUpdate` to the progress callback, or print it the way this crate always
+    /// has if none was given (see `new` vs `new_with_progress`).
+    fn _report(&self, update: ProgressUpdate) {
+        match &self.progress {
+            Some(progress_fn) => progress_fn(update),
+            // `StdoutObserver` leaves `PageStart`/`BytesWritten`/`PageVerified` as no-ops: they're
+            // fine-grained enough that the default stdout mode would be noisy, and the page-level
+            // `FlashProgress`/`Message` updates already cover the CLI's needs.
+            None => observer_to_progress_fn(StdoutObserver)(update),
+        }
+    }
+
+    /// Report `err` as a `ProgressUpdate::Timeout` if it's a send/receive failure, so a
+    /// `StatsObserver` still sees a tally for an exchange that aborted the whole flash
+    fn _report_timeout(&self, err: &Error) {
+        let reason = match err {
+            Error::ComError(_) => Some(TimeoutReason::SendError),
+            Error::ComNoResponse | Error::MsgCorruption(_) => Some(TimeoutReason::RecvTimeout),
+            _ => None,
+        };
+
+        if let Some(reason) = reason {
+            self._report(ProgressUpdate::Timeout(reason));
+        }
+    }
+
+    /// Report the "Firmware Data" line `flash`/`flash_with_trial_boot` start with, plus a
+    /// compression ratio line if `fwi` was transparently decompressed (see
+    /// `firmware::compressed`)
+    fn _report_firmware_info<FWI: FirmwareDataInterface>(
+        &self,
+        fwi: &FWI,
+        fw_size: u32,
+        fw_num_pages: u32,
+        fast: bool,
+    ) {
+        self._report(ProgressUpdate::Message(format!(
+            "Firmware Data: Size: {:#.2} kB Num Pages: {} Mode: {}",
+            (fw_size as f32 / 1024.0),
+            fw_num_pages,
+            if fast { "fast (single CRC check at the end)" } else { "conservative (per-page verify)" }
+        )));
+
+        if let Some(info) = fwi.compression_info() {
+            if info.codec != Codec::None {
+                self._report(ProgressUpdate::Message(format!(
+                    "Compression: {} ({} B -> {} B, {:.1}x ratio)",
+                    info.codec,
+                    info.compressed_size,
+                    info.decompressed_size,
+                    info.decompressed_size as f32 / info.compressed_size.max(1) as f32
+                )));
+            }
+        }
+    }
+
+    /// Check the cancel token, if any, between units of work in `erase`/`flash`
+    ///
+    /// If cancelled, clears the device's page buffer so a half-written page is never committed or
+    /// started (best effort; a link that's already going away is not itself an error here), reports
+    /// an "Aborted" message, and returns `Error::Cancelled`.
+    fn _check_cancelled(&mut self) -> Result<(), Error> {
+        if !self.cancel.as_ref().is_some_and(|token| token.load(Ordering::SeqCst)) {
+            return Ok(());
+        }
+
+        let _ = self
+            .entries
+            .get_entry_mut(RequestType::PageBufferClear)
+            .exec(&mut self.interface, 0);
+
+        self._report(ProgressUpdate::Message("Aborted".to_string()));
+
+        Err(Error::Cancelled)
+    }
+
     fn _add_entry(&mut self, entry_type: EntryType, request_type: RequestType) {
         self.entries.push(Entry::new(entry_type, request_type));
     }
@@ -283,81 +1049,357 @@ where
             }
         }
 
-        return Ok(());
+        return Ok(());
+    }
+
+    fn _flash_app_pages(&mut self, app: &AppFirmware, fast: bool) -> Result<(), Error> {
+        self._negotiate_write_window();
+
+        let mut page_cnt: u32 = 1;
+        let mut use_window = self.write_window > 1;
+
+        // Pages that are entirely the flash's erase value have nothing to program - already-
+        // erased flash matches them without a write. They still count toward the whole-app CRC
+        // (computed up front by `append_firmware`), so the device-side `AppInfoCRCCalc` matches
+        // on the first try without needing `_erase_unused_pages`'s erase-and-retry dance.
+        let pages_to_flash: Vec<&FlashPage> = app.get_pages_to_flash(None).collect();
+
+        for app_page in &pages_to_flash {
+            self._check_cancelled()?;
+            self._maybe_send_keepalive()?;
+
+            let app_section = self.flash_desc.get_section("Application").unwrap();
+            let flash_page_id = app_page.get_id() + app_section.get_flash_page_id();
+
+            // Report progress
+            self._report(ProgressUpdate::FlashProgress {
+                current: page_cnt,
+                total: pages_to_flash.len() as u32,
+            });
+            self._report(ProgressUpdate::Message(format!(
+                "Flashing {}. page of {}. [Page: {}/{} | Address: {:#08X}]",
+                page_cnt,
+                pages_to_flash.len(),
+                flash_page_id + 1,
+                app.get_flash_num_pages(),
+                app_page.get_address()
+            )));
+            self._report(ProgressUpdate::PageStart {
+                id: flash_page_id,
+                address: app_page.get_address(),
+            });
+
+            // Clear page buffer
+            let result = self
+                .entries
+                .get_entry_mut(RequestType::PageBufferClear)
+                .exec(&mut self.interface, 0);
+            if let Err(ref e) = result {
+                self._report_timeout(e);
+            }
+            result?;
+
+            let fw_page_byte_lst = app_page.get_bytes();
+            let num_words = (app.get_flash_page_size() as usize) / 4;
+
+            if use_window {
+                let windowed_result = self._write_page_words_windowed(fw_page_byte_lst, num_words);
+                if let Err(ref e) = windowed_result {
+                    self._report_timeout(e);
+                }
+                if !windowed_result? {
+                    self._report(ProgressUpdate::Message(
+                        "Device reported a write buffer overrun, falling back to synchronous page writes"
+                            .to_string(),
+                    ));
+                    use_window = false;
+
+                    let result = self
+                        .entries
+                        .get_entry_mut(RequestType::PageBufferClear)
+                        .exec(&mut self.interface, 0);
+                    if let Err(ref e) = result {
+                        self._report_timeout(e);
+                    }
+                    result?;
+
+                    let result = self._write_page_words_sync(fw_page_byte_lst, num_words);
+                    if let Err(ref e) = result {
+                        self._report_timeout(e);
+                    }
+                    result?;
+                }
+            } else {
+                let result = self._write_page_words_sync(fw_page_byte_lst, num_words);
+                if let Err(ref e) = result {
+                    self._report_timeout(e);
+                }
+                result?;
+            }
+
+            // In fast mode, skip the page buffer CRC round trip and defer all verification to
+            // the single whole-application CRC check that runs once flashing is done.
+            if !fast {
+                let page_dev_crc = match self.read_entry_value(RequestType::PageBufferCalcCRC) {
+                    Ok(data) => data.to_word(),
+                    Err(e) => {
+                        self._report_timeout(&e);
+                        return Err(e);
+                    }
+                };
+                let page_calc_crc = app_page.get_crc();
+
+                if page_dev_crc != page_calc_crc {
+                    self._report(ProgressUpdate::CrcMismatch {
+                        page_id: flash_page_id,
+                        expected: page_calc_crc,
+                        actual: page_dev_crc,
+                    });
+                    self._report(ProgressUpdate::Timeout(TimeoutReason::CrcMismatch));
+
+                    return Err(Error::PageCrcMismatch {
+                        page_id: flash_page_id,
+                        expected: page_calc_crc,
+                        actual: page_dev_crc,
+                    });
+                }
+
+                self._report(ProgressUpdate::PageVerified { crc: page_dev_crc });
+            }
+
+            // Application area was already bulk-erased up front by `flash`, just commit the page
+            let result = self
+                .entries
+                .get_entry_mut(RequestType::PageBufferWriteToFlash)
+                .exec(&mut self.interface, flash_page_id);
+            if let Err(ref e) = result {
+                self._report_timeout(e);
+            }
+            result?;
+
+            self._report(ProgressUpdate::PageCommitted { id: flash_page_id });
+
+            self.last_exchange = Instant::now();
+            page_cnt += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a `Ping` and waits for its ack if `keepalive_interval` has elapsed since the last
+    /// exchange, resetting the timer once the ping is acknowledged
+    ///
+    /// Called once per page from `_flash_app_pages`, not from inside the word-write loop itself -
+    /// a page takes long enough that checking at page granularity is enough to keep the link from
+    /// going quiet for longer than `keepalive_interval`.
+    fn _maybe_send_keepalive(&mut self) -> Result<(), Error> {
+        let interval = match self.keepalive_interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+
+        if self.last_exchange.elapsed() < interval {
+            return Ok(());
+        }
+
+        let request = Msg::new_std_request(RequestType::Ping);
+        self.interface.send(&request)?;
+        let response = self.interface.recv()?;
+        request.is_response_ok(&response)?;
+
+        self.last_exchange = Instant::now();
+        Ok(())
+    }
+
+    /// Ask the device how many `PageBufferWriteWord` requests it can buffer at once via
+    /// `FlashInfoWriteWindowSize`, clamping the result to `FLASH_WRITE_WINDOW`.
+    ///
+    /// A bootloader that predates this request responds with an error (or nothing matches its
+    /// fixed set of supported requests at all), which is tolerated by simply keeping the
+    /// conservative one-word-at-a-time default `write_window` already has.
+    fn _negotiate_write_window(&mut self) {
+        if let Ok(window) = self.read_entry_value(RequestType::FlashInfoWriteWindowSize) {
+            self.write_window = (window.to_word() as usize).clamp(1, FLASH_WRITE_WINDOW);
+        }
+    }
+
+    /// Stream one page's words to the device, keeping up to `self.write_window` requests (see
+    /// `FlashInfoWriteWindowSize`) in flight instead of waiting on each acknowledgement before
+    /// sending the next one.
+    ///
+    /// Returns `Ok(true)` once every word has been sent and acknowledged, or `Ok(false)` if the
+    /// device reported `ErrPageFull`, in which case the in-flight window is drained and the
+    /// caller should retry the page with `_write_page_words_sync` instead.
+    fn _write_page_words_windowed(
+        &mut self,
+        page_bytes: &[u8],
+        num_words: usize,
+    ) -> Result<bool, Error> {
+        let mut in_flight: VecDeque<Msg> = VecDeque::with_capacity(self.write_window);
+
+        for msg_idx in 0..num_words {
+            let byte_offset = msg_idx * 4;
+            let msg_data = MsgData::from_array(&[
+                page_bytes[byte_offset],
+                page_bytes[byte_offset + 1],
+                page_bytes[byte_offset + 2],
+                page_bytes[byte_offset + 3],
+            ]);
+            let packet_id = (msg_idx % 256) as u8;
+            let request = Msg::new(
+                RequestType::PageBufferWriteWord,
+                ResultType::None,
+                packet_id,
+                &msg_data,
+            );
+
+            self.interface.send(&request)?;
+            in_flight.push_back(request);
+            self._report(ProgressUpdate::BytesWritten(4));
+
+            if in_flight.len() >= self.write_window && !self._drain_one_write_ack(&mut in_flight)? {
+                self._drain_in_flight(&mut in_flight);
+                return Ok(false);
+            }
+        }
+
+        while !in_flight.is_empty() {
+            if !self._drain_one_write_ack(&mut in_flight)? {
+                self._drain_in_flight(&mut in_flight);
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Wait for the oldest in-flight write's acknowledgement. Returns `Ok(false)` without
+    /// erroring on `ErrPageFull`, since that is the expected signal to fall back, not a failure.
+    fn _drain_one_write_ack(&mut self, in_flight: &mut VecDeque<Msg>) -> Result<bool, Error> {
+        let request = in_flight.pop_front().unwrap();
+        let response = self.interface.recv()?;
+
+        if response.get_result() == ResultType::ErrPageFull {
+            return Ok(false);
+        }
+
+        request.is_response_ok(&response)?;
+        request.is_response_data_ok(&response)?;
+
+        Ok(true)
     }
 
-    fn _flash_app_pages(&mut self, app: &AppFirmware) -> Result<(), Error> {
-        let mut page_cnt = 1;
-        for app_page in app.get_page_lst().iter() {
-            let app_section = self.flash_desc.get_section("Application").unwrap();
-            let flash_page_id = app_page.get_id() + app_section.get_flash_page_id();
+    /// Receive and discard the acknowledgements for every request still in flight, so the link
+    /// is back in sync before the page is retried synchronously.
+    fn _drain_in_flight(&mut self, in_flight: &mut VecDeque<Msg>) {
+        while in_flight.pop_front().is_some() {
+            let _ = self.interface.recv();
+        }
+    }
 
-            // Print info
-            println!(
-                "Flashing {}. page of {}. [Page: {}/{} | Address: {:#08X}]",
-                page_cnt,
-                app.get_page_lst().len(),
-                flash_page_id + 1,
-                app.get_flash_num_pages(),
-                app_page.get_address()
-            );
+    /// Read a single word directly from flash at `address` via `FlashReadWord`
+    ///
+    /// Bypasses `Entry`, since this request's payload carries the address to read rather than
+    /// being a plain no-argument read like the other `RO`/`Const` entries.
+    fn _read_flash_word(&mut self, address: u32) -> Result<u32, Error> {
+        let request = Msg::new(
+            RequestType::FlashReadWord,
+            ResultType::None,
+            0,
+            &MsgData::from_word(address),
+        );
 
-            // Clear page buffer
-            self.entries
-                .get_entry_mut(RequestType::PageBufferClear)
-                .exec(&mut self.interface, 0)?;
+        self.interface.send(&request)?;
+        let response = self.interface.recv()?;
+        request.is_response_ok(&response)?;
 
-            // Write bytes to page buffer
-            let fw_page_byte_lst = app_page.get_bytes();
+        Ok(response.get_data().to_word())
+    }
 
-            // One word per message
-            for msg_idx in 0..((app.get_flash_page_size() as usize) / 4) {
-                let byte_offset = msg_idx * 4;
+    /// Ask the device for the CRC of flash page `flash_page_id`'s actual on-flash content via
+    /// `FlashPageCRCCalc`
+    ///
+    /// Used by `verify` as a cheap per-page check before falling back to a full `FlashReadWord`
+    /// readback of that page. Bypasses `Entry` for the same reason as `_read_flash_word`: the
+    /// request's payload carries the page index rather than taking no argument.
+    fn _read_flash_page_crc(&mut self, flash_page_id: u32) -> Result<u32, Error> {
+        let request = Msg::new(
+            RequestType::FlashPageCRCCalc,
+            ResultType::None,
+            0,
+            &MsgData::from_word(flash_page_id),
+        );
 
-                // Create data
-                let msg_data = MsgData::from_array(&[
-                    fw_page_byte_lst[byte_offset],
-                    fw_page_byte_lst[byte_offset + 1],
-                    fw_page_byte_lst[byte_offset + 2],
-                    fw_page_byte_lst[byte_offset + 3],
-                ]);
+        self.interface.send(&request)?;
+        let response = self.interface.recv()?;
+        request.is_response_ok(&response)?;
 
-                // Calculate packet id
-                let packet_id = (msg_idx % 256) as u8;
+        Ok(response.get_data().to_word())
+    }
 
-                // Write word to page buffer
-                self.entries
-                    .get_entry_mut(RequestType::PageBufferWriteWord)
-                    .write_value(&mut self.interface, packet_id, &msg_data)?;
+    fn _write_page_words_sync(&mut self, page_bytes: &[u8], num_words: usize) -> Result<(), Error> {
+        for msg_idx in 0..num_words {
+            let byte_offset = msg_idx * 4;
+            let msg_data = MsgData::from_array(&[
+                page_bytes[byte_offset],
+                page_bytes[byte_offset + 1],
+                page_bytes[byte_offset + 2],
+                page_bytes[byte_offset + 3],
+            ]);
+            let packet_id = (msg_idx % 256) as u8;
+
+            if !self.word_write_delay.is_zero() {
+                std::thread::sleep(self.word_write_delay);
             }
 
-            // Read CRC value of page buffer from device
-            let page_dev_crc = self
-                .read_entry_value(RequestType::PageBufferCalcCRC)?
-                .to_word();
-            let page_calc_crc = app_page.get_crc();
+            self._write_page_word_with_retry(msg_idx as u32, packet_id, &msg_data)?;
+            self._report(ProgressUpdate::BytesWritten(4));
+        }
 
-            if page_dev_crc != page_calc_crc {
+        Ok(())
+    }
+
+    /// Send one `PageBufferWriteWord`, resending the same packet id/data up to `max_word_retries`
+    /// times if the exchange times out or comes back corrupted, instead of failing the whole
+    /// flash on the first bad exchange
+    fn _write_page_word_with_retry(
+        &mut self,
+        word_index: u32,
+        packet_id: u8,
+        data: &MsgData,
+    ) -> Result<(), Error> {
+        let mut retries = 0;
+
+        loop {
+            let err = match self
+                .entries
+                .get_entry_mut(RequestType::PageBufferWriteWord)
+                .write_value(&mut self.interface, packet_id, data)
+            {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            self._report_timeout(&err);
+
+            if !Self::_is_retryable_word_error(&err) || retries >= self.max_word_retries {
                 return Err(Error::Error(format!(
-                    "Page buffer CRC is invalid! Calc: {:#010X} Dev: {:#010X}!",
-                    page_calc_crc, page_dev_crc
+                    "Word {} of the page buffer still failed after {} retries ({} timeouts): {}",
+                    word_index, retries, retries, err
                 )));
             }
 
-            // Erase flash page
-            self.entries
-                .get_entry_mut(RequestType::FlashWriteErasePage)
-                .exec(&mut self.interface, flash_page_id)?;
-
-            // Write page buffer to flash
-            self.entries
-                .get_entry_mut(RequestType::PageBufferWriteToFlash)
-                .exec(&mut self.interface, flash_page_id)?;
-
-            page_cnt += 1;
+            retries += 1;
+            self._report(ProgressUpdate::Retransmit { word_index });
         }
+    }
 
-        Ok(())
+    /// Whether a `PageBufferWriteWord` failure is worth resending rather than aborting outright
+    fn _is_retryable_word_error(err: &Error) -> bool {
+        matches!(
+            err,
+            Error::ComNoResponse | Error::ComError(_) | Error::MsgCorruption(_)
+        )
     }
 
     fn _erase_unused_pages(&mut self, app: &AppFirmware) -> Result<(), Error> {
@@ -397,11 +1439,10 @@ where
             // Check again if CRC is valid
             if !self.is_app_crc_valid(app)? {
                 // CRC still invalid throw error
-                return Err(Error::Error(format!(
-                    "CRC check failed! App-CRC: {:#010X} Device-App-CRC: {:#010X}",
-                    app.get_crc(),
-                    self.entries.get_entry_value(RequestType::AppInfoCRCCalc)
-                )));
+                return Err(Error::AppCrcMismatch {
+                    expected: app.get_crc(),
+                    actual: self.entries.get_entry_value(RequestType::AppInfoCRCCalc),
+                });
             }
         }
 
@@ -415,6 +1456,12 @@ where
     }
 }
 
+/// Hashes a human readable config key into the 32 bit key identifier carried in `MsgData` by
+/// `config_read`/`config_write`/`config_erase`/`config_list`
+pub fn config_key_hash(key: &str) -> u32 {
+    CONFIG_KEY_CRC32.checksum(key.as_bytes())
+}
+
 // Tests ------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -477,6 +1524,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn device_check_compatibility_accepts_a_matching_header() {
+        let com = new_com_sim_with_data();
+        let mut device = Device::new(com);
+        device.init().unwrap();
+
+        let metadata = FirmwareMetadata {
+            vendor_id: 1,
+            product_id: 2,
+            app_version: 0x0100,
+            flash_start_address: 0x08000000,
+            flash_page_size: 0x0400,
+            flash_num_pages: 0x0F,
+        };
+
+        assert!(device.check_compatibility(&metadata).is_ok());
+    }
+
+    #[test]
+    fn device_check_compatibility_rejects_a_mismatched_vendor_id() {
+        let com = new_com_sim_with_data();
+        let mut device = Device::new(com);
+        device.init().unwrap();
+
+        let metadata = FirmwareMetadata {
+            vendor_id: 0xAA,
+            product_id: 2,
+            app_version: 0x0100,
+            flash_start_address: 0x08000000,
+            flash_page_size: 0x0400,
+            flash_num_pages: 0x0F,
+        };
+
+        assert!(device.check_compatibility(&metadata).is_err());
+    }
+
+    #[test]
+    fn device_check_compatibility_rejects_mismatched_flash_geometry() {
+        let com = new_com_sim_with_data();
+        let mut device = Device::new(com);
+        device.init().unwrap();
+
+        let metadata = FirmwareMetadata {
+            vendor_id: 1,
+            product_id: 2,
+            app_version: 0x0100,
+            flash_start_address: 0x08000000,
+            flash_page_size: 0x0800,
+            flash_num_pages: 0x0F,
+        };
+
+        assert!(device.check_compatibility(&metadata).is_err());
+    }
+
+    #[test]
+    fn device_init_defaults_erase_value_to_0xff() {
+        let com = new_com_sim_with_data();
+        let mut device = Device::new(com);
+        device.init().unwrap();
+
+        assert_eq!(device.flash_desc.get_erase_value(), 0xFF);
+    }
+
+    #[test]
+    fn device_set_erase_value_is_used_by_init() {
+        let com = new_com_sim_with_data();
+        let mut device = Device::new(com);
+        device.set_erase_value(0x00);
+        device.init().unwrap();
+
+        assert_eq!(device.flash_desc.get_erase_value(), 0x00);
+    }
+
+    #[test]
+    fn device_negotiate_write_window_uses_device_advertised_value() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::FlashInfoWriteWindowSize,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(4),
+        ));
+
+        let mut device = Device::new(interface);
+        device._negotiate_write_window();
+
+        assert_eq!(device.write_window, 4);
+    }
+
+    #[test]
+    fn device_negotiate_write_window_clamps_to_flash_write_window() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::FlashInfoWriteWindowSize,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(1000),
+        ));
+
+        let mut device = Device::new(interface);
+        device._negotiate_write_window();
+
+        assert_eq!(device.write_window, FLASH_WRITE_WINDOW);
+    }
+
+    #[test]
+    fn device_negotiate_write_window_falls_back_to_one_word_at_a_time() {
+        let interface = ComSimulator::new();
+
+        let mut device = Device::new(interface);
+        device._negotiate_write_window();
+
+        assert_eq!(device.write_window, 1);
+    }
+
+    #[test]
+    fn device_keepalive_is_disabled_by_default() {
+        let mut device = Device::new(ComSimulator::new());
+
+        // No Ping response was queued; if the keepalive fired anyway, recv() would error.
+        device._maybe_send_keepalive().unwrap();
+    }
+
+    #[test]
+    fn device_keepalive_sends_ping_once_interval_elapses() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(RequestType::Ping, ResultType::Ok, 0, &MsgData::new()));
+
+        let mut device = Device::new(interface);
+        device.set_keepalive_interval(Duration::from_secs(0));
+        device.last_exchange = Instant::now() - Duration::from_secs(1);
+
+        device._maybe_send_keepalive().unwrap();
+
+        assert_eq!(device.interface.get_result(), None);
+    }
+
+    #[test]
+    fn device_keepalive_does_not_fire_before_the_interval_elapses() {
+        let interface = ComSimulator::new();
+
+        let mut device = Device::new(interface);
+        device.set_keepalive_interval(Duration::from_secs(60));
+
+        // No Ping response queued; if the keepalive fired, recv() would error since the
+        // ComSimulator's queue is empty.
+        device._maybe_send_keepalive().unwrap();
+    }
+
     #[test]
     fn device_new_no_init_get_entry() {
         let device = Device::new(ComSimulator::new());
@@ -567,6 +1763,171 @@ mod tests {
         device.get_entry_value(RequestType::DevInfoBootloaderVersion);
     }
 
+    #[test]
+    fn device_swap_status() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::SwapStatus,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(SwapState::InProgress.to_u8() as u32),
+        ));
+
+        let mut device = Device::new(interface);
+
+        assert_eq!(device.swap_status().unwrap(), SwapState::InProgress);
+    }
+
+    #[test]
+    fn device_config_write_and_read() {
+        let mut interface = ComSimulator::new();
+
+        // config_write("node-id", [0x2A, 0x00, 0x00, 0x00]): clear page buffer, write one word,
+        // commit as the "node-id" record
+        interface.add_response(Msg::new(
+            RequestType::PageBufferClear,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+        interface.add_response(Msg::new(
+            RequestType::PageBufferWriteWord,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+        interface.add_response(Msg::new(
+            RequestType::ConfigWrite,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+
+        // config_read("node-id", 4): one word read back
+        interface.add_response(Msg::new(
+            RequestType::ConfigRead,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(0x2A),
+        ));
+
+        let mut device = Device::new(interface);
+
+        device.config_write("node-id", &[0x2A, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(device.config_read("node-id", 4).unwrap(), vec![0x2A, 0, 0, 0]);
+    }
+
+    #[test]
+    fn device_erase_respects_cancel_token() {
+        let mut interface = new_com_sim_with_data();
+
+        // _check_cancelled clears the page buffer before reporting the cancellation, so that's
+        // the only request erase() should send once the token is set.
+        interface.add_response(Msg::new(
+            RequestType::PageBufferClear,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+
+        let mut device = Device::new(interface);
+        device.init().unwrap();
+        device.set_cancel_token(Arc::new(AtomicBool::new(true)));
+
+        assert_eq!(device.erase(false), Err(Error::Cancelled));
+    }
+
+    #[test]
+    fn device_erase_rejects_a_read_only_application_section() {
+        let mut interface = new_com_sim_with_data();
+        let mut device = Device::new(interface);
+        device.init().unwrap();
+
+        let app_address = device.flash_desc.get_section_address("Application").unwrap();
+        let app_size = device.flash_desc.get_section_size("Application").unwrap();
+
+        device.flash_desc = FlashDesc::new(
+            device.flash_desc.get_address(),
+            device.flash_desc.get_size(),
+            device.flash_desc.get_page_size(),
+        );
+        device
+            .flash_desc
+            .add_section_with_flags("Application", app_address, app_size, SectionFlags::READ_ONLY)
+            .unwrap();
+
+        assert!(device.erase(false).is_err());
+    }
+
+    #[test]
+    fn device_mass_erase_sends_single_command() {
+        let mut interface = new_com_sim_with_data();
+
+        // init() polls SwapStatus after reading the constants
+        interface.add_response(Msg::new(
+            RequestType::SwapStatus,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(SwapState::None.to_u8() as u32),
+        ));
+        interface.add_response(Msg::new(
+            RequestType::FlashWriteMassErase,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+
+        let mut device = Device::new(interface);
+        device.init().unwrap();
+
+        assert!(device.erase(true).is_ok());
+    }
+
+    #[test]
+    fn device_config_erase() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::ConfigErase,
+            ResultType::Ok,
+            0,
+            &MsgData::new(),
+        ));
+
+        let mut device = Device::new(interface);
+
+        assert!(device.config_erase("node-id").is_ok());
+    }
+
+    #[test]
+    fn device_config_list() {
+        let mut interface = ComSimulator::new();
+        interface.add_response(Msg::new(
+            RequestType::ConfigList,
+            ResultType::Ok,
+            0,
+            &MsgData::from_word(config_key_hash("node-id")),
+        ));
+        interface.add_response(Msg::new(
+            RequestType::ConfigList,
+            ResultType::Ok,
+            1,
+            &MsgData::from_word(config_key_hash("ip-address")),
+        ));
+        interface.add_response(Msg::new(
+            RequestType::ConfigList,
+            ResultType::ErrInvldArg,
+            2,
+            &MsgData::new(),
+        ));
+
+        let mut device = Device::new(interface);
+
+        assert_eq!(
+            device.config_list().unwrap(),
+            vec![config_key_hash("node-id"), config_key_hash("ip-address")]
+        );
+    }
+
     // Helpers ------------------------------------------------------------------------------------
 
     fn new_com_sim_with_data() -> ComSimulator {
@@ -665,4 +2026,58 @@ mod tests {
 
         interface
     }
+
+    #[test]
+    fn device_write_page_word_with_retry_recovers_from_a_transient_error() {
+        let mut interface = ComSimulator::new();
+        interface.set_recv_error(Error::ComNoResponse);
+        interface.add_response(Msg::new(
+            RequestType::PageBufferWriteWord,
+            ResultType::Ok,
+            3,
+            &MsgData::from_word(0xAABBCCDD),
+        ));
+
+        let mut device = Device::new(interface);
+        device
+            ._write_page_word_with_retry(0, 3, &MsgData::from_word(0xAABBCCDD))
+            .unwrap();
+    }
+
+    #[test]
+    fn device_write_page_word_with_retry_gives_up_once_retries_are_exhausted() {
+        // An empty `ComSimulator` fails every `recv()` with `ComNoResponse`, standing in for a
+        // node that never comes back - there's no transient success to retry into.
+        let mut device = Device::new(ComSimulator::new());
+        device.set_max_word_retries(2);
+
+        let err = device
+            ._write_page_word_with_retry(7, 0, &MsgData::from_word(0))
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains('7'), "{}", message);
+        assert!(message.contains('2'), "{}", message);
+    }
+
+    #[test]
+    fn device_set_max_word_retries_zero_fails_on_the_first_error() {
+        let mut device = Device::new(ComSimulator::new());
+        device.set_max_word_retries(0);
+
+        let err = device
+            ._write_page_word_with_retry(0, 0, &MsgData::from_word(0))
+            .unwrap_err();
+
+        assert!(err.to_string().contains("0 retries"));
+    }
+
+    #[test]
+    fn device_set_word_write_delay_updates_the_pacing_delay() {
+        let mut device = Device::new(ComSimulator::new());
+        assert_eq!(device.word_write_delay, Duration::ZERO);
+
+        device.set_word_write_delay(Duration::from_millis(5));
+        assert_eq!(device.word_write_delay, Duration::from_millis(5));
+    }
 }
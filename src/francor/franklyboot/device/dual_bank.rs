@@ -0,0 +1,523 @@
+use super::flash::{FlashDesc, SectionFlags};
+use crate::francor::franklyboot::{
+    com::{
+        flash_session::{FlashSession, FlashSessionConfig},
+        ComInterface,
+    },
+    Error,
+};
+
+// Dual-Bank Update Subsystem -----------------------------------------------------------------------
+
+/// State-partition byte marking a swap as pending/unconfirmed
+pub const SWAP_MAGIC: u8 = 0xA5;
+
+/// State-partition byte marking the active image as confirmed
+pub const BOOT_MAGIC: u8 = 0x5A;
+
+///
+/// Boot decision read back from the state partition
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DualBankState {
+    /// The active image has been confirmed; nothing to do
+    Boot,
+
+    /// A swap is pending, or completed but was never confirmed; the next `perform_swap` call
+    /// advances it (or, if it already ran to completion once, reverts it)
+    Swap,
+
+    /// The state partition holds neither magic - no update has ever been staged
+    DfuDetach,
+}
+
+///
+/// Power-fail-safe A/B update driver built on top of `FlashDesc`
+///
+/// Mirrors a real bootloader's trial-boot scheme: the new image is written to a DFU partition,
+/// then `mark_updated` stages a swap. `perform_swap` - meant to be called once on every boot,
+/// before the application is started - exchanges the active and DFU partitions page by page,
+/// recording its progress in a small state partition after every page so a swap interrupted by a
+/// power loss resumes where it left off instead of restarting. Exchanging a page takes two
+/// separate device-side writes (active gets the DFU content, then DFU gets the active content),
+/// so resuming can't simply re-read "active"/"DFU" off live flash - if power is lost between the
+/// two writes, one side already holds its new content and re-deriving from it would swap the same
+/// page twice. Instead, the state partition's second page is used as a scratch slot: before either
+/// write, the page's pre-swap active content is copied there and a sub-step marker is recorded
+/// after each write, so every step is idempotent and a crash at any point resumes from exactly
+/// where it left off. If the swapped-in application never calls `mark_booted`, the state partition
+/// still reads `SWAP_MAGIC` on the next boot; since the swap only exchanges two partitions'
+/// contents, running it again undoes it - the same `perform_swap` call serves as both the forward
+/// swap and its own revert.
+///
+pub struct DualBankUpdater<'a, I: ComInterface> {
+    interface: &'a mut I,
+    active_address: u32,
+    active_page_id: u32,
+    dfu_address: u32,
+    dfu_page_id: u32,
+    state_address: u32,
+    state_page_id: u32,
+    scratch_page_id: u32,
+    page_size: u32,
+    num_pages: u32,
+}
+
+/// Per-page swap sub-step, recorded in the state partition so a crash mid-page resumes exactly
+/// where it left off instead of re-deriving (possibly already-mutated) state from live flash
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum SwapStep {
+    /// Neither partition has been touched yet for the current page
+    NotStarted,
+
+    /// The scratch page holds this page's pre-swap active content; `active` not yet overwritten
+    ScratchSaved,
+
+    /// `active` now holds the DFU content; `dfu` not yet overwritten with the scratch content
+    ActiveWritten,
+}
+
+impl SwapStep {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => SwapStep::ScratchSaved,
+            2 => SwapStep::ActiveWritten,
+            _ => SwapStep::NotStarted,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            SwapStep::NotStarted => 0,
+            SwapStep::ScratchSaved => 1,
+            SwapStep::ActiveWritten => 2,
+        }
+    }
+}
+
+impl<'a, I: ComInterface> DualBankUpdater<'a, I> {
+    ///
+    /// Builds an updater over `active_section`/`dfu_section` (equal-size partitions swapped page
+    /// by page) and `state_section` (holding the swap-progress/confirmation marker), all looked up
+    /// by name from `flash_desc`
+    ///
+    pub fn new(
+        interface: &'a mut I,
+        flash_desc: &FlashDesc,
+        active_section: &str,
+        dfu_section: &str,
+        state_section: &str,
+    ) -> Result<Self, Error> {
+        let active = flash_desc.get_section(active_section).ok_or_else(|| {
+            Error::Error(format!("Unknown active partition '{}'!", active_section))
+        })?;
+        let dfu = flash_desc
+            .get_section(dfu_section)
+            .ok_or_else(|| Error::Error(format!("Unknown DFU partition '{}'!", dfu_section)))?;
+        let state = flash_desc.get_section(state_section).ok_or_else(|| {
+            Error::Error(format!("Unknown state partition '{}'!", state_section))
+        })?;
+
+        if active.get_size() != dfu.get_size() {
+            return Err(Error::Error(
+                "Active and DFU partitions must be the same size!".to_string(),
+            ));
+        }
+
+        if state.get_size() < 2 * state.get_page_size() {
+            return Err(Error::Error(
+                "State partition must hold at least two pages (header + scratch)!".to_string(),
+            ));
+        }
+
+        for name in [active_section, dfu_section, state_section] {
+            flash_desc
+                .check_section_writable(name)
+                .map_err(|e| Error::Error(format!("Partition '{}': {}", name, e)))?;
+        }
+
+        Ok(DualBankUpdater {
+            interface,
+            active_address: active.get_address(),
+            active_page_id: active.get_flash_page_id(),
+            dfu_address: dfu.get_address(),
+            dfu_page_id: dfu.get_flash_page_id(),
+            state_address: state.get_address(),
+            state_page_id: state.get_flash_page_id(),
+            scratch_page_id: state.get_flash_page_id() + 1,
+            page_size: active.get_page_size(),
+            num_pages: active.get_size() / active.get_page_size(),
+        })
+    }
+
+    ///
+    /// Stages a pending swap by writing `SWAP_MAGIC` (and a zeroed progress index) to the state
+    /// partition. Call once the update image has been written to the DFU partition.
+    ///
+    pub fn mark_updated(&mut self) -> Result<(), Error> {
+        let mut session = FlashSession::new(self.interface, FlashSessionConfig::default())?;
+        Self::_write_state(
+            &mut session,
+            self.state_page_id,
+            self.page_size,
+            SWAP_MAGIC,
+            0,
+            SwapStep::NotStarted,
+        )
+    }
+
+    ///
+    /// Confirms the currently running image by writing `BOOT_MAGIC` to the state partition. Must
+    /// be called by the application after a successful boot, or the next `perform_swap` will treat
+    /// the swap as unconfirmed and revert it.
+    ///
+    pub fn mark_booted(&mut self) -> Result<(), Error> {
+        let mut session = FlashSession::new(self.interface, FlashSessionConfig::default())?;
+        Self::_write_state(
+            &mut session,
+            self.state_page_id,
+            self.page_size,
+            BOOT_MAGIC,
+            0,
+            SwapStep::NotStarted,
+        )
+    }
+
+    ///
+    /// Reads the state partition's current boot decision
+    ///
+    pub fn current_state(&mut self) -> Result<DualBankState, Error> {
+        let mut session = FlashSession::new(self.interface, FlashSessionConfig::default())?;
+        let (magic, _, _) = Self::_read_state(&mut session, self.state_address)?;
+
+        Ok(match magic {
+            SWAP_MAGIC => DualBankState::Swap,
+            BOOT_MAGIC => DualBankState::Boot,
+            _ => DualBankState::DfuDetach,
+        })
+    }
+
+    ///
+    /// Drives the active/DFU swap to completion, one page at a time, recording progress in the
+    /// state partition after every page so an interrupted swap resumes from the page it stopped
+    /// on. A no-op unless the state partition currently reads `SWAP_MAGIC`.
+    ///
+    /// Each page is exchanged in three recorded sub-steps - stash the page's pre-swap active
+    /// content in the scratch page, overwrite `active` with the DFU content, then overwrite `dfu`
+    /// with the stashed content - so a power loss between any two device writes resumes from
+    /// exactly the sub-step it stopped on instead of re-deriving (possibly already-mutated)
+    /// content from live flash.
+    ///
+    pub fn perform_swap(&mut self, config: FlashSessionConfig) -> Result<(), Error> {
+        let mut session = FlashSession::new(self.interface, config)?;
+        let (magic, mut progress, mut step) = Self::_read_state(&mut session, self.state_address)?;
+
+        if magic != SWAP_MAGIC {
+            return Ok(());
+        }
+
+        while progress < self.num_pages {
+            let offset = progress * self.page_size;
+
+            if step == SwapStep::NotStarted {
+                let active_bytes =
+                    session.read_page(self.active_address + offset, self.page_size as usize)?;
+                session.flash_page(self.scratch_page_id, &active_bytes)?;
+                step = SwapStep::ScratchSaved;
+                Self::_write_state(
+                    &mut session,
+                    self.state_page_id,
+                    self.page_size,
+                    SWAP_MAGIC,
+                    progress,
+                    step,
+                )?;
+            }
+
+            if step == SwapStep::ScratchSaved {
+                let dfu_bytes =
+                    session.read_page(self.dfu_address + offset, self.page_size as usize)?;
+                session.flash_page(self.active_page_id + progress, &dfu_bytes)?;
+                step = SwapStep::ActiveWritten;
+                Self::_write_state(
+                    &mut session,
+                    self.state_page_id,
+                    self.page_size,
+                    SWAP_MAGIC,
+                    progress,
+                    step,
+                )?;
+            }
+
+            let scratch_bytes = session.read_page(
+                self.state_address + self.page_size,
+                self.page_size as usize,
+            )?;
+            session.flash_page(self.dfu_page_id + progress, &scratch_bytes)?;
+
+            progress += 1;
+            step = SwapStep::NotStarted;
+            Self::_write_state(
+                &mut session,
+                self.state_page_id,
+                self.page_size,
+                SWAP_MAGIC,
+                progress,
+                step,
+            )?;
+        }
+
+        // Fully swapped but still unconfirmed; reset the progress index so a revert (another
+        // unconfirmed boot) re-runs the same page-by-page exchange from the start.
+        Self::_write_state(
+            &mut session,
+            self.state_page_id,
+            self.page_size,
+            SWAP_MAGIC,
+            0,
+            SwapStep::NotStarted,
+        )
+    }
+
+    fn _write_state(
+        session: &mut FlashSession<'_, I>,
+        state_page_id: u32,
+        page_size: u32,
+        magic: u8,
+        progress: u32,
+        step: SwapStep,
+    ) -> Result<(), Error> {
+        let mut page = vec![0u8; page_size as usize];
+        page[0] = magic;
+        page[1..5].copy_from_slice(&progress.to_le_bytes());
+        page[5] = step.to_u8();
+
+        session.flash_page(state_page_id, &page)
+    }
+
+    fn _read_state(
+        session: &mut FlashSession<'_, I>,
+        state_address: u32,
+    ) -> Result<(u8, u32, SwapStep), Error> {
+        let bytes = session.read_page(state_address, 8)?;
+        let progress = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+
+        Ok((bytes[0], progress, SwapStep::from_u8(bytes[5])))
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::com::{ComMode, ComSimulator, SimModel};
+
+    // PageBufferWriteWord addresses words within a page by an 8 bit packet id, so a page can hold
+    // at most 256 words (1024 bytes); keep the test pages within that limit.
+    const TEST_PAGE_SIZE: u32 = 0x100;
+
+    fn flash_desc() -> FlashDesc {
+        let mut flash_desc = FlashDesc::new(0x08000000, 4 * TEST_PAGE_SIZE, TEST_PAGE_SIZE);
+        flash_desc.add_section("Active", 0x08000000, TEST_PAGE_SIZE).unwrap();
+        flash_desc
+            .add_section("Dfu", 0x08000000 + TEST_PAGE_SIZE, TEST_PAGE_SIZE)
+            .unwrap();
+        // Two pages: a header page (magic/progress/sub-step) and a scratch page wide enough to
+        // stash one active/DFU page's content across the two-write boundary.
+        flash_desc
+            .add_section("State", 0x08000000 + 2 * TEST_PAGE_SIZE, 2 * TEST_PAGE_SIZE)
+            .unwrap();
+
+        flash_desc
+    }
+
+    fn interface() -> ComSimulator {
+        let mut interface = ComSimulator::new();
+        interface.set_mode(ComMode::Specific(0)).unwrap();
+        interface.register_model(0, SimModel::new(0x08000000, TEST_PAGE_SIZE, 4));
+
+        interface
+    }
+
+    #[test]
+    fn dual_bank_updater_new_rejects_unequal_active_and_dfu_sizes() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 3 * TEST_PAGE_SIZE, TEST_PAGE_SIZE);
+        flash_desc
+            .add_section("Active", 0x08000000, 2 * TEST_PAGE_SIZE)
+            .unwrap();
+        flash_desc
+            .add_section("Dfu", 0x08000000 + 2 * TEST_PAGE_SIZE, TEST_PAGE_SIZE)
+            .unwrap();
+
+        let mut interface = interface();
+        let result = DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "Dfu");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dual_bank_updater_new_rejects_a_static_active_partition() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 4 * TEST_PAGE_SIZE, TEST_PAGE_SIZE);
+        flash_desc
+            .add_section_with_flags("Active", 0x08000000, TEST_PAGE_SIZE, SectionFlags::STATIC)
+            .unwrap();
+        flash_desc
+            .add_section("Dfu", 0x08000000 + TEST_PAGE_SIZE, TEST_PAGE_SIZE)
+            .unwrap();
+        flash_desc
+            .add_section("State", 0x08000000 + 2 * TEST_PAGE_SIZE, 2 * TEST_PAGE_SIZE)
+            .unwrap();
+
+        let mut interface = interface();
+        let result = DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dual_bank_updater_current_state_starts_as_dfu_detach() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+
+        assert_eq!(updater.current_state().unwrap(), DualBankState::DfuDetach);
+    }
+
+    #[test]
+    fn dual_bank_updater_mark_updated_and_mark_booted_roundtrip_state() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+
+        updater.mark_updated().unwrap();
+        assert_eq!(updater.current_state().unwrap(), DualBankState::Swap);
+
+        updater.mark_booted().unwrap();
+        assert_eq!(updater.current_state().unwrap(), DualBankState::Boot);
+    }
+
+    #[test]
+    fn dual_bank_updater_perform_swap_exchanges_active_and_dfu_contents() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+
+        // Seed the active partition's page buffer with a distinct byte pattern, then commit it,
+        // mirroring how a real image would already be running there.
+        {
+            let mut session =
+                FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+            session.flash_page(0, &vec![0xAA; TEST_PAGE_SIZE as usize]).unwrap();
+            session.flash_page(1, &vec![0xBB; TEST_PAGE_SIZE as usize]).unwrap();
+        }
+
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+        updater.mark_updated().unwrap();
+        updater.perform_swap(FlashSessionConfig::default()).unwrap();
+
+        // The swap completed but was never confirmed, so it is still reported as pending.
+        assert_eq!(updater.current_state().unwrap(), DualBankState::Swap);
+        drop(updater);
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        assert_eq!(session.read_page(0x08000000, 4).unwrap(), vec![0xBB; 4]);
+        assert_eq!(
+            session.read_page(0x08000000 + TEST_PAGE_SIZE, 4).unwrap(),
+            vec![0xAA; 4]
+        );
+    }
+
+    #[test]
+    fn dual_bank_updater_reverts_an_unconfirmed_swap_on_the_next_perform_swap_call() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+
+        {
+            let mut session =
+                FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+            session.flash_page(0, &vec![0xAA; TEST_PAGE_SIZE as usize]).unwrap();
+            session.flash_page(1, &vec![0xBB; TEST_PAGE_SIZE as usize]).unwrap();
+        }
+
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+        updater.mark_updated().unwrap();
+
+        // First boot after flashing: swap runs, application never confirms.
+        updater.perform_swap(FlashSessionConfig::default()).unwrap();
+
+        // Next boot: still unconfirmed, so the same call reverts it.
+        updater.perform_swap(FlashSessionConfig::default()).unwrap();
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        assert_eq!(session.read_page(0x08000000, 4).unwrap(), vec![0xAA; 4]);
+        assert_eq!(
+            session.read_page(0x08000000 + TEST_PAGE_SIZE, 4).unwrap(),
+            vec![0xBB; 4]
+        );
+    }
+
+    #[test]
+    fn dual_bank_updater_perform_swap_is_a_no_op_without_a_pending_swap() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+
+        {
+            let mut session =
+                FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+            session.flash_page(0, &vec![0xAA; TEST_PAGE_SIZE as usize]).unwrap();
+            session.flash_page(1, &vec![0xBB; TEST_PAGE_SIZE as usize]).unwrap();
+        }
+
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+        updater.perform_swap(FlashSessionConfig::default()).unwrap();
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        assert_eq!(session.read_page(0x08000000, 4).unwrap(), vec![0xAA; 4]);
+        assert_eq!(
+            session.read_page(0x08000000 + TEST_PAGE_SIZE, 4).unwrap(),
+            vec![0xBB; 4]
+        );
+    }
+
+    #[test]
+    fn dual_bank_updater_perform_swap_resumes_correctly_after_active_is_written_but_dfu_is_not() {
+        let flash_desc = flash_desc();
+        let mut interface = interface();
+
+        // Simulate a power loss that lands exactly between the two `flash_page` calls for page 0:
+        // the scratch page already holds the pre-swap active content, `active` already holds the
+        // new (DFU) content, but `dfu` has not been overwritten yet and the progress index is
+        // still 0. A correct resume must pull the old active content back out of the scratch page
+        // instead of re-deriving it from (already-mutated) live flash.
+        {
+            let mut session =
+                FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+            session.flash_page(0, &vec![0xBB; TEST_PAGE_SIZE as usize]).unwrap(); // active <- new
+            session.flash_page(1, &vec![0xBB; TEST_PAGE_SIZE as usize]).unwrap(); // dfu, untouched
+            session.flash_page(3, &vec![0xAA; TEST_PAGE_SIZE as usize]).unwrap(); // scratch <- old active
+
+            let mut header = vec![0u8; TEST_PAGE_SIZE as usize];
+            header[0] = SWAP_MAGIC;
+            header[1..5].copy_from_slice(&0u32.to_le_bytes());
+            header[5] = SwapStep::ActiveWritten.to_u8();
+            session.flash_page(2, &header).unwrap();
+        }
+
+        let mut updater =
+            DualBankUpdater::new(&mut interface, &flash_desc, "Active", "Dfu", "State").unwrap();
+        updater.perform_swap(FlashSessionConfig::default()).unwrap();
+        drop(updater);
+
+        let mut session = FlashSession::new(&mut interface, FlashSessionConfig::default()).unwrap();
+        assert_eq!(session.read_page(0x08000000, 4).unwrap(), vec![0xBB; 4]);
+        assert_eq!(
+            session.read_page(0x08000000 + TEST_PAGE_SIZE, 4).unwrap(),
+            vec![0xAA; 4]
+        );
+    }
+}
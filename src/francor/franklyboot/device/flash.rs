@@ -19,6 +19,24 @@ pub enum FlashDescError {
 
     /// Flash name is already used
     FlashNameAlreadyUsed,
+
+    /// FMAP blob is too short, malformed, or carries an unsupported signature/version
+    FlashFmapInvalid,
+
+    /// Flash region size is not a multiple of its own page size
+    FlashRegionSizeInvalid,
+
+    /// Flash region does not fit into flash
+    FlashRegionSizeTooBig,
+
+    /// Flash region overlaps with another region
+    FlashRegionOverlap,
+
+    /// Flash section does not fall entirely within a single region
+    FlashSectionRegionMismatch,
+
+    /// Section is not writable, see `SectionFlags::READ_ONLY` / `SectionFlags::STATIC`
+    FlashSectionNotWritable,
 }
 
 /// Implementation of the Display trait for the FlashDescError enumeration
@@ -53,10 +71,69 @@ impl fmt::Display for FlashDescError {
             FlashDescError::FlashNameAlreadyUsed => {
                 write!(f, "FlashNameAlreadyUsed: Flash name is already used!")
             }
+
+            FlashDescError::FlashFmapInvalid => {
+                write!(
+                    f,
+                    "FlashFmapInvalid: FMAP blob is too short, malformed, or has an unsupported signature/version!"
+                )
+            }
+
+            FlashDescError::FlashRegionSizeInvalid => {
+                write!(
+                    f,
+                    "FlashRegionSizeInvalid: Flash region size must be a multiple of its own page size!"
+                )
+            }
+
+            FlashDescError::FlashRegionSizeTooBig => {
+                write!(
+                    f,
+                    "FlashRegionSizeTooBig: Flash region does not fit into flash!"
+                )
+            }
+
+            FlashDescError::FlashRegionOverlap => {
+                write!(f, "FlashRegionOverlap: Flash region overlaps with another region!")
+            }
+
+            FlashDescError::FlashSectionRegionMismatch => {
+                write!(
+                    f,
+                    "FlashSectionRegionMismatch: Flash section does not fall entirely within a single region!"
+                )
+            }
+
+            FlashDescError::FlashSectionNotWritable => {
+                write!(
+                    f,
+                    "FlashSectionNotWritable: Section is marked READ_ONLY or STATIC and cannot be programmed or erased!"
+                )
+            }
         }
     }
 }
 
+// FMAP ---------------------------------------------------------------------------------------------
+
+/// FMAP header signature every valid blob must start with
+const FMAP_SIGNATURE: [u8; 8] = *b"__FMAP__";
+
+/// FMAP major version this implementation reads/writes
+const FMAP_VER_MAJOR: u8 = 1;
+
+/// FMAP minor version this implementation reads/writes
+const FMAP_VER_MINOR: u8 = 1;
+
+/// Byte length of an FMAP area/region name field
+const FMAP_NAME_LEN: usize = 32;
+
+/// Byte length of the FMAP header: signature + ver_major + ver_minor + base + size + name + nareas
+const FMAP_HEADER_LEN: usize = 8 + 1 + 1 + 8 + 4 + FMAP_NAME_LEN + 2;
+
+/// Byte length of a single FMAP area record: offset + size + name + flags
+const FMAP_AREA_LEN: usize = 4 + 4 + FMAP_NAME_LEN + 2;
+
 // Flash ------------------------------------------------------------------------------------------
 
 ///
@@ -66,27 +143,102 @@ pub struct FlashDesc {
     address: u32,
     size: u32,
     page_size: u32,
+    erase_value: u8,
     section_lst: Vec<FlashSection>,
+    region_lst: Vec<FlashRegion>,
 }
 
+/// Byte value flash reads back as once erased, following embassy-boot's `ERASE_VALUE`. Used by
+/// `FlashDesc::new` and overridable with `FlashDesc::new_with_erase_value` for flashes that don't
+/// erase to `0xFF`.
+pub const DFT_ERASE_VALUE: u8 = 0xFF;
+
 impl FlashDesc {
     ///
-    /// Creates a new flash description.
+    /// Creates a new flash description, assuming the flash erases to `DFT_ERASE_VALUE` (`0xFF`).
     ///
     pub fn new(address: u32, size: u32, page_size: u32) -> FlashDesc {
+        Self::new_with_erase_value(address, size, page_size, DFT_ERASE_VALUE)
+    }
+
+    ///
+    /// Like `new`, but for a flash whose erased state reads back as `erase_value` instead of
+    /// `0xFF`.
+    ///
+    pub fn new_with_erase_value(
+        address: u32,
+        size: u32,
+        page_size: u32,
+        erase_value: u8,
+    ) -> FlashDesc {
         FlashDesc {
             address: address,
             size: size,
             page_size: page_size,
+            erase_value: erase_value,
             section_lst: Vec::new(),
+            region_lst: Vec::new(),
         }
     }
 
+    ///
+    /// Declares an erase region spanning `[address, address + size)` with its own `page_size`,
+    /// for flashes with heterogeneous erase granularity (e.g. STM32 parts mixing 16K/64K/128K
+    /// sectors)
+    ///
+    /// Once at least one region has been added, `add_section` validates sections against the
+    /// page size of whichever region they fall into instead of the single flash-wide `page_size`
+    /// passed to `new`. Regions must not overlap and must fit entirely within the flash.
+    ///
+    pub fn add_region(
+        &mut self,
+        address: u32,
+        size: u32,
+        page_size: u32,
+    ) -> Result<(), FlashDescError> {
+        if size % page_size != 0 {
+            return Err(FlashDescError::FlashRegionSizeInvalid);
+        }
+
+        if address < self.address || address + size > self.address + self.size {
+            return Err(FlashDescError::FlashRegionSizeTooBig);
+        }
+
+        for region in &self.region_lst {
+            if region.get_address() < address + size && address < region.get_address() + region.get_size() {
+                return Err(FlashDescError::FlashRegionOverlap);
+            }
+        }
+
+        self.region_lst.push(FlashRegion::new(address, size, page_size));
+        self.region_lst.sort_by_key(|region| region.get_address());
+
+        Ok(())
+    }
+
+    ///
+    /// Like `add_section_with_flags`, with `SectionFlags::NONE`
+    ///
     pub fn add_section(
         &mut self,
         name: &str,
         address: u32,
         size: u32,
+    ) -> Result<(), FlashDescError> {
+        self.add_section_with_flags(name, address, size, SectionFlags::NONE)
+    }
+
+    ///
+    /// Declares a section spanning `[address, address + size)`, marked with `flags` (e.g.
+    /// `SectionFlags::READ_ONLY`, `SectionFlags::PRESERVE`, `SectionFlags::STATIC`) so updater
+    /// logic can refuse to program or erase it
+    ///
+    pub fn add_section_with_flags(
+        &mut self,
+        name: &str,
+        address: u32,
+        size: u32,
+        flags: SectionFlags,
     ) -> Result<(), FlashDescError> {
         // Check if section name is already used
         for section in &self.section_lst {
@@ -95,13 +247,42 @@ impl FlashDesc {
             }
         }
 
+        // Determine which page size governs this section, and how many pages precede it: the
+        // single flash-wide page size when no regions were declared, or the containing region's
+        // page size plus the page counts of every region before it otherwise.
+        let (page_size, preceding_pages) = if self.region_lst.is_empty() {
+            (self.page_size, None)
+        } else {
+            let mut preceding_pages = 0u32;
+            let mut found = None;
+
+            for region in &self.region_lst {
+                if region.get_address() <= address
+                    && address + size <= region.get_address() + region.get_size()
+                {
+                    found = Some((
+                        region.get_page_size(),
+                        preceding_pages + (address - region.get_address()) / region.get_page_size(),
+                    ));
+                    break;
+                }
+
+                preceding_pages += region.get_num_pages();
+            }
+
+            match found {
+                Some((page_size, page_id)) => (page_size, Some(page_id)),
+                None => return Err(FlashDescError::FlashSectionRegionMismatch),
+            }
+        };
+
         // section must start aligned to pages
-        if address % self.page_size != 0 {
+        if address % page_size != 0 {
             return Err(FlashDescError::FlashSectionAddressInvalid);
         }
 
         // section size must be multiple of page szize
-        if size % self.page_size != 0 {
+        if size % page_size != 0 {
             return Err(FlashDescError::FlashSectionSizeInvalid);
         }
 
@@ -126,15 +307,113 @@ impl FlashDesc {
         }
 
         // calculate page offset
-        let page_id = (address - self.address) / self.page_size;
+        let page_id = match preceding_pages {
+            Some(page_id) => page_id,
+            None => (address - self.address) / self.page_size,
+        };
 
         // add flash section
-        self.section_lst
-            .push(FlashSection::new(name, address, size, page_id));
+        self.section_lst.push(FlashSection::new_with_flags(
+            name,
+            address,
+            size,
+            page_id,
+            page_size,
+            self.erase_value,
+            flags,
+        ));
 
         Ok(())
     }
 
+    ///
+    /// Parses an FMAP (Flash Map) descriptor blob into a new `FlashDesc`, importing each area as a
+    /// `FlashSection` at `base + offset`
+    ///
+    /// FMAP carries no erase-page-size concept of its own, so the result is built against a
+    /// byte-granular (`page_size = 1`) flash: `add_section`'s alignment/size checks become
+    /// trivially satisfied and only the overlap check does real validation work, which matches
+    /// what an FMAP blob actually promises about its areas. Callers that need real per-region
+    /// erase granularity should treat the result as a starting point and rebuild sections against
+    /// a `FlashDesc` constructed with the device's actual page size.
+    ///
+    pub fn from_fmap(data: &[u8]) -> Result<FlashDesc, FlashDescError> {
+        if data.len() < FMAP_HEADER_LEN
+            || data[0..8] != FMAP_SIGNATURE
+            || data[8] != FMAP_VER_MAJOR
+        {
+            return Err(FlashDescError::FlashFmapInvalid);
+        }
+
+        let base = u64::from_le_bytes(data[10..18].try_into().unwrap());
+        let size = u32::from_le_bytes(data[18..22].try_into().unwrap());
+        let nareas = u16::from_le_bytes(data[54..56].try_into().unwrap()) as usize;
+
+        let areas_len = nareas * FMAP_AREA_LEN;
+        if data.len() < FMAP_HEADER_LEN + areas_len {
+            return Err(FlashDescError::FlashFmapInvalid);
+        }
+
+        let mut flash_desc = FlashDesc::new(base as u32, size, 1);
+
+        for i in 0..nareas {
+            let area = &data[FMAP_HEADER_LEN + i * FMAP_AREA_LEN
+                ..FMAP_HEADER_LEN + (i + 1) * FMAP_AREA_LEN];
+
+            let offset = u32::from_le_bytes(area[0..4].try_into().unwrap());
+            let area_size = u32::from_le_bytes(area[4..8].try_into().unwrap());
+
+            let name_bytes = &area[8..8 + FMAP_NAME_LEN];
+            let name_len = name_bytes
+                .iter()
+                .position(|&b| b == 0)
+                .unwrap_or(FMAP_NAME_LEN);
+            let name = String::from_utf8_lossy(&name_bytes[..name_len]).to_string();
+
+            let flags = SectionFlags::from_bits(u16::from_le_bytes(
+                area[8 + FMAP_NAME_LEN..8 + FMAP_NAME_LEN + 2].try_into().unwrap(),
+            ));
+
+            flash_desc.add_section_with_flags(&name, base as u32 + offset, area_size, flags)?;
+        }
+
+        Ok(flash_desc)
+    }
+
+    ///
+    /// Emits this flash description as an FMAP descriptor blob, writing sections back as areas
+    /// sorted by address
+    ///
+    pub fn to_fmap(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(FMAP_HEADER_LEN + self.section_lst.len() * FMAP_AREA_LEN);
+
+        out.extend_from_slice(&FMAP_SIGNATURE);
+        out.push(FMAP_VER_MAJOR);
+        out.push(FMAP_VER_MINOR);
+        out.extend_from_slice(&(self.address as u64).to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        out.extend_from_slice(&[0u8; FMAP_NAME_LEN]);
+        out.extend_from_slice(&(self.section_lst.len() as u16).to_le_bytes());
+
+        let mut sections: Vec<&FlashSection> = self.section_lst.iter().collect();
+        sections.sort_by_key(|section| section.get_address());
+
+        for section in sections {
+            out.extend_from_slice(&(section.get_address() - self.address).to_le_bytes());
+            out.extend_from_slice(&section.get_size().to_le_bytes());
+
+            let mut name_bytes = [0u8; FMAP_NAME_LEN];
+            let name = section.get_name().as_bytes();
+            let copy_len = name.len().min(FMAP_NAME_LEN);
+            name_bytes[..copy_len].copy_from_slice(&name[..copy_len]);
+            out.extend_from_slice(&name_bytes);
+
+            out.extend_from_slice(&section.get_flags().bits().to_le_bytes());
+        }
+
+        out
+    }
+
     // Getters ------------------------------------------------------------------------------------
 
     ///
@@ -158,6 +437,14 @@ impl FlashDesc {
         self.page_size
     }
 
+    ///
+    /// Returns the byte value the flash reads back as once erased (`0xFF` unless overridden via
+    /// `new_with_erase_value`).
+    ///
+    pub fn get_erase_value(&self) -> u8 {
+        self.erase_value
+    }
+
     ///
     /// Returns the number of pages in the flash memory
     ///
@@ -220,11 +507,64 @@ impl FlashDesc {
     ///
     pub fn get_section_num_pages(&self, name: &str) -> Option<u32> {
         match self._get_section(name) {
-            Some(section) => Some(section.get_size() / self.page_size),
+            // Each section already carries the page size of the region it was validated
+            // against, so this is correct whether or not regions were ever declared.
+            Some(section) => Some(section.get_size() / section.get_page_size()),
             None => None,
         }
     }
 
+    ///
+    /// Returns the section with the given name.
+    ///
+    pub fn get_section(&self, name: &str) -> Option<&FlashSection> {
+        self._get_section(name)
+    }
+
+    ///
+    /// Returns the flags of the section with the given name.
+    ///
+    pub fn get_section_flags(&self, name: &str) -> Option<SectionFlags> {
+        self._get_section(name).map(|section| section.get_flags())
+    }
+
+    ///
+    /// Returns every section that may be programmed or erased, i.e. not carrying
+    /// `SectionFlags::READ_ONLY` or `SectionFlags::STATIC`
+    ///
+    pub fn writable_sections(&self) -> Vec<&FlashSection> {
+        self.section_lst
+            .iter()
+            .filter(|section| section.get_flags().is_writable())
+            .collect()
+    }
+
+    ///
+    /// Returns `Ok(())` if the named section exists and may be programmed or erased, or
+    /// `Err(FlashDescError::FlashSectionNotWritable)` if it is unknown or carries
+    /// `SectionFlags::READ_ONLY`/`SectionFlags::STATIC`
+    ///
+    pub fn check_section_writable(&self, name: &str) -> Result<(), FlashDescError> {
+        match self._get_section(name) {
+            Some(section) if section.get_flags().is_writable() => Ok(()),
+            _ => Err(FlashDescError::FlashSectionNotWritable),
+        }
+    }
+
+    ///
+    /// Returns the number of erase regions declared via `add_region`.
+    ///
+    pub fn get_num_regions(&self) -> usize {
+        self.region_lst.len()
+    }
+
+    ///
+    /// Returns the region at `index`, ordered by address.
+    ///
+    pub fn get_region(&self, index: usize) -> Option<&FlashRegion> {
+        self.region_lst.get(index)
+    }
+
     // Private functions ---------------------------------------------------------------------------
 
     fn _get_section(&self, name: &str) -> Option<&FlashSection> {
@@ -238,6 +578,130 @@ impl FlashDesc {
     }
 }
 
+/// Flash region -----------------------------------------------------------------------------------
+
+///
+/// A contiguous erase region with its own page size, used to describe flash with heterogeneous
+/// erase granularity (see `FlashDesc::add_region`)
+///
+pub struct FlashRegion {
+    /// Absolute address the region starts at
+    address: u32,
+
+    /// Size of the region in bytes
+    size: u32,
+
+    /// Erase page size within this region
+    page_size: u32,
+}
+
+impl FlashRegion {
+    ///
+    /// Create a new flash region
+    ///
+    pub fn new(address: u32, size: u32, page_size: u32) -> FlashRegion {
+        FlashRegion {
+            address,
+            size,
+            page_size,
+        }
+    }
+
+    ///
+    /// Returns the start address of the region.
+    ///
+    pub fn get_address(&self) -> u32 {
+        self.address
+    }
+
+    ///
+    /// Returns the size of the region in bytes.
+    ///
+    pub fn get_size(&self) -> u32 {
+        self.size
+    }
+
+    ///
+    /// Returns the erase page size within this region.
+    ///
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    ///
+    /// Returns the number of pages contained in this region.
+    ///
+    pub fn get_num_pages(&self) -> u32 {
+        self.size / self.page_size
+    }
+}
+
+/// Section flags -----------------------------------------------------------------------------------
+
+///
+/// Bitmask of properties a `FlashSection` can carry, gating whether the updater logic is allowed
+/// to program or erase it
+///
+/// Round-trips through the area flags field of the FMAP format (see `FlashDesc::from_fmap` /
+/// `FlashDesc::to_fmap`) rather than through any FMAP-standard bit meaning of its own.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SectionFlags(u16);
+
+impl SectionFlags {
+    /// No restrictions: the section may be erased and programmed freely
+    pub const NONE: SectionFlags = SectionFlags(0);
+
+    /// Section must never be programmed or erased (e.g. a read-only calibration area)
+    pub const READ_ONLY: SectionFlags = SectionFlags(1 << 0);
+
+    /// Section holds data that must survive an update (e.g. persistent configuration).
+    /// Informational only for now: unlike `READ_ONLY`/`STATIC` it is not enforced by
+    /// `is_writable`/`check_section_writable`, since neither `Device` nor `DualBankUpdater`
+    /// currently erase/program more than one caller-named section at a time for it to be
+    /// skipped from
+    pub const PRESERVE: SectionFlags = SectionFlags(1 << 1);
+
+    /// Section is the bootloader itself and must never be touched by an application update
+    pub const STATIC: SectionFlags = SectionFlags(1 << 2);
+
+    ///
+    /// Returns true if `self` carries every bit set in `other`
+    ///
+    pub fn contains(&self, other: SectionFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    ///
+    /// Returns true unless `self` carries `READ_ONLY` or `STATIC`
+    ///
+    pub fn is_writable(&self) -> bool {
+        !self.contains(SectionFlags::READ_ONLY) && !self.contains(SectionFlags::STATIC)
+    }
+
+    ///
+    /// Returns the raw bit pattern, as stored in an FMAP area's flags field
+    ///
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    ///
+    /// Builds a `SectionFlags` from a raw bit pattern, as read from an FMAP area's flags field
+    ///
+    pub fn from_bits(bits: u16) -> SectionFlags {
+        SectionFlags(bits)
+    }
+}
+
+impl std::ops::BitOr for SectionFlags {
+    type Output = SectionFlags;
+
+    fn bitor(self, rhs: SectionFlags) -> SectionFlags {
+        SectionFlags(self.0 | rhs.0)
+    }
+}
+
 /// Flash section ---------------------------------------------------------------------------------
 
 ///
@@ -255,18 +719,60 @@ pub struct FlashSection {
 
     /// Page id in flash
     flash_page_id: u32,
+
+    /// Page size of the flash the section belongs to
+    page_size: u32,
+
+    /// Byte value the flash the section belongs to reads back as once erased
+    erase_value: u8,
+
+    /// Properties gating whether this section may be programmed or erased
+    flags: SectionFlags,
 }
 
 impl FlashSection {
     ///
-    /// Create a new flash section
+    /// Create a new flash section with `SectionFlags::NONE`
+    ///
+    pub fn new(
+        name: &str,
+        address: u32,
+        size: u32,
+        flash_page_id: u32,
+        page_size: u32,
+        erase_value: u8,
+    ) -> FlashSection {
+        Self::new_with_flags(
+            name,
+            address,
+            size,
+            flash_page_id,
+            page_size,
+            erase_value,
+            SectionFlags::NONE,
+        )
+    }
+
+    ///
+    /// Create a new flash section with the given flags
     ///
-    pub fn new(name: &str, address: u32, size: u32, flash_page_id: u32) -> FlashSection {
+    pub fn new_with_flags(
+        name: &str,
+        address: u32,
+        size: u32,
+        flash_page_id: u32,
+        page_size: u32,
+        erase_value: u8,
+        flags: SectionFlags,
+    ) -> FlashSection {
         FlashSection {
             name: name.to_string(),
             address: address,
             size: size,
             flash_page_id: flash_page_id,
+            page_size: page_size,
+            erase_value: erase_value,
+            flags: flags,
         }
     }
 
@@ -299,6 +805,34 @@ impl FlashSection {
     pub fn get_flash_page_id(&self) -> u32 {
         self.flash_page_id
     }
+
+    ///
+    /// Returns the page size of the flash the section belongs to.
+    ///
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    ///
+    /// Returns the range of flash page ids covered by this section.
+    ///
+    pub fn get_page_range(&self) -> std::ops::Range<u32> {
+        self.flash_page_id..(self.flash_page_id + self.size / self.page_size)
+    }
+
+    ///
+    /// Returns the byte value the flash this section belongs to reads back as once erased.
+    ///
+    pub fn get_erase_value(&self) -> u8 {
+        self.erase_value
+    }
+
+    ///
+    /// Returns the properties gating whether this section may be programmed or erased.
+    ///
+    pub fn get_flags(&self) -> SectionFlags {
+        self.flags
+    }
 }
 
 // Tests ------------------------------------------------------------------------------------------
@@ -315,6 +849,14 @@ mod tests {
         assert_eq!(flash_desc.get_size(), 0x10000);
         assert_eq!(flash_desc.get_page_size(), 0x400);
         assert_eq!(flash_desc.get_num_pages(), 0x40);
+        assert_eq!(flash_desc.get_erase_value(), DFT_ERASE_VALUE);
+    }
+
+    #[test]
+    fn flash_desc_new_with_erase_value() {
+        let flash_desc = FlashDesc::new_with_erase_value(0x08000000, 0x10000, 0x400, 0x00);
+
+        assert_eq!(flash_desc.get_erase_value(), 0x00);
     }
 
     #[test]
@@ -512,4 +1054,257 @@ mod tests {
         assert_eq!(result.is_some(), true);
         assert_eq!(result.unwrap(), 4);
     }
+
+    #[test]
+    fn flash_desc_get_section_pub_by_name() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+
+        let name = String::from("test");
+        let address = 0x08000000;
+        let size = 0x1000;
+
+        flash_desc.add_section(&name, address, size).unwrap();
+
+        let result = flash_desc.get_section(&name);
+
+        assert_eq!(result.is_some(), true);
+        assert_eq!(result.unwrap().get_name(), &name);
+    }
+
+    #[test]
+    fn flash_section_get_page_range() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+
+        let name = String::from("test");
+        flash_desc.add_section(&name, 0x08000400, 0x1000).unwrap();
+
+        let section = flash_desc.get_section(&name).unwrap();
+
+        assert_eq!(section.get_page_size(), 0x400);
+        assert_eq!(section.get_page_range(), 1..5);
+    }
+
+    #[test]
+    fn flash_desc_to_fmap_round_trips_through_from_fmap() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+        flash_desc.add_section("bootloader", 0x08000000, 0x4000).unwrap();
+        flash_desc.add_section("app", 0x08004000, 0xC000).unwrap();
+
+        let blob = flash_desc.to_fmap();
+        let parsed = FlashDesc::from_fmap(&blob).unwrap();
+
+        assert_eq!(parsed.get_address(), 0x08000000);
+        assert_eq!(parsed.get_size(), 0x10000);
+        assert_eq!(parsed.get_num_section(), 2);
+        assert_eq!(parsed.get_section_address("bootloader"), Some(0x08000000));
+        assert_eq!(parsed.get_section_size("bootloader"), Some(0x4000));
+        assert_eq!(parsed.get_section_address("app"), Some(0x08004000));
+        assert_eq!(parsed.get_section_size("app"), Some(0xC000));
+    }
+
+    #[test]
+    fn flash_desc_to_fmap_starts_with_the_fmap_signature() {
+        let flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+
+        let blob = flash_desc.to_fmap();
+
+        assert_eq!(&blob[0..8], b"__FMAP__");
+        assert_eq!(blob[8], 1);
+        assert_eq!(blob[9], 1);
+    }
+
+    #[test]
+    fn flash_desc_from_fmap_orders_areas_by_address_regardless_of_insertion_order() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+        flash_desc.add_section("app", 0x08004000, 0xC000).unwrap();
+        flash_desc.add_section("bootloader", 0x08000000, 0x4000).unwrap();
+
+        let blob = flash_desc.to_fmap();
+        let parsed = FlashDesc::from_fmap(&blob).unwrap();
+
+        assert_eq!(
+            parsed.get_section_name_list(),
+            vec![String::from("bootloader"), String::from("app")]
+        );
+    }
+
+    #[test]
+    fn flash_desc_from_fmap_rejects_a_bad_signature() {
+        let mut blob = FlashDesc::new(0x08000000, 0x10000, 0x400).to_fmap();
+        blob[0] = b'X';
+
+        let result = FlashDesc::from_fmap(&blob);
+
+        assert_eq!(result.err(), Some(FlashDescError::FlashFmapInvalid));
+    }
+
+    #[test]
+    fn flash_desc_from_fmap_rejects_a_truncated_blob() {
+        let blob = FlashDesc::new(0x08000000, 0x10000, 0x400).to_fmap();
+
+        let result = FlashDesc::from_fmap(&blob[0..FMAP_HEADER_LEN - 1]);
+
+        assert_eq!(result.err(), Some(FlashDescError::FlashFmapInvalid));
+    }
+
+    #[test]
+    fn flash_section_inherits_erase_value_from_flash_desc() {
+        let mut flash_desc = FlashDesc::new_with_erase_value(0x08000000, 0x10000, 0x400, 0x00);
+
+        let name = String::from("test");
+        flash_desc.add_section(&name, 0x08000000, 0x1000).unwrap();
+
+        let section = flash_desc.get_section(&name).unwrap();
+
+        assert_eq!(section.get_erase_value(), 0x00);
+    }
+
+    #[test]
+    fn flash_desc_add_region() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+
+        flash_desc.add_region(0x08000000, 0x10000, 0x4000).unwrap();
+        flash_desc.add_region(0x08010000, 0x10000, 0x10000).unwrap();
+
+        assert_eq!(flash_desc.get_num_regions(), 2);
+        assert_eq!(flash_desc.get_region(0).unwrap().get_page_size(), 0x4000);
+        assert_eq!(flash_desc.get_region(1).unwrap().get_page_size(), 0x10000);
+    }
+
+    #[test]
+    fn flash_desc_add_region_invalid_size() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+
+        let result = flash_desc.add_region(0x08000000, 0x10001, 0x4000);
+
+        assert_eq!(result, Err(FlashDescError::FlashRegionSizeInvalid));
+    }
+
+    #[test]
+    fn flash_desc_add_region_too_big() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+
+        let result = flash_desc.add_region(0x08000000, 0x30000, 0x4000);
+
+        assert_eq!(result, Err(FlashDescError::FlashRegionSizeTooBig));
+    }
+
+    #[test]
+    fn flash_desc_add_region_overlap() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+
+        flash_desc.add_region(0x08000000, 0x10000, 0x4000).unwrap();
+
+        let result = flash_desc.add_region(0x08008000, 0x10000, 0x4000);
+
+        assert_eq!(result, Err(FlashDescError::FlashRegionOverlap));
+    }
+
+    #[test]
+    fn flash_desc_add_section_validates_against_containing_region_page_size() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+        flash_desc.add_region(0x08000000, 0x10000, 0x4000).unwrap();
+        flash_desc.add_region(0x08010000, 0x10000, 0x10000).unwrap();
+
+        // Aligned to the small-sector region's 0x4000 page size, but not the flash-wide 0x400.
+        flash_desc
+            .add_section("bootloader", 0x08000000, 0x4000)
+            .unwrap();
+
+        // Aligned to the large-sector region's 0x10000 page size.
+        flash_desc.add_section("app", 0x08010000, 0x10000).unwrap();
+
+        assert_eq!(flash_desc.get_section_page_id("bootloader"), Some(0));
+        assert_eq!(flash_desc.get_section_num_pages("bootloader"), Some(1));
+
+        // Page id accounts for the 4 pages of the preceding small-sector region.
+        assert_eq!(flash_desc.get_section_page_id("app"), Some(4));
+        assert_eq!(flash_desc.get_section_num_pages("app"), Some(1));
+    }
+
+    #[test]
+    fn flash_desc_add_section_rejects_misaligned_address_within_its_region() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+        flash_desc.add_region(0x08000000, 0x10000, 0x4000).unwrap();
+
+        let result = flash_desc.add_section("bootloader", 0x08000400, 0x4000);
+
+        assert_eq!(result, Err(FlashDescError::FlashSectionAddressInvalid));
+    }
+
+    #[test]
+    fn flash_desc_add_section_rejects_straddling_a_region_boundary() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x20000, 0x400);
+        flash_desc.add_region(0x08000000, 0x10000, 0x4000).unwrap();
+        flash_desc.add_region(0x08010000, 0x10000, 0x10000).unwrap();
+
+        let result = flash_desc.add_section("straddling", 0x0800C000, 0x8000);
+
+        assert_eq!(result, Err(FlashDescError::FlashSectionRegionMismatch));
+    }
+
+    #[test]
+    fn flash_desc_add_section_defaults_to_no_flags() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+        flash_desc.add_section("test", 0x08000000, 0x1000).unwrap();
+
+        assert_eq!(
+            flash_desc.get_section_flags("test"),
+            Some(SectionFlags::NONE)
+        );
+        assert_eq!(flash_desc.writable_sections().len(), 1);
+    }
+
+    #[test]
+    fn flash_desc_add_section_with_flags_is_excluded_from_writable_sections() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+        flash_desc
+            .add_section_with_flags("bootloader", 0x08000000, 0x4000, SectionFlags::STATIC)
+            .unwrap();
+        flash_desc.add_section("app", 0x08004000, 0xC000).unwrap();
+
+        assert_eq!(
+            flash_desc.get_section_flags("bootloader"),
+            Some(SectionFlags::STATIC)
+        );
+
+        let writable = flash_desc.writable_sections();
+        assert_eq!(writable.len(), 1);
+        assert_eq!(writable[0].get_name(), "app");
+    }
+
+    #[test]
+    fn section_flags_read_only_and_static_are_not_writable() {
+        assert_eq!(SectionFlags::NONE.is_writable(), true);
+        assert_eq!(SectionFlags::READ_ONLY.is_writable(), false);
+        assert_eq!(SectionFlags::STATIC.is_writable(), false);
+        assert_eq!(SectionFlags::PRESERVE.is_writable(), true);
+    }
+
+    #[test]
+    fn section_flags_can_be_combined_with_bitor() {
+        let combined = SectionFlags::READ_ONLY | SectionFlags::PRESERVE;
+
+        assert_eq!(combined.contains(SectionFlags::READ_ONLY), true);
+        assert_eq!(combined.contains(SectionFlags::PRESERVE), true);
+        assert_eq!(combined.contains(SectionFlags::STATIC), false);
+    }
+
+    #[test]
+    fn flash_desc_to_fmap_round_trips_section_flags() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x10000, 0x400);
+        flash_desc
+            .add_section_with_flags("bootloader", 0x08000000, 0x4000, SectionFlags::STATIC)
+            .unwrap();
+        flash_desc.add_section("app", 0x08004000, 0xC000).unwrap();
+
+        let blob = flash_desc.to_fmap();
+        let parsed = FlashDesc::from_fmap(&blob).unwrap();
+
+        assert_eq!(
+            parsed.get_section_flags("bootloader"),
+            Some(SectionFlags::STATIC)
+        );
+        assert_eq!(parsed.get_section_flags("app"), Some(SectionFlags::NONE));
+    }
 }
@@ -1,11 +1,15 @@
 pub mod device;
+pub mod dual_bank;
 pub mod entry;
 pub mod flash;
+pub mod network;
 
 //pub use device::Device;
 pub use device::Device;
+pub use dual_bank::{DualBankState, DualBankUpdater, BOOT_MAGIC, SWAP_MAGIC};
 pub use entry::Entry;
 pub use entry::EntryType;
+pub use network::{Network, NetworkReport, NodeResult, NodeState};
 
 pub use flash::FlashDesc;
 pub use flash::FlashSection;
@@ -0,0 +1,231 @@
+use crate::francor::franklyboot::{
+    com::ComInterface, device::Device, firmware::FirmwareDataInterface, Error,
+};
+
+// Node State ---------------------------------------------------------------------------------------
+
+///
+/// Where a single node of a `Network` batch currently stands
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeState {
+    /// Not yet attempted
+    Pending,
+    /// Interface opened, device initialized, currently being written
+    Flashing,
+    /// Flashed and its whole-image CRC confirmed
+    Verified,
+    /// Opening the interface, initializing, validating, or flashing the node failed
+    Failed(String),
+}
+
+///
+/// Outcome of flashing a single node, as recorded in a `NetworkReport`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeResult {
+    pub node_id: u8,
+    pub state: NodeState,
+}
+
+///
+/// Per-node outcome of a `Network::flash_all` run
+///
+#[derive(Debug, Clone, Default)]
+pub struct NetworkReport {
+    pub results: Vec<NodeResult>,
+}
+
+impl NetworkReport {
+    /// Node ids that reached `NodeState::Verified`
+    pub fn succeeded(&self) -> Vec<u8> {
+        self.results
+            .iter()
+            .filter(|r| r.state == NodeState::Verified)
+            .map(|r| r.node_id)
+            .collect()
+    }
+
+    /// Node ids that ended in `NodeState::Failed`
+    pub fn failed(&self) -> Vec<u8> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.state, NodeState::Failed(_)))
+            .map(|r| r.node_id)
+            .collect()
+    }
+
+    /// `true` if every attempted node reached `NodeState::Verified`
+    pub fn all_succeeded(&self) -> bool {
+        !self.results.is_empty() && self.failed().is_empty()
+    }
+}
+
+// Network --------------------------------------------------------------------------------------------
+
+///
+/// Flashes one firmware image across every node a discovery call (e.g. `CANInterface::ping_network`,
+/// `NetInterface::ping_network`, a simulated network's own node listing, ...) turned up
+///
+/// `open_node` is called once per node id to obtain an interface already bound to that node - e.g.
+/// `CANInterface::open(bus)` followed by `set_mode(ComMode::Specific(node_id))`. This mirrors how
+/// every multi-node path already in this crate opens one interface per target rather than sharing a
+/// single interface across nodes. A bad node is recorded as `NodeState::Failed` and does not stop the
+/// rest of the batch, so a handful of unreachable or mid-flash-fault nodes on a populated bus don't
+/// abort an otherwise successful field update.
+///
+pub struct Network<I, F>
+where
+    I: ComInterface,
+    F: FnMut(u8) -> Result<I, Error>,
+{
+    open_node: F,
+    expected_chip: Option<String>,
+}
+
+impl<I, F> Network<I, F>
+where
+    I: ComInterface,
+    F: FnMut(u8) -> Result<I, Error>,
+{
+    pub fn new(open_node: F) -> Self {
+        Network {
+            open_node,
+            expected_chip: None,
+        }
+    }
+
+    /// Reject any node whose chip database entry does not match `chip`, the same check
+    /// `Device::validate_image` already applies to a single device
+    pub fn with_expected_chip(mut self, chip: impl Into<String>) -> Self {
+        self.expected_chip = Some(chip.into());
+        self
+    }
+
+    /// Flashes `firmware` to every id in `node_lst`, continuing past individual node failures
+    pub fn flash_all<FWI: FirmwareDataInterface>(
+        &mut self,
+        node_lst: &[u8],
+        firmware: &FWI,
+        fast: bool,
+        mass_erase: bool,
+    ) -> NetworkReport {
+        let results = node_lst
+            .iter()
+            .map(|&node_id| NodeResult {
+                node_id,
+                state: self._flash_node(node_id, firmware, fast, mass_erase),
+            })
+            .collect();
+
+        NetworkReport { results }
+    }
+
+    fn _flash_node<FWI: FirmwareDataInterface>(
+        &mut self,
+        node_id: u8,
+        firmware: &FWI,
+        fast: bool,
+        mass_erase: bool,
+    ) -> NodeState {
+        let interface = match (self.open_node)(node_id) {
+            Ok(interface) => interface,
+            Err(e) => return NodeState::Failed(e.to_string()),
+        };
+
+        let mut device = Device::new(interface);
+
+        if let Err(e) = device.init() {
+            return NodeState::Failed(e.to_string());
+        }
+
+        if let Err(e) = device.validate_image(firmware, self.expected_chip.as_deref()) {
+            return NodeState::Failed(e.to_string());
+        }
+
+        if let Err(e) = device.flash(firmware, fast, mass_erase) {
+            return NodeState::Failed(e.to_string());
+        }
+
+        NodeState::Verified
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::francor::franklyboot::{com::ComSimulator, firmware::hex_file::HexFile};
+
+    /// A minimal one-byte image - enough for `HexFile::from_string` to accept it. None of these
+    /// tests reach `Device::flash`, so its contents never matter beyond parsing successfully.
+    fn minimal_firmware() -> HexFile {
+        HexFile::from_string(":01000000AA55\n:00000001FF\n").unwrap()
+    }
+
+    #[test]
+    fn network_report_classifies_succeeded_and_failed() {
+        let report = NetworkReport {
+            results: vec![
+                NodeResult {
+                    node_id: 1,
+                    state: NodeState::Verified,
+                },
+                NodeResult {
+                    node_id: 2,
+                    state: NodeState::Failed("no response".to_string()),
+                },
+            ],
+        };
+
+        assert_eq!(report.succeeded(), vec![1]);
+        assert_eq!(report.failed(), vec![2]);
+        assert!(!report.all_succeeded());
+    }
+
+    #[test]
+    fn network_report_all_succeeded_requires_at_least_one_node() {
+        assert!(!NetworkReport::default().all_succeeded());
+    }
+
+    #[test]
+    fn network_continues_past_a_node_whose_interface_cannot_be_opened() {
+        let mut network = Network::new(|node_id| {
+            if node_id == 2 {
+                Err(Error::ComNoResponse)
+            } else {
+                let mut interface = ComSimulator::new();
+                interface.set_recv_error(Error::ComNoResponse);
+                Ok(interface)
+            }
+        });
+
+        let report = network.flash_all(&[1, 2, 3], &minimal_firmware(), false, false);
+
+        let node_ids: Vec<u8> = report.results.iter().map(|r| r.node_id).collect();
+        assert_eq!(node_ids, vec![1, 2, 3]);
+        assert_eq!(
+            report.results[1].state,
+            NodeState::Failed(Error::ComNoResponse.to_string())
+        );
+        // Every node failed here (1/3 at `init`, 2 at opening the interface), but each was still
+        // attempted independently - the batch never stopped after node 2's open failure.
+        assert!(report.failed().len() == 3);
+    }
+
+    #[test]
+    fn network_fails_a_node_whose_device_never_inits() {
+        let mut network = Network::new(|_node_id| {
+            // An empty `ComSimulator` has no queued responses, so every request the handshake
+            // sends comes back as `Error::ComNoResponse` - standing in for an unreachable node.
+            Ok(ComSimulator::new())
+        });
+
+        let report = network.flash_all(&[5], &minimal_firmware(), false, false);
+
+        assert_eq!(report.results.len(), 1);
+        assert!(matches!(report.results[0].state, NodeState::Failed(_)));
+        assert!(!report.all_succeeded());
+    }
+}
@@ -0,0 +1,280 @@
+use rusb::{DeviceHandle, GlobalContext};
+use std::time::Duration;
+
+use crate::francor::franklyboot::Error;
+
+// DFU Interface ------------------------------------------------------------------------------------
+
+// USB DFU 1.1 class requests (DFU spec, table 3.2)
+const DFU_DETACH: u8 = 0;
+const DFU_DNLOAD: u8 = 1;
+const DFU_UPLOAD: u8 = 2;
+const DFU_GETSTATUS: u8 = 3;
+const DFU_CLRSTATUS: u8 = 4;
+const DFU_GETSTATE: u8 = 5;
+const DFU_ABORT: u8 = 6;
+
+/// Maximum size of a single `DFU_DNLOAD` block.
+///
+/// The real limit is reported in the interface's DFU functional descriptor (`wTransferSize`), but
+/// every STM32 ROM bootloader accepts at least this much, so it is used as a fixed value for now.
+const DFU_BLOCK_SIZE: usize = 2048;
+
+const DFU_STATE_DFU_DNBUSY: u8 = 0x04;
+const DFU_STATE_DFU_ERROR: u8 = 0x0a;
+
+///
+/// DFU interface
+///
+/// Flashes a device over USB DFU 1.1, talking directly to a ROM DFU bootloader (e.g. the one built
+/// into STM32 parts) rather than FranklyBoot. Covers the requests a flashing round trip needs -
+/// DETACH, DNLOAD/UPLOAD, GETSTATUS/GETSTATE, and CLRSTATUS/ABORT for error recovery. The DFU
+/// control-transfer state machine has nothing in common with FranklyBoot's `Msg` wire protocol (no
+/// request/result opcodes, no page buffer, enumeration by alt-setting rather than node id), so this
+/// does not implement `ComInterface` like the other interfaces in `com`; it is a self-contained
+/// flashing path instead, driven directly by `main`'s DFU subcommand handlers.
+///
+pub struct DfuInterface {
+    /// Opened USB device handle
+    handle: DeviceHandle<GlobalContext>,
+
+    /// DFU interface number on the device
+    interface: u8,
+}
+
+impl DfuInterface {
+    ///
+    /// Open a USB device in DFU mode
+    ///
+    /// This function opens the first USB device matching the given vendor/product id and claims
+    /// its DFU interface.
+    ///
+    pub fn open(vid: u16, pid: u16, interface: u8) -> Result<DfuInterface, Error> {
+        let handle = rusb::open_device_with_vid_pid(vid, pid).ok_or_else(|| {
+            Error::Error(format!(
+                "No USB device found with VID:PID {:04X}:{:04X}",
+                vid, pid
+            ))
+        })?;
+
+        handle
+            .claim_interface(interface)
+            .map_err(|e| Error::Error(format!("Failed to claim DFU interface: {}", e)))?;
+
+        Ok(DfuInterface { handle, interface })
+    }
+
+    ///
+    /// Download firmware to the device
+    ///
+    /// Sends `data` to the device in `DFU_DNLOAD` blocks of up to `DFU_BLOCK_SIZE` bytes, polling
+    /// `DFU_GETSTATUS` after each block and waiting the returned `bwPollTimeout` before sending the
+    /// next one. A final zero-length `DFU_DNLOAD` followed by `DFU_GETSTATUS` triggers manifestation
+    /// of the firmware on the device.
+    ///
+    pub fn download(&mut self, data: &[u8]) -> Result<(), Error> {
+        for (block_num, block) in data.chunks(DFU_BLOCK_SIZE).enumerate() {
+            self._dnload(block_num as u16, block)?;
+            self._wait_until_ready()?;
+        }
+
+        // Zero-length DNLOAD requests manifestation of the transferred firmware
+        let block_num = data.len().div_ceil(DFU_BLOCK_SIZE) as u16;
+        self._dnload(block_num, &[])?;
+        self._wait_until_ready()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Detach the device from DFU mode
+    ///
+    /// Requests `DFU_DETACH` so the device resets and boots the newly flashed application.
+    ///
+    pub fn detach(&mut self) -> Result<(), Error> {
+        self.handle
+            .write_control(
+                rusb::request_type(
+                    rusb::Direction::Out,
+                    rusb::RequestType::Class,
+                    rusb::Recipient::Interface,
+                ),
+                DFU_DETACH,
+                0,
+                self.interface as u16,
+                &[],
+                Duration::from_secs(1),
+            )
+            .map_err(|e| Error::Error(format!("DFU_DETACH failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    ///
+    /// Read firmware back from the device
+    ///
+    /// Sends `DFU_UPLOAD` requests of up to `DFU_BLOCK_SIZE` bytes until the device returns a
+    /// short block (the DFU spec's end-of-upload signal) or `max_len` bytes have been read.
+    ///
+    pub fn upload(&mut self, max_len: usize) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+
+        for block_num in 0u16.. {
+            let mut block = vec![0u8; DFU_BLOCK_SIZE.min(max_len - data.len())];
+            let read = self
+                .handle
+                .read_control(
+                    rusb::request_type(
+                        rusb::Direction::In,
+                        rusb::RequestType::Class,
+                        rusb::Recipient::Interface,
+                    ),
+                    DFU_UPLOAD,
+                    block_num,
+                    self.interface as u16,
+                    &mut block,
+                    Duration::from_secs(1),
+                )
+                .map_err(|e| Error::Error(format!("DFU_UPLOAD failed: {}", e)))?;
+
+            data.extend_from_slice(&block[..read]);
+
+            if read < block.len() || data.len() >= max_len {
+                break;
+            }
+        }
+
+        Ok(data)
+    }
+
+    ///
+    /// Read the device's current DFU state via `DFU_GETSTATE`
+    ///
+    pub fn get_state(&mut self) -> Result<u8, Error> {
+        let mut state = [0u8; 1];
+        self.handle
+            .read_control(
+                rusb::request_type(
+                    rusb::Direction::In,
+                    rusb::RequestType::Class,
+                    rusb::Recipient::Interface,
+                ),
+                DFU_GETSTATE,
+                0,
+                self.interface as u16,
+                &mut state,
+                Duration::from_secs(1),
+            )
+            .map_err(|e| Error::Error(format!("DFU_GETSTATE failed: {}", e)))?;
+
+        Ok(state[0])
+    }
+
+    ///
+    /// Clear a `dfuERROR` status via `DFU_CLRSTATUS`, returning the device to `dfuIDLE`
+    ///
+    pub fn clear_status(&mut self) -> Result<(), Error> {
+        self.handle
+            .write_control(
+                rusb::request_type(
+                    rusb::Direction::Out,
+                    rusb::RequestType::Class,
+                    rusb::Recipient::Interface,
+                ),
+                DFU_CLRSTATUS,
+                0,
+                self.interface as u16,
+                &[],
+                Duration::from_secs(1),
+            )
+            .map_err(|e| Error::Error(format!("DFU_CLRSTATUS failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    ///
+    /// Abort the current DFU transfer via `DFU_ABORT`, returning the device to `dfuIDLE`
+    ///
+    pub fn abort(&mut self) -> Result<(), Error> {
+        self.handle
+            .write_control(
+                rusb::request_type(
+                    rusb::Direction::Out,
+                    rusb::RequestType::Class,
+                    rusb::Recipient::Interface,
+                ),
+                DFU_ABORT,
+                0,
+                self.interface as u16,
+                &[],
+                Duration::from_secs(1),
+            )
+            .map_err(|e| Error::Error(format!("DFU_ABORT failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Private functions --------------------------------------------------------------------------
+
+    fn _dnload(&mut self, block_num: u16, block: &[u8]) -> Result<(), Error> {
+        self.handle
+            .write_control(
+                rusb::request_type(
+                    rusb::Direction::Out,
+                    rusb::RequestType::Class,
+                    rusb::Recipient::Interface,
+                ),
+                DFU_DNLOAD,
+                block_num,
+                self.interface as u16,
+                block,
+                Duration::from_secs(1),
+            )
+            .map_err(|e| Error::Error(format!("DFU_DNLOAD failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Poll `DFU_GETSTATUS` until the device leaves the busy state, honoring `bwPollTimeout`
+    fn _wait_until_ready(&mut self) -> Result<(), Error> {
+        loop {
+            let mut status = [0u8; 6];
+            self.handle
+                .read_control(
+                    rusb::request_type(
+                        rusb::Direction::In,
+                        rusb::RequestType::Class,
+                        rusb::Recipient::Interface,
+                    ),
+                    DFU_GETSTATUS,
+                    0,
+                    self.interface as u16,
+                    &mut status,
+                    Duration::from_secs(1),
+                )
+                .map_err(|e| Error::Error(format!("DFU_GETSTATUS failed: {}", e)))?;
+
+            let dfu_status = status[0];
+            let poll_timeout_ms =
+                u32::from_le_bytes([status[1], status[2], status[3], 0]);
+            let dfu_state = status[4];
+
+            if dfu_status != 0 || dfu_state == DFU_STATE_DFU_ERROR {
+                // Clear dfuERROR so a subsequent operation (e.g. a retry) isn't stuck behind it;
+                // ignore the result since we are already returning the original failure.
+                let _ = self.clear_status();
+
+                return Err(Error::Error(format!(
+                    "Device reported DFU error: status {:#04X}, state {:#04X}",
+                    dfu_status, dfu_state
+                )));
+            }
+
+            if dfu_state != DFU_STATE_DFU_DNBUSY {
+                return Ok(());
+            }
+
+            std::thread::sleep(Duration::from_millis(poll_timeout_ms as u64));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "uniffi")]
+
+//! UniFFI bindings exposing the update session to Swift/Kotlin
+//!
+//! Wraps `Device<SerialInterface>` behind a UniFFI-exported object so mobile/desktop apps can
+//! drive a firmware update without hand-rolling FFI. Every exported method runs through
+//! `_catch_panic`, which converts a Rust panic into `UpdateError::Unexpected` instead of letting
+//! it unwind across the FFI boundary - UniFFI's documented catch-panic/status-code convention for
+//! keeping bindings crash-safe.
+
+use crate::francor::franklyboot::{
+    com::serial::SerialInterface, device::Device, firmware::bin_file::BinFile, Error,
+};
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+
+uniffi::setup_scaffolding!();
+
+/// Error surfaced across the FFI boundary, one variant per failure a caller needs to branch on
+///
+/// Mirrors the crate's internal `Error` enum, but flattened: UniFFI's `#[uniffi(flat_error)]`
+/// exposes each variant's `Display` string to the binding side rather than its fields, which is
+/// enough detail for a mobile app to show the user without needing the full `Error` type.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum UpdateError {
+    /// The device responded, but rejected the request (`Error::ResultError`)
+    #[error("device rejected the request: {0}")]
+    Rejected(String),
+
+    /// The response didn't match the request - a dropped or corrupted frame (`Error::MsgCorruption`)
+    #[error("link corruption: {0}")]
+    Corruption(String),
+
+    /// The transport itself failed (`Error::ComError`)
+    #[error("communication error: {0}")]
+    Communication(String),
+
+    /// No response was received before the transport's timeout (`Error::ComNoResponse`)
+    #[error("device did not respond")]
+    NoResponse,
+
+    /// The device doesn't implement the requested operation (`Error::NotSupported`)
+    #[error("operation not supported by this device")]
+    NotSupported,
+
+    /// The flashed application's CRC didn't match the firmware image (`Error::AppCrcMismatch`)
+    #[error("firmware CRC mismatch after flashing: expected {expected:#010x}, got {actual:#010x}")]
+    CrcMismatch { expected: u32, actual: u32 },
+
+    /// Any other `Error` variant, carrying its `Display` message
+    #[error("{0}")]
+    Other(String),
+
+    /// A Rust panic was caught at the FFI boundary instead of unwinding into the host language
+    #[error("internal error: {0}")]
+    Unexpected(String),
+}
+
+impl From<Error> for UpdateError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::ResultError(msg) => UpdateError::Rejected(msg),
+            Error::MsgCorruption(msg) => UpdateError::Corruption(msg),
+            Error::ComError(msg) => UpdateError::Communication(msg),
+            Error::ComNoResponse => UpdateError::NoResponse,
+            Error::NotSupported => UpdateError::NotSupported,
+            Error::AppCrcMismatch { expected, actual } => {
+                UpdateError::CrcMismatch { expected, actual }
+            }
+            other => UpdateError::Other(other.to_string()),
+        }
+    }
+}
+
+/// Bootloader/device identification, as read back by `UpdateSession::read_device_info`
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct DeviceInfo {
+    pub bootloader_version: String,
+    pub vid: u32,
+    pub pid: u32,
+    pub prd: u32,
+    pub uid: String,
+}
+
+/// A firmware update session over a serial-attached FranklyBoot device
+#[derive(uniffi::Object)]
+pub struct UpdateSession {
+    device: Mutex<Device<SerialInterface>>,
+}
+
+#[uniffi::export]
+impl UpdateSession {
+    /// Opens `port_name` at `baud_rate` and initializes the device
+    #[uniffi::constructor]
+    pub fn new(port_name: String, baud_rate: u32) -> Result<UpdateSession, UpdateError> {
+        _catch_panic(move || {
+            let interface =
+                SerialInterface::open(&port_name, baud_rate).map_err(UpdateError::Communication)?;
+
+            let mut device = Device::new(interface);
+            device.init()?;
+
+            Ok(UpdateSession {
+                device: Mutex::new(device),
+            })
+        })
+    }
+
+    /// Returns the device's bootloader version as read when the session was opened; no request
+    /// is sent to the device
+    pub fn ping(&self) -> Result<String, UpdateError> {
+        _catch_panic(|| {
+            let device = self.lock_device();
+            Ok(device.get_bootloader_version())
+        })
+    }
+
+    /// Reads the device's identification entries
+    pub fn read_device_info(&self) -> Result<DeviceInfo, UpdateError> {
+        _catch_panic(|| {
+            let device = self.lock_device();
+            Ok(DeviceInfo {
+                bootloader_version: device.get_bootloader_version(),
+                vid: device.get_device_info_vid(),
+                pid: device.get_device_info_pid(),
+                prd: device.get_device_info_prd(),
+                uid: format!("{:#034x}", device.get_device_info_uid()),
+            })
+        })
+    }
+
+    /// Flashes `data`, a raw binary image, to the device's application area and starts it
+    pub fn flash(&self, data: Vec<u8>) -> Result<(), UpdateError> {
+        _catch_panic(move || {
+            let mut device = self.lock_device();
+            let (start_address, _) = device.get_application_region();
+            let firmware = BinFile::from_bytes(&data, start_address);
+
+            device.flash(&firmware, false, false)?;
+            Ok(())
+        })
+    }
+}
+
+impl UpdateSession {
+    /// Locks `device`, recovering it if a previous call panicked while holding the lock
+    ///
+    /// `_catch_panic` already stops such a panic from unwinding across the FFI boundary, but a
+    /// poisoned `Mutex` would otherwise keep failing every call after it for the rest of the
+    /// session's lifetime. The `Device` itself may be left mid-operation by the panic, same as it
+    /// would be after any other interrupted call - the caller is expected to retry or re-init as
+    /// usual, not to assume a poison recovery rolled anything back.
+    fn lock_device(&self) -> std::sync::MutexGuard<'_, Device<SerialInterface>> {
+        self.device.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Runs `f`, converting a caught panic into `UpdateError::Unexpected` instead of letting it
+/// unwind across the FFI boundary
+fn _catch_panic<F, T>(f: F) -> Result<T, UpdateError>
+where
+    F: FnOnce() -> Result<T, UpdateError>,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+
+        Err(UpdateError::Unexpected(message))
+    })
+}
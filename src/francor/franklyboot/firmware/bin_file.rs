@@ -0,0 +1,153 @@
+use crate::francor::franklyboot::{
+    device::flash::FlashDesc,
+    firmware::{FirmwareDataInterface, FirmwareDataRaw},
+    Error,
+};
+
+// Raw Binary File Representation -------------------------------------------------------------------
+
+///
+/// Raw binary firmware image
+///
+/// A `.bin` file carries no address information of its own, so every byte is placed starting at
+/// `load_address`, the way `objcopy -O binary` output is meant to be flashed.
+///
+pub struct BinFile {
+    data: FirmwareDataRaw,
+}
+
+impl BinFile {
+    pub fn from_file(filename: &str, load_address: u32) -> Result<BinFile, String> {
+        let bytes = std::fs::read(filename)
+            .map_err(|e| format!("Failed to open file '{}': {}", filename, e))?;
+
+        Ok(Self::from_bytes(&bytes, load_address))
+    }
+
+    pub fn from_bytes(bytes: &[u8], load_address: u32) -> BinFile {
+        let mut firmware_map = FirmwareDataRaw::new();
+        for (byte_idx, byte_value) in bytes.iter().enumerate() {
+            firmware_map.insert(load_address + byte_idx as u32, *byte_value);
+        }
+
+        BinFile { data: firmware_map }
+    }
+
+    ///
+    /// Like `from_file`, but `base_address` defaults to `flash_desc`'s start address, and the
+    /// resulting span is validated against `flash_desc`'s geometry the same way
+    /// `FlashPageList::from_firmware_data` validates a parsed image, rejecting a file that starts
+    /// before flash or would overflow its last page.
+    ///
+    pub fn from_file_with_flash_desc(
+        filename: &str,
+        flash_desc: &FlashDesc,
+        base_address: Option<u32>,
+    ) -> Result<BinFile, Error> {
+        let bytes = std::fs::read(filename)
+            .map_err(|e| Error::Error(format!("Failed to open file '{}': {}", filename, e)))?;
+
+        Self::from_bytes_with_flash_desc(&bytes, flash_desc, base_address)
+    }
+
+    /// See `from_file_with_flash_desc`
+    pub fn from_bytes_with_flash_desc(
+        bytes: &[u8],
+        flash_desc: &FlashDesc,
+        base_address: Option<u32>,
+    ) -> Result<BinFile, Error> {
+        let base_address = base_address.unwrap_or_else(|| flash_desc.get_address());
+
+        if base_address < flash_desc.get_address() {
+            return Err(Error::Error(format!(
+                "Adress {:#X} is out of range! Flash starts at {:#X}!",
+                base_address,
+                flash_desc.get_address()
+            )));
+        }
+
+        if let Some(last_byte_offset) = bytes.len().checked_sub(1) {
+            let last_address = base_address + last_byte_offset as u32;
+            let page_idx = (last_address - flash_desc.get_address()) / flash_desc.get_page_size();
+            let num_pages = flash_desc.get_num_pages();
+
+            if page_idx >= num_pages {
+                return Err(Error::Error(format!(
+                    "Page {} is out of range! Flash has only {} pages!",
+                    page_idx, num_pages
+                )));
+            }
+        }
+
+        Ok(Self::from_bytes(bytes, base_address))
+    }
+
+    pub fn get_data(&self) -> &FirmwareDataRaw {
+        &self.data
+    }
+
+    ///
+    /// Returns the lowest byte address contained in the binary image, or `None` if it is empty.
+    ///
+    pub fn min_address(&self) -> Option<u32> {
+        self.data.keys().min().copied()
+    }
+
+    ///
+    /// Returns the highest byte address contained in the binary image, or `None` if it is empty.
+    ///
+    pub fn max_address(&self) -> Option<u32> {
+        self.data.keys().max().copied()
+    }
+}
+
+impl FirmwareDataInterface for BinFile {
+    fn get_firmware_data(&self) -> Option<&FirmwareDataRaw> {
+        Some(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bin_file_places_bytes_at_load_address() {
+        let bin = BinFile::from_bytes(&[0xDE, 0xAD, 0xBE, 0xEF], 0x08000000);
+
+        assert_eq!(bin.min_address(), Some(0x08000000));
+        assert_eq!(bin.max_address(), Some(0x08000003));
+        assert_eq!(*bin.get_data().get(&0x08000001).unwrap(), 0xAD);
+    }
+
+    #[test]
+    fn bin_file_defaults_base_address_to_flash_start() {
+        let flash_desc = FlashDesc::new(0x08000000, 0x1000, 0x400);
+
+        let bin =
+            BinFile::from_bytes_with_flash_desc(&[0xDE, 0xAD, 0xBE, 0xEF], &flash_desc, None)
+                .unwrap();
+
+        assert_eq!(bin.min_address(), Some(0x08000000));
+    }
+
+    #[test]
+    fn bin_file_rejects_base_address_before_flash() {
+        let flash_desc = FlashDesc::new(0x08000000, 0x1000, 0x400);
+
+        let result =
+            BinFile::from_bytes_with_flash_desc(&[0xDE], &flash_desc, Some(0x07FFFFFF));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn bin_file_rejects_overflow_of_last_page() {
+        let flash_desc = FlashDesc::new(0x08000000, 0x1000, 0x400);
+
+        let bytes = vec![0u8; 0x1001];
+        let result = BinFile::from_bytes_with_flash_desc(&bytes, &flash_desc, None);
+
+        assert!(result.is_err());
+    }
+}
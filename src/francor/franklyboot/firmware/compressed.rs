@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io::Read;
+
+use crate::francor::franklyboot::Error;
+
+// Compression Codec --------------------------------------------------------------------------------
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const XZ_MAGIC: [u8; 6] = [0xFD, b'7', b'z', b'X', b'Z', 0x00];
+
+///
+/// Compression container detected around a firmware image, by magic bytes
+///
+/// Mirrors how the Linux kernel's firmware loader picks a decompressor for `zImage`/`uImage`
+/// blobs: sniff the container's magic, fall through to treating the input as a raw uncompressed
+/// image when nothing matches.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// Not a recognized compression container; treated as a raw binary image
+    None,
+
+    /// Zstandard frame (`0x28 0xB5 0x2F 0xFD` magic)
+    Zstd,
+
+    /// `.xz`/LZMA2 container (`0xFD 7zXZ 0x00` magic)
+    Xz,
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Codec::None => write!(f, "none"),
+            Codec::Zstd => write!(f, "zstd"),
+            Codec::Xz => write!(f, "xz"),
+        }
+    }
+}
+
+///
+/// Detects the compression container wrapping `bytes` from its leading magic number
+///
+pub fn detect(bytes: &[u8]) -> Codec {
+    if bytes.len() >= ZSTD_MAGIC.len() && bytes[0..ZSTD_MAGIC.len()] == ZSTD_MAGIC {
+        Codec::Zstd
+    } else if bytes.len() >= XZ_MAGIC.len() && bytes[0..XZ_MAGIC.len()] == XZ_MAGIC {
+        Codec::Xz
+    } else {
+        Codec::None
+    }
+}
+
+///
+/// Codec and size bookkeeping for a (possibly) decompressed firmware image
+///
+/// Kept around after `decompress` so callers like `Device::flash` can print the compression
+/// ratio alongside the existing "Firmware Data" line.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionInfo {
+    pub codec: Codec,
+    pub compressed_size: usize,
+    pub decompressed_size: usize,
+}
+
+///
+/// Transparently decompresses `bytes` if it is a recognized zstd or xz container
+///
+/// Falls through to returning `bytes` unchanged (as `Codec::None`) when no known magic is
+/// present, so callers can run every input - compressed or not - through this before handing it
+/// to `AppFirmware::append_firmware`.
+///
+pub fn decompress(bytes: &[u8]) -> Result<(Vec<u8>, CompressionInfo), Error> {
+    let codec = detect(bytes);
+
+    let decompressed = match codec {
+        Codec::None => bytes.to_vec(),
+        Codec::Zstd => zstd::stream::decode_all(bytes)
+            .map_err(|e| Error::Error(format!("Failed to decompress zstd image: {}", e)))?,
+        Codec::Xz => {
+            let mut out = Vec::new();
+            xz2::read::XzDecoder::new(bytes)
+                .read_to_end(&mut out)
+                .map_err(|e| Error::Error(format!("Failed to decompress xz image: {}", e)))?;
+            out
+        }
+    };
+
+    let info = CompressionInfo {
+        codec,
+        compressed_size: bytes.len(),
+        decompressed_size: decompressed.len(),
+    };
+
+    Ok((decompressed, info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_recognizes_zstd_magic() {
+        assert_eq!(detect(&[0x28, 0xB5, 0x2F, 0xFD, 0x00]), Codec::Zstd);
+    }
+
+    #[test]
+    fn detect_recognizes_xz_magic() {
+        assert_eq!(
+            detect(&[0xFD, b'7', b'z', b'X', b'Z', 0x00, 0x00]),
+            Codec::Xz
+        );
+    }
+
+    #[test]
+    fn detect_falls_back_to_none() {
+        assert_eq!(detect(&[0xDE, 0xAD, 0xBE, 0xEF]), Codec::None);
+    }
+
+    #[test]
+    fn decompress_passes_through_uncompressed_data() {
+        let (data, info) = decompress(&[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+
+        assert_eq!(data, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(info.codec, Codec::None);
+        assert_eq!(info.compressed_size, 4);
+        assert_eq!(info.decompressed_size, 4);
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use crate::francor::franklyboot::firmware::{FirmwareDataInterface, FirmwareDataRaw};
+
+// ELF File Representation -------------------------------------------------------------------------
+
+const ELF_MAGIC: [u8; 4] = [0x7F, b'E', b'L', b'F'];
+const PT_LOAD: u32 = 1;
+
+///
+/// ELF firmware image
+///
+/// Reads the `PT_LOAD` program headers of a 32 or 64 bit little-endian ELF image and places each
+/// segment's `p_paddr` bytes into the same byte-address map `HexFile` produces, so `AppFirmware`
+/// does not need to know which input format it was given. Parses the header fields directly
+/// instead of depending on a crate like `xmas-elf`, since this crate carries no dependency
+/// manifest to add one to; `PT_LOAD` is the only program header type FranklyBoot images need.
+///
+pub struct ElfFile {
+    data: FirmwareDataRaw,
+}
+
+impl ElfFile {
+    pub fn from_file(filename: &str) -> Result<ElfFile, String> {
+        Self::from_file_with_bss(filename, false)
+    }
+
+    ///
+    /// Like `from_file`, but with `include_bss` the `[p_filesz..p_memsz)` tail of every `PT_LOAD`
+    /// segment is zero-filled instead of left unmapped. Leave this `false` (the default `from_file`
+    /// uses) unless the target relies on the loader zeroing BSS, since an unmapped tail is instead
+    /// filled with `FLASH_DFT_VALUE` by `append_firmware`.
+    ///
+    pub fn from_file_with_bss(filename: &str, include_bss: bool) -> Result<ElfFile, String> {
+        let bytes = std::fs::read(filename)
+            .map_err(|e| format!("Failed to open file '{}': {}", filename, e))?;
+
+        Self::from_bytes_with_bss(&bytes, include_bss)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<ElfFile, String> {
+        Self::from_bytes_with_bss(bytes, false)
+    }
+
+    /// Like `from_bytes`, but see `from_file_with_bss` for what `include_bss` does
+    pub fn from_bytes_with_bss(bytes: &[u8], include_bss: bool) -> Result<ElfFile, String> {
+        Ok(ElfFile {
+            data: parse_elf_file(bytes, include_bss)?,
+        })
+    }
+
+    pub fn get_data(&self) -> &FirmwareDataRaw {
+        &self.data
+    }
+
+    ///
+    /// Returns the lowest byte address contained in the parsed ELF image, or `None` if it
+    /// contains no loadable data.
+    ///
+    pub fn min_address(&self) -> Option<u32> {
+        self.data.keys().min().copied()
+    }
+
+    ///
+    /// Returns the highest byte address contained in the parsed ELF image, or `None` if it
+    /// contains no loadable data.
+    ///
+    pub fn max_address(&self) -> Option<u32> {
+        self.data.keys().max().copied()
+    }
+}
+
+impl FirmwareDataInterface for ElfFile {
+    fn get_firmware_data(&self) -> Option<&FirmwareDataRaw> {
+        Some(&self.data)
+    }
+}
+
+/// Checks whether `bytes` starts with the ELF magic number
+pub fn is_elf_file(bytes: &[u8]) -> bool {
+    bytes.len() >= 4 && bytes[0..4] == ELF_MAGIC
+}
+
+fn parse_elf_file(bytes: &[u8], include_bss: bool) -> Result<FirmwareDataRaw, String> {
+    if !is_elf_file(bytes) {
+        return Err("Not an ELF file: missing 0x7F 'E' 'L' 'F' magic".to_string());
+    }
+
+    let is_64_bit = match bytes.get(4) {
+        Some(1) => false,
+        Some(2) => true,
+        _ => return Err("Unsupported ELF class (neither ELFCLASS32 nor ELFCLASS64)".to_string()),
+    };
+
+    if bytes.get(5) != Some(&1) {
+        return Err("Unsupported ELF byte order: only little-endian images are supported".to_string());
+    }
+
+    let mut firmware_map = HashMap::new();
+
+    if is_64_bit {
+        let e_phoff = read_u64(bytes, 0x20)? as usize;
+        let e_phentsize = read_u16(bytes, 0x36)? as usize;
+        let e_phnum = read_u16(bytes, 0x38)?;
+
+        for idx in 0..e_phnum {
+            let ph_off = e_phoff + idx as usize * e_phentsize;
+            let p_type = read_u32(bytes, ph_off)?;
+            if p_type != PT_LOAD {
+                continue;
+            }
+
+            let p_offset = read_u64(bytes, ph_off + 0x08)? as usize;
+            let p_paddr = read_u64(bytes, ph_off + 0x18)? as u32;
+            let p_filesz = read_u64(bytes, ph_off + 0x20)? as usize;
+            let p_memsz = read_u64(bytes, ph_off + 0x28)? as usize;
+
+            _copy_segment(
+                bytes,
+                p_offset,
+                p_paddr,
+                p_filesz,
+                p_memsz,
+                include_bss,
+                &mut firmware_map,
+            )?;
+        }
+    } else {
+        let e_phoff = read_u32(bytes, 0x1C)? as usize;
+        let e_phentsize = read_u16(bytes, 0x2A)? as usize;
+        let e_phnum = read_u16(bytes, 0x2C)?;
+
+        for idx in 0..e_phnum {
+            let ph_off = e_phoff + idx as usize * e_phentsize;
+            let p_type = read_u32(bytes, ph_off)?;
+            if p_type != PT_LOAD {
+                continue;
+            }
+
+            let p_offset = read_u32(bytes, ph_off + 0x04)? as usize;
+            let p_paddr = read_u32(bytes, ph_off + 0x0C)?;
+            let p_filesz = read_u32(bytes, ph_off + 0x10)? as usize;
+            let p_memsz = read_u32(bytes, ph_off + 0x14)? as usize;
+
+            _copy_segment(
+                bytes,
+                p_offset,
+                p_paddr,
+                p_filesz,
+                p_memsz,
+                include_bss,
+                &mut firmware_map,
+            )?;
+        }
+    }
+
+    if firmware_map.is_empty() {
+        return Err("ELF file contains no PT_LOAD segments with data".to_string());
+    }
+
+    Ok(firmware_map)
+}
+
+fn _copy_segment(
+    bytes: &[u8],
+    p_offset: usize,
+    p_paddr: u32,
+    p_filesz: usize,
+    p_memsz: usize,
+    include_bss: bool,
+    firmware_map: &mut FirmwareDataRaw,
+) -> Result<(), String> {
+    let segment = bytes
+        .get(p_offset..p_offset + p_filesz)
+        .ok_or_else(|| "ELF program header points outside of the file".to_string())?;
+
+    for (byte_idx, byte_value) in segment.iter().enumerate() {
+        firmware_map.insert(p_paddr + byte_idx as u32, *byte_value);
+    }
+
+    if include_bss {
+        for byte_idx in p_filesz..p_memsz {
+            firmware_map.insert(p_paddr + byte_idx as u32, 0x00);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, String> {
+    let slice = bytes
+        .get(offset..offset + 2)
+        .ok_or_else(|| "ELF header truncated".to_string())?;
+    Ok(u16::from_le_bytes([slice[0], slice[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    let slice = bytes
+        .get(offset..offset + 4)
+        .ok_or_else(|| "ELF header truncated".to_string())?;
+    Ok(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    let slice = bytes
+        .get(offset..offset + 8)
+        .ok_or_else(|| "ELF header truncated".to_string())?;
+    Ok(u64::from_le_bytes([
+        slice[0], slice[1], slice[2], slice[3], slice[4], slice[5], slice[6], slice[7],
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_elf32(phdrs: &[(u32, u32, u32, &[u8])]) -> Vec<u8> {
+        build_elf32_with_memsz(
+            &phdrs
+                .iter()
+                .map(|&(p_type, p_paddr, _p_filesz, segment)| {
+                    (p_type, p_paddr, segment, segment.len() as u32)
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn build_elf32_with_memsz(phdrs: &[(u32, u32, &[u8], u32)]) -> Vec<u8> {
+        // Minimal ELF32 header followed by one program header per entry and the segment data
+        // placed directly after its program header table slot.
+        let ehsize = 0x34usize;
+        let phentsize = 0x20usize;
+        let phoff = ehsize;
+        let mut data_offset = phoff + phdrs.len() * phentsize;
+
+        let mut bytes = vec![0u8; data_offset];
+        bytes[0..4].copy_from_slice(&ELF_MAGIC);
+        bytes[4] = 1; // ELFCLASS32
+        bytes[5] = 1; // little-endian
+        bytes[0x1C..0x20].copy_from_slice(&(phoff as u32).to_le_bytes());
+        bytes[0x2A..0x2C].copy_from_slice(&(phentsize as u16).to_le_bytes());
+        bytes[0x2C..0x2E].copy_from_slice(&(phdrs.len() as u16).to_le_bytes());
+
+        for (idx, (p_type, p_paddr, segment, p_memsz)) in phdrs.iter().enumerate() {
+            let ph_off = phoff + idx * phentsize;
+            bytes[ph_off..ph_off + 4].copy_from_slice(&p_type.to_le_bytes());
+            bytes[ph_off + 4..ph_off + 8].copy_from_slice(&(data_offset as u32).to_le_bytes());
+            bytes[ph_off + 0x0C..ph_off + 0x10].copy_from_slice(&p_paddr.to_le_bytes());
+            bytes[ph_off + 0x10..ph_off + 0x14].copy_from_slice(&(segment.len() as u32).to_le_bytes());
+            bytes[ph_off + 0x14..ph_off + 0x18].copy_from_slice(&p_memsz.to_le_bytes());
+
+            bytes.extend_from_slice(segment);
+            data_offset += segment.len();
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn elf_file_loads_pt_load_segment() {
+        let bytes = build_elf32(&[(PT_LOAD, 0x08000000, 4, &[0xDE, 0xAD, 0xBE, 0xEF])]);
+
+        let elf = ElfFile::from_bytes(&bytes).unwrap();
+
+        assert_eq!(elf.min_address(), Some(0x08000000));
+        assert_eq!(elf.max_address(), Some(0x08000003));
+        assert_eq!(*elf.get_data().get(&0x08000000).unwrap(), 0xDE);
+        assert_eq!(*elf.get_data().get(&0x08000003).unwrap(), 0xEF);
+    }
+
+    #[test]
+    fn elf_file_skips_non_load_segments() {
+        let bytes = build_elf32(&[(0x6474e551 /* PT_GNU_STACK */, 0, 4, &[1, 2, 3, 4])]);
+
+        let result = ElfFile::from_bytes(&bytes);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn elf_file_rejects_non_elf_input() {
+        let result = ElfFile::from_bytes(&[0, 1, 2, 3]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn elf_file_leaves_bss_unmapped_by_default() {
+        let bytes =
+            build_elf32_with_memsz(&[(PT_LOAD, 0x08000000, &[0xDE, 0xAD, 0xBE, 0xEF], 8)]);
+
+        let elf = ElfFile::from_bytes(&bytes).unwrap();
+
+        assert_eq!(elf.max_address(), Some(0x08000003));
+        assert!(elf.get_data().get(&0x08000004).is_none());
+    }
+
+    #[test]
+    fn elf_file_zero_fills_bss_when_requested() {
+        let bytes =
+            build_elf32_with_memsz(&[(PT_LOAD, 0x08000000, &[0xDE, 0xAD, 0xBE, 0xEF], 8)]);
+
+        let elf = ElfFile::from_bytes_with_bss(&bytes, true).unwrap();
+
+        assert_eq!(elf.max_address(), Some(0x08000007));
+        assert_eq!(*elf.get_data().get(&0x08000004).unwrap(), 0x00);
+        assert_eq!(*elf.get_data().get(&0x08000007).unwrap(), 0x00);
+    }
+}
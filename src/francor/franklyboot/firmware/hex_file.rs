@@ -4,6 +4,9 @@ use super::{FirmwareDataInterface, FirmwareDataRaw};
 
 const HEX_LINE_MIN_CHARS: usize = 10;
 
+/// Data bytes per record `HexFile::to_hex_string` emits when the caller passes `0`
+const DEFAULT_BYTES_PER_RECORD: usize = 16;
+
 // Hex File Record Type ---------------------------------------------------------------------------
 
 #[derive(Debug, PartialEq, Clone)]
@@ -46,6 +49,28 @@ pub enum ErrorType {
     InvalidChecksum,
 }
 
+impl std::fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ErrorType::NoValidData => write!(f, "hex file does not contain valid data"),
+            ErrorType::ByteCountParseError => write!(f, "failed to parse byte count"),
+            ErrorType::OffsetParseError => write!(f, "failed to parse offset"),
+            ErrorType::RecordTypeParseError => write!(f, "failed to parse record type"),
+            ErrorType::DataParseError => write!(f, "failed to parse data bytes"),
+            ErrorType::ChecksumParseError => write!(f, "failed to parse checksum"),
+            ErrorType::InvalidEntryLength => write!(f, "entry is shorter than the minimum record length"),
+            ErrorType::InvalidByteCount => write!(f, "declared byte count does not match the entry length"),
+            ErrorType::InvalidRecordType => write!(f, "unknown record type"),
+            ErrorType::InvalidChecksum => write!(f, "checksum does not match the entry's contents"),
+        }
+    }
+}
+
+/// A leaf error: `ErrorType` never wraps another error, so the default `source()` (`None`) is
+/// correct as-is. Implementing the trait lets `Error::HexParse` chain to it via its own
+/// `source()`.
+impl std::error::Error for ErrorType {}
+
 // Hex File Entry ----------------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
@@ -123,25 +148,54 @@ impl Entry {
             checksum,
         })
     }
+
+    /// Formats one Intel HEX record line (including the leading `:` and trailing newline)
+    fn format_record(offset: u16, record_type: u8, data: &[u8]) -> String {
+        let mut checksum_calc = 0u16;
+        checksum_calc += data.len() as u16;
+        checksum_calc += (offset >> 8) as u16;
+        checksum_calc += (offset & 0xFF) as u16;
+        checksum_calc += record_type as u16;
+        for byte in data {
+            checksum_calc += *byte as u16;
+        }
+        let checksum = ((!checksum_calc + 1) & 0x00FF) as u8;
+
+        let mut line = format!(":{:02X}{:04X}{:02X}", data.len(), offset, record_type);
+        for byte in data {
+            line.push_str(&format!("{:02X}", byte));
+        }
+        line.push_str(&format!("{:02X}\n", checksum));
+        line
+    }
+
+    /// Formats an `ExtendedLinearAddress` (0x04) record carrying `high_word`, the upper 16 bits
+    /// of the addresses that follow
+    fn extended_linear_address_record(high_word: u32) -> String {
+        let data = [((high_word >> 8) & 0xFF) as u8, (high_word & 0xFF) as u8];
+        Self::format_record(0, 0x04, &data)
+    }
+
+    /// Formats a `Data` (0x00) record for `data` starting at `offset` within the current segment
+    fn data_record(offset: u16, data: &[u8]) -> String {
+        Self::format_record(offset, 0x00, data)
+    }
 }
 
 // Hex File Representation ------------------------------------------------------------------------
 
 pub struct HexFile {
     data: FirmwareDataRaw,
+    entry_point: Option<u32>,
 }
 
 impl HexFile {
-    pub fn from_file(filename: &str) -> Result<HexFile, String> {
-        match std::fs::read_to_string(filename) {
-            Ok(f) => return Self::from_string(&f.as_str()),
-            Err(e) => {
-                return Err(format!("Failed to open file '{}': {}", filename, e));
-            }
-        };
+    pub fn from_file(filename: &str) -> Result<HexFile, crate::francor::franklyboot::Error> {
+        let hex_data = std::fs::read_to_string(filename)?;
+        Self::from_string(&hex_data)
     }
 
-    pub fn from_string(hex_data: &str) -> Result<HexFile, String> {
+    pub fn from_string(hex_data: &str) -> Result<HexFile, crate::francor::franklyboot::Error> {
         Self::parse_hex_file(hex_data)
     }
 
@@ -149,22 +203,111 @@ impl HexFile {
         &self.data
     }
 
-    fn parse_hex_file(hex_data: &str) -> Result<HexFile, String> {
+    ///
+    /// Returns the lowest byte address contained in the parsed hex file, or `None` if the file
+    /// contains no data.
+    ///
+    pub fn min_address(&self) -> Option<u32> {
+        self.data.keys().min().copied()
+    }
+
+    ///
+    /// Returns the highest byte address contained in the parsed hex file, or `None` if the file
+    /// contains no data.
+    ///
+    pub fn max_address(&self) -> Option<u32> {
+        self.data.keys().max().copied()
+    }
+
+    ///
+    /// Returns the 32-bit execution entry address captured from a `StartSegmentAddress` (0x03) or
+    /// `StartLinearAddress` (0x05) record, or `None` if the file contained neither.
+    ///
+    pub fn entry_point(&self) -> Option<u32> {
+        self.entry_point
+    }
+
+    ///
+    /// Re-emits this image as Intel HEX text, with at most `bytes_per_record` data bytes per
+    /// `Data` record (`0` falls back to `DEFAULT_BYTES_PER_RECORD`)
+    ///
+    /// Contiguous runs of addresses are each split into records that never straddle a 64 KiB
+    /// boundary, with an `ExtendedLinearAddress` record emitted whenever the upper 16 bits of the
+    /// address change, followed by the `:00000001FF` EOF record.
+    ///
+    pub fn to_hex_string(&self, bytes_per_record: u8) -> String {
+        let bytes_per_record = if bytes_per_record == 0 {
+            DEFAULT_BYTES_PER_RECORD
+        } else {
+            bytes_per_record
+        } as usize;
+
+        let mut addresses: Vec<u32> = self.data.keys().copied().collect();
+        addresses.sort_unstable();
+
+        let mut output = String::new();
+        let mut address_extended: Option<u32> = None;
+
+        let mut idx = 0;
+        while idx < addresses.len() {
+            let run_start = idx;
+            while idx + 1 < addresses.len() && addresses[idx + 1] == addresses[idx] + 1 {
+                idx += 1;
+            }
+            let run = &addresses[run_start..=idx];
+            idx += 1;
+
+            let mut chunk_start = 0;
+            while chunk_start < run.len() {
+                let base_address = run[chunk_start];
+                let high_word = base_address >> 16;
+
+                // A record may never straddle a 64 KiB boundary, so it ends there even if that
+                // cuts it shorter than `bytes_per_record`.
+                let room_in_segment = (0x10000 - (base_address & 0xFFFF)) as usize;
+                let chunk_len = (run.len() - chunk_start)
+                    .min(bytes_per_record)
+                    .min(room_in_segment);
+
+                if address_extended != Some(high_word) {
+                    output.push_str(&Entry::extended_linear_address_record(high_word));
+                    address_extended = Some(high_word);
+                }
+
+                let chunk = &run[chunk_start..chunk_start + chunk_len];
+                let data: Vec<u8> = chunk.iter().map(|addr| self.data[addr]).collect();
+                output.push_str(&Entry::data_record(base_address as u16, &data));
+
+                chunk_start += chunk_len;
+            }
+        }
+
+        output.push_str(":00000001FF\n");
+        output
+    }
+
+    ///
+    /// Like `to_hex_string`, but writes the result to `filename`
+    ///
+    pub fn to_file(
+        &self,
+        filename: &str,
+        bytes_per_record: u8,
+    ) -> Result<(), crate::francor::franklyboot::Error> {
+        std::fs::write(filename, self.to_hex_string(bytes_per_record))?;
+        Ok(())
+    }
+
+    fn parse_hex_file(hex_data: &str) -> Result<HexFile, crate::francor::franklyboot::Error> {
         let mut entries = Vec::new();
 
         // Pares every line in hex file
         let mut line_idx = 0;
         for line in hex_data.lines() {
             if line.len() > 0 && line.chars().nth(0).unwrap() == ':' {
-                let entry = match Entry::from_hex_line(&line[1..]) {
-                    Ok(e) => e,
-                    Err(e) => {
-                        return Err(format!(
-                            "Hex file parse error: {:?} in line {}",
-                            e, line_idx
-                        ));
-                    }
-                };
+                let entry = Entry::from_hex_line(&line[1..]).map_err(|kind| {
+                    crate::francor::franklyboot::Error::HexParse { line: line_idx, kind }
+                })?;
                 entries.push(entry);
             }
 
@@ -173,46 +316,68 @@ impl HexFile {
 
         // Convert to map
         let mut firmware_map = FirmwareDataRaw::new();
+        let mut entry_point = None;
         let mut address_extended = 0 as u32;
         for entry in &entries {
             match entry.record_type {
                 RecordType::ExtendedLinearAddress => {
                     address_extended = (entry.data[0] as u32) << 24 | (entry.data[1] as u32) << 16;
                 }
+                RecordType::ExtendedSegmentAddress => {
+                    // x86 segment:offset addressing - the segment is added to, not OR-ed with,
+                    // the 16-bit offset below, since the two can overlap in their low bits.
+                    let segment = (entry.data[0] as u32) << 8 | entry.data[1] as u32;
+                    address_extended = segment << 4;
+                }
                 RecordType::Data => {
-                    let address = address_extended | entry.offset as u32;
+                    let address = address_extended + entry.offset as u32;
                     for i in 0..entry.byte_count {
                         firmware_map.insert(address + i as u32, entry.data[i as usize]);
                     }
                 }
+                RecordType::StartSegmentAddress => {
+                    let cs = (entry.data[0] as u32) << 8 | entry.data[1] as u32;
+                    let ip = (entry.data[2] as u32) << 8 | entry.data[3] as u32;
+                    entry_point = Some((cs << 4) + ip);
+                }
+                RecordType::StartLinearAddress => {
+                    entry_point = Some(
+                        (entry.data[0] as u32) << 24
+                            | (entry.data[1] as u32) << 16
+                            | (entry.data[2] as u32) << 8
+                            | entry.data[3] as u32,
+                    );
+                }
                 RecordType::EndOfFile => {
                     break;
                 }
-                _ => {}
             }
         }
 
         if firmware_map.len() == 0 {
-            return Err(format!("Hex file does not contain valid data!"));
+            return Err(crate::francor::franklyboot::Error::Error(
+                "Hex file does not contain valid data!".to_string(),
+            ));
         } else {
-            return Ok(HexFile { data: firmware_map });
+            return Ok(HexFile {
+                data: firmware_map,
+                entry_point,
+            });
         }
     }
 }
 
-pub fn parse_hex_file(hex_file: &str) -> Result<HashMap<u32, u8>, ErrorType> {
+pub fn parse_hex_file(
+    hex_file: &str,
+) -> Result<HashMap<u32, u8>, crate::francor::franklyboot::Error> {
     let mut entries = Vec::new();
 
     let mut line_idx = 0;
     for line in hex_file.lines() {
         if line.len() > 0 && line.chars().nth(0).unwrap() == ':' {
-            let entry = match Entry::from_hex_line(&line[1..]) {
-                Ok(e) => e,
-                Err(e) => {
-                    eprintln!("Hex file parse error in line {}", line_idx);
-                    return Err(e);
-                }
-            };
+            let entry = Entry::from_hex_line(&line[1..]).map_err(|kind| {
+                crate::francor::franklyboot::Error::HexParse { line: line_idx, kind }
+            })?;
             entries.push(entry);
         }
 
@@ -227,8 +392,12 @@ pub fn parse_hex_file(hex_file: &str) -> Result<HashMap<u32, u8>, ErrorType> {
             RecordType::ExtendedLinearAddress => {
                 address_extended = (entry.data[0] as u32) << 24 | (entry.data[1] as u32) << 16;
             }
+            RecordType::ExtendedSegmentAddress => {
+                let segment = (entry.data[0] as u32) << 8 | entry.data[1] as u32;
+                address_extended = segment << 4;
+            }
             RecordType::Data => {
-                let address = address_extended | entry.offset as u32;
+                let address = address_extended + entry.offset as u32;
                 for i in 0..entry.byte_count {
                     firmware_map.insert(address + i as u32, entry.data[i as usize]);
                 }
@@ -236,12 +405,12 @@ pub fn parse_hex_file(hex_file: &str) -> Result<HashMap<u32, u8>, ErrorType> {
             RecordType::EndOfFile => {
                 break;
             }
-            _ => {}
+            RecordType::StartSegmentAddress | RecordType::StartLinearAddress => {}
         }
     }
 
     if firmware_map.len() == 0 {
-        return Err(ErrorType::NoValidData);
+        return Err(ErrorType::NoValidData.into());
     }
 
     Ok(firmware_map)
@@ -309,6 +478,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hex_file_min_max_address() {
+        let hex_data = ":020000040800F2\r\n\
+             :102000000000012009230008D1220008D522000881\r\n\
+             :10201000D9220008DD220008E122000800000000AB\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.min_address(), Some(0x8002000));
+        assert_eq!(hex_file.max_address(), Some(0x800201F));
+    }
+
     #[test]
     fn entry_from_hex_line_too_short() {
         let line = "00";
@@ -448,6 +630,155 @@ mod tests {
         assert_eq!(RecordType::from_byte(0x06), None);
     }
 
+    #[test]
+    fn hex_file_extended_segment_address_is_added_to_the_offset() {
+        // Segment 0x0800 -> base 0x8000, offset 0x0010 -> address 0x8010 (base + offset, not OR).
+        let hex_data = ":020000020800F4\r\n\
+             :01001000AA45\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.get_data()[&0x8010], 0xAA);
+    }
+
+    #[test]
+    fn hex_file_extended_linear_address_resets_a_prior_segment_base() {
+        let hex_data = ":020000020800F4\r\n\
+             :020000040800F2\r\n\
+             :01001000AA45\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.get_data()[&0x08000010], 0xAA);
+    }
+
+    #[test]
+    fn hex_file_captures_start_linear_address_as_the_entry_point() {
+        let hex_data = ":0400000508000101ED\r\n\
+             :1010000000000000000000000000000000000000E0\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.entry_point(), Some(0x08000101));
+    }
+
+    #[test]
+    fn hex_file_captures_start_segment_address_as_the_entry_point() {
+        // CS:IP = 0x0800:0x0100 -> (0x0800 << 4) + 0x0100 = 0x8100
+        let hex_data = ":0400000308000100F0\r\n\
+             :1010000000000000000000000000000000000000E0\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.entry_point(), Some(0x8100));
+    }
+
+    #[test]
+    fn hex_file_entry_point_defaults_to_none() {
+        let hex_data = ":020000040800F2\r\n\
+             :01001000AA45\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+
+        assert_eq!(hex_file.entry_point(), None);
+    }
+
+    #[test]
+    fn hex_file_to_hex_string_round_trips_through_from_string() {
+        let hex_data = ":020000040800F2\r\n\
+             :102000000000012009230008D1220008D522000881\r\n\
+             :10201000D9220008DD220008E122000800000000AB\r\n\
+             :00000001FF\r\n";
+
+        let hex_file = HexFile::from_string(hex_data).unwrap();
+        let round_tripped = HexFile::from_string(&hex_file.to_hex_string(16)).unwrap();
+
+        assert_eq!(round_tripped.get_data(), hex_file.get_data());
+    }
+
+    #[test]
+    fn hex_file_to_hex_string_splits_on_bytes_per_record() {
+        let mut data = FirmwareDataRaw::new();
+        for i in 0..20u32 {
+            data.insert(i, i as u8);
+        }
+        let hex_file = HexFile {
+            data,
+            entry_point: None,
+        };
+
+        let hex_string = hex_file.to_hex_string(8);
+        let data_records: Vec<&str> = hex_string
+            .lines()
+            .filter(|line| &line[7..9] == "00")
+            .collect();
+
+        // 20 bytes at 8 per record -> three records (8 + 8 + 4).
+        assert_eq!(data_records.len(), 3);
+        assert_eq!(&data_records[0][1..3], "08");
+        assert_eq!(&data_records[2][1..3], "04");
+    }
+
+    #[test]
+    fn hex_file_to_hex_string_never_straddles_a_64kib_boundary() {
+        let mut data = FirmwareDataRaw::new();
+        data.insert(0x0000_FFFE, 0xAA);
+        data.insert(0x0000_FFFF, 0xBB);
+        data.insert(0x0001_0000, 0xCC);
+        let hex_file = HexFile {
+            data,
+            entry_point: None,
+        };
+
+        let round_tripped = HexFile::from_string(&hex_file.to_hex_string(16)).unwrap();
+        assert_eq!(round_tripped.get_data()[&0x0000_FFFE], 0xAA);
+        assert_eq!(round_tripped.get_data()[&0x0000_FFFF], 0xBB);
+        assert_eq!(round_tripped.get_data()[&0x0001_0000], 0xCC);
+    }
+
+    #[test]
+    fn hex_file_to_hex_string_emits_extended_linear_address_on_segment_change() {
+        let mut data = FirmwareDataRaw::new();
+        data.insert(0x0000_0000, 0x11);
+        data.insert(0x0001_0000, 0x22);
+        let hex_file = HexFile {
+            data,
+            entry_point: None,
+        };
+
+        let hex_string = hex_file.to_hex_string(16);
+        let extended_records = hex_string
+            .lines()
+            .filter(|line| line.len() >= 9 && &line[7..9] == "04")
+            .count();
+
+        assert_eq!(extended_records, 2);
+    }
+
+    #[test]
+    fn hex_file_to_file_writes_a_loadable_hex_file() {
+        let mut data = FirmwareDataRaw::new();
+        data.insert(0x0800_0000, 0x42);
+        let hex_file = HexFile {
+            data,
+            entry_point: None,
+        };
+
+        let path = std::env::temp_dir().join("franklyboot_hex_file_to_file_test.hex");
+        let path_str = path.to_str().unwrap();
+
+        hex_file.to_file(path_str, 16).unwrap();
+        let round_tripped = HexFile::from_file(path_str).unwrap();
+
+        assert_eq!(round_tripped.get_data()[&0x0800_0000], 0x42);
+        std::fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn hex_file_firmware_data_interface_trait() {
         let hex_data = ":020000040800F2\r\n\
@@ -0,0 +1,211 @@
+use crate::francor::franklyboot::firmware::{
+    bin_file::BinFile, compressed, elf_file::ElfFile, hex_file::HexFile, srec_file::SrecFile,
+    CompressionInfo, FirmwareDataInterface, FirmwareDataRaw,
+};
+
+// Firmware Image Format ----------------------------------------------------------------------------
+
+///
+/// On-disk firmware image format
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FirmwareFormat {
+    Hex,
+    Srec,
+    Elf,
+    Bin,
+}
+
+impl FirmwareFormat {
+    ///
+    /// Parses a `--format` flag value ("hex"/"srec"/"elf"/"bin"), returning `None` for anything
+    /// else.
+    ///
+    pub fn from_str(name: &str) -> Option<FirmwareFormat> {
+        match name {
+            "hex" => Some(FirmwareFormat::Hex),
+            "srec" => Some(FirmwareFormat::Srec),
+            "elf" => Some(FirmwareFormat::Elf),
+            "bin" => Some(FirmwareFormat::Bin),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Detects the format of `filename` from its extension, falling back to sniffing the ELF
+    /// magic number and finally to Intel HEX, since a `.bin` has no reliable magic of its own.
+    ///
+    pub fn detect(filename: &str, bytes: &[u8]) -> FirmwareFormat {
+        match std::path::Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+        {
+            Some("hex") => return FirmwareFormat::Hex,
+            Some("srec") | Some("s19") | Some("s28") | Some("s37") => return FirmwareFormat::Srec,
+            Some("elf") => return FirmwareFormat::Elf,
+            Some("bin") => return FirmwareFormat::Bin,
+            _ => {}
+        }
+
+        if super::elf_file::is_elf_file(bytes) {
+            FirmwareFormat::Elf
+        } else {
+            FirmwareFormat::Hex
+        }
+    }
+}
+
+// Firmware Image ------------------------------------------------------------------------------------
+
+///
+/// One of the parsed, on-disk firmware formats `FirmwareImage` can hold
+///
+enum FirmwareImageData {
+    Hex(HexFile),
+    Srec(SrecFile),
+    Elf(ElfFile),
+    Bin(BinFile),
+}
+
+///
+/// Parsed firmware image, regardless of its on-disk format
+///
+/// Produces the same byte-address map `HexFile` always has, so `AppFirmware::append_firmware`
+/// (and everything downstream of it) does not need to know whether the input was Intel HEX, ELF,
+/// or a raw binary. If the file was a zstd/xz container, `load` transparently decompresses it
+/// first, and `compression_info` reports what was done.
+///
+pub struct FirmwareImage {
+    data: FirmwareImageData,
+    compression: Option<CompressionInfo>,
+}
+
+impl FirmwareImage {
+    ///
+    /// Loads `filename` as `format`, auto-detecting it from the extension/ELF magic if `format`
+    /// is `None`. `load_address` is required for the `Bin` format and ignored otherwise.
+    ///
+    /// If `filename`'s contents are a zstd or xz container, they are transparently decompressed
+    /// before format detection and parsing run, so a compressed `.bin`/`.hex`/`.elf` loads the
+    /// same way as its uncompressed counterpart.
+    ///
+    pub fn load(
+        filename: &str,
+        format: Option<FirmwareFormat>,
+        load_address: Option<u32>,
+    ) -> Result<FirmwareImage, String> {
+        let raw_bytes = std::fs::read(filename)
+            .map_err(|e| format!("Failed to open file '{}': {}", filename, e))?;
+
+        let (bytes, compression) = compressed::decompress(&raw_bytes).map_err(|e| e.to_string())?;
+
+        let format = format.unwrap_or_else(|| FirmwareFormat::detect(filename, &bytes));
+
+        let data = match format {
+            FirmwareFormat::Hex => {
+                let hex_data = String::from_utf8(bytes)
+                    .map_err(|e| format!("File '{}' is not valid UTF-8 HEX data: {}", filename, e))?;
+                FirmwareImageData::Hex(HexFile::from_string(&hex_data)?)
+            }
+            FirmwareFormat::Srec => {
+                let srec_data = String::from_utf8(bytes)
+                    .map_err(|e| format!("File '{}' is not valid UTF-8 SREC data: {}", filename, e))?;
+                FirmwareImageData::Srec(SrecFile::from_string(&srec_data)?)
+            }
+            FirmwareFormat::Elf => FirmwareImageData::Elf(ElfFile::from_bytes(&bytes)?),
+            FirmwareFormat::Bin => {
+                let load_address = load_address
+                    .ok_or_else(|| "--load-address is required for the \"bin\" format".to_string())?;
+                FirmwareImageData::Bin(BinFile::from_bytes(&bytes, load_address))
+            }
+        };
+
+        let compression = if compression.codec == compressed::Codec::None {
+            None
+        } else {
+            Some(compression)
+        };
+
+        Ok(FirmwareImage { data, compression })
+    }
+
+    ///
+    /// Returns the lowest byte address contained in the image, or `None` if it contains no data.
+    ///
+    pub fn min_address(&self) -> Option<u32> {
+        match &self.data {
+            FirmwareImageData::Hex(image) => image.min_address(),
+            FirmwareImageData::Srec(image) => image.min_address(),
+            FirmwareImageData::Elf(image) => image.min_address(),
+            FirmwareImageData::Bin(image) => image.min_address(),
+        }
+    }
+
+    ///
+    /// Returns the highest byte address contained in the image, or `None` if it contains no data.
+    ///
+    pub fn max_address(&self) -> Option<u32> {
+        match &self.data {
+            FirmwareImageData::Hex(image) => image.max_address(),
+            FirmwareImageData::Srec(image) => image.max_address(),
+            FirmwareImageData::Elf(image) => image.max_address(),
+            FirmwareImageData::Bin(image) => image.max_address(),
+        }
+    }
+}
+
+impl FirmwareDataInterface for FirmwareImage {
+    fn get_firmware_data(&self) -> Option<&FirmwareDataRaw> {
+        match &self.data {
+            FirmwareImageData::Hex(image) => image.get_firmware_data(),
+            FirmwareImageData::Srec(image) => image.get_firmware_data(),
+            FirmwareImageData::Elf(image) => image.get_firmware_data(),
+            FirmwareImageData::Bin(image) => image.get_firmware_data(),
+        }
+    }
+
+    fn compression_info(&self) -> Option<CompressionInfo> {
+        self.compression
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firmware_format_from_str() {
+        assert_eq!(FirmwareFormat::from_str("hex"), Some(FirmwareFormat::Hex));
+        assert_eq!(FirmwareFormat::from_str("srec"), Some(FirmwareFormat::Srec));
+        assert_eq!(FirmwareFormat::from_str("elf"), Some(FirmwareFormat::Elf));
+        assert_eq!(FirmwareFormat::from_str("bin"), Some(FirmwareFormat::Bin));
+        assert_eq!(FirmwareFormat::from_str("garbage"), None);
+    }
+
+    #[test]
+    fn firmware_format_detect_recognizes_srec_extensions() {
+        assert_eq!(FirmwareFormat::detect("app.srec", &[]), FirmwareFormat::Srec);
+        assert_eq!(FirmwareFormat::detect("app.s19", &[]), FirmwareFormat::Srec);
+    }
+
+    #[test]
+    fn firmware_format_detect_prefers_extension() {
+        assert_eq!(
+            FirmwareFormat::detect("app.bin", &[0x7F, b'E', b'L', b'F']),
+            FirmwareFormat::Bin
+        );
+    }
+
+    #[test]
+    fn firmware_format_detect_sniffs_elf_magic() {
+        assert_eq!(
+            FirmwareFormat::detect("app.out", &[0x7F, b'E', b'L', b'F']),
+            FirmwareFormat::Elf
+        );
+    }
+
+    #[test]
+    fn firmware_format_detect_falls_back_to_hex() {
+        assert_eq!(FirmwareFormat::detect("app.out", &[b':', b'0']), FirmwareFormat::Hex);
+    }
+}
@@ -1,13 +1,21 @@
+pub mod bin_file;
+pub mod compressed;
+pub mod elf_file;
 pub mod hex_file;
+pub mod image;
+pub mod segment;
+pub mod srec_file;
 
 mod flash_page;
 pub use flash_page::FlashPage;
+pub use compressed::CompressionInfo;
+pub use segment::{Segment, SegmentPage, SegmentedFirmware};
 
 use crc::{Crc, CRC_32_ISO_HDLC};
 use std::collections::HashMap;
 const CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
 
-use crate::francor::franklyboot::Error;
+use crate::francor::franklyboot::{device::flash::FlashSection, Error};
 
 // Firmware Data Trait ----------------------------------------------------------------------------
 
@@ -15,6 +23,25 @@ pub type FirmwareDataRaw = HashMap<u32, u8>;
 
 pub trait FirmwareDataInterface {
     fn get_firmware_data(&self) -> Option<&FirmwareDataRaw>;
+
+    /// Returns the lowest byte address contained in the image, or `None` if it contains no data.
+    fn min_address(&self) -> Option<u32> {
+        self.get_firmware_data()?.keys().min().copied()
+    }
+
+    /// Returns the highest byte address contained in the image, or `None` if it contains no data.
+    fn max_address(&self) -> Option<u32> {
+        self.get_firmware_data()?.keys().max().copied()
+    }
+
+    /// Codec and size info if this image was transparently decompressed, `None` otherwise
+    ///
+    /// Only `FirmwareImage` (loaded straight from a file that may be zstd/xz-compressed)
+    /// overrides this; an image built in memory from already-decompressed data has nothing to
+    /// report.
+    fn compression_info(&self) -> Option<CompressionInfo> {
+        None
+    }
 }
 
 // Firmware ---------------------------------------------------------------------------------------
@@ -40,19 +67,43 @@ pub struct AppFirmware {
     /// Vector containing all pages of the firmware
     page_lst: Vec<FlashPage>,
 
+    /// Byte value unmapped bytes (gaps inside a page, or whole pages never touched) are filled
+    /// with before CRCs are computed, matching what the flash reads back as once erased
+    erase_value: u8,
+
     // CRC32 value of the complete firmware
     crc: u32,
 }
 
 impl AppFirmware {
     ///
-    /// Create new empty firmware object
+    /// Create new empty firmware object, assuming the flash erases to `FLASH_DFT_VALUE` (`0xFF`)
     ///
     pub fn new(
         flash_start_address: u32,
         flash_app_page_id: u32,
         flash_page_size: u32,
         flash_num_pages: u32,
+    ) -> Self {
+        Self::new_with_erase_value(
+            flash_start_address,
+            flash_app_page_id,
+            flash_page_size,
+            flash_num_pages,
+            FLASH_DFT_VALUE,
+        )
+    }
+
+    ///
+    /// Like `new`, but for a flash whose erased state reads back as `erase_value` instead of
+    /// `0xFF`
+    ///
+    pub fn new_with_erase_value(
+        flash_start_address: u32,
+        flash_app_page_id: u32,
+        flash_page_size: u32,
+        flash_num_pages: u32,
+        erase_value: u8,
     ) -> Self {
         AppFirmware {
             flash_start_address: flash_start_address,
@@ -60,10 +111,38 @@ impl AppFirmware {
             flash_page_size: flash_page_size,
             flash_num_pages: flash_num_pages,
             page_lst: Vec::new(),
+            erase_value: erase_value,
             crc: 0,
         }
     }
 
+    ///
+    /// Create a new empty firmware object covering exactly the given flash section
+    ///
+    /// Lets a caller work directly off `FlashDesc::get_section` instead of pulling the section's
+    /// address/page size/page count back apart by hand, which is what makes it possible for
+    /// `Device` to flash a chip whose sections (e.g. "Bootloader", "Application") have different
+    /// sizes without `AppFirmware` itself knowing anything about sector geometry. Also carries
+    /// over the section's erase value (see `new_with_erase_value`).
+    ///
+    pub fn from_section(section: &FlashSection) -> Self {
+        let flash_app_page_id = section.get_flash_page_id();
+        let flash_num_pages = flash_app_page_id + section.get_size() / section.get_page_size();
+
+        // Page ids are counted from the flash start, so step back from the section's own address
+        // by the pages that precede it
+        let flash_start_address =
+            section.get_address() - flash_app_page_id * section.get_page_size();
+
+        AppFirmware::new_with_erase_value(
+            flash_start_address,
+            flash_app_page_id,
+            section.get_page_size(),
+            flash_num_pages,
+            section.get_erase_value(),
+        )
+    }
+
     ///
     /// Append firmware data to the firmware object
     ///
@@ -94,7 +173,7 @@ impl AppFirmware {
                     self.page_lst.push(FlashPage::new(
                         page_id,
                         self.flash_start_address + page_id * self.flash_page_size,
-                        vec![FLASH_DFT_VALUE; self.flash_page_size as usize],
+                        vec![self.erase_value; self.flash_page_size as usize],
                     ));
 
                     self._get_page_mut(page_id).unwrap()
@@ -119,6 +198,111 @@ impl AppFirmware {
         Ok(())
     }
 
+    ///
+    /// Reads a raw binary firmware blob from `reader`, transparently decompressing it if it is a
+    /// zstd or xz container, and appends the decompressed bytes starting at `load_address` through
+    /// the same `append_firmware` page-building and CRC logic every other loader uses
+    ///
+    /// Large images sent over a slow serial/CAN link spend most of their transfer time on the wire
+    /// rather than in this call, so storing the artifact compressed (the same magic-byte detection
+    /// `compressed::decompress` already applies to on-disk images) shrinks what has to move
+    /// without changing anything downstream. `expected_codec`, if given, rejects a stream whose
+    /// detected container doesn't match what the caller expected - the same check
+    /// `validate_image`'s `expected_chip` makes against a chip id - rather than silently trusting
+    /// the magic bytes alone.
+    ///
+    pub fn append_compressed(
+        &mut self,
+        reader: &mut dyn std::io::Read,
+        expected_codec: Option<compressed::Codec>,
+        load_address: u32,
+    ) -> Result<CompressionInfo, Error> {
+        let mut raw_bytes = Vec::new();
+        reader
+            .read_to_end(&mut raw_bytes)
+            .map_err(|e| Error::Error(format!("Failed to read firmware stream: {}", e)))?;
+
+        let (bytes, info) = compressed::decompress(&raw_bytes)?;
+
+        if let Some(expected_codec) = expected_codec {
+            if info.codec != expected_codec {
+                return Err(Error::Error(format!(
+                    "Expected a {} compressed firmware stream, but detected {}!",
+                    expected_codec, info.codec
+                )));
+            }
+        }
+
+        let mut data_raw = FirmwareDataRaw::new();
+        for (i, byte) in bytes.iter().enumerate() {
+            data_raw.insert(load_address + i as u32, *byte);
+        }
+
+        self.append_firmware(&data_raw)?;
+
+        Ok(info)
+    }
+
+    ///
+    /// Writes the CRC32 computed over the application (see `get_crc`) back into the last four
+    /// bytes of the last flash page, little-endian
+    ///
+    /// Mirrors the integrity check performed by embedded bootloaders that compare a CRC32 over
+    /// `[_begin.._end-4)` against a linker-placed `_crc` word at boot, so a flashed application
+    /// can confirm it was transferred intact without host involvement. `_calc_app_crc` already
+    /// excludes these last four bytes from the checksum it computes, so writing the trailer here
+    /// does not change `get_crc`'s result. Creates the final page first if `append_firmware` never
+    /// touched it (an image that otherwise ends exactly on the prior page's boundary).
+    ///
+    pub fn finalize_with_crc_trailer(&mut self) {
+        self._calc_app_crc();
+
+        let last_page_id = self.flash_num_pages - 1;
+        if self._get_page_mut(last_page_id).is_none() {
+            self.page_lst.push(FlashPage::new(
+                last_page_id,
+                self.flash_start_address + last_page_id * self.flash_page_size,
+                vec![self.erase_value; self.flash_page_size as usize],
+            ));
+        }
+
+        let page_size = self.flash_page_size as usize;
+        let crc_bytes = self.crc.to_le_bytes();
+        let page = self._get_page_mut(last_page_id).unwrap();
+        for (i, byte) in crc_bytes.iter().enumerate() {
+            page.set_byte(page_size - 4 + i, *byte);
+        }
+        page.calculate_crc();
+    }
+
+    ///
+    /// Rewrites every page's flash address by adding `bank_offset`, retargeting the built image at
+    /// an inactive secondary bank instead of the active region it was laid out against
+    ///
+    /// Used by a dual-bank update scheme: build the image against the active bank's address range
+    /// as usual (`new`/`append_firmware`), then call this right before flashing to shift every
+    /// page to the secondary bank `bank_offset` bytes away, leaving the active bank untouched
+    /// until the bootloader swaps to it (see `mark_swap`). A bad image simply fails its trial boot
+    /// and `mark_revert` keeps the previous, untouched bank.
+    ///
+    pub fn stage_to_secondary(&mut self, bank_offset: u32) {
+        self.page_lst = self
+            .page_lst
+            .iter()
+            .map(|page| {
+                let mut shifted = FlashPage::new(
+                    page.get_id(),
+                    page.get_address() + bank_offset,
+                    page.get_bytes().to_vec(),
+                );
+                shifted.calculate_crc();
+                shifted
+            })
+            .collect();
+
+        self.flash_start_address += bank_offset;
+    }
+
     // Getters ------------------------------------------------------------------------------------
 
     ///
@@ -128,6 +312,34 @@ impl AppFirmware {
         self.crc
     }
 
+    ///
+    /// Returns the pages that actually need to be programmed
+    ///
+    /// Drops any page whose bytes are entirely the flash's erase value, since already-erased
+    /// flash matches it without writing anything. If `device_crc` is given, a page whose freshly
+    /// computed CRC equals `device_crc(page_id)` is also dropped, since the device already holds
+    /// that exact content - the same per-page checksum comparison an incremental update uses to
+    /// skip unchanged pages instead of reflashing the whole image.
+    ///
+    pub fn get_pages_to_flash(
+        &self,
+        device_crc: Option<&dyn Fn(u32) -> Option<u32>>,
+    ) -> impl Iterator<Item = &FlashPage> + '_ {
+        self.page_lst.iter().filter(move |page| {
+            if page.get_bytes().iter().all(|byte| *byte == self.erase_value) {
+                return false;
+            }
+
+            if let Some(device_crc) = device_crc {
+                if device_crc(page.get_id()) == Some(page.get_crc()) {
+                    return false;
+                }
+            }
+
+            true
+        })
+    }
+
     // Private Functions --------------------------------------------------------------------------
 
     fn _get_page_mut(&mut self, page_id: u32) -> Option<&mut FlashPage> {
@@ -163,9 +375,9 @@ impl AppFirmware {
                     }
                 }
                 None => {
-                    // Page does not exist fill bytes with default value
+                    // Page does not exist fill bytes with the flash's erase value
                     for _byte_idx in 0..self.flash_page_size {
-                        app_flash.push(FLASH_DFT_VALUE);
+                        app_flash.push(self.erase_value);
                     }
                 }
             }
@@ -182,6 +394,118 @@ impl AppFirmware {
     }
 }
 
+// Dual-Bank Boot State -----------------------------------------------------------------------------
+
+/// Magic word identifying a valid boot-state page, recognizable at a glance in a flash dump
+pub const BOOT_STATE_MAGIC: u32 = 0xD00D_F00D;
+
+///
+/// Decision a dual-bank bootloader makes at boot, read back from a small state page
+///
+/// `Boot` keeps running the active bank as normal; `Swap` tells the bootloader to activate the
+/// bank `AppFirmware::stage_to_secondary` staged before continuing - the same trial-boot rationale
+/// as the device-side `SwapStart`/`SwapStatus` commands (see `device::Device::swap_start`), but
+/// with the decision round-tripped through an ordinary `FirmwareDataRaw` page the host writes
+/// directly instead of a protocol exchange.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum State {
+    Boot,
+    Swap,
+}
+
+impl State {
+    fn to_word(self) -> u32 {
+        match self {
+            State::Boot => 0,
+            State::Swap => 1,
+        }
+    }
+}
+
+///
+/// Builds the boot-state page data telling the bootloader to swap to the staged secondary bank on
+/// next reset: `BOOT_STATE_MAGIC` followed by `State::Swap`, both little-endian, starting at
+/// `address`
+///
+pub fn mark_swap(address: u32) -> FirmwareDataRaw {
+    boot_state_page(address, State::Swap)
+}
+
+///
+/// Builds the boot-state page data telling the bootloader to keep booting the current bank -
+/// used to revert a trial boot that never confirmed itself
+///
+pub fn mark_revert(address: u32) -> FirmwareDataRaw {
+    boot_state_page(address, State::Boot)
+}
+
+fn boot_state_page(address: u32, state: State) -> FirmwareDataRaw {
+    let mut page = FirmwareDataRaw::new();
+    for (i, byte) in BOOT_STATE_MAGIC.to_le_bytes().iter().enumerate() {
+        page.insert(address + i as u32, *byte);
+    }
+    for (i, byte) in state.to_word().to_le_bytes().iter().enumerate() {
+        page.insert(address + 4 + i as u32, *byte);
+    }
+    page
+}
+
+// Firmware Metadata --------------------------------------------------------------------------------
+
+/// Byte length of a `FirmwareMetadata` header: six little-endian `u32` fields
+pub const FIRMWARE_METADATA_LEN: u32 = 24;
+
+///
+/// Firmware compatibility header embedded at a fixed offset inside a built image
+///
+/// Declares which device this image targets, so `Device::check_compatibility` can refuse to flash
+/// it onto a mismatched board instead of silently bricking it - the same rationale
+/// `Device::validate_image` applies via the chip database, but keyed off a header the image
+/// declares about itself rather than a lookup keyed on the device's reported chip id.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FirmwareMetadata {
+    pub vendor_id: u32,
+    pub product_id: u32,
+    pub app_version: u32,
+    pub flash_start_address: u32,
+    pub flash_page_size: u32,
+    pub flash_num_pages: u32,
+}
+
+impl FirmwareMetadata {
+    ///
+    /// Reads a `FirmwareMetadata` header out of `data` at `offset`, as six consecutive
+    /// little-endian `u32` fields: vendor id, product id, app version, flash start address, flash
+    /// page size, flash number of pages (`FIRMWARE_METADATA_LEN` bytes total)
+    ///
+    pub fn from_firmware_data(data: &FirmwareDataRaw, offset: u32) -> Result<FirmwareMetadata, Error> {
+        let read_u32 = |field_offset: u32| -> Result<u32, Error> {
+            let mut bytes = [0u8; 4];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                let address = offset + field_offset + i as u32;
+                *byte = *data.get(&address).ok_or_else(|| {
+                    Error::Error(format!(
+                        "Firmware metadata header at offset {:#010X} is missing byte at {:#010X}!",
+                        offset, address
+                    ))
+                })?;
+            }
+            Ok(u32::from_le_bytes(bytes))
+        };
+
+        Ok(FirmwareMetadata {
+            vendor_id: read_u32(0)?,
+            product_id: read_u32(4)?,
+            app_version: read_u32(8)?,
+            flash_start_address: read_u32(12)?,
+            flash_page_size: read_u32(16)?,
+            flash_num_pages: read_u32(20)?,
+        })
+    }
+}
+
 pub struct FlashPageList {
     page_vec: Vec<FlashPage>,
 }
@@ -193,6 +517,25 @@ impl FlashPageList {
         }
     }
 
+    ///
+    /// Build a page list for the given firmware data, laid out against a single flash section
+    ///
+    /// Like `from_firmware_data`, but takes the section's address/page size/page count from a
+    /// `FlashSection` (e.g. `FlashDesc::get_section`) instead of the caller pulling them apart by
+    /// hand, mirroring `AppFirmware::from_section`.
+    ///
+    pub fn from_section(
+        firmware_data: &FirmwareDataRaw,
+        section: &FlashSection,
+    ) -> Result<FlashPageList, Error> {
+        FlashPageList::from_firmware_data(
+            firmware_data,
+            section.get_address(),
+            section.get_page_size(),
+            section.get_size() / section.get_page_size(),
+        )
+    }
+
     pub fn from_firmware_data(
         firmware_data: &FirmwareDataRaw,
         flash_address: u32,
@@ -289,11 +632,36 @@ impl FlashPageList {
     pub fn len(&self) -> usize {
         self.page_vec.len()
     }
+
+    ///
+    /// Returns the pages that actually need to be programmed
+    ///
+    /// See `AppFirmware::get_pages_to_flash` for the filters applied.
+    ///
+    pub fn get_pages_to_flash(
+        &self,
+        device_crc: Option<&dyn Fn(u32) -> Option<u32>>,
+    ) -> impl Iterator<Item = &FlashPage> + '_ {
+        self.page_vec.iter().filter(move |page| {
+            if page.get_bytes().iter().all(|byte| *byte == FLASH_DFT_VALUE) {
+                return false;
+            }
+
+            if let Some(device_crc) = device_crc {
+                if device_crc(page.get_id()) == Some(page.get_crc()) {
+                    return false;
+                }
+            }
+
+            true
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::francor::franklyboot::device::flash::FlashDesc;
 
     #[test]
     fn from_firmware_map_invalid_address() {
@@ -380,6 +748,270 @@ mod tests {
         assert_eq!(page.get_byte_vec()[15], 0x12);
     }
 
+    #[test]
+    fn app_firmware_get_pages_to_flash_skips_blank_pages() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 2);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        map.insert(0x08000400, FLASH_DFT_VALUE);
+        app_fw.append_firmware(&map).unwrap();
+
+        // Page 1 only holds FLASH_DFT_VALUE, so it is blank and must be skipped.
+        let page_ids: Vec<u32> = app_fw
+            .get_pages_to_flash(None)
+            .map(|page| page.get_id())
+            .collect();
+        assert_eq!(page_ids, vec![0]);
+    }
+
+    #[test]
+    fn app_firmware_with_erase_value_pads_gaps_with_it_instead_of_0xff() {
+        let mut app_fw = AppFirmware::new_with_erase_value(0x08000000, 0, 0x400, 1, 0x00);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        app_fw.append_firmware(&map).unwrap();
+
+        let page = app_fw.get_pages_to_flash(None).next().unwrap();
+        assert_eq!(page.get_bytes()[0], 0xAA);
+        assert_eq!(page.get_bytes()[1], 0x00);
+    }
+
+    #[test]
+    fn app_firmware_get_pages_to_flash_skips_blank_pages_with_custom_erase_value() {
+        let mut app_fw = AppFirmware::new_with_erase_value(0x08000000, 0, 0x400, 2, 0x00);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        map.insert(0x08000400, 0x00);
+        app_fw.append_firmware(&map).unwrap();
+
+        // Page 1 only holds 0x00 (this flash's erase value), so it is blank and must be skipped
+        // the same way an all-0xFF page would on a flash using the default.
+        let page_ids: Vec<u32> = app_fw
+            .get_pages_to_flash(None)
+            .map(|page| page.get_id())
+            .collect();
+        assert_eq!(page_ids, vec![0]);
+    }
+
+    #[test]
+    fn app_firmware_get_pages_to_flash_skips_pages_matching_device_crc() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 2);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        map.insert(0x08000400, 0xBB);
+        app_fw.append_firmware(&map).unwrap();
+
+        let page0_crc = app_fw.get_pages_to_flash(None).next().unwrap().get_crc();
+
+        let device_crc = |page_id: u32| -> Option<u32> {
+            if page_id == 0 {
+                Some(page0_crc)
+            } else {
+                None
+            }
+        };
+
+        let page_ids: Vec<u32> = app_fw
+            .get_pages_to_flash(Some(&device_crc))
+            .map(|page| page.get_id())
+            .collect();
+        assert_eq!(page_ids, vec![1]);
+    }
+
+    #[test]
+    fn app_firmware_finalize_with_crc_trailer_writes_the_computed_crc_little_endian() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 1);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        app_fw.append_firmware(&map).unwrap();
+
+        let crc = app_fw.get_crc();
+        app_fw.finalize_with_crc_trailer();
+
+        let page = app_fw._get_page(0).unwrap();
+        let bytes = page.get_bytes();
+        let trailer = &bytes[bytes.len() - 4..];
+        assert_eq!(trailer, &crc.to_le_bytes());
+
+        // Writing the trailer must not change the checksum it records.
+        assert_eq!(app_fw.get_crc(), crc);
+    }
+
+    #[test]
+    fn app_firmware_finalize_with_crc_trailer_materializes_a_missing_final_page() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 2);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        app_fw.append_firmware(&map).unwrap();
+
+        // Page 1 was never touched by append_firmware.
+        let crc = app_fw.get_crc();
+        app_fw.finalize_with_crc_trailer();
+
+        let page = app_fw._get_page(1).unwrap();
+        let bytes = page.get_bytes();
+        let trailer = &bytes[bytes.len() - 4..];
+        assert_eq!(trailer, &crc.to_le_bytes());
+    }
+
+    #[test]
+    fn app_firmware_stage_to_secondary_shifts_every_page_address() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 2);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000000, 0xAA);
+        map.insert(0x08000400, 0xBB);
+        app_fw.append_firmware(&map).unwrap();
+
+        app_fw.stage_to_secondary(0x10000);
+
+        let page0 = app_fw._get_page(0).unwrap();
+        assert_eq!(page0.get_address(), 0x08010000);
+        assert_eq!(page0.get_bytes()[0], 0xAA);
+
+        let page1 = app_fw._get_page(1).unwrap();
+        assert_eq!(page1.get_address(), 0x08010400);
+        assert_eq!(page1.get_bytes()[0], 0xBB);
+    }
+
+    #[test]
+    fn app_firmware_append_compressed_loads_an_uncompressed_stream_at_the_load_address() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 1);
+
+        let raw = vec![0xAA, 0xBB, 0xCC];
+        let mut reader = &raw[..];
+        let info = app_fw
+            .append_compressed(&mut reader, None, 0x08000000)
+            .unwrap();
+
+        assert_eq!(info.codec, compressed::Codec::None);
+        assert_eq!(info.decompressed_size, raw.len());
+
+        let page = app_fw._get_page(0).unwrap();
+        assert_eq!(&page.get_bytes()[0..3], &raw[..]);
+    }
+
+    #[test]
+    fn app_firmware_append_compressed_rejects_a_codec_mismatch() {
+        let mut app_fw = AppFirmware::new(0x08000000, 0, 0x400, 1);
+
+        let raw = vec![0xAA, 0xBB, 0xCC];
+        let mut reader = &raw[..];
+        let result = app_fw.append_compressed(&mut reader, Some(compressed::Codec::Zstd), 0x08000000);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mark_swap_writes_the_magic_word_and_swap_state() {
+        let page = mark_swap(0x0801_FC00);
+
+        assert_eq!(page.len(), 8);
+        assert_eq!(page[&0x0801_FC00], 0x0D);
+        assert_eq!(page[&0x0801_FC01], 0xF0);
+        assert_eq!(page[&0x0801_FC02], 0x0D);
+        assert_eq!(page[&0x0801_FC03], 0xD0);
+        assert_eq!(page[&0x0801_FC04], 1);
+        assert_eq!(page[&0x0801_FC05], 0);
+        assert_eq!(page[&0x0801_FC06], 0);
+        assert_eq!(page[&0x0801_FC07], 0);
+    }
+
+    #[test]
+    fn mark_revert_writes_the_magic_word_and_boot_state() {
+        let page = mark_revert(0x0801_FC00);
+
+        assert_eq!(page[&0x0801_FC00], 0x0D);
+        assert_eq!(page[&0x0801_FC04], 0);
+    }
+
+    #[test]
+    fn app_firmware_from_section_matches_manual_layout() {
+        // Two differently sized sections ("Bootloader" then "Application"), like a chip with a
+        // small fixed bootloader sector followed by a larger application sector.
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x2000, 0x400);
+        flash_desc.add_section("Bootloader", 0x08000000, 0x400).unwrap();
+        flash_desc.add_section("Application", 0x08000400, 0x1C00).unwrap();
+
+        let app_section = flash_desc.get_section("Application").unwrap();
+        let mut app_fw = AppFirmware::from_section(&app_section);
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000400, 0xAA);
+        app_fw.append_firmware(&map).unwrap();
+
+        let mut manual_fw = AppFirmware::new(0x08000000, 1, 0x400, 8);
+        manual_fw.append_firmware(&map).unwrap();
+
+        assert_eq!(app_fw.get_crc(), manual_fw.get_crc());
+    }
+
+    #[test]
+    fn flash_page_list_from_section_matches_manual_layout() {
+        let mut flash_desc = FlashDesc::new(0x08000000, 0x2000, 0x400);
+        flash_desc.add_section("Application", 0x08000400, 0x1C00).unwrap();
+
+        let app_section = flash_desc.get_section("Application").unwrap();
+
+        let mut map: FirmwareDataRaw = HashMap::new();
+        map.insert(0x08000400, 0xAA);
+
+        let page_lst = FlashPageList::from_section(&map, &app_section).unwrap();
+
+        assert_eq!(page_lst.len(), 1);
+        assert_eq!(page_lst.get(0).unwrap().get_byte_vec()[0], 0xAA);
+    }
+
+    #[test]
+    fn firmware_metadata_from_firmware_data_reads_little_endian_fields() {
+        let mut map: FirmwareDataRaw = HashMap::new();
+        let bytes: [u8; 24] = [
+            0x01, 0x00, 0x00, 0x00, // vendor_id = 1
+            0x02, 0x00, 0x00, 0x00, // product_id = 2
+            0x00, 0x01, 0x00, 0x00, // app_version = 0x0100
+            0x00, 0x00, 0x00, 0x08, // flash_start_address = 0x08000000
+            0x00, 0x04, 0x00, 0x00, // flash_page_size = 0x0400
+            0x0F, 0x00, 0x00, 0x00, // flash_num_pages = 0x0F
+        ];
+        for (i, byte) in bytes.iter().enumerate() {
+            map.insert(0x100 + i as u32, *byte);
+        }
+
+        let metadata = FirmwareMetadata::from_firmware_data(&map, 0x100).unwrap();
+
+        assert_eq!(
+            metadata,
+            FirmwareMetadata {
+                vendor_id: 1,
+                product_id: 2,
+                app_version: 0x0100,
+                flash_start_address: 0x08000000,
+                flash_page_size: 0x0400,
+                flash_num_pages: 0x0F,
+            }
+        );
+    }
+
+    #[test]
+    fn firmware_metadata_from_firmware_data_errors_on_a_gap_in_the_header() {
+        let mut map: FirmwareDataRaw = HashMap::new();
+        for i in 0..24u32 {
+            if i == 10 {
+                continue;
+            }
+            map.insert(i, 0x00);
+        }
+
+        let result = FirmwareMetadata::from_firmware_data(&map, 0);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_crc32_checksum_algo() {
         let bytes = [1, 2, 3, 4, 5, 6, 7, 8, 9];
@@ -0,0 +1,327 @@
+use super::FirmwareDataRaw;
+
+// Segment ------------------------------------------------------------------------------------------
+
+///
+/// One contiguous run of firmware bytes, starting at `start`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    start: u32,
+    data: Vec<u8>,
+}
+
+impl Segment {
+    pub fn get_start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn get_data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    /// Address one past the last byte this segment carries
+    fn end(&self) -> u32 {
+        self.start + self.data.len() as u32
+    }
+}
+
+// Segment Page ---------------------------------------------------------------------------------------
+
+///
+/// One flash-aligned, gap-filled block produced by `SegmentedFirmware::pages`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentPage {
+    pub address: u32,
+    pub bytes: Vec<u8>,
+}
+
+// Segmented Firmware -----------------------------------------------------------------------------
+
+///
+/// Sparse firmware image backed by a sorted, run-length list of byte segments instead of a
+/// per-byte `HashMap<u32, u8>` (`FirmwareDataRaw`)
+///
+/// Adjacent or touching runs are coalesced on insert, so a firmware image built one data record
+/// at a time ends up as a handful of segments rather than one map entry per byte - the same
+/// memory-density idea emulators use to model large addressable memories without a byte-granular
+/// map. This is additive alongside `FirmwareDataRaw`, not a replacement for it - every existing
+/// parser/consumer (`HexFile`, `SrecFile`, `AppFirmware`, ...) still speaks `FirmwareDataRaw`, and
+/// `From<FirmwareDataRaw>` bridges the two for callers that want the denser representation.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentedFirmware {
+    segments: Vec<Segment>,
+}
+
+impl SegmentedFirmware {
+    pub fn new() -> Self {
+        SegmentedFirmware {
+            segments: Vec::new(),
+        }
+    }
+
+    ///
+    /// Adds the byte run `[start, start + data.len())`, merging it into a neighbouring segment it
+    /// touches or abuts, otherwise inserting a new segment that keeps the list sorted by `start`
+    ///
+    pub fn insert_run(&mut self, start: u32, data: Vec<u8>) {
+        if data.is_empty() {
+            return;
+        }
+
+        let end = start + data.len() as u32;
+
+        // First segment whose start is not before `start`
+        let idx = self.segments.partition_point(|seg| seg.start < start);
+
+        let touches_prev = idx > 0 && self.segments[idx - 1].end() == start;
+        let touches_next = idx < self.segments.len() && self.segments[idx].start == end;
+
+        match (touches_prev, touches_next) {
+            (true, true) => {
+                let next = self.segments.remove(idx);
+                let prev = &mut self.segments[idx - 1];
+                prev.data.extend(data);
+                prev.data.extend(next.data);
+            }
+            (true, false) => {
+                self.segments[idx - 1].data.extend(data);
+            }
+            (false, true) => {
+                let next = &mut self.segments[idx];
+                let mut merged = data;
+                merged.append(&mut next.data);
+                next.start = start;
+                next.data = merged;
+            }
+            (false, false) => {
+                self.segments.insert(idx, Segment { start, data });
+            }
+        }
+    }
+
+    /// The segments making up this image, sorted by `start` with no two touching; a run inserted
+    /// over an existing segment is kept as its own, overlapping segment
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Iterates every `(address, byte)` pair across all segments, in address order
+    pub fn iter_bytes(&self) -> impl Iterator<Item = (u32, u8)> + '_ {
+        self.segments
+            .iter()
+            .flat_map(|seg| {
+                seg.data
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, byte)| (seg.start + i as u32, *byte))
+            })
+    }
+
+    ///
+    /// `true` if any address in `range` is not covered by a segment
+    ///
+    pub fn contains_gap(&self, range: std::ops::Range<u32>) -> bool {
+        if range.start >= range.end {
+            return false;
+        }
+
+        let mut cursor = range.start;
+        for seg in &self.segments {
+            if seg.end() <= cursor {
+                continue;
+            }
+            if seg.start > cursor {
+                return true;
+            }
+            cursor = seg.end().min(range.end);
+            if cursor >= range.end {
+                return false;
+            }
+        }
+
+        cursor < range.end
+    }
+
+    ///
+    /// Yields fixed-size, `page_size`-aligned blocks covering every occupied address, with any gap
+    /// filled by `fill_byte` - ready for the device module to flash page by page
+    ///
+    pub fn pages(&self, page_size: u32, fill_byte: u8) -> impl Iterator<Item = SegmentPage> {
+        let mut out = Vec::new();
+
+        if page_size == 0 {
+            return out.into_iter();
+        }
+
+        if let (Some(first), Some(last)) = (self.segments.first(), self.segments.last()) {
+            let min_address = first.start;
+            let max_address_exclusive = last.end();
+
+            let mut page_start = (min_address / page_size) * page_size;
+            while page_start < max_address_exclusive {
+                let page_end = page_start + page_size;
+                let mut bytes = vec![fill_byte; page_size as usize];
+
+                for seg in &self.segments {
+                    if seg.end() <= page_start || seg.start >= page_end {
+                        continue;
+                    }
+
+                    let overlap_start = seg.start.max(page_start);
+                    let overlap_end = seg.end().min(page_end);
+                    for address in overlap_start..overlap_end {
+                        bytes[(address - page_start) as usize] =
+                            seg.data[(address - seg.start) as usize];
+                    }
+                }
+
+                out.push(SegmentPage { address: page_start, bytes });
+                page_start += page_size;
+            }
+        }
+
+        out.into_iter()
+    }
+}
+
+impl From<FirmwareDataRaw> for SegmentedFirmware {
+    fn from(data: FirmwareDataRaw) -> Self {
+        let mut addresses: Vec<u32> = data.keys().copied().collect();
+        addresses.sort_unstable();
+
+        let mut firmware = SegmentedFirmware::new();
+
+        let mut idx = 0;
+        while idx < addresses.len() {
+            let run_start_idx = idx;
+            while idx + 1 < addresses.len() && addresses[idx + 1] == addresses[idx] + 1 {
+                idx += 1;
+            }
+
+            let run_bytes: Vec<u8> = addresses[run_start_idx..=idx]
+                .iter()
+                .map(|address| data[address])
+                .collect();
+            firmware.insert_run(addresses[run_start_idx], run_bytes);
+
+            idx += 1;
+        }
+
+        firmware
+    }
+}
+
+// Tests --------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_run_keeps_disjoint_runs_separate() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x10, vec![1, 2]);
+        fw.insert_run(0x20, vec![3, 4]);
+
+        assert_eq!(fw.segments().len(), 2);
+        assert_eq!(fw.segments()[0].get_start(), 0x10);
+        assert_eq!(fw.segments()[1].get_start(), 0x20);
+    }
+
+    #[test]
+    fn insert_run_merges_with_a_segment_it_extends() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x10, vec![1, 2]);
+        fw.insert_run(0x12, vec![3, 4]);
+
+        assert_eq!(fw.segments().len(), 1);
+        assert_eq!(fw.segments()[0].get_start(), 0x10);
+        assert_eq!(fw.segments()[0].get_data(), &vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn insert_run_merges_with_a_segment_it_precedes() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x12, vec![3, 4]);
+        fw.insert_run(0x10, vec![1, 2]);
+
+        assert_eq!(fw.segments().len(), 1);
+        assert_eq!(fw.segments()[0].get_start(), 0x10);
+        assert_eq!(fw.segments()[0].get_data(), &vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn insert_run_bridges_two_segments_it_touches_on_both_sides() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x10, vec![1, 2]);
+        fw.insert_run(0x14, vec![5, 6]);
+        fw.insert_run(0x12, vec![3, 4]);
+
+        assert_eq!(fw.segments().len(), 1);
+        assert_eq!(fw.segments()[0].get_start(), 0x10);
+        assert_eq!(fw.segments()[0].get_data(), &vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn iter_bytes_yields_every_address_in_order() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x20, vec![3, 4]);
+        fw.insert_run(0x10, vec![1, 2]);
+
+        let bytes: Vec<(u32, u8)> = fw.iter_bytes().collect();
+        assert_eq!(bytes, vec![(0x10, 1), (0x11, 2), (0x20, 3), (0x21, 4)]);
+    }
+
+    #[test]
+    fn contains_gap_detects_an_unwritten_address_inside_the_range() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x10, vec![1, 2]);
+
+        assert!(!fw.contains_gap(0x10..0x12));
+        assert!(fw.contains_gap(0x10..0x13));
+        assert!(fw.contains_gap(0x00..0x10));
+    }
+
+    #[test]
+    fn pages_fills_gaps_with_the_given_byte() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x02, vec![0xAA, 0xBB]);
+
+        let pages: Vec<SegmentPage> = fw.pages(4, 0xFF).collect();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].address, 0x00);
+        assert_eq!(pages[0].bytes, vec![0xFF, 0xFF, 0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn pages_spans_multiple_aligned_blocks() {
+        let mut fw = SegmentedFirmware::new();
+        fw.insert_run(0x03, vec![1, 2, 3, 4]);
+
+        let pages: Vec<SegmentPage> = fw.pages(4, 0x00).collect();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].address, 0x00);
+        assert_eq!(pages[0].bytes, vec![0x00, 0x00, 0x00, 1]);
+        assert_eq!(pages[1].address, 0x04);
+        assert_eq!(pages[1].bytes, vec![2, 3, 4, 0x00]);
+    }
+
+    #[test]
+    fn from_firmware_data_raw_coalesces_contiguous_bytes() {
+        let mut map = FirmwareDataRaw::new();
+        map.insert(0x10, 1);
+        map.insert(0x11, 2);
+        map.insert(0x20, 9);
+
+        let fw = SegmentedFirmware::from(map);
+
+        assert_eq!(fw.segments().len(), 2);
+        let bytes: Vec<(u32, u8)> = fw.iter_bytes().collect();
+        assert_eq!(bytes, vec![(0x10, 1), (0x11, 2), (0x20, 9)]);
+    }
+}
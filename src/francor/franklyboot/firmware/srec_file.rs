@@ -0,0 +1,371 @@
+use super::{FirmwareDataInterface, FirmwareDataRaw};
+
+// SRecord Type ------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RecordType {
+    /// S0: header/comment, carries no firmware data
+    Header,
+    /// S1: 16-bit address data record
+    Data16,
+    /// S2: 24-bit address data record
+    Data24,
+    /// S3: 32-bit address data record
+    Data32,
+    /// S5/S6: record count, carries no firmware data
+    Count,
+    /// S7/S8/S9: start address / termination record, carries no firmware data
+    StartAddress,
+}
+
+impl RecordType {
+    pub fn from_digit(digit: u8) -> Option<RecordType> {
+        match digit {
+            b'0' => Some(RecordType::Header),
+            b'1' => Some(RecordType::Data16),
+            b'2' => Some(RecordType::Data24),
+            b'3' => Some(RecordType::Data32),
+            b'5' | b'6' => Some(RecordType::Count),
+            b'7' | b'8' | b'9' => Some(RecordType::StartAddress),
+            _ => None,
+        }
+    }
+
+    /// Number of bytes the address field occupies for a record type whose data this crate cares
+    /// about. `Header`/`Count`/`StartAddress` carry no firmware data and their address field width
+    /// varies by subtype (`S7`/`S8`/`S9` differ), so they're left as `None` and parsed as one
+    /// opaque byte run - the checksum, a plain byte sum, comes out the same either way.
+    fn address_len(&self) -> Option<usize> {
+        match self {
+            RecordType::Data16 => Some(2),
+            RecordType::Data24 => Some(3),
+            RecordType::Data32 => Some(4),
+            RecordType::Header | RecordType::Count | RecordType::StartAddress => None,
+        }
+    }
+}
+
+// SRecord Error Type --------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ErrorType {
+    NoValidData,
+    InvalidEntryLength,
+    RecordTypeParseError,
+    InvalidRecordType,
+    ByteCountParseError,
+    InvalidByteCount,
+    /// `byte_count` is too small to even hold this record type's address field
+    AddressWidthError,
+    DataParseError,
+    ChecksumParseError,
+    InvalidChecksum,
+}
+
+// SRecord Entry -------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    record_type: RecordType,
+    address: u32,
+    data: Vec<u8>,
+    checksum: u8,
+}
+
+impl Entry {
+    /// Parses a single S-record line, starting at the record type digit (the leading `S` already
+    /// stripped by the caller, same convention as `hex_file::Entry::from_hex_line`)
+    pub fn from_srec_line(line: &str) -> Result<Entry, ErrorType> {
+        if line.len() < 4 {
+            return Err(ErrorType::InvalidEntryLength);
+        }
+
+        let record_type = RecordType::from_digit(line.as_bytes()[0])
+            .ok_or(ErrorType::RecordTypeParseError)?;
+
+        let byte_count =
+            u8::from_str_radix(&line[1..3], 16).map_err(|_| ErrorType::ByteCountParseError)?;
+        let expected_len = 3 + byte_count as usize * 2;
+        if line.len() != expected_len {
+            return Err(ErrorType::InvalidByteCount);
+        }
+
+        let address_len = record_type.address_len().unwrap_or(0);
+        if (byte_count as usize) < address_len + 1 {
+            return Err(ErrorType::AddressWidthError);
+        }
+
+        let mut pos = 3;
+        let mut address: u32 = 0;
+        for _ in 0..address_len {
+            let byte =
+                u8::from_str_radix(&line[pos..pos + 2], 16).map_err(|_| ErrorType::DataParseError)?;
+            address = (address << 8) | byte as u32;
+            pos += 2;
+        }
+
+        let data_len = byte_count as usize - address_len - 1;
+        let mut data = Vec::with_capacity(data_len);
+        for _ in 0..data_len {
+            let byte =
+                u8::from_str_radix(&line[pos..pos + 2], 16).map_err(|_| ErrorType::DataParseError)?;
+            data.push(byte);
+            pos += 2;
+        }
+
+        let checksum =
+            u8::from_str_radix(&line[pos..pos + 2], 16).map_err(|_| ErrorType::ChecksumParseError)?;
+
+        // Checksum is the one's complement of the sum of the byte count, address, and data bytes.
+        let mut checksum_calc = byte_count as u32;
+        for i in 0..address_len {
+            checksum_calc += ((address >> ((address_len - 1 - i) * 8)) & 0xFF) as u32;
+        }
+        for byte in &data {
+            checksum_calc += *byte as u32;
+        }
+        let checksum_calc = !(checksum_calc as u8);
+
+        if checksum_calc != checksum {
+            return Err(ErrorType::InvalidChecksum);
+        }
+
+        Ok(Entry {
+            record_type,
+            address,
+            data,
+            checksum,
+        })
+    }
+}
+
+// SRecord File Representation -------------------------------------------------------------------
+
+/// A parsed Motorola S-record (SREC) firmware image
+///
+/// Mirrors `HexFile`: both parsers produce a sparse `FirmwareDataRaw` address-to-byte map so gaps
+/// between data records (vector tables, config words, separate data sections) are never zero-filled
+/// over existing flash content - only addresses an `S1`/`S2`/`S3` record actually carried data for
+/// end up in the map.
+pub struct SrecFile {
+    data: FirmwareDataRaw,
+    entry_point: Option<u32>,
+}
+
+impl SrecFile {
+    pub fn from_file(filename: &str) -> Result<SrecFile, String> {
+        match std::fs::read_to_string(filename) {
+            Ok(f) => Self::from_string(&f),
+            Err(e) => Err(format!("Failed to open file '{}': {}", filename, e)),
+        }
+    }
+
+    pub fn from_string(srec_data: &str) -> Result<SrecFile, String> {
+        Self::parse_srec_file(srec_data)
+    }
+
+    pub fn get_data(&self) -> &FirmwareDataRaw {
+        &self.data
+    }
+
+    pub fn min_address(&self) -> Option<u32> {
+        self.data.keys().min().copied()
+    }
+
+    pub fn max_address(&self) -> Option<u32> {
+        self.data.keys().max().copied()
+    }
+
+    /// Returns the execution entry address captured from an `S7`/`S8`/`S9` termination record, or
+    /// `None` if the file contained none. Callers can cross-check this against the device's
+    /// configured `app_start_address` before flashing.
+    pub fn entry_point(&self) -> Option<u32> {
+        self.entry_point
+    }
+
+    fn parse_srec_file(srec_data: &str) -> Result<SrecFile, String> {
+        let mut firmware_map = FirmwareDataRaw::new();
+        let mut data_record_count: u32 = 0;
+        let mut declared_record_count: Option<u32> = None;
+        let mut entry_point = None;
+
+        for (line_idx, line) in srec_data.lines().enumerate() {
+            if line.is_empty() || line.as_bytes()[0] != b'S' {
+                continue;
+            }
+
+            let entry = Entry::from_srec_line(&line[1..])
+                .map_err(|e| format!("SREC parse error: {:?} in line {}", e, line_idx))?;
+
+            match entry.record_type {
+                RecordType::Data16 | RecordType::Data24 | RecordType::Data32 => {
+                    for (i, byte) in entry.data.iter().enumerate() {
+                        firmware_map.insert(entry.address + i as u32, *byte);
+                    }
+                    data_record_count += 1;
+                }
+                RecordType::Count => {
+                    // S5/S6 carry their count as a big-endian value in place of an address field,
+                    // which `Entry::from_srec_line` leaves in `data` since `Count` has no fixed
+                    // address width of its own.
+                    let count = entry
+                        .data
+                        .iter()
+                        .fold(0u32, |acc, byte| (acc << 8) | *byte as u32);
+                    declared_record_count = Some(count);
+                }
+                RecordType::StartAddress => {
+                    // S7/S8/S9 carry their (32/24/16-bit) entry address in place of a fixed-width
+                    // address field, which `Entry::from_srec_line` leaves in `data` since
+                    // `StartAddress` has no fixed address width of its own (see `address_len`).
+                    entry_point = Some(
+                        entry
+                            .data
+                            .iter()
+                            .fold(0u32, |acc, byte| (acc << 8) | *byte as u32),
+                    );
+                }
+                RecordType::Header => {}
+            }
+        }
+
+        if let Some(declared) = declared_record_count {
+            if declared != data_record_count {
+                return Err(format!(
+                    "SREC record count mismatch: S5/S6 declared {} data record(s) but {} were parsed",
+                    declared, data_record_count
+                ));
+            }
+        }
+
+        if firmware_map.is_empty() {
+            Err("SREC file does not contain valid data!".to_string())
+        } else {
+            Ok(SrecFile {
+                data: firmware_map,
+                entry_point,
+            })
+        }
+    }
+}
+
+impl FirmwareDataInterface for SrecFile {
+    fn get_firmware_data(&self) -> Option<&FirmwareDataRaw> {
+        Some(&self.data)
+    }
+}
+
+// Tests ------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_type_from_digit() {
+        assert_eq!(RecordType::from_digit(b'0'), Some(RecordType::Header));
+        assert_eq!(RecordType::from_digit(b'1'), Some(RecordType::Data16));
+        assert_eq!(RecordType::from_digit(b'2'), Some(RecordType::Data24));
+        assert_eq!(RecordType::from_digit(b'3'), Some(RecordType::Data32));
+        assert_eq!(RecordType::from_digit(b'5'), Some(RecordType::Count));
+        assert_eq!(RecordType::from_digit(b'9'), Some(RecordType::StartAddress));
+        assert_eq!(RecordType::from_digit(b'4'), None);
+    }
+
+    #[test]
+    fn entry_from_srec_line_parses_s1_data_record() {
+        let entry = Entry::from_srec_line("110000048656C6C6F2C20776F726C642166").unwrap();
+        assert_eq!(entry.record_type, RecordType::Data16);
+        assert_eq!(entry.address, 0x0000);
+        assert_eq!(entry.data, b"Hello, world!");
+    }
+
+    #[test]
+    fn entry_from_srec_line_rejects_bad_checksum() {
+        let result = Entry::from_srec_line("110000048656C6C6F2C20776F726C6421FF");
+        assert_eq!(result.unwrap_err(), ErrorType::InvalidChecksum);
+    }
+
+    #[test]
+    fn entry_from_srec_line_parses_s3_32bit_address() {
+        let entry = Entry::from_srec_line("30A0010000668656C6C6FCB").unwrap();
+        assert_eq!(entry.record_type, RecordType::Data32);
+        assert_eq!(entry.address, 0x0010_0006);
+        assert_eq!(entry.data, vec![0x68, 0x65, 0x6C, 0x6C, 0x6F]);
+    }
+
+    #[test]
+    fn srec_file_parses_s1_records_into_sparse_map() {
+        let srec_data = "S0030000FC\n\
+            S11000000102030405060708090A0B0C0D94\n\
+            S5030001FB\n\
+            S9030000FC\n";
+
+        let srec_file = SrecFile::from_string(srec_data).unwrap();
+        let data = srec_file.get_data();
+
+        assert_eq!(data.len(), 13);
+        for i in 0u32..13 {
+            assert_eq!(data[&i], i as u8 + 1);
+        }
+    }
+
+    #[test]
+    fn srec_file_min_max_address() {
+        let srec_data = "S11000000102030405060708090A0B0C0D94\n";
+        let srec_file = SrecFile::from_string(srec_data).unwrap();
+
+        assert_eq!(srec_file.min_address(), Some(0x0000));
+        assert_eq!(srec_file.max_address(), Some(0x000C));
+    }
+
+    #[test]
+    fn srec_file_rejects_a_record_count_that_does_not_match_the_data_records_parsed() {
+        // S5 declares 2 data records, but only one S1 record follows.
+        let srec_data = "S1040000AA51\nS5030002FA\nS9030000FC\n";
+
+        let result = SrecFile::from_string(srec_data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn entry_from_srec_line_rejects_a_byte_count_too_small_for_the_address_width() {
+        // S3 needs a 4-byte address, but byte_count (3) only leaves room for 2 address bytes plus
+        // the checksum.
+        let result = Entry::from_srec_line("303AABBCC");
+        assert_eq!(result.unwrap_err(), ErrorType::AddressWidthError);
+    }
+
+    #[test]
+    fn srec_file_rejects_no_data() {
+        let srec_data = "S0030000FC\nS9030000FC\n";
+        assert!(SrecFile::from_string(srec_data).is_err());
+    }
+
+    #[test]
+    fn srec_file_captures_start_address_as_the_entry_point() {
+        let srec_data = "S11000000102030405060708090A0B0C0D94\nS9030000FC\n";
+        let srec_file = SrecFile::from_string(srec_data).unwrap();
+
+        assert_eq!(srec_file.entry_point(), Some(0x0000));
+    }
+
+    #[test]
+    fn srec_file_entry_point_defaults_to_none() {
+        let srec_data = "S11000000102030405060708090A0B0C0D94\n";
+        let srec_file = SrecFile::from_string(srec_data).unwrap();
+
+        assert_eq!(srec_file.entry_point(), None);
+    }
+
+    #[test]
+    fn srec_file_firmware_data_interface_trait() {
+        let srec_data = "S11000000102030405060708090A0B0C0D94\n";
+        let srec_file = SrecFile::from_string(srec_data).unwrap();
+        let firmware_interface_trait: Box<dyn FirmwareDataInterface> = Box::new(srec_file);
+
+        let data = firmware_interface_trait.get_firmware_data().unwrap();
+        assert_eq!(data.len(), 13);
+        assert_eq!(data[&0], 0x01);
+    }
+}
@@ -1,11 +1,17 @@
 // Defininition of modules ------------------------------------------------------------------------
 
+pub mod chip_db;
 pub mod com;
 pub mod device;
+pub mod dfu;
+#[cfg(feature = "uniffi")]
+pub mod ffi;
 pub mod firmware;
 pub mod flash;
 
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, Mutex};
 
 // Error ------------------------------------------------------------------------------------------
 
@@ -32,6 +38,45 @@ pub enum Error {
     /// Function not supported/implemented
     NotSupported,
 
+    /// A single page's buffer CRC, read back right after writing it, does not match what was
+    /// sent. Names the failing page so a corrupt link can be told apart from a corrupt image.
+    PageCrcMismatch {
+        page_id: u32,
+        expected: u32,
+        actual: u32,
+    },
+
+    /// The whole application's CRC, read back after flashing every page, does not match the image
+    AppCrcMismatch { expected: u32, actual: u32 },
+
+    /// A page's on-device contents, found during a readback verification pass, don't match what
+    /// was flashed there. Analogous to `hf2`'s `ContentsDifferent`, but pinpoints which page: its
+    /// id, its flash address, the CRC computed locally, and the CRC the device reported.
+    PageContentsDiffer {
+        page_id: u32,
+        address: u32,
+        expected: u32,
+        actual: u32,
+    },
+
+    /// A running `erase`/`flash` was cancelled via its cancel token before it finished
+    Cancelled,
+
+    /// A segmented transfer (see `com::iso_tp`) was aborted by the remote side, carrying the
+    /// negative-response code it gave (e.g. a UDS NRC such as `0x31` "request out of range")
+    TransferAborted(u8),
+
+    /// A hex file record on a specific line failed to parse; `kind` is the underlying
+    /// `firmware::hex_file::ErrorType` and is reachable through `source()`
+    HexParse {
+        line: usize,
+        kind: firmware::hex_file::ErrorType,
+    },
+
+    /// A filesystem operation failed, stringified since `std::io::Error` is not `Clone`/`PartialEq`
+    /// and every other variant of this enum is
+    Io(String),
+
     /// General error
     Error(String),
 }
@@ -55,9 +100,355 @@ impl fmt::Display for Error {
             Error::NotSupported => {
                 write!(f, "NotSupported: Command is not supported/implemented")
             }
+            Error::PageCrcMismatch {
+                page_id,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "PageCrcMismatch: Page {} buffer CRC is invalid! Calc: {:#010X} Dev: {:#010X}!",
+                    page_id, expected, actual
+                )
+            }
+            Error::AppCrcMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "AppCrcMismatch: CRC check failed! App-CRC: {:#010X} Device-App-CRC: {:#010X}",
+                    expected, actual
+                )
+            }
+            Error::PageContentsDiffer {
+                page_id,
+                address,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "PageContentsDiffer: Page {} at {:#010X} does not match! Calc: {:#010X} Dev: {:#010X}!",
+                    page_id, address, expected, actual
+                )
+            }
+            Error::Cancelled => {
+                write!(f, "Cancelled: Operation was cancelled")
+            }
+            Error::TransferAborted(nrc) => {
+                write!(f, "TransferAborted: Remote side aborted the transfer ({:#04X})", nrc)
+            }
+            Error::HexParse { line, kind } => {
+                write!(f, "HexParse: {} (line {})", kind, line)
+            }
+            Error::Io(desc) => {
+                write!(f, "Io: {}", desc)
+            }
             Error::Error(desc) => {
                 write!(f, "Error: {}", desc)
             }
         }
     }
 }
+
+/// `source()` can only chain to a cause this enum actually stores as an error value rather than
+/// a pre-formatted string; `HexParse` is the one variant that does (its `kind`), so it is the one
+/// variant `source()` resolves. `Io`/`ComError`/etc. stay message-only because keeping this enum
+/// `Clone`/`PartialEq` - relied on throughout `com::ComSimulator` and the device retry paths - rules
+/// out holding a boxed `dyn std::error::Error` (not `Clone`) or the original `std::io::Error` (not
+/// `Clone`/`PartialEq`) directly.
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::HexParse { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err.to_string())
+    }
+}
+
+/// Lets `?` convert an `Error` inside a function that still reports failures as a bare `String`
+/// (the firmware-loading layer's convention, e.g. `firmware::image::FirmwareImage::load`)
+impl From<Error> for String {
+    fn from(err: Error) -> String {
+        err.to_string()
+    }
+}
+
+/// Converts a bare `hex_file::ErrorType` with no line context into the general `Error::Error`
+/// case; call sites parsing a hex file line by line know which line failed and should build
+/// `Error::HexParse { line, kind }` directly instead of going through this conversion.
+impl From<firmware::hex_file::ErrorType> for Error {
+    fn from(kind: firmware::hex_file::ErrorType) -> Error {
+        Error::Error(kind.to_string())
+    }
+}
+
+// Progress -----------------------------------------------------------------------------------------
+
+///
+/// A step of progress reported by a long-running `Device` operation.
+///
+/// `Device::new` reports progress the same way the CLI always has, by printing to stdout; embedding
+/// this crate as a library instead calls `Device::new_with_progress` with a callback so the core
+/// protocol code never has to assume a terminal is attached.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressUpdate {
+    /// A page of the application area has been erased
+    EraseProgress { current: u32, total: u32 },
+
+    /// A page of the firmware has been written to the device
+    FlashProgress { current: u32, total: u32 },
+
+    /// A page of the flashed application has been verified against the firmware image
+    VerifyProgress { current: u32, total: u32 },
+
+    /// A new page has started flashing, at the given flash page index and on-device address
+    PageStart { id: u32, address: u32 },
+
+    /// `n` more bytes of the current page have been sent to the device's page buffer
+    ///
+    /// Fired once per word written (4 bytes), so a UI can render a per-page byte progress bar
+    /// without waiting for the page-level `FlashProgress` tick.
+    BytesWritten(usize),
+
+    /// The current page's buffer CRC was read back from the device and matched the firmware
+    PageVerified { crc: u32 },
+
+    /// The current page was committed to flash (`PageBufferWriteToFlash` succeeded)
+    PageCommitted { id: u32 },
+
+    /// A page's buffer CRC, read back from the device, did not match the firmware image
+    CrcMismatch { page_id: u32, expected: u32, actual: u32 },
+
+    /// A word write was retransmitted after the device did not acknowledge it in time
+    Retransmit { word_index: u32 },
+
+    /// A send/receive exchange with the device failed, categorized by why
+    Timeout(TimeoutReason),
+
+    /// A one-off status line that doesn't fit the progress counters above
+    Message(String),
+}
+
+///
+/// Why a `flash`/`flash_with_trial_boot` exchange with the device failed
+///
+/// Reported alongside `ProgressUpdate::Timeout` so a caller tallying `FlashStats` (or its own
+/// telemetry) can tell a dropped frame apart from a corrupted one.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutReason {
+    /// Sending the request to the device failed
+    SendError,
+    /// The device did not respond before the interface's timeout elapsed
+    RecvTimeout,
+    /// A page's buffer CRC, read back from the device, did not match the firmware image
+    CrcMismatch,
+}
+
+///
+/// Typed alternative to matching on `ProgressUpdate` directly
+///
+/// `Device::new_with_progress` takes a raw `Fn(ProgressUpdate)` closure, which is fine for a quick
+/// one-off callback but means every consumer re-derives the same `match` over variants it mostly
+/// doesn't care about. Implementing `ProgressObserver` instead only requires overriding the events
+/// that matter; `observer_to_progress_fn` adapts it into the closure `new_with_progress` expects.
+/// All methods default to doing nothing.
+///
+pub trait ProgressObserver {
+    /// A page of the application area has been erased
+    fn on_erase_page(&self, _current: u32, _total: u32) {}
+
+    /// A page of the firmware has been written to the device
+    fn on_write_page(&self, _current: u32, _total: u32) {}
+
+    /// A page of the flashed application has been verified against the firmware image
+    fn on_verify(&self, _current: u32, _total: u32) {}
+
+    /// A new page has started flashing, at the given flash page index and on-device address
+    fn on_page_start(&self, _id: u32, _address: u32) {}
+
+    /// `n` more bytes of the current page have been sent to the device's page buffer
+    fn on_bytes_written(&self, _n: usize) {}
+
+    /// The current page's buffer CRC was read back from the device and matched the firmware
+    fn on_page_verified(&self, _crc: u32) {}
+
+    /// The current page was committed to flash
+    fn on_page_committed(&self, _id: u32) {}
+
+    /// A page's buffer CRC, read back from the device, did not match the firmware image
+    fn on_crc_mismatch(&self, _page_id: u32, _expected: u32, _actual: u32) {}
+
+    /// A word write was retransmitted after the device did not acknowledge it in time
+    fn on_retransmit(&self, _word_index: u32) {}
+
+    /// A send/receive exchange with the device failed, categorized by why
+    fn on_timeout(&self, _reason: TimeoutReason) {}
+
+    /// A one-off status line that doesn't fit the progress counters above
+    fn on_state_change(&self, _message: &str) {}
+}
+
+///
+/// Default `ProgressObserver`, printing to stdout the way this crate always has
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutObserver;
+
+impl ProgressObserver for StdoutObserver {
+    fn on_erase_page(&self, current: u32, total: u32) {
+        println!("Erasing app pages [Flash-Page: {}/{}]", current, total);
+    }
+
+    fn on_write_page(&self, current: u32, total: u32) {
+        println!("Flashing {}. page of {}.", current, total);
+    }
+
+    fn on_verify(&self, current: u32, total: u32) {
+        println!("Verifying page {}/{}", current, total);
+    }
+
+    fn on_state_change(&self, message: &str) {
+        println!("{}", message);
+    }
+}
+
+///
+/// Adapts a `ProgressObserver` into the `Fn(ProgressUpdate)` closure `Device::new_with_progress`
+/// expects, dispatching each `ProgressUpdate` variant to the matching observer method.
+///
+pub fn observer_to_progress_fn<O>(observer: O) -> impl Fn(ProgressUpdate) + Send
+where
+    O: ProgressObserver + Send + 'static,
+{
+    move |update: ProgressUpdate| match update {
+        ProgressUpdate::EraseProgress { current, total } => observer.on_erase_page(current, total),
+        ProgressUpdate::FlashProgress { current, total } => observer.on_write_page(current, total),
+        ProgressUpdate::VerifyProgress { current, total } => observer.on_verify(current, total),
+        ProgressUpdate::PageStart { id, address } => observer.on_page_start(id, address),
+        ProgressUpdate::BytesWritten(n) => observer.on_bytes_written(n),
+        ProgressUpdate::PageVerified { crc } => observer.on_page_verified(crc),
+        ProgressUpdate::PageCommitted { id } => observer.on_page_committed(id),
+        ProgressUpdate::CrcMismatch { page_id, expected, actual } => {
+            observer.on_crc_mismatch(page_id, expected, actual)
+        }
+        ProgressUpdate::Retransmit { word_index } => observer.on_retransmit(word_index),
+        ProgressUpdate::Timeout(reason) => observer.on_timeout(reason),
+        ProgressUpdate::Message(msg) => observer.on_state_change(&msg),
+    }
+}
+
+///
+/// Running counters for a `flash`/`flash_with_trial_boot` run, built up by a `StatsObserver`
+///
+/// Useful for batch tooling or a GUI that wants a final tally (pages written, bytes transferred,
+/// words retransmitted, failures by reason) rather than - or in addition to - a live progress bar.
+///
+#[derive(Debug, Clone, Default)]
+pub struct FlashStats {
+    /// Number of pages successfully committed to flash
+    pub pages_flashed: u32,
+    /// Total bytes written to the device's page buffer, across every page
+    pub bytes_transferred: usize,
+    /// Number of word writes that had to be retransmitted
+    pub retransmitted_words: u32,
+    /// Failed exchanges, tallied by `TimeoutReason`
+    pub timeouts: HashMap<TimeoutReason, u32>,
+}
+
+impl FlashStats {
+    /// Number of failures recorded for `reason`, or 0 if none occurred
+    pub fn timeout_count(&self, reason: TimeoutReason) -> u32 {
+        self.timeouts.get(&reason).copied().unwrap_or(0)
+    }
+}
+
+///
+/// `ProgressObserver` that tallies a `FlashStats` instead of rendering anything
+///
+/// Cheap to `Clone` - every clone shares the same counters, so keep one handle to call `snapshot`
+/// from while the other is handed to `Device::new_with_observer`.
+///
+#[derive(Clone, Default)]
+pub struct StatsObserver {
+    stats: Arc<Mutex<FlashStats>>,
+}
+
+impl StatsObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A point-in-time copy of the counters accumulated so far
+    pub fn snapshot(&self) -> FlashStats {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+impl ProgressObserver for StatsObserver {
+    fn on_page_committed(&self, _id: u32) {
+        self.stats.lock().unwrap().pages_flashed += 1;
+    }
+
+    fn on_bytes_written(&self, n: usize) {
+        self.stats.lock().unwrap().bytes_transferred += n;
+    }
+
+    fn on_retransmit(&self, _word_index: u32) {
+        self.stats.lock().unwrap().retransmitted_words += 1;
+    }
+
+    fn on_timeout(&self, reason: TimeoutReason) {
+        *self.stats.lock().unwrap().timeouts.entry(reason).or_insert(0) += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flash_stats_timeout_count_defaults_to_zero() {
+        let stats = FlashStats::default();
+        assert_eq!(stats.timeout_count(TimeoutReason::SendError), 0);
+    }
+
+    #[test]
+    fn stats_observer_tallies_every_kind_of_update() {
+        let observer = StatsObserver::new();
+        let progress_fn = observer_to_progress_fn(observer.clone());
+
+        progress_fn(ProgressUpdate::PageCommitted { id: 0 });
+        progress_fn(ProgressUpdate::PageCommitted { id: 1 });
+        progress_fn(ProgressUpdate::BytesWritten(4));
+        progress_fn(ProgressUpdate::BytesWritten(4));
+        progress_fn(ProgressUpdate::Retransmit { word_index: 3 });
+        progress_fn(ProgressUpdate::Timeout(TimeoutReason::RecvTimeout));
+        progress_fn(ProgressUpdate::Timeout(TimeoutReason::RecvTimeout));
+        progress_fn(ProgressUpdate::Timeout(TimeoutReason::CrcMismatch));
+
+        let stats = observer.snapshot();
+        assert_eq!(stats.pages_flashed, 2);
+        assert_eq!(stats.bytes_transferred, 8);
+        assert_eq!(stats.retransmitted_words, 1);
+        assert_eq!(stats.timeout_count(TimeoutReason::RecvTimeout), 2);
+        assert_eq!(stats.timeout_count(TimeoutReason::CrcMismatch), 1);
+        assert_eq!(stats.timeout_count(TimeoutReason::SendError), 0);
+    }
+
+    #[test]
+    fn stats_observer_clones_share_the_same_counters() {
+        let observer = StatsObserver::new();
+        let clone = observer.clone();
+
+        observer_to_progress_fn(clone)(ProgressUpdate::PageCommitted { id: 0 });
+
+        assert_eq!(observer.snapshot().pages_flashed, 1);
+    }
+}
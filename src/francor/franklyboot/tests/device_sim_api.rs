@@ -8,6 +8,7 @@ use crate::francor::franklyboot::{
 
 // Device Simulator C API -------------------------------------------------------------------------
 
+#[cfg(feature = "sim-cpp")]
 #[link(name = "franklyboot-device-sim-api", kind = "static")]
 extern "C" {
     pub fn SIM_reset();
@@ -26,8 +27,10 @@ extern "C" {
 /// Device Simulator API
 ///
 /// This struct implements the C-API for the device simulator.
+#[cfg(feature = "sim-cpp")]
 struct DeviceSimAPI;
 
+#[cfg(feature = "sim-cpp")]
 impl DeviceSimAPI {
     pub fn new() -> Self {
         DeviceSimAPI {}
@@ -69,12 +72,10 @@ impl DeviceSimAPI {
         let mut node_id = [0u8; 1];
         let mut raw_msg = [0u8; 8];
         match unsafe { SIM_getBroadcastResponseMsg(node_id.as_mut_ptr(), raw_msg.as_mut_ptr()) } {
-            true => {
-                return Some((node_id[0], Msg::from_raw_data_array(&raw_msg)));
-            }
-            _ => {
-                return None;
-            }
+            true => Msg::try_from_raw_data_array(&raw_msg)
+                .ok()
+                .map(|msg| (node_id[0], msg)),
+            _ => None,
         }
     }
 
@@ -83,14 +84,74 @@ impl DeviceSimAPI {
             let mut raw_msg = [0u8; 8];
 
             if SIM_getNodeResponseMsg(node_id, raw_msg.as_mut_ptr()) {
-                return Some(Msg::from_raw_data_array(&raw_msg));
+                Msg::try_from_raw_data_array(&raw_msg).ok()
             } else {
-                return None;
+                None
             }
         }
     }
 }
 
+///
+/// Device Simulator API - pure Rust backend
+///
+/// Same surface as the `sim-cpp` `DeviceSimAPI`, but backed by `sim_device::SimNetwork` running
+/// in-process instead of the C++ static library, so a plain `cargo test --features sim-native`
+/// needs no C++ toolchain and can be driven by Rust fuzzers/property tests.
+#[cfg(feature = "sim-native")]
+struct DeviceSimAPI;
+
+#[cfg(feature = "sim-native")]
+impl DeviceSimAPI {
+    pub fn new() -> Self {
+        DeviceSimAPI {}
+    }
+
+    fn network() -> &'static std::sync::Mutex<crate::francor::franklyboot::com::sim_device::SimNetwork>
+    {
+        static NETWORK: std::sync::OnceLock<
+            std::sync::Mutex<crate::francor::franklyboot::com::sim_device::SimNetwork>,
+        > = std::sync::OnceLock::new();
+
+        NETWORK.get_or_init(|| {
+            std::sync::Mutex::new(crate::francor::franklyboot::com::sim_device::SimNetwork::new())
+        })
+    }
+
+    pub fn reset(&self) {
+        Self::network().lock().unwrap().reset();
+    }
+
+    pub fn add_device(&self, node_id: u8) -> bool {
+        Self::network().lock().unwrap().add_device(node_id)
+    }
+
+    pub fn get_device_count(&self) -> u32 {
+        Self::network().lock().unwrap().get_device_count()
+    }
+
+    pub fn send_broadcast_msg(&self, msg: &Msg) {
+        Self::network().lock().unwrap().send_broadcast_msg(msg);
+    }
+
+    pub fn send_node_msg(&self, node_id: u8, msg: &Msg) {
+        Self::network().lock().unwrap().send_node_msg(node_id, msg);
+    }
+
+    pub fn update_devices(&self) {
+        // The native backend handles each request synchronously when sent, so there is no
+        // separate update step to run.
+    }
+
+    pub fn get_broadcast_response_msg(&self) -> Option<(u8, Msg)> {
+        Self::network().lock().unwrap().get_broadcast_response_msg()
+    }
+
+    pub fn get_node_response_msg(&self, node_id: u8) -> Option<Msg> {
+        Self::network().lock().unwrap().get_node_response_msg(node_id)
+    }
+}
+
 // Device Simulator COM Interface -----------------------------------------------------------------
 
 pub struct DeviceSimInterface {
@@ -1,42 +1,204 @@
 use clap::{Arg, ArgAction, Command};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
 use frankly_fw_update_cli::francor::franklyboot::{
-    com::{can::CANInterface, serial::SerialInterface, sim::SIMInterface, ComInterface, ComMode},
+    com::{
+        can::CANInterface,
+        net::NetInterface,
+        serial::SerialInterface,
+        sim::{SIMInterface, SimCapture},
+        timing::{CanBitTiming, CanTimingConstraints, CLASSIC_SAMPLE_POINT_PERMILLE, FD_DATA_SAMPLE_POINT_PERMILLE},
+        ComInterface, ComMode,
+    },
     device::Device,
-    firmware::hex_file::HexFile,
+    dfu::DfuInterface,
+    firmware::{
+        hex_file::HexFile,
+        image::{FirmwareFormat, FirmwareImage},
+        FirmwareDataInterface, FLASH_DFT_VALUE,
+    },
+    Error, ProgressUpdate,
 };
 
 const SIM_NODE_LST: [u8; 4] = [1, 3, 31, 8];
 
+/// Default USB interface number of the DFU interface exposed by STM32 ROM bootloaders
+const DFU_INTERFACE_NUM: u8 = 0;
+
 pub enum InterfaceType {
     Sim,
     Serial,
     CAN,
-    Ethernet,
+    Net,
+    Dfu,
+}
+
+// CLI Error ----------------------------------------------------------------------------------------
+
+///
+/// CLI error enumeration
+///
+/// Every interface path returns one of these instead of panicking, so a single unreachable node
+/// or malformed hex file is reported and exits with a distinct, scriptable code rather than
+/// crashing the whole process.
+///
+#[derive(Debug)]
+pub enum CliError {
+    /// Failed to open or configure the communication interface
+    InterfaceOpen(String),
+
+    /// Failed to initialize the device after connecting
+    Init(String),
+
+    /// Failed to erase the device
+    Erase(String),
+
+    /// Failed to flash the device
+    Flash(String),
+
+    /// A flashed page's buffer CRC did not match what was sent
+    PageCrcMismatch(String),
+
+    /// The flashed application's CRC did not match the firmware image
+    AppCrcMismatch(String),
+
+    /// Flashing was cancelled before it finished
+    Cancelled(String),
+
+    /// Failed to parse the given firmware image
+    FirmwareParse(String),
+
+    /// Image/chip validation failed before flashing
+    Validation(String),
+
+    /// Verify found the device's flash does not match the given firmware image
+    Verify(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::InterfaceOpen(desc) => write!(f, "Failed to open interface: {}", desc),
+            CliError::Init(desc) => write!(f, "Failed to initialize device: {}", desc),
+            CliError::Erase(desc) => write!(f, "Failed to erase device: {}", desc),
+            CliError::Flash(desc) => write!(f, "Failed to flash device: {}", desc),
+            CliError::PageCrcMismatch(desc) => write!(f, "Page CRC mismatch: {}", desc),
+            CliError::AppCrcMismatch(desc) => write!(f, "App CRC mismatch: {}", desc),
+            CliError::Cancelled(desc) => write!(f, "{}", desc),
+            CliError::FirmwareParse(desc) => write!(f, "Failed to parse firmware image: {}", desc),
+            CliError::Validation(desc) => write!(f, "Image validation failed: {}", desc),
+            CliError::Verify(desc) => write!(f, "Verification failed: {}", desc),
+        }
+    }
+}
+
+impl CliError {
+    /// Distinct, stable exit code per error category for scripted/CI use
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::InterfaceOpen(_) => 1,
+            CliError::Init(_) => 2,
+            CliError::Erase(_) => 3,
+            CliError::Flash(_) => 4,
+            CliError::FirmwareParse(_) => 5,
+            CliError::Validation(_) => 6,
+            CliError::Verify(_) => 7,
+            CliError::PageCrcMismatch(_) => 8,
+            CliError::AppCrcMismatch(_) => 9,
+            CliError::Cancelled(_) => 10,
+        }
+    }
 }
 
-pub fn search_for_devices(interface_type: InterfaceType, interface_name: &String) {
+///
+/// Map a `flash` failure to its `CliError` category
+///
+/// `Device::flash` reports a page or app CRC mismatch as a distinct `Error` variant so the CLI can
+/// tell "the link is flaky" apart from "the write didn't take"; a cancelled flash becomes
+/// `CliError::Cancelled`, and everything else the catch-all `CliError::Flash`.
+///
+fn flash_error_to_cli_error(e: Error) -> CliError {
+    match e {
+        Error::PageCrcMismatch { .. } => CliError::PageCrcMismatch(e.to_string()),
+        Error::AppCrcMismatch { .. } => CliError::AppCrcMismatch(e.to_string()),
+        Error::Cancelled => CliError::Cancelled(e.to_string()),
+        _ => CliError::Flash(e.to_string()),
+    }
+}
+
+///
+/// Parse a DFU "VID:PID" interface name (e.g. "0483:df11") into its two u16 parts
+///
+pub fn parse_dfu_vid_pid(interface_name: &str) -> Result<(u16, u16), CliError> {
+    let (vid, pid) = interface_name.split_once(':').ok_or_else(|| {
+        CliError::InterfaceOpen(format!(
+            "DFU interface name \"{}\" must be in \"VID:PID\" format, e.g. \"0483:df11\"",
+            interface_name
+        ))
+    })?;
+
+    let vid = u16::from_str_radix(vid, 16)
+        .map_err(|e| CliError::InterfaceOpen(format!("Invalid VID \"{}\": {}", vid, e)))?;
+    let pid = u16::from_str_radix(pid, 16)
+        .map_err(|e| CliError::InterfaceOpen(format!("Invalid PID \"{}\": {}", pid, e)))?;
+
+    Ok((vid, pid))
+}
+
+///
+/// Parse a `--load-address` value, accepting both plain decimal and "0x"-prefixed hex
+///
+pub fn parse_load_address(value: &str) -> Result<u32, CliError> {
+    let (value, radix) = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (value, 10),
+    };
+
+    u32::from_str_radix(value, radix)
+        .map_err(|e| CliError::FirmwareParse(format!("Invalid --load-address \"{}\": {}", value, e)))
+}
+
+pub fn search_for_devices(
+    interface_type: InterfaceType,
+    interface_name: &String,
+    capture_file: Option<&str>,
+) -> Result<(), CliError> {
     match interface_type {
         InterfaceType::Serial => {
             println!(
                 "--> Searching for devices on serial port {}",
                 interface_name
             );
-            search_for_serial_devices(interface_name);
+            search_for_serial_devices(interface_name)
         }
         InterfaceType::CAN => {
             println!("--> Searching for devices on CAN bus {}", interface_name);
+            search_for_can_devices(interface_name)
         }
-        InterfaceType::Ethernet => {
-            println!("--> Searching for devices on Ethernet {}", interface_name);
+        InterfaceType::Net => {
+            println!("--> Searching for device at {}", interface_name);
+            search_for_net_devices(interface_name)
         }
         InterfaceType::Sim => {
             println!("--> Searching for devices on simulated network");
-            search_for_sim_devices();
+            search_for_sim_devices(capture_file)
+        }
+        InterfaceType::Dfu => {
+            println!("--> Searching for DFU devices with VID:PID {}", interface_name);
+            search_for_dfu_devices(interface_name)
         }
     }
 }
 
-pub fn erase_device(interface_type: InterfaceType, interface_name: &String, node: u8) {
+pub fn erase_device(
+    interface_type: InterfaceType,
+    interface_name: &String,
+    node: u8,
+    mass_erase: bool,
+) -> Result<(), CliError> {
     match interface_type {
         InterfaceType::Serial => {
             println!(
@@ -44,61 +206,674 @@ pub fn erase_device(interface_type: InterfaceType, interface_name: &String, node
                 interface_name, node
             );
 
-            let interface = SerialInterface::open(interface_name, 115200).unwrap();
+            let interface = SerialInterface::open(interface_name, 115200)
+                .map_err(CliError::InterfaceOpen)?;
             let mut device = Device::new(interface);
-            device.init().unwrap();
-            device.erase().unwrap();
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+            Ok(())
         }
         InterfaceType::CAN => {
             println!(
                 "--> Erasing devices on CAN bus {} with node id {}",
                 interface_name, node
             );
+
+            let mut interface =
+                CANInterface::open(interface_name).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            interface
+                .set_mode(ComMode::Specific(node))
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+            Ok(())
+        }
+        InterfaceType::Net => {
+            println!("--> Erasing device at {}", interface_name);
+
+            let interface = NetInterface::open(interface_name)
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+            Ok(())
+        }
+        InterfaceType::Sim => {
+            println!(
+                "--> Erasing devices on simulated network with node id {}",
+                node
+            );
+
+            let node_lst = SIM_NODE_LST.to_vec();
+            SIMInterface::config_nodes(node_lst).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut interface = SIMInterface::open("").map_err(CliError::InterfaceOpen)?;
+            interface
+                .set_mode(ComMode::Specific(node))
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+            Ok(())
+        }
+        InterfaceType::Dfu => {
+            println!("DFU devices do not support a standalone erase; flash a new image instead");
+            Ok(())
+        }
+    }
+}
+
+///
+/// Non-destructively check a device's flashed application against a firmware image
+///
+/// Connects to the device, checks its application flash page by page (by the page CRC from
+/// `FlashPageCRCCalc` where the bootloader supports it, reading back words only where that CRC
+/// disagrees or is unavailable, see `Device::verify`), and compares it against `firmware` without erasing or writing anything. Prints every mismatching address and
+/// returns `CliError::Verify` if any were found, so this can be used to confirm what is already
+/// installed on a device - e.g. after a CI flash, or to diagnose a field unit - without risking the
+/// running image.
+///
+pub fn verify_device<FWI: FirmwareDataInterface>(
+    interface_type: InterfaceType,
+    interface_name: &String,
+    node: u8,
+    firmware: &FWI,
+    expected_chip: Option<&str>,
+) -> Result<(), CliError> {
+    let mismatches = match interface_type {
+        InterfaceType::Serial => {
+            println!(
+                "--> Verifying device on serial bus {} with node id {}",
+                interface_name, node
+            );
+
+            let interface = SerialInterface::open(interface_name, 115200)
+                .map_err(CliError::InterfaceOpen)?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device
+                .validate_image(firmware, expected_chip)
+                .map_err(|e| CliError::Validation(e.to_string()))?;
+            device.verify(firmware).map_err(|e| CliError::Verify(e.to_string()))?
         }
-        InterfaceType::Ethernet => {
+        InterfaceType::CAN => {
             println!(
-                "--> Erasing devices on Ethernet {} with node id {}",
+                "--> Verifying devices on CAN bus {} with node id {}",
                 interface_name, node
             );
+
+            let mut interface =
+                CANInterface::open(interface_name).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            interface
+                .set_mode(ComMode::Specific(node))
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device
+                .validate_image(firmware, expected_chip)
+                .map_err(|e| CliError::Validation(e.to_string()))?;
+            device.verify(firmware).map_err(|e| CliError::Verify(e.to_string()))?
+        }
+        InterfaceType::Net => {
+            println!("--> Verifying device at {}", interface_name);
+
+            let interface = NetInterface::open(interface_name)
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device
+                .validate_image(firmware, expected_chip)
+                .map_err(|e| CliError::Validation(e.to_string()))?;
+            device.verify(firmware).map_err(|e| CliError::Verify(e.to_string()))?
         }
         InterfaceType::Sim => {
             println!(
-                "--> Erasing devices on simulated network with node id {}",
+                "--> Verifying devices on simulated network with node id {}",
                 node
             );
 
             let node_lst = SIM_NODE_LST.to_vec();
-            SIMInterface::config_nodes(node_lst).unwrap();
-            let mut interface = SIMInterface::open("").unwrap();
-            interface.set_mode(ComMode::Specific(node)).unwrap();
+            SIMInterface::config_nodes(node_lst).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+            let mut interface = SIMInterface::open("").map_err(CliError::InterfaceOpen)?;
+            interface
+                .set_mode(ComMode::Specific(node))
+                .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
             let mut device = Device::new(interface);
-            device.init().unwrap();
-            device.erase().unwrap();
+            device.init().map_err(|e| CliError::Init(e.to_string()))?;
+            device
+                .validate_image(firmware, expected_chip)
+                .map_err(|e| CliError::Validation(e.to_string()))?;
+            device.verify(firmware).map_err(|e| CliError::Verify(e.to_string()))?
+        }
+        InterfaceType::Dfu => {
+            println!(
+                "DFU devices do not support readback; verify is not available for this interface"
+            );
+            return Ok(());
         }
+    };
+
+    if mismatches.is_empty() {
+        println!("Verify OK: device flash matches the firmware image");
+        Ok(())
+    } else {
+        for address in &mismatches {
+            println!("Mismatch at address {:#010X}", address);
+        }
+        Err(CliError::Verify(format!(
+            "{} word(s) did not match the firmware image",
+            mismatches.len()
+        )))
     }
 }
 
-pub fn search_for_sim_devices() {
+pub fn search_for_sim_devices(capture_file: Option<&str>) -> Result<(), CliError> {
     let node_lst = SIM_NODE_LST.to_vec();
-    SIMInterface::config_nodes(node_lst).unwrap();
-    let node_lst = SIMInterface::ping_network().unwrap();
+    SIMInterface::config_nodes(node_lst).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+
+    let mut capture = SimCapture::new();
+    let node_lst = SIMInterface::ping_network_with_capture(Some(&mut capture))
+        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+
+    let mut failed = Vec::new();
+    for node in node_lst {
+        let result: Result<(), String> = (|| {
+            let mut interface = SIMInterface::open("")?;
+            interface
+                .set_mode(ComMode::Specific(node))
+                .map_err(|e| e.to_string())?;
+            let mut device = Device::new(interface);
+            device.init().map_err(|e| e.to_string())?;
+
+            println!("Device found[{:3}]: {}", node, device);
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            println!("Device[{:3}]: Failed to initialize: {}", node, e);
+            failed.push(node);
+        }
+    }
+
+    println!("{}", capture.summary());
+    if let Some(capture_file) = capture_file {
+        capture
+            .write_csv(capture_file)
+            .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+        println!("--> Wrote packet capture to {}", capture_file);
+    }
+
+    if !failed.is_empty() {
+        println!("--> Failed to initialize {} node(s): {:?}", failed.len(), failed);
+    }
+
+    Ok(())
+}
+
+pub fn search_for_can_devices(interface_name: &String) -> Result<(), CliError> {
+    let node_lst = CANInterface::ping_network(interface_name)
+        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
 
+    let mut failed = Vec::new();
     for node in node_lst {
-        let mut interface = SIMInterface::open("").unwrap();
-        interface.set_mode(ComMode::Specific(node)).unwrap();
+        let mut interface = match CANInterface::open(interface_name) {
+            Ok(interface) => interface,
+            Err(e) => {
+                println!("Device[{:3}]: Failed to open interface: {}", node, e);
+                failed.push(node);
+                continue;
+            }
+        };
+
+        if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
+            println!("Device[{:3}]: Failed to select node: {}", node, e);
+            failed.push(node);
+            continue;
+        }
+
         let mut device = Device::new(interface);
-        device.init().unwrap();
+        match device.init() {
+            Ok(_) => println!("Device found[{:3}]: {}", node, device),
+            Err(e) => {
+                println!("Device[{:3}]: Failed to initialize: {}", node, e);
+                failed.push(node);
+            }
+        }
+    }
 
-        println!("Device found[{:3}]: {}", node, device);
+    if !failed.is_empty() {
+        println!("--> Failed to reach {} node(s): {:?}", failed.len(), failed);
     }
+
+    Ok(())
 }
 
-pub fn search_for_serial_devices(interface_name: &String) {
-    let interface = SerialInterface::open(interface_name, 115200).unwrap();
+pub fn search_for_serial_devices(interface_name: &String) -> Result<(), CliError> {
+    let interface =
+        SerialInterface::open(interface_name, 115200).map_err(CliError::InterfaceOpen)?;
     let mut device = Device::new(interface);
-    device.init().unwrap();
+    device.init().map_err(|e| CliError::Init(e.to_string()))?;
 
     println!("Device found: {}", device);
+    Ok(())
+}
+
+pub fn search_for_net_devices(interface_name: &String) -> Result<(), CliError> {
+    if let Some(broadcast_addr) = interface_name.strip_prefix("udp-broadcast:") {
+        let addrs = NetInterface::ping_network(broadcast_addr)
+            .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+
+        if addrs.is_empty() {
+            println!("No devices responded to the broadcast on {}", broadcast_addr);
+        }
+        for addr in addrs {
+            println!("Device found: {} (connect with --interface udp:{})", addr, addr);
+        }
+
+        return Ok(());
+    }
+
+    let interface =
+        NetInterface::open(interface_name).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+    let mut device = Device::new(interface);
+    device.init().map_err(|e| CliError::Init(e.to_string()))?;
+
+    println!("Device found: {}", device);
+    Ok(())
+}
+
+///
+/// Enumerate available serial ports
+///
+/// Returns a list of `(port name, description)` pairs. The description includes the USB
+/// VID/PID/serial string when the port reports one.
+///
+pub fn list_serial_interfaces() -> Vec<(String, String)> {
+    serialport::available_ports()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|port| {
+            let description = match port.port_type {
+                serialport::SerialPortType::UsbPort(info) => format!(
+                    "USB VID:PID {:04X}:{:04X}{}",
+                    info.vid,
+                    info.pid,
+                    match info.serial_number {
+                        Some(serial) => format!(", serial {}", serial),
+                        None => String::new(),
+                    }
+                ),
+                _ => String::new(),
+            };
+
+            (port.port_name, description)
+        })
+        .collect()
+}
+
+///
+/// Enumerate available SocketCAN interfaces
+///
+/// Lists every network interface under `/sys/class/net` whose name starts with "can" or "vcan".
+///
+pub fn list_can_interfaces() -> Vec<(String, String)> {
+    let entries = match std::fs::read_dir("/sys/class/net") {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("can") || name.starts_with("vcan"))
+        .map(|name| (name, String::new()))
+        .collect()
+}
+
+///
+/// List the configured sim network node ids
+///
+pub fn list_sim_interfaces() -> Vec<(String, String)> {
+    SIM_NODE_LST
+        .iter()
+        .map(|node| (node.to_string(), "simulated node".to_string()))
+        .collect()
+}
+
+fn print_interface_table(entries: &[(String, String)]) {
+    for (idx, (name, description)) in entries.iter().enumerate() {
+        if description.is_empty() {
+            println!("  [{}] {}", idx + 1, name);
+        } else {
+            println!("  [{}] {} ({})", idx + 1, name, description);
+        }
+    }
+}
+
+pub fn list_interfaces() {
+    println!("Serial ports:");
+    print_interface_table(&list_serial_interfaces());
+
+    println!("CAN interfaces:");
+    print_interface_table(&list_can_interfaces());
+
+    println!("Sim nodes:");
+    print_interface_table(&list_sim_interfaces());
+}
+
+///
+/// Interactively pick an interface for the given interface type
+///
+/// Prints a numbered table of the available interfaces of `interface_type` and reads a selection
+/// from stdin. Used by `search`/`erase`/`flash`/`verify` when `--interface` is not given.
+///
+pub fn pick_interface(interface_type: &str) -> String {
+    if interface_type == "net" {
+        print!("Enter device address [tcp:host:port | udp:host:port]: ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).unwrap();
+        return line.trim().to_string();
+    }
+
+    let entries = match interface_type {
+        "serial" => list_serial_interfaces(),
+        "can" => list_can_interfaces(),
+        "sim" => list_sim_interfaces(),
+        _ => Vec::new(),
+    };
+
+    if entries.is_empty() {
+        println!("No {} interfaces found", interface_type);
+        std::process::exit(1);
+    }
+
+    println!("Available {} interfaces:", interface_type);
+    print_interface_table(&entries);
+
+    print!("Select interface [1-{}]: ", entries.len());
+    io::stdout().flush().unwrap();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).unwrap();
+
+    let choice: usize = line
+        .trim()
+        .parse()
+        .unwrap_or_else(|_| panic!("\"{}\" is not a valid selection", line.trim()));
+
+    if choice == 0 || choice > entries.len() {
+        panic!("Selection {} is out of range", choice);
+    }
+
+    entries[choice - 1].0.clone()
+}
+
+pub fn search_for_dfu_devices(interface_name: &String) -> Result<(), CliError> {
+    let (vid, pid) = parse_dfu_vid_pid(interface_name)?;
+
+    DfuInterface::open(vid, pid, DFU_INTERFACE_NUM)
+        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+    println!("Device found: DFU {:04X}:{:04X}", vid, pid);
+
+    Ok(())
+}
+
+///
+/// Linearize a firmware image's sparse byte map into a contiguous buffer
+///
+/// DFU transfers a flat firmware image rather than FranklyBoot's page-addressed writes, so any
+/// gaps between the image's lowest and highest address are padded with `FLASH_DFT_VALUE`.
+///
+pub fn firmware_to_raw_bytes<FWI: FirmwareDataInterface>(firmware: &FWI) -> Result<Vec<u8>, CliError> {
+    let min_address = firmware
+        .min_address()
+        .ok_or_else(|| CliError::FirmwareParse("Firmware image does not contain any data".to_string()))?;
+    let max_address = firmware.max_address().unwrap();
+    let data = firmware.get_firmware_data().unwrap();
+
+    Ok((min_address..=max_address)
+        .map(|address| *data.get(&address).unwrap_or(&FLASH_DFT_VALUE))
+        .collect())
+}
+
+pub fn flash_dfu<FWI: FirmwareDataInterface>(
+    interface_name: &String,
+    firmware: &FWI,
+) -> Result<(), CliError> {
+    let (vid, pid) = parse_dfu_vid_pid(interface_name)?;
+    let raw_data = firmware_to_raw_bytes(firmware)?;
+
+    let mut interface = DfuInterface::open(vid, pid, DFU_INTERFACE_NUM)
+        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+    interface
+        .download(&raw_data)
+        .map_err(|e| CliError::Flash(e.to_string()))?;
+    interface
+        .detach()
+        .map_err(|e| CliError::Flash(e.to_string()))?;
+
+    Ok(())
+}
+
+pub fn flash_net<FWI: FirmwareDataInterface>(
+    interface_name: &String,
+    firmware: &FWI,
+    expected_chip: Option<&str>,
+    fast: bool,
+    mass_erase: bool,
+) -> Result<(), CliError> {
+    let interface =
+        NetInterface::open(interface_name).map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+    let mut device = Device::new(interface);
+    device.init().map_err(|e| CliError::Init(e.to_string()))?;
+    device
+        .validate_image(firmware, expected_chip)
+        .map_err(|e| CliError::Validation(e.to_string()))?;
+    device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+    device.flash(firmware, fast, mass_erase).map_err(flash_error_to_cli_error)?;
+
+    Ok(())
+}
+
+/// Flash a single node: opens its own CAN socket (sockets are not shared across threads) and
+/// reports its progress on `pb`, the node's own bar in the caller's `MultiProgress`.
+fn flash_can_node<FWI: FirmwareDataInterface>(
+    interface_name: &str,
+    node: u8,
+    firmware: &FWI,
+    expected_chip: Option<&str>,
+    can_fd: bool,
+    fast: bool,
+    mass_erase: bool,
+    pb: &ProgressBar,
+) -> Result<(), String> {
+    pb.set_message("connecting");
+    let mut interface = if can_fd {
+        CANInterface::open_fd(interface_name).map_err(|e| e.to_string())?
+    } else {
+        CANInterface::open(interface_name).map_err(|e| e.to_string())?
+    };
+    interface
+        .set_mode(ComMode::Specific(node))
+        .map_err(|e| e.to_string())?;
+
+    // Route progress through the node's own bar instead of `Device`'s default println!, which
+    // would otherwise scribble over every other node's bar in the shared `MultiProgress`.
+    let progress_pb = pb.clone();
+    let progress_fn = Box::new(move |update: ProgressUpdate| match update {
+        ProgressUpdate::EraseProgress { current, total } => {
+            progress_pb.set_message(format!("erasing page {}/{}", current, total))
+        }
+        ProgressUpdate::FlashProgress { current, total } => {
+            progress_pb.set_message(format!("flashing page {}/{}", current, total))
+        }
+        ProgressUpdate::VerifyProgress { current, total } => {
+            progress_pb.set_message(format!("verifying page {}/{}", current, total))
+        }
+        // Per-word granularity would update the bar faster than it can render, and the per-page
+        // events are left out as well; the progress variants above already drive it.
+        ProgressUpdate::PageStart { .. }
+        | ProgressUpdate::BytesWritten(_)
+        | ProgressUpdate::PageVerified { .. }
+        | ProgressUpdate::PageCommitted { .. } => {}
+        ProgressUpdate::CrcMismatch { page_id, expected, actual } => progress_pb.set_message(
+            format!("page {} CRC mismatch (expected {:#010X}, got {:#010X})", page_id, expected, actual),
+        ),
+        ProgressUpdate::Retransmit { word_index } => {
+            progress_pb.set_message(format!("retransmitting word {}", word_index))
+        }
+        ProgressUpdate::Timeout(_) => {}
+        ProgressUpdate::Message(_) => {}
+    }) as Box<dyn Fn(ProgressUpdate) + Send>;
+    let mut device = Device::new_with_progress(interface, Some(progress_fn));
+    device.init().map_err(|e| e.to_string())?;
+
+    pb.set_message("validating image");
+    device
+        .validate_image(firmware, expected_chip)
+        .map_err(|e| e.to_string())?;
+
+    pb.set_message(if mass_erase { "mass erasing" } else { "erasing" });
+    device.erase(mass_erase).map_err(|e| e.to_string())?;
+
+    pb.set_message(if fast { "flashing (fast)" } else { "flashing" });
+    device.flash(firmware, fast, mass_erase).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Flash a firmware image to every node discovered on a CAN bus, in parallel
+///
+/// This scans the bus for responding nodes, then flashes all of them concurrently (one thread
+/// and one CAN socket per node), showing each node's own progress bar in a `MultiProgress` rather
+/// than waiting on nodes one at a time. Failures are aggregated so one bad node does not stop the
+/// rest.
+pub fn flash_can_broadcast<FWI: FirmwareDataInterface + Sync>(
+    interface_name: &String,
+    firmware: &FWI,
+    expected_chip: Option<&str>,
+    can_fd: bool,
+    fast: bool,
+    mass_erase: bool,
+) -> Result<(), CliError> {
+    let node_lst = CANInterface::ping_network(interface_name)
+        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+
+    let multi_progress = MultiProgress::new();
+    let style = ProgressStyle::with_template("{spinner:.green} Node[{prefix:>3}]: {msg}")
+        .unwrap();
+
+    let results: Vec<(u8, Result<(), String>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = node_lst
+            .iter()
+            .map(|&node| {
+                let pb = multi_progress.add(ProgressBar::new_spinner());
+                pb.set_style(style.clone());
+                pb.set_prefix(node.to_string());
+                pb.enable_steady_tick(Duration::from_millis(100));
+
+                scope.spawn(move || {
+                    let result = flash_can_node(
+                        interface_name,
+                        node,
+                        firmware,
+                        expected_chip,
+                        can_fd,
+                        fast,
+                        mass_erase,
+                        &pb,
+                    );
+                    match &result {
+                        Ok(_) => pb.finish_with_message("done"),
+                        Err(e) => pb.finish_with_message(format!("failed: {}", e)),
+                    }
+                    (node, result)
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+    for (node, result) in results {
+        match result {
+            Ok(_) => succeeded.push(node),
+            Err(_) => failed.push(node),
+        }
+    }
+
+    println!(
+        "--> Broadcast flash finished: {} succeeded {:?}, {} failed {:?}",
+        succeeded.len(),
+        succeeded,
+        failed.len(),
+        failed
+    );
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Flash(format!("{} node(s) failed to flash", failed.len())))
+    }
+}
+
+/// Flash a firmware image to a list of CAN node IDs, one after another
+///
+/// Unlike `flash_can_broadcast`, which flashes every discovered node concurrently, this flashes
+/// `node_lst` sequentially - useful for a bus or set of targets that can't tolerate several nodes
+/// flashing at once. Each node still gets its own progress bar and one bad node does not stop the
+/// rest; a succeeded/failed summary is printed once every node has been attempted.
+pub fn flash_can_sequential<FWI: FirmwareDataInterface>(
+    interface_name: &String,
+    node_lst: &[u8],
+    firmware: &FWI,
+    expected_chip: Option<&str>,
+    can_fd: bool,
+    fast: bool,
+    mass_erase: bool,
+) -> Result<(), CliError> {
+    let style = ProgressStyle::with_template("{spinner:.green} Node[{prefix:>3}]: {msg}").unwrap();
+
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+
+    for &node in node_lst {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(style.clone());
+        pb.set_prefix(node.to_string());
+        pb.enable_steady_tick(Duration::from_millis(100));
+
+        let result = flash_can_node(
+            interface_name, node, firmware, expected_chip, can_fd, fast, mass_erase, &pb,
+        );
+        match &result {
+            Ok(_) => pb.finish_with_message("done"),
+            Err(e) => pb.finish_with_message(format!("failed: {}", e)),
+        }
+
+        match result {
+            Ok(_) => succeeded.push(node),
+            Err(_) => failed.push(node),
+        }
+    }
+
+    println!(
+        "--> Sequential flash finished: {} succeeded {:?}, {} failed {:?}",
+        succeeded.len(),
+        succeeded,
+        failed.len(),
+        failed
+    );
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Flash(format!("{} node(s) failed to flash", failed.len())))
+    }
 }
 
 pub fn run_can_test() {
@@ -108,24 +883,24 @@ pub fn run_can_test() {
 
     let mut device = Device::new(CANInterface::open("can0").unwrap());
     device.init().unwrap();
-    device.erase().unwrap();
+    device.erase(false).unwrap();
 }
 
 pub fn run_serial_test() {
     let mut device = Device::new(SerialInterface::open("/dev/ttyACM0", 115200).unwrap());
     device.init().unwrap();
-    device.erase().unwrap();
+    device.erase(false).unwrap();
 
     device
-        .flash(&HexFile::from_file("./tests/data/example_app_g431rb.hex").unwrap())
+        .flash(&HexFile::from_file("./tests/data/example_app_g431rb.hex").unwrap(), false, false)
         .unwrap();
 }
 
-fn main() {
+fn run() -> Result<(), CliError> {
     let type_arg = Arg::new("type")
         .short('t')
         .long("type")
-        .help("Interface type \"sim\", \"serial\", \"can\"")
+        .help("Interface type \"sim\", \"serial\", \"can\", \"net\", \"dfu\"")
         .required(true)
         .action(ArgAction::Set)
         .num_args(1);
@@ -133,8 +908,8 @@ fn main() {
     let interface_arg = Arg::new("interface")
         .short('i')
         .long("interface")
-        .help("Interface name \"can0\", \"ttyACM0\", \"sim\"")
-        .required(true)
+        .help("Interface name \"can0\", \"ttyACM0\", \"sim\", \"0483:df11\" (DFU VID:PID). If omitted, an interactive picker is shown.")
+        .required(false)
         .action(ArgAction::Set)
         .num_args(1);
 
@@ -147,18 +922,61 @@ fn main() {
         .action(ArgAction::Set)
         .num_args(1);
 
+    let capture_arg = Arg::new("capture")
+        .long("capture")
+        .help("Write a CSV packet capture of the sim session to this file (sim only)")
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let hex_file_arg = Arg::new("hex-file")
+        .long("hex-file")
+        .help("Path to the firmware image (Intel HEX, SREC, ELF, or raw binary)")
+        .required(true)
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let format_arg = Arg::new("format")
+        .long("format")
+        .help("Firmware image format \"hex\", \"srec\", \"elf\", \"bin\" (default: auto-detect from extension/magic)")
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let load_address_arg = Arg::new("load-address")
+        .long("load-address")
+        .help("Address byte 0 of the image is loaded at; required for \"bin\", ignored otherwise")
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let expected_chip_arg = Arg::new("expected-chip")
+        .long("expected-chip")
+        .help("Abort if the device's chip database entry does not match this name")
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let mass_erase_arg = Arg::new("mass-erase")
+        .long("mass-erase")
+        .help("Erase the whole application area in a single bulk command up front instead of page by page")
+        .action(ArgAction::SetTrue);
+
     let matches = Command::new("frankly-fw-update")
         .version("0.1.0")
         .author("Martin Bauernschmitt - FRANCOR e.V.")
         .arg_required_else_help(true)
         .subcommand_required(true)
+        .subcommand(
+            Command::new("list")
+                .short_flag('l')
+                .long_flag("list")
+                .about("List available serial ports, CAN interfaces and sim nodes"),
+        )
         .subcommand(
             Command::new("search")
                 .short_flag('s')
                 .long_flag("search")
                 .about("Search for connected devices on specified network")
                 .arg(type_arg.clone())
-                .arg(interface_arg.clone()),
+                .arg(interface_arg.clone())
+                .arg(capture_arg.clone()),
         )
         .subcommand(
             Command::new("erase")
@@ -167,7 +985,8 @@ fn main() {
                 .about("Erases the application from the device")
                 .arg(type_arg.clone())
                 .arg(interface_arg.clone())
-                .arg(node_arg.clone()),
+                .arg(node_arg.clone())
+                .arg(mass_erase_arg.clone()),
         )
         .subcommand(
             Command::new("flash")
@@ -177,13 +996,87 @@ fn main() {
                 .arg(type_arg.clone())
                 .arg(interface_arg.clone())
                 .arg(node_arg.clone())
+                .arg(hex_file_arg.clone())
+                .arg(format_arg.clone())
+                .arg(load_address_arg.clone())
+                .arg(
+                    Arg::new("broadcast")
+                        .long("broadcast")
+                        .help("Flash the hex file to every node discovered on the bus (CAN only)")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("nodes")
+                        .long("nodes")
+                        .help("Comma-separated list of node IDs to flash one after another (CAN only)")
+                        .value_parser(clap::value_parser!(u8).range(0..))
+                        .value_delimiter(',')
+                        .action(ArgAction::Set)
+                        .num_args(1..),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .help("Flash every node discovered on the bus, one after another (CAN only)")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("can-fd")
+                        .long("can-fd")
+                        .help("Use CAN FD with the BRS bit set for a faster data phase (CAN only)")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("fast")
+                        .long("fast")
+                        .help("Skip the per-page buffer CRC check and verify only once at the end, for speed over slow links")
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(mass_erase_arg.clone())
+                .arg(expected_chip_arg.clone()),
+        )
+        .subcommand(
+            Command::new("verify")
+                .short_flag('v')
+                .long_flag("verify")
+                .about("Non-destructively checks the device's flashed application against a firmware image")
+                .arg(type_arg.clone())
+                .arg(interface_arg.clone())
+                .arg(node_arg.clone())
+                .arg(hex_file_arg.clone())
+                .arg(format_arg.clone())
+                .arg(load_address_arg.clone())
+                .arg(expected_chip_arg.clone()),
+        )
+        .subcommand(
+            Command::new("can-timing")
+                .long_flag("can-timing")
+                .about("Compute CAN bit timing register values for a target bitrate")
+                .arg(
+                    Arg::new("fclk")
+                        .long("fclk")
+                        .help("Controller clock in Hz")
+                        .value_parser(clap::value_parser!(u32))
+                        .required(true)
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
                 .arg(
-                    Arg::new("hex-file")
-                        .long("hex-file")
-                        .help("Path to hex file")
+                    Arg::new("bitrate")
+                        .long("bitrate")
+                        .help("Target arbitration/classic bitrate in bps")
+                        .value_parser(clap::value_parser!(u32))
                         .required(true)
                         .action(ArgAction::Set)
                         .num_args(1),
+                )
+                .arg(
+                    Arg::new("data-bitrate")
+                        .long("data-bitrate")
+                        .help("Target CAN FD data phase bitrate in bps; when given, targets a lower FD-style sample point instead of classic CAN's 87.5%")
+                        .value_parser(clap::value_parser!(u32))
+                        .action(ArgAction::Set)
+                        .num_args(1),
                 ),
         )
         .get_matches();
@@ -191,40 +1084,87 @@ fn main() {
     println!("Frankly Firmware Update CLI (c) 2021 Martin Bauernschmitt - FRANCOR e.V.");
 
     match matches.subcommand() {
+        Some(("list", _)) => {
+            list_interfaces();
+            Ok(())
+        }
         Some(("search", search_matches)) => {
-            let interface_name = search_matches.get_one::<String>("interface").unwrap();
             let interface_type = search_matches.get_one::<String>("type").unwrap();
+            let interface_name = search_matches
+                .get_one::<String>("interface")
+                .cloned()
+                .unwrap_or_else(|| pick_interface(interface_type));
+            let capture_file = search_matches
+                .get_one::<String>("capture")
+                .map(|s| s.as_str());
 
             if interface_type == "serial" {
-                search_for_devices(InterfaceType::Serial, &interface_name);
+                search_for_devices(InterfaceType::Serial, &interface_name, capture_file)
             } else if interface_type == "can" {
-                search_for_devices(InterfaceType::CAN, &interface_name);
+                search_for_devices(InterfaceType::CAN, &interface_name, capture_file)
             } else if interface_type == "sim" {
-                search_for_devices(InterfaceType::Sim, &interface_name);
+                search_for_devices(InterfaceType::Sim, &interface_name, capture_file)
+            } else if interface_type == "net" {
+                search_for_devices(InterfaceType::Net, &interface_name, capture_file)
+            } else if interface_type == "dfu" {
+                search_for_devices(InterfaceType::Dfu, &interface_name, capture_file)
             } else {
                 println!("Unknown interface type {}", interface_type);
+                Ok(())
             }
         }
         Some(("erase", erase_matches)) => {
-            let interface_name = erase_matches.get_one::<String>("interface").unwrap();
             let interface_type = erase_matches.get_one::<String>("type").unwrap();
+            let interface_name = erase_matches
+                .get_one::<String>("interface")
+                .cloned()
+                .unwrap_or_else(|| pick_interface(interface_type));
             let node_id = *erase_matches.get_one::<u8>("node").unwrap();
+            let mass_erase = erase_matches.get_flag("mass-erase");
 
             if interface_type == "serial" {
-                erase_device(InterfaceType::Serial, &interface_name, node_id);
+                erase_device(InterfaceType::Serial, &interface_name, node_id, mass_erase)
             } else if interface_type == "can" {
-                erase_device(InterfaceType::CAN, &interface_name, node_id);
+                erase_device(InterfaceType::CAN, &interface_name, node_id, mass_erase)
             } else if interface_type == "sim" {
-                erase_device(InterfaceType::Sim, &interface_name, node_id);
+                erase_device(InterfaceType::Sim, &interface_name, node_id, mass_erase)
+            } else if interface_type == "net" {
+                erase_device(InterfaceType::Net, &interface_name, node_id, mass_erase)
+            } else if interface_type == "dfu" {
+                erase_device(InterfaceType::Dfu, &interface_name, node_id, mass_erase)
             } else {
                 println!("Unknown interface type {}", interface_type);
+                Ok(())
             }
         }
         Some(("flash", flash_matches)) => {
-            let interface_name = flash_matches.get_one::<String>("interface").unwrap();
             let interface_type = flash_matches.get_one::<String>("type").unwrap();
+            let interface_name = flash_matches
+                .get_one::<String>("interface")
+                .cloned()
+                .unwrap_or_else(|| pick_interface(interface_type));
             let node_id = *flash_matches.get_one::<u8>("node").unwrap();
             let hex_file_path = flash_matches.get_one::<String>("hex-file").unwrap();
+            let expected_chip = flash_matches
+                .get_one::<String>("expected-chip")
+                .map(|s| s.as_str());
+            let format = flash_matches
+                .get_one::<String>("format")
+                .map(|s| {
+                    FirmwareFormat::from_str(s).ok_or_else(|| {
+                        CliError::FirmwareParse(format!(
+                            "Unknown --format \"{}\" (expected \"hex\", \"srec\", \"elf\", or \"bin\")",
+                            s
+                        ))
+                    })
+                })
+                .transpose()?;
+            let load_address = flash_matches
+                .get_one::<String>("load-address")
+                .map(|s| parse_load_address(s))
+                .transpose()?;
+            let fast = flash_matches.get_flag("fast");
+            let mass_erase = flash_matches.get_flag("mass-erase");
 
             if interface_type == "serial" {
                 println!(
@@ -233,62 +1173,252 @@ fn main() {
                 );
 
                 // Open interface
-                let interface = SerialInterface::open(interface_name, 115200).unwrap();
+                let interface =
+                    SerialInterface::open(&interface_name, 115200).map_err(CliError::InterfaceOpen)?;
 
-                // Open hex file
-                let hex_file = HexFile::from_file(hex_file_path).unwrap();
+                // Load firmware image
+                let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                    .map_err(CliError::FirmwareParse)?;
 
                 // Create device
                 let mut device = Device::new(interface);
 
                 // Init device
-                device.init().unwrap();
+                device.init().map_err(|e| CliError::Init(e.to_string()))?;
+
+                // Validate image against the target's chip database entry
+                device
+                    .validate_image(&firmware, expected_chip)
+                    .map_err(|e| CliError::Validation(e.to_string()))?;
 
                 // Flash device
-                device.flash(&hex_file).unwrap();
+                device.flash(&firmware, fast, mass_erase).map_err(flash_error_to_cli_error)?;
+
+                Ok(())
             } else if interface_type == "can" {
-                println!(
-                    "--> Flashing {} devices on CAN bus {} with node id {}",
-                    hex_file_path, interface_name, node_id
-                );
+                let broadcast = flash_matches.get_flag("broadcast");
+                let all = flash_matches.get_flag("all");
+                let nodes: Option<Vec<u8>> = flash_matches
+                    .get_many::<u8>("nodes")
+                    .map(|values| values.copied().collect());
+                let can_fd = flash_matches.get_flag("can-fd");
+                let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                    .map_err(CliError::FirmwareParse)?;
+
+                if broadcast {
+                    println!(
+                        "--> Flashing {} to every device found on CAN bus {}",
+                        hex_file_path, interface_name
+                    );
+                    flash_can_broadcast(&interface_name, &firmware, expected_chip, can_fd, fast, mass_erase)
+                } else if all {
+                    println!(
+                        "--> Flashing {} sequentially to every device found on CAN bus {}",
+                        hex_file_path, interface_name
+                    );
+                    let node_lst = CANInterface::ping_network(&interface_name)
+                        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+                    flash_can_sequential(
+                        &interface_name, &node_lst, &firmware, expected_chip, can_fd, fast, mass_erase,
+                    )
+                } else if let Some(node_lst) = nodes {
+                    println!(
+                        "--> Flashing {} sequentially to nodes {:?} on CAN bus {}",
+                        hex_file_path, node_lst, interface_name
+                    );
+                    flash_can_sequential(
+                        &interface_name, &node_lst, &firmware, expected_chip, can_fd, fast, mass_erase,
+                    )
+                } else {
+                    println!(
+                        "--> Flashing {} devices on CAN bus {} with node id {}",
+                        hex_file_path, interface_name, node_id
+                    );
+
+                    let mut interface = if can_fd {
+                        CANInterface::open_fd(&interface_name)
+                            .map_err(|e| CliError::InterfaceOpen(e.to_string()))?
+                    } else {
+                        CANInterface::open(&interface_name)
+                            .map_err(|e| CliError::InterfaceOpen(e.to_string()))?
+                    };
+                    interface
+                        .set_mode(ComMode::Specific(node_id))
+                        .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
+                    let mut device = Device::new(interface);
+                    device.init().map_err(|e| CliError::Init(e.to_string()))?;
+                    device
+                        .validate_image(&firmware, expected_chip)
+                        .map_err(|e| CliError::Validation(e.to_string()))?;
+                    device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
+                    device.flash(&firmware, fast, mass_erase).map_err(flash_error_to_cli_error)?;
+
+                    Ok(())
+                }
             } else if interface_type == "sim" {
                 // Create sim network
                 let node_lst = SIM_NODE_LST.to_vec();
-                SIMInterface::config_nodes(node_lst).unwrap();
+                SIMInterface::config_nodes(node_lst)
+                    .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
 
                 // Open interface
-                let mut interface = SIMInterface::open("").unwrap();
-                interface.set_mode(ComMode::Specific(node_id)).unwrap();
+                let mut interface = SIMInterface::open("").map_err(CliError::InterfaceOpen)?;
+                interface
+                    .set_mode(ComMode::Specific(node_id))
+                    .map_err(|e| CliError::InterfaceOpen(e.to_string()))?;
 
-                // Open hex file
-                let hex_file = HexFile::from_file(hex_file_path).unwrap();
+                // Load firmware image
+                let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                    .map_err(CliError::FirmwareParse)?;
 
                 // Create device
                 let mut device = Device::new(interface);
 
                 // Init device
-                device.init().unwrap();
+                device.init().map_err(|e| CliError::Init(e.to_string()))?;
+
+                // Validate image against the target's chip database entry
+                device
+                    .validate_image(&firmware, expected_chip)
+                    .map_err(|e| CliError::Validation(e.to_string()))?;
 
                 // Erase device
-                device.erase().unwrap();
+                device.erase(mass_erase).map_err(|e| CliError::Erase(e.to_string()))?;
 
                 // Flash device
-                device.flash(&hex_file).unwrap();
+                device.flash(&firmware, fast, mass_erase).map_err(flash_error_to_cli_error)?;
 
                 println!(
                     "--> Flashing {} devices on simulated network with node id {}",
                     hex_file_path, node_id
                 );
+
+                Ok(())
+            } else if interface_type == "net" {
+                println!("--> Flashing {} to device at {}", hex_file_path, interface_name);
+
+                let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                    .map_err(CliError::FirmwareParse)?;
+                flash_net(&interface_name, &firmware, expected_chip, fast, mass_erase)
+            } else if interface_type == "dfu" {
+                println!(
+                    "--> Flashing {} to DFU device {}",
+                    hex_file_path, interface_name
+                );
+
+                let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                    .map_err(CliError::FirmwareParse)?;
+                flash_dfu(&interface_name, &firmware)
             } else {
                 println!("Unknown interface type {}", interface_type);
+                Ok(())
             }
         }
+        Some(("verify", verify_matches)) => {
+            let interface_type = verify_matches.get_one::<String>("type").unwrap();
+            let interface_name = verify_matches
+                .get_one::<String>("interface")
+                .cloned()
+                .unwrap_or_else(|| pick_interface(interface_type));
+            let node_id = *verify_matches.get_one::<u8>("node").unwrap();
+            let hex_file_path = verify_matches.get_one::<String>("hex-file").unwrap();
+            let expected_chip = verify_matches
+                .get_one::<String>("expected-chip")
+                .map(|s| s.as_str());
+            let format = verify_matches
+                .get_one::<String>("format")
+                .map(|s| {
+                    FirmwareFormat::from_str(s).ok_or_else(|| {
+                        CliError::FirmwareParse(format!(
+                            "Unknown --format \"{}\" (expected \"hex\", \"srec\", \"elf\", or \"bin\")",
+                            s
+                        ))
+                    })
+                })
+                .transpose()?;
+            let load_address = verify_matches
+                .get_one::<String>("load-address")
+                .map(|s| parse_load_address(s))
+                .transpose()?;
+
+            let firmware = FirmwareImage::load(hex_file_path, format, load_address)
+                .map_err(CliError::FirmwareParse)?;
+
+            if interface_type == "serial" {
+                verify_device(InterfaceType::Serial, &interface_name, node_id, &firmware, expected_chip)
+            } else if interface_type == "can" {
+                verify_device(InterfaceType::CAN, &interface_name, node_id, &firmware, expected_chip)
+            } else if interface_type == "sim" {
+                verify_device(InterfaceType::Sim, &interface_name, node_id, &firmware, expected_chip)
+            } else if interface_type == "net" {
+                verify_device(InterfaceType::Net, &interface_name, node_id, &firmware, expected_chip)
+            } else if interface_type == "dfu" {
+                verify_device(InterfaceType::Dfu, &interface_name, node_id, &firmware, expected_chip)
+            } else {
+                println!("Unknown interface type {}", interface_type);
+                Ok(())
+            }
+        }
+        Some(("can-timing", timing_matches)) => {
+            let fclk = *timing_matches.get_one::<u32>("fclk").unwrap();
+            let bitrate = *timing_matches.get_one::<u32>("bitrate").unwrap();
+            let data_bitrate = timing_matches.get_one::<u32>("data-bitrate").copied();
+
+            // No single set of register limits fits every CAN controller; these mirror a common
+            // bxCAN-class controller and are meant as a reasonable default, not a hardware truth -
+            // pass the real datasheet limits instead if the target controller differs.
+            let constraints = CanTimingConstraints {
+                brp_min: 1,
+                brp_max: 1024,
+                tseg1_min: 1,
+                tseg1_max: 16,
+                tseg2_min: 1,
+                tseg2_max: 8,
+                sjw_max: 4,
+            };
+
+            let arbitration = CanBitTiming::calculate(fclk, bitrate, &constraints, CLASSIC_SAMPLE_POINT_PERMILLE)
+                .map_err(|e| CliError::Validation(e.to_string()))?;
+            println!(
+                "--> Arbitration phase: brp={} tseg1={} tseg2={} sjw={} (bitrate={} bps, sample point={:.1}%)",
+                arbitration.brp,
+                arbitration.tseg1,
+                arbitration.tseg2,
+                arbitration.sjw,
+                arbitration.achieved_bitrate,
+                arbitration.sample_point_permille as f32 / 10.0
+            );
+
+            if let Some(data_bitrate) = data_bitrate {
+                let data = CanBitTiming::calculate(fclk, data_bitrate, &constraints, FD_DATA_SAMPLE_POINT_PERMILLE)
+                    .map_err(|e| CliError::Validation(e.to_string()))?;
+                println!(
+                    "--> Data phase: brp={} tseg1={} tseg2={} sjw={} (bitrate={} bps, sample point={:.1}%)",
+                    data.brp,
+                    data.tseg1,
+                    data.tseg2,
+                    data.sjw,
+                    data.achieved_bitrate,
+                    data.sample_point_permille as f32 / 10.0
+                );
+            }
+
+            Ok(())
+        }
         _ => {
             println!("Unknown command");
+            Ok(())
         }
     }
 }
 
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
 // Tests ------------------------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -0,0 +1,77 @@
+//! Modal component stack for dialogs layered on top of a screen.
+//!
+//! Before this module, every overlay (the search progress popup, and now confirmation/error
+//! dialogs) had to be special-cased in both `ui` (to draw it on top of the base screen) and
+//! `run_app_async` (to decide whether the base screen should still see the keypress). This stack
+//! gives overlays a uniform home: push a `Box<dyn Component>` to show one, and `handle_key`/
+//! `render` take care of input precedence and draw order without the call sites needing to know
+//! what's currently on top.
+
+use crate::{App, Screen};
+use crossterm::event::KeyCode;
+use ratatui::{layout::Rect, Frame};
+
+/// Outcome of offering a key press to a `Component` on the stack.
+pub enum EventResult {
+    /// The component handled the key; the screen underneath does not see it.
+    Consumed,
+    /// The component has no use for this key; try the component below it (or, if this was the
+    /// bottom of the stack, the active screen's own handler).
+    Ignored,
+    /// Remove this component from the stack; the key is still considered handled.
+    Pop,
+    /// Remove this component from the stack and switch the active screen.
+    PushScreen(Screen),
+}
+
+/// A modal component layered on top of the active screen's base render.
+pub trait Component {
+    /// Draws the component over `area`, which is the full terminal frame. Implementations that
+    /// only occupy part of the screen are expected to compute their own sub-`Rect` and `Clear` it
+    /// first, the way `draw_search_overlay` always has.
+    fn render(&self, f: &mut Frame, app: &App, area: Rect);
+
+    /// Handles a key press. See `EventResult` for how the return value affects the stack.
+    fn handle_key(&mut self, app: &mut App, key: KeyCode) -> EventResult;
+}
+
+/// Renders every component on `app.popups` bottom-to-top, so later entries draw over earlier
+/// ones, mirroring how the stack is pushed (most recently pushed = topmost = drawn last).
+pub fn render(app: &App, f: &mut Frame) {
+    let area = f.area();
+    for index in 0..app.popups.len() {
+        // `Component::render` only needs `&App`, so borrow each component out of the `Vec`
+        // immutably one at a time instead of trying to hold the whole slice alongside `app`.
+        let popup = app.popups[index].as_ref();
+        popup.render(f, app, area);
+    }
+}
+
+/// Offers `key` to the topmost component, falling back down the stack while components return
+/// `Ignored`. Returns `true` if some component consumed the key, in which case the caller must
+/// not also dispatch it to the active screen's own handler.
+pub fn handle_key(app: &mut App, key: KeyCode) -> bool {
+    let mut index = app.popups.len();
+    while index > 0 {
+        index -= 1;
+
+        // Temporarily remove the component so `handle_key` can take `&mut App` without aliasing
+        // the `Vec` it lives in; put it back unless it asked to be popped.
+        let mut component = app.popups.remove(index);
+        match component.handle_key(app, key) {
+            EventResult::Consumed => {
+                app.popups.insert(index, component);
+                return true;
+            }
+            EventResult::Ignored => {
+                app.popups.insert(index, component);
+            }
+            EventResult::Pop => return true,
+            EventResult::PushScreen(screen) => {
+                app.current_screen = screen;
+                return true;
+            }
+        }
+    }
+    false
+}
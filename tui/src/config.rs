@@ -0,0 +1,165 @@
+//! Per-interface configuration defaults: serial baud, CAN bitrate, timeouts, and flash retries.
+//!
+//! Settings are read from a small TOML-like `key = value` file, searched for first in the
+//! working directory (`frankly_fw_update.toml`) and then in the platform config directory
+//! (`$XDG_CONFIG_HOME/frankly_fw_update/frankly_fw_update.toml`, falling back to `$HOME/.config/...`). A
+//! missing file, an unreadable one, or one with no recognized keys all just fall back to
+//! [`InterfaceConfig::default`] rather than failing the whole application — this is meant to tune
+//! defaults for a particular lab setup, not to be load-bearing configuration.
+//!
+//! Only a flat subset of TOML is supported (`#` comments, blank lines, `key = value`); section
+//! headers and nested tables are not needed for the handful of scalar settings here, so a real
+//! TOML parser dependency isn't pulled in for it, the same way `operation_message_to_json` hand-
+//! rolls its JSON rather than depending on `serde_json`.
+//!
+//! [`load_bookmarks`]/[`save_bookmarks`] persist the `FileBrowser`'s bookmarked directories the
+//! same way, just to a plain newline-separated file rather than `key = value` pairs, since a
+//! bookmark list has no keys to speak of.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CONFIG_FILE_NAME: &str = "frankly_fw_update.toml";
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.txt";
+
+/// Resolved per-interface defaults, loaded once via [`load`] and copied into background
+/// operation threads alongside `ComConnParams`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterfaceConfig {
+    /// Default serial baud rate, used by `ComConnParams::for_serial_conn`
+    pub serial_baud: u32,
+    /// Default CAN bitrate, prefilled into the `CanConfig` screen
+    pub can_bitrate: u32,
+    /// How long to wait for a response once the interface is open, in milliseconds
+    pub ack_timeout_ms: u64,
+    /// Extra attempts for `device.init()`/`device.flash()` after a transient failure, on top of
+    /// the first attempt (`0` means no retry)
+    pub flash_retry_count: u32,
+}
+
+impl Default for InterfaceConfig {
+    fn default() -> Self {
+        InterfaceConfig {
+            serial_baud: 115200,
+            can_bitrate: 500_000,
+            ack_timeout_ms: 500,
+            flash_retry_count: 0,
+        }
+    }
+}
+
+impl InterfaceConfig {
+    /// The configured ack timeout as a `Duration`, for `ComInterface::set_timeout`
+    pub fn ack_timeout(&self) -> Duration {
+        Duration::from_millis(self.ack_timeout_ms)
+    }
+}
+
+/// Searches the working directory, then the platform config directory, for a config file and
+/// applies any recognized keys on top of [`InterfaceConfig::default`].
+pub fn load() -> InterfaceConfig {
+    let mut config = InterfaceConfig::default();
+
+    if let Some(text) = find_and_read_config() {
+        apply(&mut config, &text);
+    }
+
+    config
+}
+
+fn find_and_read_config() -> Option<String> {
+    if let Ok(text) = std::fs::read_to_string(CONFIG_FILE_NAME) {
+        return Some(text);
+    }
+
+    std::fs::read_to_string(platform_config_dir()?.join(CONFIG_FILE_NAME)).ok()
+}
+
+/// `$XDG_CONFIG_HOME/frankly_fw_update`, falling back to `$HOME/.config/frankly_fw_update`; `None`
+/// if neither environment variable is set.
+fn platform_config_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .or_else(|| std::env::var("HOME").ok().map(|home| format!("{}/.config", home)))?;
+    Some(PathBuf::from(config_dir).join("frankly_fw_update"))
+}
+
+/// Loads the `FileBrowser`'s saved bookmark directories, one absolute path per line. A missing
+/// file (nothing bookmarked yet) or an unreadable one both just yield no bookmarks, the same
+/// "fall back rather than fail" philosophy as [`load`].
+///
+/// Unlike `load`/`find_and_read_config`, there's no working-directory override here: bookmarks are
+/// written by the app itself (via [`save_bookmarks`]) rather than hand-edited per project, so only
+/// the platform config directory is consulted.
+pub fn load_bookmarks() -> Vec<PathBuf> {
+    let Some(dir) = platform_config_dir() else {
+        return Vec::new();
+    };
+    let Ok(text) = std::fs::read_to_string(dir.join(BOOKMARKS_FILE_NAME)) else {
+        return Vec::new();
+    };
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Persists `bookmarks`, one absolute path per line, creating the platform config directory if it
+/// doesn't exist yet. Errors (read-only filesystem, missing `$HOME`) are silently ignored, the same
+/// rationale as [`load_bookmarks`] — bookmarks are a convenience, not load-bearing state.
+pub fn save_bookmarks(bookmarks: &[PathBuf]) {
+    let Some(dir) = platform_config_dir() else {
+        return;
+    };
+    if std::fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let text = bookmarks
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let _ = std::fs::write(dir.join(BOOKMARKS_FILE_NAME), text);
+}
+
+/// Applies every recognized `key = value` line in `text` to `config`. Unknown keys and lines
+/// that fail to parse are silently skipped, so a typo in one setting doesn't lose the rest.
+fn apply(config: &mut InterfaceConfig, text: &str) {
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim().trim_matches('"');
+
+        match key {
+            "serial_baud" => {
+                if let Ok(v) = value.parse() {
+                    config.serial_baud = v;
+                }
+            }
+            "can_bitrate" => {
+                if let Ok(v) = value.parse() {
+                    config.can_bitrate = v;
+                }
+            }
+            "ack_timeout_ms" => {
+                if let Ok(v) = value.parse() {
+                    config.ack_timeout_ms = v;
+                }
+            }
+            "flash_retry_count" => {
+                if let Ok(v) = value.parse() {
+                    config.flash_retry_count = v;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Fuzzy subsequence matching for the incremental filters on `DeviceList` and `FileBrowser`.
+//!
+//! [`rank`] is deliberately simple rather than a full fuzzy-finder port: it only needs to narrow
+//! a few dozen device names or directory entries per keystroke, not rank a fuzzy-find corpus of
+//! thousands, so there's no need for the bitap/Smith-Waterman machinery a general-purpose matcher
+//! would use.
+
+/// Returns the indices of `candidates` whose text contains `query` as a (case-insensitive)
+/// subsequence, sorted by descending match score (ties keep `candidates`' original order).
+///
+/// An empty `query` matches everything and returns `candidates` in their original order, so
+/// clearing the filter text shows the full list rather than an empty one.
+pub fn rank(query: &str, candidates: &[&str]) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..candidates.len()).collect();
+    }
+
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+
+    let mut scored: Vec<(usize, u32)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| score(&query, candidate).map(|(score, _)| (i, score)))
+        .collect();
+
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Character indices into `candidate` that `query` matched against, for highlighting a
+/// [`rank`]ed entry in its `ListItem`. Empty for an empty `query` (nothing to highlight) or if
+/// `query` isn't a subsequence of `candidate` at all.
+pub fn highlight_positions(query: &str, candidate: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    score(&query, candidate)
+        .map(|(_, positions)| positions)
+        .unwrap_or_default()
+}
+
+/// Matches `query` against `candidate` as a subsequence, taking the earliest occurrence of each
+/// query character in turn (a later, more contiguous placement is not searched for); returns `None` if `query` isn't a subsequence at all,
+/// otherwise the score alongside the matched character positions (for highlighting).
+///
+/// Scoring, per matched character: a flat base point, a large bonus for directly continuing the
+/// previous match (rewards contiguous runs over scattered hits), and a bonus for landing right
+/// after a path separator or other word boundary (rewards matching a whole path segment or word,
+/// e.g. typing "fw" to jump to "firm`w`are.hex" after the `/`).
+fn score(query: &[char], candidate: &str) -> Option<(u32, Vec<usize>)> {
+    const CONTIGUOUS_BONUS: u32 = 10;
+    const BOUNDARY_BONUS: u32 = 8;
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut query_pos = 0;
+    let mut score = 0u32;
+    let mut prev_match_pos: Option<usize> = None;
+    let mut positions = Vec::with_capacity(query.len());
+
+    for (pos, &c) in lower.iter().enumerate() {
+        if query_pos >= query.len() {
+            break;
+        }
+        if c != query[query_pos] {
+            continue;
+        }
+
+        score += 1;
+        if prev_match_pos == Some(pos.wrapping_sub(1)) {
+            score += CONTIGUOUS_BONUS;
+        }
+        if pos == 0 || is_boundary(chars[pos - 1]) {
+            score += BOUNDARY_BONUS;
+        }
+
+        positions.push(pos);
+        prev_match_pos = Some(pos);
+        query_pos += 1;
+    }
+
+    (query_pos == query.len()).then_some((score, positions))
+}
+
+/// True for characters that separate path segments or words, i.e. ones after which a match
+/// should score as if it were a fresh start.
+fn is_boundary(c: char) -> bool {
+    matches!(c, '/' | '\\' | '-' | '_' | '.' | ' ')
+}
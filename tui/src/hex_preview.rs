@@ -0,0 +1,172 @@
+//! Lightweight Intel HEX parsing for the `FileBrowser` preview pane.
+//!
+//! This is intentionally separate from `frankly_fw_update_common`'s firmware-loading hex parser:
+//! that one builds a byte-addressable `FirmwareDataRaw` map for flashing, while this one only
+//! needs the shape of the file (total size, contiguous address ranges, whether every record's
+//! checksum is valid) to render a quick memory map before the user commits to flashing.
+
+const HEX_LINE_MIN_CHARS: usize = 10;
+
+/// Summary of a parsed Intel HEX file, as shown in the `FileBrowser` preview pane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    /// Total number of data bytes across all type-00 records
+    pub total_bytes: u64,
+    /// Contiguous `[start, end)` byte-address ranges covered by data records, merged and sorted
+    pub ranges: Vec<(u32, u32)>,
+}
+
+/// A record failed to parse or its checksum didn't validate; `line` is 1-indexed into the source
+/// text, for the "invalid checksum at line N" banner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parses `hex_data` as an Intel HEX file and summarizes its memory map.
+///
+/// Every `:`-prefixed line is parsed and its checksum verified; the first failure aborts with a
+/// [`PreviewError`] naming the offending line rather than trying to recover partial data, since a
+/// preview of a corrupt file should say so rather than guess. Lines not starting with `:` (blank
+/// lines, stray whitespace) are skipped, matching the tolerance of the flashing-path hex parser.
+pub fn parse(hex_data: &str) -> Result<Preview, PreviewError> {
+    let mut upper_base: u32 = 0;
+    let mut total_bytes: u64 = 0;
+    let mut data_ranges: Vec<(u32, u32)> = Vec::new();
+
+    for (idx, raw_line) in hex_data.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line.trim_end_matches(['\r', '\n']);
+        if !line.starts_with(':') {
+            continue;
+        }
+
+        let record = parse_record(&line[1..]).map_err(|err| PreviewError {
+            line: line_no,
+            message: match err {
+                RecordError::ChecksumMismatch => {
+                    format!("invalid checksum at line {}", line_no)
+                }
+                RecordError::Malformed(reason) => {
+                    format!("{} at line {}", reason, line_no)
+                }
+            },
+        })?;
+
+        match record.record_type {
+            0x00 => {
+                let address = upper_base | record.offset as u32;
+                total_bytes += record.data.len() as u64;
+                data_ranges.push((address, address + record.data.len() as u32));
+            }
+            0x01 => break,
+            0x02 => {
+                upper_base = (word_from(&record.data) as u32) << 4;
+            }
+            0x04 => {
+                upper_base = (word_from(&record.data) as u32) << 16;
+            }
+            _ => {}
+        }
+    }
+
+    if data_ranges.is_empty() {
+        return Err(PreviewError {
+            line: 0,
+            message: "no data records found".to_string(),
+        });
+    }
+
+    Ok(Preview {
+        total_bytes,
+        ranges: merge_ranges(data_ranges),
+    })
+}
+
+fn word_from(data: &[u8]) -> u16 {
+    (*data.first().unwrap_or(&0) as u16) << 8 | *data.get(1).unwrap_or(&0) as u16
+}
+
+struct Record {
+    record_type: u8,
+    offset: u16,
+    data: Vec<u8>,
+}
+
+/// Why a record failed to parse; kept separate from the line number (which only `parse` knows)
+/// so the formatted message can put "at line N" after either flavor of failure.
+enum RecordError {
+    ChecksumMismatch,
+    Malformed(String),
+}
+
+/// Parses and checksum-validates one record, `line` being everything after the leading `:`.
+fn parse_record(line: &str) -> Result<Record, RecordError> {
+    if line.len() < HEX_LINE_MIN_CHARS {
+        return Err(RecordError::Malformed("record too short".to_string()));
+    }
+
+    let byte_count = u8::from_str_radix(&line[0..2], 16)
+        .map_err(|_| RecordError::Malformed("bad byte count".to_string()))?;
+    let expected_len = byte_count as usize * 2 + HEX_LINE_MIN_CHARS;
+    if line.len() != expected_len {
+        return Err(RecordError::Malformed(format!(
+            "byte count {} doesn't match record length",
+            byte_count
+        )));
+    }
+
+    let offset = u16::from_str_radix(&line[2..6], 16)
+        .map_err(|_| RecordError::Malformed("bad address".to_string()))?;
+    let record_type = u8::from_str_radix(&line[6..8], 16)
+        .map_err(|_| RecordError::Malformed("bad record type".to_string()))?;
+
+    let mut data = Vec::with_capacity(byte_count as usize);
+    for i in 0..byte_count as usize {
+        let byte = u8::from_str_radix(&line[8 + i * 2..10 + i * 2], 16)
+            .map_err(|_| RecordError::Malformed("bad data byte".to_string()))?;
+        data.push(byte);
+    }
+
+    let checksum = u8::from_str_radix(
+        &line[8 + byte_count as usize * 2..10 + byte_count as usize * 2],
+        16,
+    )
+    .map_err(|_| RecordError::Malformed("bad checksum".to_string()))?;
+
+    let mut sum = byte_count;
+    sum = sum.wrapping_add((offset >> 8) as u8);
+    sum = sum.wrapping_add((offset & 0xFF) as u8);
+    sum = sum.wrapping_add(record_type);
+    for &byte in &data {
+        sum = sum.wrapping_add(byte);
+    }
+    let expected_checksum = (!sum).wrapping_add(1);
+
+    if expected_checksum != checksum {
+        return Err(RecordError::ChecksumMismatch);
+    }
+
+    Ok(Record {
+        record_type,
+        offset,
+        data,
+    })
+}
+
+/// Sorts and merges overlapping/adjacent `[start, end)` ranges into their minimal covering set.
+fn merge_ranges(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_by_key(|&(start, _)| start);
+
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
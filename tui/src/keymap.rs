@@ -0,0 +1,278 @@
+//! Resolves raw key presses to named [`Action`]s, so handlers match on what the user meant
+//! instead of a hardcoded `KeyCode`.
+//!
+//! Bindings come from [`KeyMap::default`] (arrow keys, vim-style `j`/`k`, `F5`, `q`, …) with any
+//! overrides from a user keymap file layered on top, the same "defaults, then an optional file on
+//! top" shape as `config::load`. A binding can be scoped to one [`Screen`] (`device_list.refresh =
+//! F5`) or left global (`refresh = F5`); a screen-specific entry wins over a global one for that
+//! screen. A `leader` key can also be configured to gate an action (typically `Quit`) behind a
+//! two-key sequence instead of firing on the bare key.
+//!
+//! Only a flat subset of TOML is supported, same rationale as `config.rs`: `#` comments, blank
+//! lines, `key = value`, with no section headers or nested tables needed for this table of scalar
+//! bindings.
+
+use crate::Screen;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+const KEYMAP_FILE_NAME: &str = "frankly_fw_update_keymap.toml";
+
+/// A named UI action a key press can resolve to, independent of which physical key triggers it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    NavUp,
+    NavDown,
+    Select,
+    Back,
+    Refresh,
+    Quit,
+    ToggleFileBrowser,
+}
+
+/// A key press identity: the code plus whichever modifiers must be held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(event: KeyEvent) -> Self {
+        KeyBinding {
+            code: event.code,
+            modifiers: event.modifiers,
+        }
+    }
+}
+
+/// Resolves key presses to [`Action`]s for every screen, with an optional leader-gated action.
+pub struct KeyMap {
+    /// Bindings that apply on every screen unless overridden in `per_screen`.
+    global: HashMap<KeyBinding, Action>,
+    /// Screen-specific bindings, checked before falling back to `global`.
+    per_screen: HashMap<Screen, HashMap<KeyBinding, Action>>,
+    /// The leader key, if one is configured.
+    leader: Option<KeyBinding>,
+    /// Bindings only active for the key press immediately following the leader key.
+    gated: HashMap<KeyBinding, Action>,
+}
+
+impl KeyMap {
+    /// Built-in bindings: arrow keys and vim-style `j`/`k` for navigation, `Enter`/`Esc` for
+    /// select/back, `F5` to refresh, `Tab` to open the file browser, and a plain `q` to quit (not
+    /// leader-gated unless a keymap file says otherwise).
+    fn defaults() -> KeyMap {
+        let mut global = HashMap::new();
+        global.insert(plain(KeyCode::Up), Action::NavUp);
+        global.insert(plain(KeyCode::Char('k')), Action::NavUp);
+        global.insert(plain(KeyCode::Down), Action::NavDown);
+        global.insert(plain(KeyCode::Char('j')), Action::NavDown);
+        global.insert(plain(KeyCode::Enter), Action::Select);
+        global.insert(plain(KeyCode::Esc), Action::Back);
+        global.insert(plain(KeyCode::F(5)), Action::Refresh);
+        global.insert(plain(KeyCode::Tab), Action::ToggleFileBrowser);
+        global.insert(plain(KeyCode::Char('q')), Action::Quit);
+
+        KeyMap {
+            global,
+            per_screen: HashMap::new(),
+            leader: None,
+            gated: HashMap::new(),
+        }
+    }
+
+    /// Loads `KeyMap::defaults()`, then applies a keymap file if one is found (working directory
+    /// first, then the platform config directory), the same search order as `config::load`.
+    pub fn load() -> KeyMap {
+        let mut keymap = KeyMap::defaults();
+
+        if let Some(text) = find_and_read_keymap() {
+            apply(&mut keymap, &text);
+        }
+
+        keymap
+    }
+
+    /// Resolves a key press on `screen` to an action, if any binding matches. A per-screen
+    /// binding takes precedence over a global one for the same key.
+    pub fn resolve(&self, screen: Screen, event: KeyEvent) -> Option<Action> {
+        let binding = KeyBinding::from(event);
+        if let Some(action) = self
+            .per_screen
+            .get(&screen)
+            .and_then(|bindings| bindings.get(&binding))
+        {
+            return Some(*action);
+        }
+        self.global.get(&binding).copied()
+    }
+
+    /// True if `event` is this keymap's configured leader key.
+    pub fn is_leader(&self, event: KeyEvent) -> bool {
+        self.leader == Some(KeyBinding::from(event))
+    }
+
+    /// Resolves a key press that immediately follows the leader key. Only bindings registered via
+    /// `gated.<action> = <key>` in the keymap file match here.
+    pub fn resolve_gated(&self, event: KeyEvent) -> Option<Action> {
+        self.gated.get(&KeyBinding::from(event)).copied()
+    }
+}
+
+fn plain(code: KeyCode) -> KeyBinding {
+    KeyBinding {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+fn find_and_read_keymap() -> Option<String> {
+    if let Ok(text) = std::fs::read_to_string(KEYMAP_FILE_NAME) {
+        return Some(text);
+    }
+
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .or_else(|| std::env::var("HOME").ok().map(|home| format!("{}/.config", home)))?;
+
+    std::fs::read_to_string(format!(
+        "{}/frankly_fw_update/{}",
+        config_dir, KEYMAP_FILE_NAME
+    ))
+    .ok()
+}
+
+/// Applies every recognized `key = value` line in `text` to `keymap`. Unknown keys, unknown
+/// actions, and key specs that fail to parse are silently skipped, mirroring `config::apply`.
+fn apply(keymap: &mut KeyMap, text: &str) {
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let specs: Vec<KeyBinding> = value.split(',').filter_map(|s| parse_key_spec(s.trim())).collect();
+
+        if key == "leader" {
+            keymap.leader = specs.into_iter().next();
+            continue;
+        }
+
+        if let Some(action_name) = key.strip_prefix("gated.") {
+            let Some(action) = parse_action(action_name) else {
+                continue;
+            };
+            for binding in specs {
+                keymap.gated.insert(binding, action);
+                // A binding that's explicitly gated behind the leader shouldn't also fire bare,
+                // so drop any default/global entry that mapped the same key to the same action.
+                if keymap.global.get(&binding) == Some(&action) {
+                    keymap.global.remove(&binding);
+                }
+            }
+            continue;
+        }
+
+        if let Some((screen_name, action_name)) = key.split_once('.') {
+            let Some(screen) = parse_screen(screen_name) else {
+                continue;
+            };
+            let Some(action) = parse_action(action_name) else {
+                continue;
+            };
+            let bindings = keymap.per_screen.entry(screen).or_default();
+            for binding in specs {
+                bindings.insert(binding, action);
+            }
+            continue;
+        }
+
+        let Some(action) = parse_action(key) else {
+            continue;
+        };
+        for binding in specs {
+            keymap.global.insert(binding, action);
+        }
+    }
+}
+
+fn parse_action(name: &str) -> Option<Action> {
+    match name {
+        "nav_up" => Some(Action::NavUp),
+        "nav_down" => Some(Action::NavDown),
+        "select" => Some(Action::Select),
+        "back" => Some(Action::Back),
+        "refresh" => Some(Action::Refresh),
+        "quit" => Some(Action::Quit),
+        "toggle_file_browser" => Some(Action::ToggleFileBrowser),
+        _ => None,
+    }
+}
+
+fn parse_screen(name: &str) -> Option<Screen> {
+    match name {
+        "interface_type_selection" => Some(Screen::InterfaceTypeSelection),
+        "interface_selection" => Some(Screen::InterfaceSelection),
+        "can_config" => Some(Screen::CanConfig),
+        "net_config" => Some(Screen::NetConfig),
+        "searching" => Some(Screen::Searching),
+        "device_list" => Some(Screen::DeviceList),
+        "command_menu" => Some(Screen::CommandMenu),
+        "hex_file_input" => Some(Screen::HexFileInput),
+        "file_browser" => Some(Screen::FileBrowser),
+        "executing" => Some(Screen::Executing),
+        "log_view" => Some(Screen::LogView),
+        "results" => Some(Screen::Results),
+        _ => None,
+    }
+}
+
+/// Parses a key spec like `"q"`, `"F5"`, `"Up"`, or `"ctrl+shift+x"` into a `KeyBinding`.
+fn parse_key_spec(spec: &str) -> Option<KeyBinding> {
+    if spec.is_empty() {
+        return None;
+    }
+
+    let mut modifiers = KeyModifiers::NONE;
+    let mut parts = spec.split('+');
+    let mut last = parts.next()?;
+    for part in parts {
+        modifiers |= match last.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+        last = part;
+    }
+
+    let code = match last {
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Space" => KeyCode::Char(' '),
+        _ if last.len() >= 2 && (last.starts_with('F') || last.starts_with('f')) => {
+            last[1..].parse::<u8>().ok().map(KeyCode::F)?
+        }
+        _ => {
+            let mut chars = last.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c)
+        }
+    };
+
+    Some(KeyBinding { code, modifiers })
+}
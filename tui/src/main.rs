@@ -8,17 +8,26 @@
 //! The TUI follows a **screen-based state machine** architecture with background task execution:
 //!
 //! ```text
-//! InterfaceTypeSelection → InterfaceSelection → Searching → DeviceList
-//!                                                              ↓
-//!                                                         CommandMenu
-//!                                                              ↓
-//!                                                       HexFileInput ←→ FileBrowser
-//!                                                              ↓
-//!                                                          Executing
-//!                                                              ↓
-//!                                                           Results
+//! InterfaceTypeSelection → InterfaceSelection → [CanConfig] → Searching → DeviceList
+//!          ↓ (Net)
+//!       NetConfig ───────────────────────────────→ Searching
+//!                                                                            ↓
+//!                                                                       CommandMenu
+//!                                                                            ↓
+//!                                                                     HexFileInput ←→ FileBrowser
+//!                                                                            ↓
+//!                                                                        Executing
+//!                                                                            ↓
+//!                                                                  [LogView] → Results
 //! ```
 //!
+//! `CanConfig` is only entered when the selected CAN link is reported down by netlink; it lets
+//! the user set a bitrate and bring the link up before `Searching` runs. `NetConfig` is entered
+//! instead of `InterfaceSelection` for the `Net` interface type, since there is nothing to
+//! enumerate locally: the user types the `proto:host:port` address of the bridge directly.
+//! `LogView` is only entered after a successful `Flash`, so the user can watch the freshly
+//! flashed firmware boot before moving on to `Results`.
+//!
 //! ## Key Features
 //!
 //! - **Non-blocking Operations**: Long-running operations (search, erase, flash) execute in
@@ -26,9 +35,32 @@
 //! - **Live Progress Updates**: Real-time progress bars for erase/flash operations via
 //!   message-passing channels
 //! - **Device Discovery**: Automatic scanning for devices on selected interface, with F5 refresh
-//! - **File Browser**: Interactive filesystem navigation for selecting hex files
+//! - **Hotplug Awareness**: `InterfaceSelection` live-updates as adapters are plugged/unplugged,
+//!   backed by a udev monitor with a polling fallback. `DeviceList` does the same for devices:
+//!   a newly plugged adapter is probed automatically (libusb hotplug for Serial, udev for CAN)
+//!   and added to the list without a manual re-search
+//! - **File Browser**: Interactive filesystem navigation for selecting hex files, with a
+//!   non-recursive `notify` watcher on the current directory so files rebuilt or dropped in by an
+//!   external toolchain show up without a manual F5. `~`/`` ` `` quick-jump to `$HOME` and the
+//!   filesystem root, and persisted bookmarks (`b` to list, `m` to add the current directory) give
+//!   one-keypress access to frequently used firmware directories
 //! - **History Management**: Remembers last 10 firmware file paths for quick reuse
-//! - **Multi-Interface Support**: Works with Serial, CAN, and SIM (simulated) interfaces
+//! - **Multi-Interface Support**: Works with Serial, CAN, SIM (simulated), and Net (TCP/UDP
+//!   bridge) interfaces; CAN links report their real up/down state and bitrate via netlink, with
+//!   a prompt to configure a down link instead of a silent empty search
+//! - **Post-Flash Log View**: After a successful `Flash`, automatically attaches to the target's
+//!   RTT log channel (decoding defmt frames when a sibling `.elf` is found next to the flashed
+//!   `.hex`), falling back to plain UTF-8 serial lines, so the user can confirm the new firmware
+//!   boots before leaving the tool
+//! - **Scriptable Control Pipe**: `--session <dir>` opens named pipes (see `session_pipe`) that
+//!   let an external harness drive the running TUI (`msg_in`) and observe its progress and
+//!   results (`progress_out`/`result_out`/`devices_out`) without replacing the interactive screen
+//! - **Modal Popups**: A small component stack (see `compositor`) layers confirmation dialogs
+//!   before destructive commands (`Erase`, `Flash`) and error popups over whichever screen raised
+//!   them, on top of the screen state machine below
+//! - **Rebindable Keys**: `DeviceList` and the global quit shortcut resolve key presses to named
+//!   actions via `keymap`, which loads user overrides (including a leader key to gate `Quit`
+//!   behind a two-key sequence) on top of built-in defaults
 //!
 //! ## Message Passing Architecture
 //!
@@ -39,38 +71,66 @@
 //! ─────────────────          ─────────            ─────────
 //! device.erase()      ──>  EraseProgress(2/10) ──> Update progress bar
 //! device.flash()      ──>  FlashProgress(5/60) ──> Update status message
-//! operation complete  ──>  Complete            ──> Transition to Results screen
+//! operation complete  ──>  Complete            ──> Transition to LogView (Flash) or Results
+//! log capture thread  ──>  Line("boot ok")     ──> Append to the LogView scrollback
 //! ```
 //!
 //! The UI thread polls channels every 100ms using `try_recv()` to maintain responsiveness.
 //!
 //! ## Screen Flow Details
 //!
-//! 1. **InterfaceTypeSelection**: Choose between SIM, Serial, or CAN
-//! 2. **InterfaceSelection**: Select specific interface (e.g., /dev/ttyACM0, can0)
-//! 3. **Searching**: Background device discovery with progress overlay
-//! 4. **DeviceList**: Display found devices, select target device
-//! 5. **CommandMenu**: Choose operation (Reset, Erase, Flash)
-//! 6. **HexFileInput**: Enter firmware path (with history) or press Tab for browser
-//! 7. **FileBrowser**: Navigate filesystem to select .hex file
-//! 8. **Executing**: Live progress display during operation execution
-//! 9. **Results**: Show operation outcome (success/error)
+//! 1. **InterfaceTypeSelection**: Choose between SIM, Serial, CAN, or Net
+//! 2. **InterfaceSelection**: Select specific interface (e.g., /dev/ttyACM0, can0), reported via
+//!    netlink with real up/down state for CAN links (Net skips this, see `NetConfig`)
+//! 3. **CanConfig**: Set a bitrate and bring a down classic CAN link up (skipped otherwise)
+//! 3b. **NetConfig**: Enter the `proto:host:port` address of a Net interface (Net only)
+//! 4. **Searching**: Background device discovery with progress overlay
+//! 5. **DeviceList**: Display found devices, select target device
+//! 6. **CommandMenu**: Choose operation (Reset, Erase, Flash, Verify, ReadMemory)
+//! 7. **HexFileInput**: Enter firmware path (with history) or press Tab for browser
+//! 8. **FileBrowser**: Navigate filesystem to select .hex file
+//! 9. **Executing**: Live progress display during operation execution
+//! 10. **LogView**: Live RTT/defmt (or serial) log of the target, shown after a successful Flash
+//! 11. **Results**: Show operation outcome (success/error)
 //!
 //! ## Threading Model
 //!
-//! - **Main Thread**: UI rendering and event handling (60 FPS with 100ms poll)
+//! - **Main Task**: UI rendering and event handling, driven by a small tokio runtime (see
+//!   `run_app`/`run_app_async`) that blocks on crossterm's `EventStream` instead of polling on a
+//!   fixed timer, redrawing only when a `dirty` flag says the screen actually changed
 //! - **Background Threads**: Device operations (spawned via `thread::spawn`)
-//! - **Communication**: Unidirectional via `mpsc::channel` (background → main)
-
+//! - **Communication**: Unidirectional via `mpsc::channel` (background → main); since that
+//!   channel isn't `.await`-able, a short fallback tick keeps draining it with `try_recv()`
+//!   while `Screen::Searching`/`Screen::Executing` is active or another background channel is open
+
+mod compositor;
+mod config;
+mod fuzzy;
+mod hex_preview;
+mod keymap;
+mod session_pipe;
+
+use compositor::{Component, EventResult};
+use keymap::Action;
+
+use clap::{Arg, ArgAction, Command as ClapCommand};
+use config::InterfaceConfig;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, EventStream, KeyCode, KeyEventKind, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures::StreamExt;
 use frankly_fw_update_common::francor::franklyboot::{
     com::{
-        can::CANInterface, serial::SerialInterface, sim::SIMInterface, ComConnParams, ComInterface,
-        ComMode,
+        can::{configure_can_link, discover_can_links, CANInterface, CanLinkInfo},
+        net::{NetInterface, NetProtocol},
+        serial::SerialInterface,
+        sim::SIMInterface,
+        ComConnParams, ComInterface, ComMode,
     },
     device::Device,
     firmware::hex_file::HexFile,
@@ -84,12 +144,16 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
+use notify::Watcher;
+use rusb::UsbContext;
+use std::collections::VecDeque;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// Default list of simulated device node IDs for testing
 const SIM_NODE_LST: [u8; 4] = [1, 3, 31, 8];
@@ -106,6 +170,7 @@ const SIM_NODE_LST: [u8; 4] = [1, 3, 31, 8];
 /// - **Sim**: Simulated devices for testing without hardware
 /// - **Serial**: UART/USB serial connections (single device per port)
 /// - **CAN**: CAN bus networks (supports multiple devices on single bus)
+/// - **Net**: TCP/UDP bridge reached at a manually entered `host:port` (see `NetConfig`)
 #[derive(Debug, Clone, PartialEq)]
 #[allow(clippy::upper_case_acronyms)]
 enum InterfaceType {
@@ -115,6 +180,8 @@ enum InterfaceType {
     Serial,
     /// CAN bus interface
     CAN,
+    /// TCP/UDP network interface
+    Net,
 }
 
 impl InterfaceType {
@@ -124,8 +191,54 @@ impl InterfaceType {
             InterfaceType::Sim => "SIM",
             InterfaceType::Serial => "Serial",
             InterfaceType::CAN => "CAN",
+            InterfaceType::Net => "Net",
+        }
+    }
+}
+
+/// Parses a Net interface address of the form `tcp:host:port` or `udp:host:port`, as entered on
+/// `NetConfig` or given via `--interface net:tcp:host:port` in headless mode.
+fn parse_net_address(spec: &str) -> Result<(NetProtocol, String, u16), String> {
+    let (scheme, host_port) = spec.split_once(':').ok_or_else(|| {
+        format!(
+            "Network address \"{}\" must be \"tcp:host:port\" or \"udp:host:port\"",
+            spec
+        )
+    })?;
+
+    let protocol = match scheme {
+        "tcp" => NetProtocol::Tcp,
+        "udp" => NetProtocol::Udp,
+        other => return Err(format!("Unknown network scheme \"{}\" (expected tcp/udp)", other)),
+    };
+
+    let (host, port) = host_port.rsplit_once(':').ok_or_else(|| {
+        format!(
+            "Network address \"{}\" must be \"tcp:host:port\" or \"udp:host:port\"",
+            spec
+        )
+    })?;
+
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a valid port number", port))?;
+
+    Ok((protocol, host.to_string(), port))
+}
+
+/// Retries `op` up to `retry_count` extra times after its first attempt, used to ride out a
+/// transient interface error (a dropped CAN frame, a serial port hiccup) on `device.init()`/
+/// `device.flash()` rather than failing the whole operation. `retry_count` of `0` (the default)
+/// makes this a single attempt, i.e. today's behavior.
+fn with_retry<T>(retry_count: u32, mut op: impl FnMut() -> Result<T, Error>) -> Result<T, Error> {
+    let mut last_err = None;
+    for _ in 0..=retry_count {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => last_err = Some(e),
         }
     }
+    Err(last_err.expect("loop runs at least once"))
 }
 
 /// Represents a discovered device on the network or interface.
@@ -142,18 +255,27 @@ struct DiscoveredDevice {
     display_name: String,
     /// Full device information string (VID, PID, PRD, UID)
     device_info: String,
+    /// Interface instance this device was found on (e.g. "/dev/ttyACM0", "can0")
+    ///
+    /// Lets the hotplug monitor remove exactly the entries that belonged to an interface that
+    /// was just unplugged, rather than clearing the whole list.
+    interface_name: String,
 }
 
 /// Application screen states representing the UI state machine.
 ///
 /// Each variant corresponds to a distinct screen in the TUI. Navigation between
 /// screens follows the flow defined in the module-level documentation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Screen {
     /// Initial screen: Select interface type (SIM/Serial/CAN)
     InterfaceTypeSelection,
     /// Select specific interface instance (e.g., ttyACM0, can0)
     InterfaceSelection,
+    /// Set the bitrate and bring a down classic CAN link up before searching it
+    CanConfig,
+    /// Enter the `proto:host:port` address of a Net interface before searching it
+    NetConfig,
     /// Searching for devices (shows progress overlay)
     Searching,
     /// Display list of discovered devices
@@ -166,6 +288,8 @@ enum Screen {
     FileBrowser,
     /// Executing operation with live progress display
     Executing,
+    /// Live RTT/defmt (or serial) log view of the target, shown after a successful Flash
+    LogView,
     /// Show operation results (success/error)
     Results,
 }
@@ -179,15 +303,30 @@ enum Command {
     Erase,
     /// Flash new firmware from hex file
     Flash,
+    /// Re-read the flashed application region and diff it against a hex file
+    Verify,
+    /// Stream the device's application region to a coredump ELF for offline analysis
+    ReadMemory,
 }
 
 impl Command {
+    /// All selectable commands, in the order they appear in `CommandMenu`
+    const ALL: [Command; 5] = [
+        Command::Reset,
+        Command::Erase,
+        Command::Flash,
+        Command::Verify,
+        Command::ReadMemory,
+    ];
+
     /// Returns the human-readable display name for this command
     fn as_str(&self) -> &str {
         match self {
             Command::Reset => "Reset Device",
             Command::Erase => "Erase Application",
             Command::Flash => "Flash Firmware",
+            Command::Verify => "Verify Firmware",
+            Command::ReadMemory => "Read Memory (Coredump)",
         }
     }
 }
@@ -202,6 +341,9 @@ enum OperationMessage {
     Progress(ProgressUpdate),
     /// Device identification information retrieved after connection
     DeviceInfo(String),
+    /// Addresses where `spawn_verify` found the device's flash disagreeing with the hex file;
+    /// empty means the verify passed. Sent once, right before the terminal `Complete`/`Error`.
+    VerifyResult(Vec<u32>),
     /// Operation completed successfully
     Complete,
     /// Operation failed with error message
@@ -220,6 +362,60 @@ enum SearchMessage {
     Complete,
     /// Search failed with error message
     Error(String),
+    /// An interface matching the search's type was plugged in after the initial search
+    /// completed; `process_search_messages` probes it for devices
+    InterfaceArrived(String),
+    /// An interface matching the search's type was unplugged; every `DiscoveredDevice` found on
+    /// it is removed from `discovered_devices`
+    InterfaceDeparted(String),
+}
+
+/// Messages sent from the background serial port probe thread to the UI thread.
+///
+/// Opening a serial port to check whether it is accessible can block for up to its configured
+/// timeout, so probing runs in its own thread (mirroring `SearchMessage`) instead of the UI loop.
+#[derive(Debug)]
+enum ProbeMessage {
+    /// A serial port was probed and found to be accessible
+    PortFound(String),
+    /// Every candidate port has been probed
+    Complete,
+}
+
+/// Hotplug events for the interface enumerated on the `InterfaceSelection` screen.
+///
+/// Streamed from a background udev monitor (or, on platforms without udev, a polling
+/// fallback) so `available_interfaces` stays current without the user pressing F5.
+#[derive(Debug)]
+enum InterfaceEvent {
+    /// An interface matching the current selection's subsystem was plugged in
+    Added(String),
+    /// An interface matching the current selection's subsystem was unplugged
+    Removed(String),
+}
+
+/// Debounced filesystem-change signal for `file_browser_current_dir`, streamed from a background
+/// `notify` watcher so `FileBrowser` stays current when an external toolchain rebuilds or drops a
+/// hex file into the directory. Carries no event detail (path, kind) since the watcher thread
+/// already collapses a burst of individual `notify` events into one signal and the UI just
+/// re-scans the whole directory in response.
+#[derive(Debug)]
+enum FileBrowserEvent {
+    Changed,
+}
+
+/// Messages sent from the background log-capture thread to the UI thread.
+///
+/// Unlike `SearchMessage`, capture has no natural "done" point — RTT/serial output keeps
+/// arriving for as long as the target runs. The `LogView` screen just appends every `Line` it
+/// receives; leaving the screen drops `log_receiver`, after which the thread's sends fail
+/// silently and it is left to exit on its own read error.
+#[derive(Debug)]
+enum LogMessage {
+    /// A decoded line of target output, ready to display
+    Line(String),
+    /// Capture could not be started
+    Error(String),
 }
 
 /// Represents a file or directory entry in the file browser.
@@ -270,6 +466,25 @@ struct App {
     /// Currently selected interface instance
     selected_interface: Option<String>,
 
+    // === CAN Link Configuration ===
+    /// Real kernel-reported state of every discovered CAN link (name, up/down, bitrate)
+    can_links: Vec<CanLinkInfo>,
+    /// Bitrate to configure on `CanConfig`, edited as free text before being parsed
+    can_bitrate_input: String,
+    /// Bitrate to use for the selected CAN link's `ComConnParams`, once configured
+    can_bitrate: Option<u32>,
+    /// Whether `CanConfig` is in text entry mode (blocks the global 'q' quit shortcut)
+    can_config_input_mode: bool,
+
+    // === Net Interface Configuration ===
+    /// Address to connect to on `NetConfig`, edited as free text before being parsed
+    /// (`proto:host:port`, e.g. "tcp:192.168.1.10:4242")
+    net_address_input: String,
+
+    // === Interface Defaults ===
+    /// Serial baud, CAN bitrate, ack timeout, and flash retry count loaded via `config::load`
+    interface_config: InterfaceConfig,
+
     // === Device Discovery ===
     /// List of devices found during search operation
     discovered_devices: Vec<DiscoveredDevice>,
@@ -277,6 +492,10 @@ struct App {
     device_list_state: ListState,
     /// Index of selected device in discovered_devices
     selected_device_index: Option<usize>,
+    /// Fuzzy filter query, entered with `/`; `None` when not in filter mode
+    device_list_filter: Option<String>,
+    /// Indices into `discovered_devices` matching `device_list_filter`, ranked by `fuzzy::rank`
+    device_list_filtered_indices: Vec<usize>,
 
     // === Command Selection ===
     /// List widget state for command menu
@@ -310,11 +529,38 @@ struct App {
     /// Channel receiver for operation progress updates from background thread
     operation_receiver: Option<Receiver<OperationMessage>>,
 
+    // === Log Capture ===
+    /// Channel receiver for the background RTT/serial log-capture thread
+    log_receiver: Option<Receiver<LogMessage>>,
+    /// Captured target output lines, oldest first
+    log_lines: Vec<String>,
+    /// Scroll offset from the bottom of `log_lines` (0 = pinned to the latest line)
+    log_scroll: usize,
+
     // === Search Tracking ===
     /// Channel receiver for device search results from background thread
     search_receiver: Option<Receiver<SearchMessage>>,
     /// Flag indicating whether current search is a refresh operation
     is_refresh_search: bool,
+    /// Channel receiver for interface arrival/departure events on `DeviceList`, and for the
+    /// `DeviceFound` results of probing a newly-arrived interface
+    device_hotplug_receiver: Option<Receiver<SearchMessage>>,
+    /// Sender half of the same channel, cloned into each arrival's probe thread so its result
+    /// flows back alongside the monitor thread's own messages
+    device_hotplug_sender: Option<Sender<SearchMessage>>,
+    /// True once `spawn_device_hotplug_monitor` has started its background thread for the
+    /// current search session, so re-entering `DeviceList` doesn't spawn a second one
+    device_hotplug_monitor_started: bool,
+
+    // === Hotplug Monitoring ===
+    /// Channel receiver for interface add/remove events from the background udev monitor
+    interface_event_receiver: Option<Receiver<InterfaceEvent>>,
+
+    // === Serial Port Probing ===
+    /// Channel receiver for the background serial port probe thread
+    probe_receiver: Option<Receiver<ProbeMessage>>,
+    /// Whether the "Probing ports…" overlay should be shown on `InterfaceSelection`
+    is_probing_ports: bool,
 
     // === File Browser ===
     /// Current directory being browsed in file browser
@@ -323,6 +569,66 @@ struct App {
     file_browser_entries: Vec<FileEntry>,
     /// List widget state for file browser
     file_browser_list_state: ListState,
+    /// Fuzzy filter query, entered with `/`; see `device_list_filter`
+    file_browser_filter: Option<String>,
+    /// Indices into `file_browser_entries` matching `file_browser_filter`; see
+    /// `device_list_filtered_indices`
+    file_browser_filtered_indices: Vec<usize>,
+    /// Cached `hex_preview::parse` result for the `.hex` entry last highlighted in the preview
+    /// pane, keyed by path and mtime so arrow-key scrolling doesn't re-parse an unchanged file on
+    /// every redraw
+    hex_preview_cache: Option<(PathBuf, SystemTime, Result<hex_preview::Preview, hex_preview::PreviewError>)>,
+    /// Channel receiver for debounced change notifications on `file_browser_current_dir`, see
+    /// `spawn_file_browser_watcher`
+    file_browser_watch_receiver: Option<Receiver<FileBrowserEvent>>,
+    /// Keeps the background `notify` watcher alive for as long as `FileBrowser` is open; dropping
+    /// it (on `Esc`) stops watching
+    file_browser_watcher: Option<notify::RecommendedWatcher>,
+    /// LRU cache (back = most recent) of visited directories' entry lists and selections, so
+    /// `file_browser_ascend` back into a directory just descended out of restores instantly
+    /// instead of rescanning; see `navigate_file_browser_to`
+    file_browser_dir_cache: VecDeque<(PathBuf, Vec<FileEntry>, Option<usize>)>,
+    /// Bookmarked firmware directories, loaded via `config::load_bookmarks` at startup and
+    /// persisted via `config::save_bookmarks` whenever `m` adds one
+    bookmarks: Vec<PathBuf>,
+    /// List widget state for the bookmark popup opened with `b`
+    bookmark_list_state: ListState,
+    /// Whether the bookmark popup is currently drawn over `FileBrowser`; see `draw_bookmark_popup`
+    file_browser_bookmark_popup: bool,
+
+    // === Session Control Pipe ===
+    /// Named-pipe control session opened from `--session <dir>`, if any. When present,
+    /// `process_operation_messages`/`process_search_messages` mirror their messages onto it and
+    /// `process_session_commands` dispatches lines read from its `msg_in` FIFO.
+    session_pipes: Option<session_pipe::SessionPipes>,
+
+    // === Mouse Hit-Testing ===
+    /// The list widget's `Rect` as last rendered by the matching `draw_*` function, so the mouse
+    /// handlers can map a click's row back to an item index. `None` until that screen has drawn
+    /// at least once.
+    interface_type_list_rect: Option<Rect>,
+    interface_selection_list_rect: Option<Rect>,
+    device_list_list_rect: Option<Rect>,
+    command_menu_list_rect: Option<Rect>,
+    file_browser_list_rect: Option<Rect>,
+
+    // === Render Scheduling ===
+    /// Set whenever a background-thread message or an input handler changes state that affects
+    /// what's on screen; `run_app` only calls `terminal.draw` while this is true, then clears it.
+    /// Starts `true` so the first frame always renders.
+    dirty: bool,
+
+    // === Modal Overlays ===
+    /// Stack of modal components (confirmation/error dialogs, …) drawn on top of the active
+    /// screen; see `compositor`. Topmost (last) entry gets first look at each key press.
+    popups: Vec<Box<dyn Component>>,
+
+    // === Key Bindings ===
+    /// Resolves key presses to named actions; see `keymap`.
+    keymap: keymap::KeyMap,
+    /// True for exactly the one key press right after the configured leader key, during which
+    /// only `keymap`'s leader-gated bindings (e.g. a gated `Quit`) can resolve.
+    leader_pending: bool,
 }
 
 // ================================================================================================
@@ -336,6 +642,7 @@ impl App {
     /// and prepares list selections with appropriate defaults.
     fn new() -> App {
         let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let interface_config = config::load();
         let mut app = App {
             current_screen: Screen::InterfaceTypeSelection,
             interface_type_state: ListState::default(),
@@ -343,9 +650,17 @@ impl App {
             available_interfaces: Vec::new(),
             interface_list_state: ListState::default(),
             selected_interface: None,
+            can_links: Vec::new(),
+            can_bitrate_input: interface_config.can_bitrate.to_string(),
+            can_bitrate: None,
+            can_config_input_mode: false,
+            net_address_input: String::new(),
+            interface_config,
             discovered_devices: Vec::new(),
             device_list_state: ListState::default(),
             selected_device_index: None,
+            device_list_filter: None,
+            device_list_filtered_indices: Vec::new(),
             command_menu_state: ListState::default(),
             selected_command: None,
             hex_file_path: String::new(),
@@ -358,11 +673,39 @@ impl App {
             operation_progress: None,
             operation_status: String::new(),
             operation_receiver: None,
+            log_receiver: None,
+            log_lines: Vec::new(),
+            log_scroll: 0,
             search_receiver: None,
             is_refresh_search: false,
+            device_hotplug_receiver: None,
+            device_hotplug_sender: None,
+            device_hotplug_monitor_started: false,
+            interface_event_receiver: None,
+            probe_receiver: None,
+            is_probing_ports: false,
             file_browser_current_dir: current_dir,
             file_browser_entries: Vec::new(),
             file_browser_list_state: ListState::default(),
+            file_browser_filter: None,
+            file_browser_filtered_indices: Vec::new(),
+            hex_preview_cache: None,
+            file_browser_watch_receiver: None,
+            file_browser_watcher: None,
+            file_browser_dir_cache: VecDeque::new(),
+            bookmarks: config::load_bookmarks(),
+            bookmark_list_state: ListState::default(),
+            file_browser_bookmark_popup: false,
+            session_pipes: None,
+            interface_type_list_rect: None,
+            interface_selection_list_rect: None,
+            device_list_list_rect: None,
+            command_menu_list_rect: None,
+            file_browser_list_rect: None,
+            dirty: true,
+            popups: Vec::new(),
+            keymap: keymap::KeyMap::load(),
+            leader_pending: false,
         };
         // Select first item in interface type list by default
         app.interface_type_state.select(Some(0));
@@ -378,7 +721,10 @@ impl App {
     ///
     /// - **SIM**: Always returns a single "sim" interface
     /// - **Serial**: Scans for accessible serial ports, filtering out inactive/inaccessible ones
-    /// - **CAN**: Scans `/sys/class/net` for CAN interfaces (can*, vcan*)
+    /// - **CAN**: Queries rtnetlink for CAN interfaces (can*, vcan*) and their real up/down
+    ///   state and bitrate, populating `can_links`
+    /// - **Net**: No-op; `NetConfig` collects the address directly since there is nothing to
+    ///   enumerate locally
     ///
     /// ## Serial Port Filtering
     ///
@@ -399,56 +745,284 @@ impl App {
                 self.available_interfaces.push("sim".to_string());
             }
             InterfaceType::Serial => {
-                // Enumerate serial ports and filter to only accessible ones
-                match serialport::available_ports() {
-                    Ok(ports) => {
-                        for port in ports {
-                            // Try to open the port to verify it's accessible and active
-                            // Use a very short timeout to avoid hanging
-                            match serialport::new(&port.port_name, 115200)
-                                .timeout(Duration::from_millis(100))
-                                .open()
-                            {
-                                Ok(_) => {
-                                    // Port is accessible and active, add it to the list
-                                    self.available_interfaces.push(port.port_name);
-                                }
-                                Err(_) => {
-                                    // Port is not accessible or inactive, skip it
-                                }
-                            }
+                // Opening each port to check accessibility can block for its full timeout, so
+                // probing happens on a background thread instead of the UI thread (see
+                // `spawn_port_probe`); `available_interfaces` fills in as results stream back
+                // through `process_probe_messages`.
+                self.spawn_port_probe();
+            }
+            InterfaceType::CAN => {
+                // Query rtnetlink for real link state (up/down, bitrate) instead of just
+                // listing names from /sys/class/net, so a down link can be configured instead
+                // of silently producing an empty device list later.
+                match discover_can_links() {
+                    Ok(links) => {
+                        for link in &links {
+                            self.available_interfaces.push(link.name.clone());
                         }
+                        self.can_links = links;
                     }
-                    Err(_) => {
-                        self.error_message = Some("Failed to enumerate serial ports".to_string());
+                    Err(e) => {
+                        self.error_message = Some(format!("CAN discovery failed: {}", e));
                     }
                 }
 
-                if self.available_interfaces.is_empty() {
-                    self.error_message = Some("No accessible serial ports found".to_string());
+                if self.available_interfaces.is_empty() && self.error_message.is_none() {
+                    self.error_message = Some("No CAN interfaces found".to_string());
                 }
             }
-            InterfaceType::CAN => {
-                // Enumerate CAN interfaces from /sys/class/net
-                if let Ok(entries) = fs::read_dir("/sys/class/net") {
-                    for entry in entries.flatten() {
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        // Check if it's a CAN interface (can* or vcan*)
-                        if name.starts_with("can") || name.starts_with("vcan") {
-                            self.available_interfaces.push(name);
+            InterfaceType::Net => {
+                // There is nothing to enumerate locally for a network bridge; `NetConfig`
+                // collects the address directly instead of going through this list.
+            }
+        }
+
+        // Select first interface if any were found
+        if !self.available_interfaces.is_empty() {
+            self.interface_list_state.select(Some(0));
+        }
+    }
+
+    /// Probes every enumerated serial port on a background thread, 100ms timeout each.
+    ///
+    /// Only ports that can be successfully opened are reported, filtering out ports that are
+    /// already in use, lack permissions, or are disconnected. Sets `is_probing_ports` so the UI
+    /// can show a "Probing ports…" overlay while `process_probe_messages` streams results in.
+    fn spawn_port_probe(&mut self) {
+        self.is_probing_ports = true;
+        self.error_message = None;
+
+        let (tx, rx) = channel();
+        self.probe_receiver = Some(rx);
+
+        thread::spawn(move || {
+            if let Ok(ports) = serialport::available_ports() {
+                for port in ports {
+                    // Try to open the port to verify it's accessible and active
+                    // Use a very short timeout to avoid hanging
+                    let accessible = serialport::new(&port.port_name, 115200)
+                        .timeout(Duration::from_millis(100))
+                        .open()
+                        .is_ok();
+
+                    if accessible && tx.send(ProbeMessage::PortFound(port.port_name)).is_err() {
+                        // UI thread cancelled the probe; stop early
+                        return;
+                    }
+                }
+            }
+
+            tx.send(ProbeMessage::Complete).ok();
+        });
+    }
+
+    /// Applies pending `ProbeMessage`s from the background port probe.
+    ///
+    /// Called every UI tick alongside the other `process_*` methods. Clears
+    /// `is_probing_ports` (ending the overlay) once the probe thread reports `Complete`.
+    fn process_probe_messages(&mut self) {
+        let Some(ref receiver) = self.probe_receiver else {
+            return;
+        };
+
+        while let Ok(msg) = receiver.try_recv() {
+            self.dirty = true;
+            match msg {
+                ProbeMessage::PortFound(name) => {
+                    if !self.available_interfaces.contains(&name) {
+                        self.available_interfaces.push(name);
+                        if self.interface_list_state.selected().is_none() {
+                            self.interface_list_state.select(Some(0));
                         }
                     }
                 }
+                ProbeMessage::Complete => {
+                    self.is_probing_ports = false;
+                    self.probe_receiver = None;
 
-                if self.available_interfaces.is_empty() {
-                    self.error_message = Some("No CAN interfaces found".to_string());
+                    if self.available_interfaces.is_empty() {
+                        self.error_message = Some("No accessible serial ports found".to_string());
+                    }
+
+                    break;
                 }
             }
         }
+    }
 
-        // Select first interface if any were found
-        if !self.available_interfaces.is_empty() {
-            self.interface_list_state.select(Some(0));
+    /// Cancels an in-flight serial port probe, if any.
+    ///
+    /// Dropping the receiver causes the background thread's next `send` to fail, at which
+    /// point it stops probing further ports.
+    fn cancel_port_probe(&mut self) {
+        self.is_probing_ports = false;
+        self.probe_receiver = None;
+    }
+
+    /// Starts watching for interfaces of `selected_interface_type` being plugged/unplugged.
+    ///
+    /// Spawns a background thread that reports `InterfaceEvent`s through
+    /// `interface_event_receiver`, which `process_interface_events()` applies to
+    /// `available_interfaces` on every UI tick. SIM has no hotplug concept, so no thread is
+    /// spawned for it. Net likewise has no hotplug concept: its one "interface" is a manually
+    /// entered address, not a local device.
+    ///
+    /// ## Backend
+    ///
+    /// Prefers a udev monitor on the interface type's subsystem ("tty" for Serial, "net" for
+    /// CAN), falling back to periodically rescanning the same sources `discover_interfaces()`
+    /// uses when udev is unavailable (e.g. non-Linux platforms, missing permissions).
+    fn spawn_interface_monitor(&mut self) {
+        let interface_type = match &self.selected_interface_type {
+            Some(InterfaceType::Sim) | Some(InterfaceType::Net) | None => {
+                self.interface_event_receiver = None;
+                return;
+            }
+            Some(it) => it.clone(),
+        };
+
+        let (tx, rx) = channel();
+        self.interface_event_receiver = Some(rx);
+
+        thread::spawn(move || {
+            let subsystem = match interface_type {
+                InterfaceType::Serial => "tty",
+                InterfaceType::CAN => "net",
+                InterfaceType::Sim | InterfaceType::Net => return,
+            };
+
+            if Self::watch_udev(subsystem, &tx).is_err() {
+                Self::poll_interfaces(interface_type, &tx);
+            }
+        });
+    }
+
+    /// Streams add/remove events for `subsystem` from a udev monitor until the receiver is
+    /// dropped. Returns an error immediately if the monitor cannot be created, so the caller can
+    /// fall back to polling.
+    fn watch_udev(subsystem: &str, tx: &Sender<InterfaceEvent>) -> Result<(), std::io::Error> {
+        let socket = udev::MonitorBuilder::new()?
+            .match_subsystem(subsystem)?
+            .listen()?;
+
+        for event in socket.iter() {
+            let Some(name) = event.sysname().to_str() else {
+                continue;
+            };
+
+            // Only CAN/vCAN interfaces are relevant on the "net" subsystem; "tty" devices are
+            // all candidates since any of them could be a FranklyBoot-capable USB/UART adapter.
+            if subsystem == "net" && !(name.starts_with("can") || name.starts_with("vcan")) {
+                continue;
+            }
+
+            let sent = match event.event_type() {
+                udev::EventType::Add => tx.send(InterfaceEvent::Added(name.to_string())),
+                udev::EventType::Remove => tx.send(InterfaceEvent::Removed(name.to_string())),
+                _ => Ok(()),
+            };
+
+            if sent.is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Polling fallback for platforms without udev: rescans every second and diffs against the
+    /// previous snapshot to synthesize `Added`/`Removed` events.
+    fn poll_interfaces(interface_type: InterfaceType, tx: &Sender<InterfaceEvent>) {
+        const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+        let mut known: Vec<String> = Self::scan_interface_names(&interface_type);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let current = Self::scan_interface_names(&interface_type);
+
+            for name in &current {
+                if !known.contains(name) && tx.send(InterfaceEvent::Added(name.clone())).is_err() {
+                    return;
+                }
+            }
+            for name in &known {
+                if !current.contains(name)
+                    && tx.send(InterfaceEvent::Removed(name.clone())).is_err()
+                {
+                    return;
+                }
+            }
+
+            known = current;
+        }
+    }
+
+    /// One-shot scan used both as the polling fallback's source of truth and its first snapshot
+    fn scan_interface_names(interface_type: &InterfaceType) -> Vec<String> {
+        match interface_type {
+            InterfaceType::Serial => serialport::available_ports()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|port| port.port_name)
+                .collect(),
+            InterfaceType::CAN => fs::read_dir("/sys/class/net")
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.starts_with("can") || name.starts_with("vcan"))
+                .collect(),
+            InterfaceType::Sim | InterfaceType::Net => Vec::new(),
+        }
+    }
+
+    /// Applies pending `InterfaceEvent`s to `available_interfaces`.
+    ///
+    /// Called every UI tick alongside `process_operation_messages`/`process_search_messages`.
+    /// Keeps the current selection on the same interface name when the list shifts around it.
+    fn process_interface_events(&mut self) {
+        let Some(ref receiver) = self.interface_event_receiver else {
+            return;
+        };
+
+        let mut changed = false;
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                InterfaceEvent::Added(name) => {
+                    if !self.available_interfaces.contains(&name) {
+                        self.available_interfaces.push(name);
+                        changed = true;
+                    }
+                }
+                InterfaceEvent::Removed(name) => {
+                    if let Some(pos) = self.available_interfaces.iter().position(|n| n == &name) {
+                        self.available_interfaces.remove(pos);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if changed {
+            self.available_interfaces.sort();
+
+            let selected_name = self
+                .interface_list_state
+                .selected()
+                .and_then(|idx| self.available_interfaces.get(idx).cloned());
+
+            if self.available_interfaces.is_empty() {
+                self.interface_list_state.select(None);
+            } else {
+                let new_idx = selected_name
+                    .and_then(|name| self.available_interfaces.iter().position(|n| *n == name))
+                    .unwrap_or(0);
+                self.interface_list_state.select(Some(new_idx));
+            }
+
+            self.dirty = true;
         }
     }
 
@@ -521,8 +1095,8 @@ impl App {
             None => return,
         };
 
-        // Add hex file path to history if this is a flash command
-        if matches!(command, Command::Flash) && !self.hex_file_path.is_empty() {
+        // Add hex file path to history if this command uses one
+        if matches!(command, Command::Flash | Command::Verify) && !self.hex_file_path.is_empty() {
             self.add_to_hex_file_history(self.hex_file_path.clone());
         }
 
@@ -552,6 +1126,15 @@ impl App {
                         device_node,
                         hex_file_path,
                     ),
+                    Command::Verify => self.spawn_verify::<SIMInterface>(
+                        tx,
+                        conn_params,
+                        device_node,
+                        hex_file_path,
+                    ),
+                    Command::ReadMemory => {
+                        self.spawn_read_memory::<SIMInterface>(tx, conn_params, device_node)
+                    }
                 }
             }
             InterfaceType::Serial => match command {
@@ -562,6 +1145,15 @@ impl App {
                 Command::Flash => {
                     self.spawn_flash::<SerialInterface>(tx, conn_params, device_node, hex_file_path)
                 }
+                Command::Verify => self.spawn_verify::<SerialInterface>(
+                    tx,
+                    conn_params,
+                    device_node,
+                    hex_file_path,
+                ),
+                Command::ReadMemory => {
+                    self.spawn_read_memory::<SerialInterface>(tx, conn_params, device_node)
+                }
             },
             InterfaceType::CAN => match command {
                 Command::Reset => {
@@ -571,10 +1163,53 @@ impl App {
                 Command::Flash => {
                     self.spawn_flash::<CANInterface>(tx, conn_params, device_node, hex_file_path)
                 }
+                Command::Verify => self.spawn_verify::<CANInterface>(
+                    tx,
+                    conn_params,
+                    device_node,
+                    hex_file_path,
+                ),
+                Command::ReadMemory => {
+                    self.spawn_read_memory::<CANInterface>(tx, conn_params, device_node)
+                }
+            },
+            InterfaceType::Net => match command {
+                Command::Reset => {
+                    self.spawn_operation::<NetInterface>(tx, conn_params, device_node, None)
+                }
+                Command::Erase => self.spawn_erase::<NetInterface>(tx, conn_params, device_node),
+                Command::Flash => {
+                    self.spawn_flash::<NetInterface>(tx, conn_params, device_node, hex_file_path)
+                }
+                Command::Verify => self.spawn_verify::<NetInterface>(
+                    tx,
+                    conn_params,
+                    device_node,
+                    hex_file_path,
+                ),
+                Command::ReadMemory => {
+                    self.spawn_read_memory::<NetInterface>(tx, conn_params, device_node)
+                }
             },
         }
     }
 
+    /// Starts `self.selected_command` once a hex file (if any) has been chosen: `Flash`
+    /// overwrites the device's application flash, so it goes through a `ConfirmDialog` popup
+    /// first instead of jumping straight to `Screen::Executing`, the same as `Erase` already does
+    /// from `handle_command_menu`.
+    fn start_selected_command(&mut self) {
+        if self.selected_command == Some(Command::Flash) {
+            self.popups.push(Box::new(ConfirmDialog {
+                message: "Flash firmware, overwriting the device's application?".to_string(),
+                command: Command::Flash,
+            }));
+        } else {
+            self.current_screen = Screen::Executing;
+            self.execute_command();
+        }
+    }
+
     fn spawn_operation<I: ComInterface + 'static>(
         &self,
         tx: Sender<OperationMessage>,
@@ -582,6 +1217,8 @@ impl App {
         node_id: Option<u8>,
         _hex_file: Option<String>,
     ) {
+        let interface_config = self.interface_config;
+
         thread::spawn(move || {
             // Create progress callback
             let progress_tx = tx.clone();
@@ -611,6 +1248,15 @@ impl App {
                 return;
             }
 
+            if let Err(e) = interface.set_timeout(interface_config.ack_timeout()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to set ack timeout: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
             if let Some(node) = node_id {
                 if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
                     tx.send(OperationMessage::Error(format!(
@@ -623,7 +1269,7 @@ impl App {
             }
 
             let mut device = Device::new_with_progress(interface, progress_fn);
-            if let Err(e) = device.init() {
+            if let Err(e) = with_retry(interface_config.flash_retry_count, || device.init()) {
                 tx.send(OperationMessage::Error(format!(
                     "Failed to initialize device: {:?}",
                     e
@@ -637,6 +1283,10 @@ impl App {
                 .replace('\t', " ")
                 .replace('\r', "")
                 .replace('\n', " ");
+            let device_info = format!(
+                "{} [ack_timeout={}ms, retries={}]",
+                device_info, interface_config.ack_timeout_ms, interface_config.flash_retry_count
+            );
             tx.send(OperationMessage::DeviceInfo(device_info)).ok();
 
             // Execute reset
@@ -658,6 +1308,8 @@ impl App {
         conn_params: ComConnParams,
         node_id: Option<u8>,
     ) {
+        let interface_config = self.interface_config;
+
         thread::spawn(move || {
             let progress_tx = tx.clone();
             let progress_fn = Some(Box::new(move |update: ProgressUpdate| {
@@ -685,6 +1337,15 @@ impl App {
                 return;
             }
 
+            if let Err(e) = interface.set_timeout(interface_config.ack_timeout()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to set ack timeout: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
             if let Some(node) = node_id {
                 if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
                     tx.send(OperationMessage::Error(format!(
@@ -697,7 +1358,7 @@ impl App {
             }
 
             let mut device = Device::new_with_progress(interface, progress_fn);
-            if let Err(e) = device.init() {
+            if let Err(e) = with_retry(interface_config.flash_retry_count, || device.init()) {
                 tx.send(OperationMessage::Error(format!(
                     "Failed to initialize device: {:?}",
                     e
@@ -710,6 +1371,10 @@ impl App {
                 .replace('\t', " ")
                 .replace('\r', "")
                 .replace('\n', " ");
+            let device_info = format!(
+                "{} [ack_timeout={}ms, retries={}]",
+                device_info, interface_config.ack_timeout_ms, interface_config.flash_retry_count
+            );
             tx.send(OperationMessage::DeviceInfo(device_info)).ok();
 
             match device.erase() {
@@ -731,6 +1396,8 @@ impl App {
         node_id: Option<u8>,
         hex_file_path: String,
     ) {
+        let interface_config = self.interface_config;
+
         thread::spawn(move || {
             let hex_file = match HexFile::from_file(&hex_file_path) {
                 Ok(hf) => hf,
@@ -770,6 +1437,15 @@ impl App {
                 return;
             }
 
+            if let Err(e) = interface.set_timeout(interface_config.ack_timeout()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to set ack timeout: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
             if let Some(node) = node_id {
                 if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
                     tx.send(OperationMessage::Error(format!(
@@ -782,7 +1458,7 @@ impl App {
             }
 
             let mut device = Device::new_with_progress(interface, progress_fn);
-            if let Err(e) = device.init() {
+            if let Err(e) = with_retry(interface_config.flash_retry_count, || device.init()) {
                 tx.send(OperationMessage::Error(format!(
                     "Failed to initialize device: {:?}",
                     e
@@ -795,9 +1471,13 @@ impl App {
                 .replace('\t', " ")
                 .replace('\r', "")
                 .replace('\n', " ");
+            let device_info = format!(
+                "{} [ack_timeout={}ms, retries={}]",
+                device_info, interface_config.ack_timeout_ms, interface_config.flash_retry_count
+            );
             tx.send(OperationMessage::DeviceInfo(device_info)).ok();
 
-            match device.flash(&hex_file) {
+            match with_retry(interface_config.flash_retry_count, || device.flash(&hex_file)) {
                 Ok(_) => {
                     tx.send(OperationMessage::Complete).ok();
                 }
@@ -809,80 +1489,467 @@ impl App {
         });
     }
 
-    fn get_selected_device(&self) -> Option<&DiscoveredDevice> {
-        self.selected_device_index
-            .and_then(|idx| self.discovered_devices.get(idx))
-    }
-
-    /// Returns the connection parameters for the currently selected interface.
+    /// Starts capturing target log output after a successful flash.
     ///
-    /// Creates a `ComConnParams` struct appropriate for the selected interface type:
-    /// - **SIM**: Simulated device parameters
-    /// - **Serial**: Serial port name + 115200 baud rate
-    /// - **CAN**: CAN interface name
-    fn get_conn_params(&self) -> ComConnParams {
-        let interface_name = self.selected_interface.as_ref().unwrap();
-        match self.selected_interface_type.as_ref().unwrap() {
-            InterfaceType::Sim => ComConnParams::for_sim_device(),
-            InterfaceType::Serial => ComConnParams::for_serial_conn(interface_name, 115200),
-            InterfaceType::CAN => ComConnParams::for_can_conn(interface_name),
-        }
-    }
+    /// Prefers RTT, decoding defmt frames when a `.elf` file is found next to the flashed
+    /// `.hex` (the embedded-trainings project standardizes on defmt-over-RTT for this
+    /// bootloader's target firmware). Falls back to reading plain UTF-8 lines from the serial
+    /// port if RTT isn't available and the device was reached over `Serial` — CAN/SIM links have
+    /// no equivalent plain-text fallback, since they only carry the bootloader protocol.
+    fn spawn_log_capture(&mut self) {
+        self.log_lines.clear();
+        self.log_scroll = 0;
 
-    /// Processes progress messages from background operation threads.
-    ///
-    /// Called every 100ms from the main event loop to check for updates from
-    /// `operation_receiver`. Updates UI state based on received messages:
-    ///
-    /// - `Progress`: Updates progress bar and status message
-    /// - `DeviceInfo`: Adds device identification to results
-    /// - `Complete`: Marks operation successful and transitions to Results screen
-    /// - `Error`: Captures error message and transitions to Results screen
-    ///
-    /// ## Non-blocking Design
-    ///
-    /// Uses `try_recv()` to avoid blocking the UI thread. Processes all pending
-    /// messages in a tight loop before returning control to the event handler.
-    fn process_operation_messages(&mut self) {
-        let mut operation_complete = false;
-        let mut operation_error = None;
+        let (tx, rx) = channel();
+        self.log_receiver = Some(rx);
 
-        if let Some(ref receiver) = self.operation_receiver {
-            // Non-blocking check for messages
-            while let Ok(msg) = receiver.try_recv() {
-                match msg {
-                    OperationMessage::Progress(update) => match update {
-                        ProgressUpdate::EraseProgress { current, total } => {
-                            self.operation_progress = Some((current, total));
-                            self.operation_status = format!("Erasing page {}/{}", current, total);
-                        }
-                        ProgressUpdate::FlashProgress { current, total } => {
-                            self.operation_progress = Some((current, total));
-                            self.operation_status = format!("Flashing page {}/{}", current, total);
-                        }
-                        ProgressUpdate::Message(msg) => {
-                            self.operation_status = msg;
+        let elf_path = Self::sibling_elf_path(&self.hex_file_path);
+        let serial_fallback = match self.selected_interface_type {
+            Some(InterfaceType::Serial) => self.selected_interface.clone(),
+            _ => None,
+        };
+        let serial_baud = self.interface_config.serial_baud;
+
+        thread::spawn(move || {
+            if let Err(rtt_err) = capture_rtt_log(elf_path.as_deref(), &tx) {
+                match serial_fallback {
+                    Some(port_name) => {
+                        if let Err(serial_err) = capture_serial_log(&port_name, serial_baud, &tx) {
+                            tx.send(LogMessage::Error(format!(
+                                "RTT unavailable ({}), serial log fallback failed: {}",
+                                rtt_err, serial_err
+                            )))
+                            .ok();
                         }
-                    },
-                    OperationMessage::DeviceInfo(info) => {
-                        self.result_message.push(format!("Device: {}", info));
-                    }
-                    OperationMessage::Complete => {
-                        self.result_message
-                            .push("Operation completed successfully".to_string());
-                        operation_complete = true;
                     }
-                    OperationMessage::Error(err) => {
-                        operation_error = Some(err);
+                    None => {
+                        tx.send(LogMessage::Error(format!(
+                            "No log source available: {}",
+                            rtt_err
+                        )))
+                        .ok();
                     }
                 }
             }
-        }
+        });
+    }
 
-        // Handle completion after the borrow ends
+    /// Returns the path of a `.elf` file next to `hex_path` with the same stem, if one exists.
+    fn sibling_elf_path(hex_path: &str) -> Option<String> {
+        let elf_path = PathBuf::from(hex_path).with_extension("elf");
+        if elf_path.is_file() {
+            elf_path.to_str().map(|s| s.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Processes log lines from the background RTT/serial capture thread.
+    ///
+    /// Called every 100ms from the main event loop, mirroring `process_probe_messages`. Runs
+    /// regardless of the current screen; leaving `LogView` (see `handle_log_view`) drops
+    /// `log_receiver`, which ends the capture.
+    fn process_log_messages(&mut self) {
+        if let Some(ref receiver) = self.log_receiver {
+            while let Ok(msg) = receiver.try_recv() {
+                self.dirty = true;
+                match msg {
+                    LogMessage::Line(line) => self.log_lines.push(line),
+                    LogMessage::Error(err) => self.log_lines.push(format!("[log] {}", err)),
+                }
+            }
+        }
+    }
+
+    /// Writes the captured log to a timestamped file in the current directory.
+    ///
+    /// Returns the path written to, or an error message on failure.
+    fn save_log_to_file(&self) -> Result<String, String> {
+        let path = format!(
+            "frankly_fw_update_{}.log",
+            self.hex_file_path
+                .rsplit('/')
+                .next()
+                .unwrap_or("session")
+                .trim_end_matches(".hex")
+        );
+
+        let mut file =
+            fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+
+        for line in &self.log_lines {
+            writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        }
+
+        Ok(path)
+    }
+
+    /// Re-reads the flashed application region and diffs it against a hex file.
+    ///
+    /// Mirrors `spawn_flash`'s connection setup, but calls `device.verify()` instead of
+    /// `device.flash()`. `device.verify()` checks each page's CRC before falling back to a full
+    /// readback of only the pages that disagree, reporting `ProgressUpdate::VerifyProgress` as it
+    /// goes. The resulting mismatch list (empty on a clean verify) is sent once via
+    /// `OperationMessage::VerifyResult` before the operation completes with `Complete` or
+    /// `Error`, so the user sees a real pass/fail outcome instead of having to trust the CRC check
+    /// alone.
+    fn spawn_verify<I: ComInterface + 'static>(
+        &self,
+        tx: Sender<OperationMessage>,
+        conn_params: ComConnParams,
+        node_id: Option<u8>,
+        hex_file_path: String,
+    ) {
+        let interface_config = self.interface_config;
+
+        thread::spawn(move || {
+            let hex_file = match HexFile::from_file(&hex_file_path) {
+                Ok(hf) => hf,
+                Err(e) => {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to load hex file: {:?}",
+                        e
+                    )))
+                    .ok();
+                    return;
+                }
+            };
+
+            let progress_tx = tx.clone();
+            let progress_fn = Some(Box::new(move |update: ProgressUpdate| {
+                progress_tx.send(OperationMessage::Progress(update)).ok();
+            }) as Box<dyn Fn(ProgressUpdate) + Send>);
+
+            let mut interface = match I::create() {
+                Ok(i) => i,
+                Err(e) => {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to create interface: {:?}",
+                        e
+                    )))
+                    .ok();
+                    return;
+                }
+            };
+
+            if let Err(e) = interface.open(&conn_params) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to open interface: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            if let Err(e) = interface.set_timeout(interface_config.ack_timeout()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to set ack timeout: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            if let Some(node) = node_id {
+                if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to set node mode: {:?}",
+                        e
+                    )))
+                    .ok();
+                    return;
+                }
+            }
+
+            let mut device = Device::new_with_progress(interface, progress_fn);
+            if let Err(e) = with_retry(interface_config.flash_retry_count, || device.init()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to initialize device: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            let device_info = format!("{}", device)
+                .replace('\t', " ")
+                .replace('\r', "")
+                .replace('\n', " ");
+            let device_info = format!(
+                "{} [ack_timeout={}ms, retries={}]",
+                device_info, interface_config.ack_timeout_ms, interface_config.flash_retry_count
+            );
+            tx.send(OperationMessage::DeviceInfo(device_info)).ok();
+
+            match device.verify(&hex_file) {
+                Ok(mismatches) => {
+                    let mismatch_count = mismatches.len();
+                    tx.send(OperationMessage::VerifyResult(mismatches)).ok();
+
+                    if mismatch_count == 0 {
+                        tx.send(OperationMessage::Complete).ok();
+                    } else {
+                        tx.send(OperationMessage::Error(format!(
+                            "Verify failed: {} word(s) did not match",
+                            mismatch_count
+                        )))
+                        .ok();
+                    }
+                }
+                Err(e) => {
+                    tx.send(OperationMessage::Error(format!("Verify failed: {:?}", e)))
+                        .ok();
+                }
+            }
+        });
+    }
+
+    /// Streams the device's application region back and wraps it into a coredump ELF.
+    ///
+    /// Analogous to a crash analyzer's "read coredump ELF" command: the region is dumped
+    /// unsymbolized, with progress reported the same way `spawn_flash` reports page progress, so
+    /// a later `objdump`/`gdb` session against the originally flashed ELF can make sense of it.
+    fn spawn_read_memory<I: ComInterface + 'static>(
+        &self,
+        tx: Sender<OperationMessage>,
+        conn_params: ComConnParams,
+        node_id: Option<u8>,
+    ) {
+        let interface_config = self.interface_config;
+
+        thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let progress_fn = Some(Box::new(move |update: ProgressUpdate| {
+                progress_tx.send(OperationMessage::Progress(update)).ok();
+            }) as Box<dyn Fn(ProgressUpdate) + Send>);
+
+            let mut interface = match I::create() {
+                Ok(i) => i,
+                Err(e) => {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to create interface: {:?}",
+                        e
+                    )))
+                    .ok();
+                    return;
+                }
+            };
+
+            if let Err(e) = interface.open(&conn_params) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to open interface: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            if let Err(e) = interface.set_timeout(interface_config.ack_timeout()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to set ack timeout: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            if let Some(node) = node_id {
+                if let Err(e) = interface.set_mode(ComMode::Specific(node)) {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to set node mode: {:?}",
+                        e
+                    )))
+                    .ok();
+                    return;
+                }
+            }
+
+            let mut device = Device::new_with_progress(interface, progress_fn);
+            if let Err(e) = with_retry(interface_config.flash_retry_count, || device.init()) {
+                tx.send(OperationMessage::Error(format!(
+                    "Failed to initialize device: {:?}",
+                    e
+                )))
+                .ok();
+                return;
+            }
+
+            let device_info = format!("{}", device)
+                .replace('\t', " ")
+                .replace('\r', "")
+                .replace('\n', " ");
+            let device_info = format!(
+                "{} [ack_timeout={}ms, retries={}]",
+                device_info, interface_config.ack_timeout_ms, interface_config.flash_retry_count
+            );
+            tx.send(OperationMessage::DeviceInfo(device_info)).ok();
+
+            let (start, size) = device.get_application_region();
+            match device.read_memory(start, size) {
+                Ok(data) => {
+                    let path = format!("frankly_fw_update_coredump_{:#010x}.elf", start);
+                    match write_coredump_elf(&path, start, &data) {
+                        Ok(()) => {
+                            tx.send(OperationMessage::DeviceInfo(format!(
+                                "Coredump saved to {}",
+                                path
+                            )))
+                            .ok();
+                            tx.send(OperationMessage::Complete).ok();
+                        }
+                        Err(e) => {
+                            tx.send(OperationMessage::Error(format!(
+                                "Failed to write coredump ELF: {}",
+                                e
+                            )))
+                            .ok();
+                        }
+                    }
+                }
+                Err(e) => {
+                    tx.send(OperationMessage::Error(format!(
+                        "Failed to read memory: {:?}",
+                        e
+                    )))
+                    .ok();
+                }
+            }
+        });
+    }
+
+    fn get_selected_device(&self) -> Option<&DiscoveredDevice> {
+        self.selected_device_index
+            .and_then(|idx| self.discovered_devices.get(idx))
+    }
+
+    /// Returns the connection parameters for the currently selected interface.
+    ///
+    /// Creates a `ComConnParams` struct appropriate for the selected interface type:
+    /// - **SIM**: Simulated device parameters
+    /// - **Serial**: Serial port name + the configured `serial_baud` (see `config::load`)
+    /// - **CAN**: CAN interface name
+    /// - **Net**: Host, port, and protocol parsed from the address entered on `NetConfig`
+    fn get_conn_params(&self) -> ComConnParams {
+        let interface_name = self.selected_interface.as_ref().unwrap();
+        match self.selected_interface_type.as_ref().unwrap() {
+            InterfaceType::Sim => ComConnParams::for_sim_device(),
+            InterfaceType::Serial => {
+                ComConnParams::for_serial_conn(interface_name, self.interface_config.serial_baud)
+            }
+            InterfaceType::CAN => match self.can_bitrate {
+                Some(bitrate) => ComConnParams::for_can_conn_with_bitrate(interface_name, bitrate),
+                None => ComConnParams::for_can_conn(interface_name),
+            },
+            InterfaceType::Net => {
+                let (protocol, host, port) = parse_net_address(interface_name)
+                    .expect("NetConfig already validated the address");
+                ComConnParams::for_net_conn(&host, port, protocol)
+            }
+        }
+    }
+
+    /// Processes progress messages from background operation threads.
+    ///
+    /// Called every 100ms from the main event loop to check for updates from
+    /// `operation_receiver`. Updates UI state based on received messages:
+    ///
+    /// - `Progress`: Updates progress bar and status message
+    /// - `DeviceInfo`: Adds device identification to results
+    /// - `VerifyResult`: Lists the mismatching addresses (if any) found by `spawn_verify`
+    /// - `Complete`: Marks operation successful and transitions to Results screen, or to
+    ///   `LogView` (starting `spawn_log_capture`) after a `Flash`
+    /// - `Error`: Captures error message and transitions to Results screen
+    ///
+    /// ## Non-blocking Design
+    ///
+    /// Uses `try_recv()` to avoid blocking the UI thread. Processes all pending
+    /// messages in a tight loop before returning control to the event handler.
+    fn process_operation_messages(&mut self) {
+        let mut operation_complete = false;
+        let mut operation_error = None;
+
+        if let Some(ref receiver) = self.operation_receiver {
+            // Non-blocking check for messages
+            while let Ok(msg) = receiver.try_recv() {
+                self.dirty = true;
+                if let Some(pipes) = self.session_pipes.as_mut() {
+                    let line = operation_message_to_json(&msg);
+                    match &msg {
+                        OperationMessage::Progress(_) | OperationMessage::DeviceInfo(_) => {
+                            pipes.write_progress(&line)
+                        }
+                        OperationMessage::VerifyResult(_)
+                        | OperationMessage::Complete
+                        | OperationMessage::Error(_) => pipes.write_result(&line),
+                    }
+                }
+
+                match msg {
+                    OperationMessage::Progress(update) => match update {
+                        ProgressUpdate::EraseProgress { current, total } => {
+                            self.operation_progress = Some((current, total));
+                            self.operation_status = format!("Erasing page {}/{}", current, total);
+                        }
+                        ProgressUpdate::FlashProgress { current, total } => {
+                            self.operation_progress = Some((current, total));
+                            self.operation_status = format!("Flashing page {}/{}", current, total);
+                        }
+                        ProgressUpdate::VerifyProgress { current, total } => {
+                            self.operation_progress = Some((current, total));
+                            self.operation_status =
+                                format!("Verifying page {}/{}", current, total);
+                        }
+                        ProgressUpdate::Message(msg) => {
+                            self.operation_status = msg;
+                        }
+                    },
+                    OperationMessage::DeviceInfo(info) => {
+                        self.result_message.push(format!("Device: {}", info));
+                    }
+                    OperationMessage::VerifyResult(mismatches) => {
+                        if mismatches.is_empty() {
+                            self.result_message
+                                .push("Verify OK: flash matches hex file".to_string());
+                        } else {
+                            for address in mismatches.iter().take(16) {
+                                self.result_message
+                                    .push(format!("Mismatch at {:#010X}", address));
+                            }
+                            if mismatches.len() > 16 {
+                                self.result_message.push(format!(
+                                    "...and {} more mismatching word(s)",
+                                    mismatches.len() - 16
+                                ));
+                            }
+                        }
+                    }
+                    OperationMessage::Complete => {
+                        self.result_message
+                            .push("Operation completed successfully".to_string());
+                        operation_complete = true;
+                    }
+                    OperationMessage::Error(err) => {
+                        operation_error = Some(err);
+                    }
+                }
+            }
+        }
+
+        // Handle completion after the borrow ends
         if operation_complete || operation_error.is_some() {
             self.operation_receiver = None;
-            self.current_screen = Screen::Results;
+
+            if operation_complete
+                && operation_error.is_none()
+                && self.selected_command == Some(Command::Flash)
+            {
+                // Let the user confirm the new firmware boots before showing Results
+                self.current_screen = Screen::LogView;
+                self.spawn_log_capture();
+            } else {
+                self.current_screen = Screen::Results;
+            }
+
             if let Some(err) = operation_error {
                 self.error_message = Some(err);
             }
@@ -903,77 +1970,287 @@ impl App {
     /// - Directories: `[DIR] dirname/` (colored blue)
     /// - Hex files: `[FILE] filename.hex` (colored green)
     fn populate_file_browser(&mut self) {
-        self.file_browser_entries.clear();
-
-        // Add parent directory entry if not at root
-        if self.file_browser_current_dir.parent().is_some() {
-            self.file_browser_entries.push(FileEntry {
-                name: "..".to_string(),
-                path: self
-                    .file_browser_current_dir
-                    .parent()
-                    .unwrap()
-                    .to_path_buf(),
-                is_dir: true,
-            });
+        // A filter typed for the previous directory's listing doesn't carry over to a new one
+        self.file_browser_filter = None;
+        self.file_browser_filtered_indices.clear();
+
+        self.file_browser_entries = scan_directory_entries(&self.file_browser_current_dir);
+
+        // Select first entry
+        if !self.file_browser_entries.is_empty() {
+            self.file_browser_list_state.select(Some(0));
+        } else {
+            self.file_browser_list_state.select(None);
         }
+    }
 
-        // Read directory entries
-        if let Ok(entries) = fs::read_dir(&self.file_browser_current_dir) {
-            let mut dirs = Vec::new();
-            let mut files = Vec::new();
+    /// Navigates the browser to `dir`, the shared path behind `..`/Enter-on-directory, the `~`/`` ` ``
+    /// quick-jumps, bookmark selection, and the Miller-column `h`/`l` ascend/descend keys.
+    ///
+    /// Saves the outgoing directory's entries and selection into `file_browser_dir_cache` first,
+    /// then either restores `dir` from that cache (a hit, most often from re-ascending into a
+    /// directory just descended out of) or falls back to a full `populate_file_browser` rescan.
+    /// Either way, re-targets the `notify` watcher at the new directory.
+    fn navigate_file_browser_to(&mut self, dir: PathBuf) {
+        self.file_browser_cache_put(
+            self.file_browser_current_dir.clone(),
+            self.file_browser_entries.clone(),
+            self.file_browser_list_state.selected(),
+        );
 
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    let path = entry.path();
-                    let name = entry.file_name().to_string_lossy().to_string();
+        self.file_browser_current_dir = dir;
+        let target_dir = self.file_browser_current_dir.clone();
+        match self.file_browser_cache_take(&target_dir) {
+            Some((entries, selected)) => {
+                self.file_browser_filter = None;
+                self.file_browser_filtered_indices.clear();
+                self.file_browser_entries = entries;
+                self.file_browser_list_state.select(selected);
+            }
+            None => self.populate_file_browser(),
+        }
+        self.spawn_file_browser_watcher();
+    }
 
-                    // Skip hidden files (starting with .)
-                    if name.starts_with('.') {
-                        continue;
-                    }
+    /// Moves to the parent of `file_browser_current_dir`, a no-op at the filesystem root.
+    fn file_browser_ascend(&mut self) {
+        if let Some(parent) = self.file_browser_current_dir.parent() {
+            self.navigate_file_browser_to(parent.to_path_buf());
+        }
+    }
 
-                    if metadata.is_dir() {
-                        dirs.push(FileEntry {
-                            name,
-                            path,
-                            is_dir: true,
-                        });
-                    } else if metadata.is_file() {
-                        // Only show .hex files
-                        if path.extension().and_then(|s| s.to_str()) == Some("hex") {
-                            files.push(FileEntry {
-                                name,
-                                path,
-                                is_dir: false,
-                            });
-                        }
-                    }
-                }
+    /// Descends into the highlighted entry if it's a directory; does nothing for a highlighted
+    /// file (Enter still opens those) or when nothing is selected.
+    fn file_browser_descend(&mut self) {
+        let Some(entry) = self
+            .file_browser_list_state
+            .selected()
+            .and_then(|pos| self.file_browser_real_index(pos))
+            .and_then(|idx| self.file_browser_entries.get(idx).cloned())
+        else {
+            return;
+        };
+        if entry.is_dir {
+            self.navigate_file_browser_to(entry.path);
+        }
+    }
+
+    /// Looks up `dir` in `file_browser_dir_cache`, removing it on a hit (the caller re-inserts it
+    /// as part of whatever directory becomes current next, via `file_browser_cache_put`).
+    fn file_browser_cache_take(&mut self, dir: &Path) -> Option<(Vec<FileEntry>, Option<usize>)> {
+        let pos = self
+            .file_browser_dir_cache
+            .iter()
+            .position(|(cached_dir, _, _)| cached_dir == dir)?;
+        let (_, entries, selected) = self.file_browser_dir_cache.remove(pos)?;
+        Some((entries, selected))
+    }
+
+    /// Records `dir`'s entries and selection as the most-recently-used cache entry, evicting the
+    /// least-recently-used one past `FILE_BROWSER_CACHE_CAP` entries.
+    fn file_browser_cache_put(&mut self, dir: PathBuf, entries: Vec<FileEntry>, selected: Option<usize>) {
+        const FILE_BROWSER_CACHE_CAP: usize = 8;
+
+        self.file_browser_dir_cache
+            .retain(|(cached_dir, _, _)| cached_dir != &dir);
+        self.file_browser_dir_cache.push_back((dir, entries, selected));
+        while self.file_browser_dir_cache.len() > FILE_BROWSER_CACHE_CAP {
+            self.file_browser_dir_cache.pop_front();
+        }
+    }
+
+    fn enter_file_browser(&mut self) {
+        // Start from current working directory
+        self.file_browser_current_dir =
+            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        self.populate_file_browser();
+        self.spawn_file_browser_watcher();
+    }
+
+    /// Re-scans `file_browser_current_dir`, preserving `file_browser_filter` and clamping
+    /// `file_browser_list_state` to the (possibly changed) entry count, instead of resetting both
+    /// the way navigating into a new directory does. Used by the watcher's auto-refresh and the
+    /// F5 manual fallback.
+    fn refresh_file_browser_entries(&mut self) {
+        let filter = self.file_browser_filter.clone();
+        let selected = self.file_browser_list_state.selected();
+
+        self.populate_file_browser();
+
+        self.file_browser_filter = filter;
+        if self.file_browser_filter.is_some() {
+            self.update_file_browser_filter();
+        }
+
+        let len = self.file_browser_len();
+        self.file_browser_list_state
+            .select(selected.filter(|_| len > 0).map(|i| i.min(len - 1)));
+    }
+
+    /// Starts watching `file_browser_current_dir` (non-recursive) for filesystem changes.
+    ///
+    /// Replaces any previously running watcher, so re-entering the browser or navigating into a
+    /// new directory retargets it rather than stacking up threads. The watcher thread debounces
+    /// bursts of individual `notify` events (e.g. a toolchain writing a hex file in several
+    /// syscalls) by waiting ~200ms after the last one before signalling `Changed`, so
+    /// `process_file_browser_events` re-scans once per burst instead of once per write.
+    fn spawn_file_browser_watcher(&mut self) {
+        const DEBOUNCE: Duration = Duration::from_millis(200);
+
+        self.file_browser_watcher = None;
+        self.file_browser_watch_receiver = None;
+
+        let (raw_tx, raw_rx) = channel::<notify::Result<notify::Event>>();
+        let mut watcher = match notify::recommended_watcher(move |res| {
+            let _ = raw_tx.send(res);
+        }) {
+            Ok(w) => w,
+            Err(_) => return,
+        };
+
+        if watcher
+            .watch(&self.file_browser_current_dir, notify::RecursiveMode::NonRecursive)
+            .is_err()
+        {
+            return;
+        }
+
+        let (tx, rx) = channel();
+        self.file_browser_watch_receiver = Some(rx);
+        self.file_browser_watcher = Some(watcher);
+
+        thread::spawn(move || loop {
+            // Block for the first event of the next burst...
+            if raw_rx.recv().is_err() {
+                return;
             }
+            // ...then drain anything else that arrives within the debounce window.
+            while raw_rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            if tx.send(FileBrowserEvent::Changed).is_err() {
+                return;
+            }
+        });
+    }
+
+    /// Applies pending `FileBrowserEvent`s by re-scanning the directory once per debounced burst.
+    ///
+    /// Called every UI tick alongside `process_operation_messages`/`process_search_messages`; a
+    /// no-op while `FileBrowser` isn't open, since `file_browser_watch_receiver` is only `Some`
+    /// between `spawn_file_browser_watcher` and the `Esc` that tears it down.
+    fn process_file_browser_events(&mut self) {
+        let Some(ref receiver) = self.file_browser_watch_receiver else {
+            return;
+        };
+
+        let mut changed = false;
+        while let Ok(FileBrowserEvent::Changed) = receiver.try_recv() {
+            changed = true;
+        }
+
+        if changed {
+            self.dirty = true;
+            self.refresh_file_browser_entries();
+        }
+    }
+
+    /// Adds `file_browser_current_dir` to `bookmarks` (if not already present) and persists the
+    /// updated list via `config::save_bookmarks`, bound to `m` on `FileBrowser`.
+    fn add_current_dir_bookmark(&mut self) {
+        if !self.bookmarks.contains(&self.file_browser_current_dir) {
+            self.bookmarks.push(self.file_browser_current_dir.clone());
+            config::save_bookmarks(&self.bookmarks);
+        }
+    }
+
+    /// Number of entries currently selectable on `DeviceList`: the fuzzy-filtered count while
+    /// `device_list_filter` is active, otherwise all of `discovered_devices`.
+    fn device_list_len(&self) -> usize {
+        match &self.device_list_filter {
+            Some(_) => self.device_list_filtered_indices.len(),
+            None => self.discovered_devices.len(),
+        }
+    }
+
+    /// Maps a `device_list_state` selection position to its index into `discovered_devices`,
+    /// accounting for `device_list_filtered_indices` while a filter is active.
+    fn device_list_real_index(&self, pos: usize) -> Option<usize> {
+        match &self.device_list_filter {
+            Some(_) => self.device_list_filtered_indices.get(pos).copied(),
+            None => (pos < self.discovered_devices.len()).then_some(pos),
+        }
+    }
+
+    /// Re-scores `discovered_devices` against `device_list_filter` and resets the selection to
+    /// the top-scored match, called after every keystroke in filter mode.
+    fn update_device_list_filter(&mut self) {
+        let query = self.device_list_filter.clone().unwrap_or_default();
+        let candidates: Vec<&str> = self
+            .discovered_devices
+            .iter()
+            .map(|d| d.display_name.as_str())
+            .collect();
+        self.device_list_filtered_indices = fuzzy::rank(&query, &candidates);
+        self.device_list_state.select(
+            (!self.device_list_filtered_indices.is_empty()).then_some(0),
+        );
+    }
+
+    /// Number of entries currently selectable on `FileBrowser`; see `device_list_len`.
+    fn file_browser_len(&self) -> usize {
+        match &self.file_browser_filter {
+            Some(_) => self.file_browser_filtered_indices.len(),
+            None => self.file_browser_entries.len(),
+        }
+    }
+
+    /// Maps a `file_browser_list_state` selection position to its index into
+    /// `file_browser_entries`; see `device_list_real_index`.
+    fn file_browser_real_index(&self, pos: usize) -> Option<usize> {
+        match &self.file_browser_filter {
+            Some(_) => self.file_browser_filtered_indices.get(pos).copied(),
+            None => (pos < self.file_browser_entries.len()).then_some(pos),
+        }
+    }
+
+    /// Re-scores `file_browser_entries` against `file_browser_filter`; see
+    /// `update_device_list_filter`.
+    fn update_file_browser_filter(&mut self) {
+        let query = self.file_browser_filter.clone().unwrap_or_default();
+        let candidates: Vec<&str> = self
+            .file_browser_entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        self.file_browser_filtered_indices = fuzzy::rank(&query, &candidates);
+        self.file_browser_list_state.select(
+            (!self.file_browser_filtered_indices.is_empty()).then_some(0),
+        );
+    }
 
-            // Sort directories and files alphabetically
-            dirs.sort_by(|a, b| a.name.cmp(&b.name));
-            files.sort_by(|a, b| a.name.cmp(&b.name));
+    /// Returns the `hex_preview` parse of `path`, re-parsing only if `path` or its mtime changed
+    /// since the last call. `hex_preview_cache` holds exactly one entry (the currently
+    /// highlighted file), since the preview pane only ever shows one at a time.
+    fn hex_preview_for(&mut self, path: &Path) -> &Result<hex_preview::Preview, hex_preview::PreviewError> {
+        let mtime = fs::metadata(path).and_then(|meta| meta.modified()).ok();
 
-            // Add directories first, then files
-            self.file_browser_entries.extend(dirs);
-            self.file_browser_entries.extend(files);
-        }
+        let stale = match &self.hex_preview_cache {
+            Some((cached_path, cached_mtime, _)) => cached_path != path || Some(*cached_mtime) != mtime,
+            None => true,
+        };
 
-        // Select first entry
-        if !self.file_browser_entries.is_empty() {
-            self.file_browser_list_state.select(Some(0));
-        } else {
-            self.file_browser_list_state.select(None);
+        if stale {
+            let result = fs::read_to_string(path)
+                .map_err(|e| hex_preview::PreviewError {
+                    line: 0,
+                    message: format!("failed to read file: {}", e),
+                })
+                .and_then(|text| hex_preview::parse(&text));
+            self.hex_preview_cache =
+                Some((path.to_path_buf(), mtime.unwrap_or(SystemTime::UNIX_EPOCH), result));
         }
-    }
 
-    fn enter_file_browser(&mut self) {
-        // Start from current working directory
-        self.file_browser_current_dir =
-            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        self.populate_file_browser();
+        &self.hex_preview_cache.as_ref().unwrap().2
     }
 
     /// Initiates an asynchronous device search operation.
@@ -1001,6 +2278,12 @@ impl App {
         self.discovered_devices.clear();
         self.error_message = None;
 
+        // A fresh search may target a different interface type than the last one, so let
+        // `spawn_device_hotplug_monitor` start a new monitor for it once `DeviceList` is reached.
+        self.device_hotplug_receiver = None;
+        self.device_hotplug_sender = None;
+        self.device_hotplug_monitor_started = false;
+
         let interface_type = match &self.selected_interface_type {
             Some(it) => it.clone(),
             None => return,
@@ -1011,6 +2294,9 @@ impl App {
             None => return,
         };
 
+        let can_bitrate = self.can_bitrate;
+        let serial_baud = self.interface_config.serial_baud;
+
         // Create channel for search updates
         let (tx, rx) = channel();
         self.search_receiver = Some(rx);
@@ -1019,20 +2305,35 @@ impl App {
         thread::spawn(move || {
             let conn_params = match interface_type {
                 InterfaceType::Sim => ComConnParams::for_sim_device(),
-                InterfaceType::Serial => ComConnParams::for_serial_conn(&interface_name, 115200),
-                InterfaceType::CAN => ComConnParams::for_can_conn(&interface_name),
+                InterfaceType::Serial => {
+                    ComConnParams::for_serial_conn(&interface_name, serial_baud)
+                }
+                InterfaceType::CAN => match can_bitrate {
+                    Some(bitrate) => {
+                        ComConnParams::for_can_conn_with_bitrate(&interface_name, bitrate)
+                    }
+                    None => ComConnParams::for_can_conn(&interface_name),
+                },
+                InterfaceType::Net => {
+                    let (protocol, host, port) = parse_net_address(&interface_name)
+                        .expect("NetConfig already validated the address");
+                    ComConnParams::for_net_conn(&host, port, protocol)
+                }
             };
 
             match interface_type {
                 InterfaceType::Sim => {
                     SIMInterface::config_nodes(SIM_NODE_LST.to_vec()).ok();
-                    Self::search_devices_async::<SIMInterface>(tx, conn_params);
+                    Self::search_devices_async::<SIMInterface>(tx, conn_params, interface_name);
                 }
                 InterfaceType::Serial => {
-                    Self::search_devices_async::<SerialInterface>(tx, conn_params);
+                    Self::search_devices_async::<SerialInterface>(tx, conn_params, interface_name);
                 }
                 InterfaceType::CAN => {
-                    Self::search_devices_async::<CANInterface>(tx, conn_params);
+                    Self::search_devices_async::<CANInterface>(tx, conn_params, interface_name);
+                }
+                InterfaceType::Net => {
+                    Self::search_devices_async::<NetInterface>(tx, conn_params, interface_name);
                 }
             }
         });
@@ -1041,6 +2342,7 @@ impl App {
     fn search_devices_async<I: ComInterface + 'static>(
         tx: Sender<SearchMessage>,
         conn_params: ComConnParams,
+        interface_name: String,
     ) {
         if I::is_network() {
             // Multi-device network interface (CAN, SIM)
@@ -1063,6 +2365,7 @@ impl App {
                                             node_id: Some(node),
                                             display_name,
                                             device_info,
+                                            interface_name: interface_name.clone(),
                                         }))
                                         .ok();
                                     }
@@ -1074,6 +2377,7 @@ impl App {
                                                 node, e
                                             ),
                                             device_info: String::new(),
+                                            interface_name: interface_name.clone(),
                                         }))
                                         .ok();
                                     }
@@ -1098,108 +2402,1058 @@ impl App {
                     .ok();
                     return;
                 }
-            }
-        } else {
-            // Single device interface (Serial)
-            match Self::connect_and_get_info::<I>(&conn_params, None) {
-                Ok((device_info, display_name)) => {
-                    tx.send(SearchMessage::DeviceFound(DiscoveredDevice {
-                        node_id: None,
-                        display_name,
-                        device_info,
-                    }))
-                    .ok();
+            }
+        } else {
+            // Single device interface (Serial)
+            match Self::connect_and_get_info::<I>(&conn_params, None) {
+                Ok((device_info, display_name)) => {
+                    tx.send(SearchMessage::DeviceFound(DiscoveredDevice {
+                        node_id: None,
+                        display_name,
+                        device_info,
+                        interface_name,
+                    }))
+                    .ok();
+                }
+                Err(e) => {
+                    tx.send(SearchMessage::Error(format!("Failed to connect: {:?}", e)))
+                        .ok();
+                    return;
+                }
+            }
+        }
+
+        tx.send(SearchMessage::Complete).ok();
+    }
+
+    fn connect_and_get_info<I: ComInterface>(
+        conn_params: &ComConnParams,
+        node_id: Option<u8>,
+    ) -> Result<(String, String), Error> {
+        let mut interface = I::create()?;
+        interface.open(conn_params)?;
+        if let Some(node) = node_id {
+            interface.set_mode(ComMode::Specific(node))?;
+        }
+
+        let mut device = Device::new(interface);
+        device.init()?;
+
+        let device_info = format!("{}", device)
+            .replace('\t', " ")
+            .replace('\r', "")
+            .replace('\n', " ");
+
+        let display_name = if let Some(node) = node_id {
+            format!("Node {:3} - {}", node, device_info)
+        } else {
+            device_info.clone()
+        };
+
+        Ok((device_info, display_name))
+    }
+
+    fn process_search_messages(&mut self) {
+        let mut search_complete = false;
+        let mut search_error = None;
+
+        if let Some(ref receiver) = self.search_receiver {
+            while let Ok(msg) = receiver.try_recv() {
+                self.dirty = true;
+                match msg {
+                    SearchMessage::DeviceFound(device) => {
+                        if let Some(pipes) = self.session_pipes.as_mut() {
+                            pipes.write_device(&device_to_json(&device));
+                        }
+                        self.discovered_devices.push(device);
+                    }
+                    SearchMessage::Complete => {
+                        if let Some(pipes) = self.session_pipes.as_mut() {
+                            pipes.write_result("{\"type\":\"complete\"}");
+                        }
+                        search_complete = true;
+                    }
+                    SearchMessage::Error(err) => {
+                        if let Some(pipes) = self.session_pipes.as_mut() {
+                            let line =
+                                format!("{{\"type\":\"error\",\"text\":\"{}\"}}", err.replace('\\', "\\\\").replace('"', "\\\""));
+                            pipes.write_result(&line);
+                        }
+                        search_error = Some(err);
+                    }
+                    // Only ever produced on `device_hotplug_receiver`, never here
+                    SearchMessage::InterfaceArrived(_) | SearchMessage::InterfaceDeparted(_) => {}
+                }
+            }
+        }
+
+        // Handle completion after the borrow ends
+        if search_complete || search_error.is_some() {
+            self.search_receiver = None;
+
+            if let Some(err) = search_error {
+                self.error_message = Some(err);
+            }
+
+            if self.discovered_devices.is_empty() && self.error_message.is_none() {
+                self.error_message = Some("No devices found".to_string());
+            }
+
+            // Set refresh message if this was a refresh operation
+            if self.is_refresh_search {
+                let device_count = self.discovered_devices.len();
+                self.device_list_refresh_message = Some(format!(
+                    "Device list refreshed - Found {} device(s)",
+                    device_count
+                ));
+                self.is_refresh_search = false;
+            }
+
+            if !self.discovered_devices.is_empty() {
+                self.device_list_state.select(Some(0));
+                self.current_screen = Screen::DeviceList;
+            } else if self.error_message.is_some() {
+                self.current_screen = Screen::Results;
+            } else {
+                self.current_screen = Screen::DeviceList;
+            }
+
+            if self.current_screen == Screen::DeviceList {
+                self.spawn_device_hotplug_monitor();
+            }
+        }
+    }
+
+    /// Drains and dispatches commands waiting on the `--session` control pipe's `msg_in`, if one
+    /// is open. A no-op when `--session` wasn't passed.
+    fn process_session_commands(&mut self) {
+        let commands = match self.session_pipes.as_mut() {
+            Some(pipes) => pipes.read_commands(),
+            None => return,
+        };
+
+        for command in commands {
+            self.dirty = true;
+            self.handle_session_command(&command);
+        }
+    }
+
+    /// Dispatches one `msg_in` line the same way the matching keypress would, reusing the
+    /// existing `spawn_search`/`execute_command` paths so the control pipe and the interactive
+    /// UI can't drift apart.
+    ///
+    /// Supported commands:
+    /// - `refresh`: re-run device search, as F5 does on `DeviceList`
+    /// - `select-node <id>`: select the discovered device with that node ID, as Enter does on
+    ///   `DeviceList`
+    /// - `flash <path>`: set the hex file path and run `Flash` against the selected device
+    ///   straight away, without the `ConfirmDialog` that Enter on `HexFileInput` opens first
+    fn handle_session_command(&mut self, command: &str) {
+        let mut parts = command.splitn(2, ' ');
+        let verb = parts.next().unwrap_or("");
+        let arg = parts.next().unwrap_or("").trim();
+
+        match verb {
+            "refresh" => {
+                self.is_refresh_search = true;
+                self.current_screen = Screen::Searching;
+                self.spawn_search();
+            }
+            "select-node" => {
+                if let Ok(node_id) = arg.parse::<u8>() {
+                    if let Some(index) = self
+                        .discovered_devices
+                        .iter()
+                        .position(|d| d.node_id == Some(node_id))
+                    {
+                        self.selected_device_index = Some(index);
+                        self.device_list_state.select(Some(index));
+                        self.current_screen = Screen::CommandMenu;
+                    }
+                }
+            }
+            "flash" => {
+                if !arg.is_empty() {
+                    self.hex_file_path = arg.to_string();
+                    self.selected_command = Some(Command::Flash);
+                    self.current_screen = Screen::Executing;
+                    self.execute_command();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Starts watching for devices arriving/departing while `DeviceList` is shown.
+    ///
+    /// Unlike `spawn_interface_monitor` (which only keeps `available_interfaces` current on
+    /// `InterfaceSelection`), this probes newly-arrived interfaces for a device and removes
+    /// `discovered_devices` entries belonging to an interface that went away, so the list stays
+    /// live without the user pressing F5. Only one monitor runs per search session; re-running
+    /// `spawn_search` resets `device_hotplug_monitor_started` so a later search with a different
+    /// interface type gets its own monitor.
+    ///
+    /// ## Backend
+    ///
+    /// Serial adapters are almost always USB devices, so this prefers a libusb hotplug callback
+    /// (`rusb`'s `Hotplug` trait, registered against every VID/PID and pumped from a dedicated
+    /// thread) over polling, falling back to the same polling diff used elsewhere if the
+    /// platform's libusb build lacks hotplug support. CAN links are not necessarily USB-backed,
+    /// so they're watched the same way `spawn_interface_monitor` does: a udev monitor on the
+    /// "net" subsystem, falling back to polling. Either path debounces repeated raw events for
+    /// the same interface name into a single `InterfaceArrived`/`InterfaceDeparted`.
+    fn spawn_device_hotplug_monitor(&mut self) {
+        if self.device_hotplug_monitor_started {
+            return;
+        }
+
+        let interface_type = match &self.selected_interface_type {
+            Some(InterfaceType::Sim) | Some(InterfaceType::Net) | None => return,
+            Some(it) => it.clone(),
+        };
+
+        self.device_hotplug_monitor_started = true;
+
+        let (tx, rx) = channel();
+        self.device_hotplug_sender = Some(tx.clone());
+        self.device_hotplug_receiver = Some(rx);
+
+        thread::spawn(move || match interface_type {
+            InterfaceType::Serial => Self::watch_usb_hotplug(&tx),
+            InterfaceType::CAN => Self::watch_can_hotplug(&tx),
+            InterfaceType::Sim | InterfaceType::Net => {}
+        });
+    }
+
+    /// Watches for USB serial adapters being plugged/unplugged via libusb hotplug callbacks.
+    ///
+    /// Falls back to `poll_device_names` if the platform's libusb build has no hotplug support
+    /// (`rusb::has_hotplug()`) or the context/registration cannot be created.
+    fn watch_usb_hotplug(tx: &Sender<SearchMessage>) {
+        if !rusb::has_hotplug() {
+            Self::poll_device_names(&InterfaceType::Serial, tx);
+            return;
+        }
+
+        let context = match rusb::Context::new() {
+            Ok(ctx) => ctx,
+            Err(_) => {
+                Self::poll_device_names(&InterfaceType::Serial, tx);
+                return;
+            }
+        };
+
+        struct SerialHotplugHandler {
+            tx: Sender<SearchMessage>,
+            debounce: HotplugDebouncer,
+        }
+
+        impl rusb::Hotplug<rusb::Context> for SerialHotplugHandler {
+            fn device_arrived(&mut self, device: rusb::Device<rusb::Context>) {
+                if let Some(name) = App::usb_device_to_serial_port(&device) {
+                    if self.debounce.should_emit(&name) {
+                        self.tx.send(SearchMessage::InterfaceArrived(name)).ok();
+                    }
+                }
+            }
+
+            fn device_left(&mut self, device: rusb::Device<rusb::Context>) {
+                if let Some(name) = App::usb_device_to_serial_port(&device) {
+                    self.tx.send(SearchMessage::InterfaceDeparted(name)).ok();
+                }
+            }
+        }
+
+        let handler = Box::new(SerialHotplugHandler {
+            tx: tx.clone(),
+            debounce: HotplugDebouncer::new(),
+        });
+
+        let registration = match rusb::HotplugBuilder::new()
+            .enumerate(false)
+            .register(&context, handler)
+        {
+            Ok(reg) => reg,
+            Err(_) => {
+                Self::poll_device_names(&InterfaceType::Serial, tx);
+                return;
+            }
+        };
+
+        while context
+            .handle_events(Some(Duration::from_millis(500)))
+            .is_ok()
+        {}
+
+        drop(registration);
+    }
+
+    /// Maps a USB device to the serial port `serialport` enumerates for it, by matching VID/PID.
+    fn usb_device_to_serial_port(device: &rusb::Device<rusb::Context>) -> Option<String> {
+        let descriptor = device.device_descriptor().ok()?;
+        let vid = descriptor.vendor_id();
+        let pid = descriptor.product_id();
+
+        serialport::available_ports()
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|port| match port.port_type {
+                serialport::SerialPortType::UsbPort(info) if info.vid == vid && info.pid == pid => {
+                    Some(port.port_name)
+                }
+                _ => None,
+            })
+    }
+
+    /// Watches for CAN/vCAN links appearing/disappearing via udev, falling back to polling.
+    fn watch_can_hotplug(tx: &Sender<SearchMessage>) {
+        if Self::watch_udev_can(tx).is_err() {
+            Self::poll_device_names(&InterfaceType::CAN, tx);
+        }
+    }
+
+    fn watch_udev_can(tx: &Sender<SearchMessage>) -> Result<(), std::io::Error> {
+        let socket = udev::MonitorBuilder::new()?
+            .match_subsystem("net")?
+            .listen()?;
+
+        let mut debounce = HotplugDebouncer::new();
+
+        for event in socket.iter() {
+            let Some(name) = event.sysname().to_str() else {
+                continue;
+            };
+
+            if !(name.starts_with("can") || name.starts_with("vcan")) {
+                continue;
+            }
+
+            let sent = match event.event_type() {
+                udev::EventType::Add => {
+                    if debounce.should_emit(name) {
+                        tx.send(SearchMessage::InterfaceArrived(name.to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+                udev::EventType::Remove => {
+                    tx.send(SearchMessage::InterfaceDeparted(name.to_string()))
+                }
+                _ => Ok(()),
+            };
+
+            if sent.is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Polling fallback shared by both backends: rescans every second and diffs against the
+    /// previous snapshot, debouncing arrivals the same way the hotplug callback paths do.
+    fn poll_device_names(interface_type: &InterfaceType, tx: &Sender<SearchMessage>) {
+        const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+        let mut known = Self::scan_interface_names(interface_type);
+        let mut debounce = HotplugDebouncer::new();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let current = Self::scan_interface_names(interface_type);
+
+            for name in &current {
+                if !known.contains(name)
+                    && debounce.should_emit(name)
+                    && tx
+                        .send(SearchMessage::InterfaceArrived(name.clone()))
+                        .is_err()
+                {
+                    return;
+                }
+            }
+            for name in &known {
+                if !current.contains(name)
+                    && tx
+                        .send(SearchMessage::InterfaceDeparted(name.clone()))
+                        .is_err()
+                {
+                    return;
+                }
+            }
+
+            known = current;
+        }
+    }
+
+    /// Applies pending device arrivals/departures from `device_hotplug_receiver`.
+    ///
+    /// Called every UI tick alongside `process_search_messages`. A departed interface's devices
+    /// are removed from `discovered_devices` directly; an arrived interface is added to
+    /// `available_interfaces` and handed to a one-shot probe thread (reusing
+    /// `search_devices_async`) whose results flow back over the same channel as a `DeviceFound`.
+    fn process_device_hotplug_messages(&mut self) {
+        let Some(ref receiver) = self.device_hotplug_receiver else {
+            return;
+        };
+
+        let mut arrived = Vec::new();
+        let mut departed = Vec::new();
+        let mut found = Vec::new();
+        let mut probe_error = None;
+
+        let mut received_any = false;
+        while let Ok(msg) = receiver.try_recv() {
+            received_any = true;
+            match msg {
+                SearchMessage::DeviceFound(device) => found.push(device),
+                SearchMessage::InterfaceArrived(name) => arrived.push(name),
+                SearchMessage::InterfaceDeparted(name) => departed.push(name),
+                SearchMessage::Error(err) => probe_error = Some(err),
+                SearchMessage::Complete => {}
+            }
+        }
+
+        if received_any {
+            self.dirty = true;
+        }
+
+        for device in found {
+            self.discovered_devices.push(device);
+        }
+
+        if !departed.is_empty() {
+            self.discovered_devices
+                .retain(|device| !departed.contains(&device.interface_name));
+            self.available_interfaces
+                .retain(|name| !departed.contains(name));
+        }
+
+        if let Some(err) = probe_error {
+            self.device_list_refresh_message = Some(format!("Hotplug probe failed: {}", err));
+        }
+
+        if !arrived.is_empty() {
+            let interface_type = self.selected_interface_type.clone();
+            let can_bitrate = self.can_bitrate;
+            let serial_baud = self.interface_config.serial_baud;
+
+            if let Some(sender) = self.device_hotplug_sender.clone() {
+                for name in arrived {
+                    if !self.available_interfaces.contains(&name) {
+                        self.available_interfaces.push(name.clone());
+                    }
+                    Self::probe_arrived_interface(
+                        interface_type.clone(),
+                        can_bitrate,
+                        serial_baud,
+                        name,
+                        sender.clone(),
+                    );
+                }
+            }
+
+            self.available_interfaces.sort();
+        }
+    }
+
+    /// Probes a single newly-arrived interface for a device, reusing `search_devices_async` so
+    /// the same connect/scan/identify logic backs both the initial search and hotplug arrivals.
+    fn probe_arrived_interface(
+        interface_type: Option<InterfaceType>,
+        can_bitrate: Option<u32>,
+        serial_baud: u32,
+        interface_name: String,
+        tx: Sender<SearchMessage>,
+    ) {
+        let Some(interface_type) = interface_type else {
+            return;
+        };
+
+        thread::spawn(move || {
+            let conn_params = match interface_type {
+                InterfaceType::Sim => ComConnParams::for_sim_device(),
+                InterfaceType::Serial => {
+                    ComConnParams::for_serial_conn(&interface_name, serial_baud)
+                }
+                InterfaceType::CAN => match can_bitrate {
+                    Some(bitrate) => {
+                        ComConnParams::for_can_conn_with_bitrate(&interface_name, bitrate)
+                    }
+                    None => ComConnParams::for_can_conn(&interface_name),
+                },
+                // Net has no hotplug concept (see `spawn_device_hotplug_monitor`), so this arm
+                // is never actually reached; kept for exhaustiveness.
+                InterfaceType::Net => ComConnParams::for_sim_device(),
+            };
+
+            match interface_type {
+                InterfaceType::Sim | InterfaceType::Net => {}
+                InterfaceType::Serial => {
+                    Self::search_devices_async::<SerialInterface>(tx, conn_params, interface_name);
                 }
-                Err(e) => {
-                    tx.send(SearchMessage::Error(format!("Failed to connect: {:?}", e)))
-                        .ok();
-                    return;
+                InterfaceType::CAN => {
+                    Self::search_devices_async::<CANInterface>(tx, conn_params, interface_name);
                 }
             }
-        }
+        });
+    }
+}
 
-        tx.send(SearchMessage::Complete).ok();
+/// Collapses repeated raw hotplug events for the same interface name into a single emission.
+///
+/// A single physical plug/unplug can fire several udev/libusb events in quick succession (e.g.
+/// a USB composite device enumerating multiple interfaces); without this, one plug would produce
+/// several `InterfaceArrived` messages and several redundant probes.
+struct HotplugDebouncer {
+    last_emitted: std::collections::HashMap<String, std::time::Instant>,
+}
+
+impl HotplugDebouncer {
+    const WINDOW: Duration = Duration::from_millis(750);
+
+    fn new() -> Self {
+        HotplugDebouncer {
+            last_emitted: std::collections::HashMap::new(),
+        }
     }
 
-    fn connect_and_get_info<I: ComInterface>(
-        conn_params: &ComConnParams,
-        node_id: Option<u8>,
-    ) -> Result<(String, String), Error> {
-        let mut interface = I::create()?;
-        interface.open(conn_params)?;
-        if let Some(node) = node_id {
-            interface.set_mode(ComMode::Specific(node))?;
+    /// Returns true the first time `name` is seen, and again only once `WINDOW` has elapsed
+    /// since the last time it was seen.
+    fn should_emit(&mut self, name: &str) -> bool {
+        let now = std::time::Instant::now();
+
+        if let Some(last) = self.last_emitted.get(name) {
+            if now.duration_since(*last) < Self::WINDOW {
+                self.last_emitted.insert(name.to_string(), now);
+                return false;
+            }
         }
 
-        let mut device = Device::new(interface);
-        device.init()?;
+        self.last_emitted.insert(name.to_string(), now);
+        true
+    }
+}
 
-        let device_info = format!("{}", device)
-            .replace('\t', " ")
-            .replace('\r', "")
-            .replace('\n', " ");
+// ================================================================================================
+// Coredump Export
+// ================================================================================================
 
-        let display_name = if let Some(node) = node_id {
-            format!("Node {:3} - {}", node, device_info)
-        } else {
-            device_info.clone()
-        };
+/// Wraps a raw memory dump into a minimal ELF file on disk.
+///
+/// Mirrors the nag52 crash analyzer's "Read coredump ELF" command: the dump is written as a
+/// single loadable section at `start_address` with no symbol table, so standard ELF tooling
+/// (`objdump`, `gdb`) can load it and, given the firmware ELF that was originally flashed, make
+/// sense of the addresses.
+fn write_coredump_elf(path: &str, start_address: u32, data: &[u8]) -> Result<(), String> {
+    let mut object = object::write::Object::new(
+        object::BinaryFormat::Elf,
+        object::Architecture::Arm,
+        object::Endianness::Little,
+    );
+
+    let section_id = object.add_section(
+        Vec::new(),
+        b".coredump".to_vec(),
+        object::SectionKind::Data,
+    );
+    object.set_section_data(section_id, data.to_vec(), 4);
+    object
+        .section_mut(section_id)
+        .flags = object::write::SectionFlags::Elf {
+        sh_flags: (object::elf::SHF_ALLOC | object::elf::SHF_WRITE) as u64,
+    };
+    object.section_mut(section_id).address = start_address as u64;
 
-        Ok((device_info, display_name))
-    }
+    let bytes = object
+        .write()
+        .map_err(|e| format!("Failed to encode ELF: {}", e))?;
 
-    fn process_search_messages(&mut self) {
-        let mut search_complete = false;
-        let mut search_error = None;
+    fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
 
-        if let Some(ref receiver) = self.search_receiver {
-            while let Ok(msg) = receiver.try_recv() {
-                match msg {
-                    SearchMessage::DeviceFound(device) => {
-                        self.discovered_devices.push(device);
-                    }
-                    SearchMessage::Complete => {
-                        search_complete = true;
-                    }
-                    SearchMessage::Error(err) => {
-                        search_error = Some(err);
-                    }
+// ================================================================================================
+// Log Capture Backend
+// ================================================================================================
+//
+// Free functions backing `App::spawn_log_capture`. Kept outside `impl App` since they run on
+// the background thread and only need a `Sender<LogMessage>`, not the app state itself.
+
+/// Decodes a defmt frame stream using the symbol table embedded in a target ELF's `.defmt`
+/// section.
+struct DefmtDecoder {
+    stream_decoder: Box<dyn defmt_decoder::StreamDecoder>,
+}
+
+impl DefmtDecoder {
+    fn from_elf(elf_path: &str) -> Result<Self, String> {
+        let elf_bytes =
+            fs::read(elf_path).map_err(|e| format!("Failed to read {}: {}", elf_path, e))?;
+        let table = defmt_decoder::Table::parse(&elf_bytes)
+            .map_err(|e| format!("Failed to parse defmt table from {}: {}", elf_path, e))?
+            .ok_or_else(|| format!("{} has no .defmt section", elf_path))?;
+        let stream_decoder = table.new_stream_decoder();
+
+        Ok(DefmtDecoder { stream_decoder })
+    }
+
+    /// Feeds newly received bytes in and returns every complete frame decoded so far, rendered
+    /// as a display-ready line.
+    fn decode(&mut self, data: &[u8]) -> Vec<String> {
+        self.stream_decoder.received(data);
+
+        let mut lines = Vec::new();
+        loop {
+            match self.stream_decoder.decode() {
+                Ok(frame) => lines.push(frame.display(false).to_string()),
+                Err(defmt_decoder::DecodeError::UnexpectedEof) => break,
+                Err(defmt_decoder::DecodeError::Malformed { .. }) => {
+                    lines.push("<defmt: malformed frame, resyncing>".to_string());
                 }
             }
         }
 
-        // Handle completion after the borrow ends
-        if search_complete || search_error.is_some() {
-            self.search_receiver = None;
+        lines
+    }
+}
 
-            if let Some(err) = search_error {
-                self.error_message = Some(err);
+/// Attaches to the target over its debug probe and streams its RTT up-channel to `tx`.
+///
+/// Decodes defmt frames when `elf_path` points at firmware built with a `.defmt` section,
+/// otherwise treats the channel as plain UTF-8 text. Runs until the probe connection drops or
+/// `tx`'s receiver is gone (the user left `LogView`).
+fn capture_rtt_log(elf_path: Option<&str>, tx: &Sender<LogMessage>) -> Result<(), String> {
+    let mut decoder = match elf_path {
+        Some(path) => Some(DefmtDecoder::from_elf(path)?),
+        None => None,
+    };
+
+    let mut session = probe_rs::Session::auto_attach(
+        probe_rs::probe::list::Lister::new()
+            .list_all()
+            .first()
+            .ok_or("No debug probe found")?,
+        probe_rs::Permissions::default(),
+    )
+    .map_err(|e| format!("Failed to attach to target: {}", e))?;
+
+    let memory_map = session.target().memory_map.clone();
+    let mut core = session.core(0).map_err(|e| format!("Failed to open core: {}", e))?;
+    let mut rtt = probe_rs::rtt::Rtt::attach(&mut core, &memory_map)
+        .map_err(|e| format!("Failed to attach RTT: {}", e))?;
+
+    let mut buf = [0u8; 1024];
+    loop {
+        for channel in rtt.up_channels.iter_mut() {
+            let n = channel.read(&mut core, &mut buf).unwrap_or(0);
+            if n == 0 {
+                continue;
             }
 
-            if self.discovered_devices.is_empty() && self.error_message.is_none() {
-                self.error_message = Some("No devices found".to_string());
+            let lines = match decoder.as_mut() {
+                Some(dec) => dec.decode(&buf[..n]),
+                None => String::from_utf8_lossy(&buf[..n])
+                    .lines()
+                    .map(|l| l.to_string())
+                    .collect(),
+            };
+
+            for line in lines {
+                if tx.send(LogMessage::Line(line)).is_err() {
+                    return Ok(());
+                }
             }
+        }
 
-            // Set refresh message if this was a refresh operation
-            if self.is_refresh_search {
-                let device_count = self.discovered_devices.len();
-                self.device_list_refresh_message = Some(format!(
-                    "Device list refreshed - Found {} device(s)",
-                    device_count
-                ));
-                self.is_refresh_search = false;
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Reopens a serial port as a plain UTF-8 line reader.
+///
+/// This is the fallback used when RTT isn't available (no debug probe attached) and the device
+/// was reached over `Serial`, since that link can at least carry a `println!`-style boot log.
+fn capture_serial_log(port_name: &str, baud: u32, tx: &Sender<LogMessage>) -> Result<(), String> {
+    let port = serialport::new(port_name, baud)
+        .timeout(Duration::from_millis(200))
+        .open()
+        .map_err(|e| format!("Failed to reopen {}: {}", port_name, e))?;
+
+    let mut reader = BufReader::new(port);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {
+                let trimmed = line.trim_end_matches(['\r', '\n']);
+                if !trimmed.is_empty() && tx.send(LogMessage::Line(trimmed.to_string())).is_err() {
+                    return Ok(());
+                }
             }
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => continue,
+            Err(e) => return Err(format!("Serial read failed: {}", e)),
+        }
+    }
+}
 
-            if !self.discovered_devices.is_empty() {
-                self.device_list_state.select(Some(0));
-                self.current_screen = Screen::DeviceList;
-            } else if self.error_message.is_some() {
-                self.current_screen = Screen::Results;
+// ================================================================================================
+// Headless CLI Mode
+// ================================================================================================
+
+/// Parses an `--interface` argument of the form "type:name" (e.g. "serial:/dev/ttyUSB0",
+/// "can:can0", "net:tcp:192.168.1.10:4242"), or the bare "sim" shorthand, into an
+/// `(InterfaceType, name)` pair. For `net`, `name` is left as the full `proto:host:port` address
+/// for `headless_conn_params` to parse.
+fn parse_interface_spec(spec: &str) -> Result<(InterfaceType, String), String> {
+    if spec == "sim" {
+        return Ok((InterfaceType::Sim, "sim".to_string()));
+    }
+
+    let (kind, name) = spec.split_once(':').ok_or_else(|| {
+        format!(
+            "--interface \"{}\" must be \"type:name\", e.g. \"serial:/dev/ttyUSB0\"",
+            spec
+        )
+    })?;
+
+    let interface_type = match kind {
+        "serial" => InterfaceType::Serial,
+        "can" => InterfaceType::CAN,
+        "sim" => InterfaceType::Sim,
+        "net" => InterfaceType::Net,
+        other => {
+            return Err(format!(
+                "Unknown interface type \"{}\" (expected serial/can/sim/net)",
+                other
+            ))
+        }
+    };
+
+    Ok((interface_type, name.to_string()))
+}
+
+/// Connection parameters for a headless command. Mirrors `App::get_conn_params`, minus the
+/// `can_bitrate` override, which only matters after `CanConfig` has run interactively.
+fn headless_conn_params(
+    interface_type: &InterfaceType,
+    interface_name: &str,
+    serial_baud: u32,
+) -> ComConnParams {
+    match interface_type {
+        InterfaceType::Sim => ComConnParams::for_sim_device(),
+        InterfaceType::Serial => ComConnParams::for_serial_conn(interface_name, serial_baud),
+        InterfaceType::CAN => ComConnParams::for_can_conn(interface_name),
+        InterfaceType::Net => {
+            let (protocol, host, port) =
+                parse_net_address(interface_name).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            ComConnParams::for_net_conn(&host, port, protocol)
+        }
+    }
+}
+
+/// Renders one `OperationMessage` as a single JSON object, for `--json` consumers such as a
+/// build server, and for the `--session` pipes. Hand-rolled rather than pulling in `serde_json`
+/// for these two call sites.
+fn operation_message_to_json(msg: &OperationMessage) -> String {
+    fn escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    match msg {
+        OperationMessage::Progress(ProgressUpdate::EraseProgress { current, total }) => format!(
+            "{{\"type\":\"erase_progress\",\"current\":{},\"total\":{}}}",
+            current, total
+        ),
+        OperationMessage::Progress(ProgressUpdate::FlashProgress { current, total }) => format!(
+            "{{\"type\":\"flash_progress\",\"current\":{},\"total\":{}}}",
+            current, total
+        ),
+        OperationMessage::Progress(ProgressUpdate::VerifyProgress { current, total }) => format!(
+            "{{\"type\":\"verify_progress\",\"current\":{},\"total\":{}}}",
+            current, total
+        ),
+        OperationMessage::Progress(ProgressUpdate::Message(text)) => {
+            format!("{{\"type\":\"message\",\"text\":\"{}\"}}", escape(text))
+        }
+        OperationMessage::DeviceInfo(info) => {
+            format!("{{\"type\":\"device_info\",\"text\":\"{}\"}}", escape(info))
+        }
+        OperationMessage::VerifyResult(mismatches) => {
+            let addresses = mismatches
+                .iter()
+                .map(|a| format!("\"{:#010X}\"", a))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                "{{\"type\":\"verify_result\",\"mismatches\":[{}]}}",
+                addresses
+            )
+        }
+        OperationMessage::Complete => "{\"type\":\"complete\"}".to_string(),
+        OperationMessage::Error(err) => {
+            format!("{{\"type\":\"error\",\"text\":\"{}\"}}", escape(err))
+        }
+    }
+}
+
+/// Renders a `DiscoveredDevice` as a single JSON object, for the `--session` control pipe's
+/// `devices_out`. Hand-rolled for the same reason as `operation_message_to_json`.
+fn device_to_json(device: &DiscoveredDevice) -> String {
+    fn escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    format!(
+        "{{\"node_id\":{},\"display_name\":\"{}\",\"device_info\":\"{}\",\"interface_name\":\"{}\"}}",
+        device
+            .node_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "null".to_string()),
+        escape(&device.display_name),
+        escape(&device.device_info),
+        escape(&device.interface_name),
+    )
+}
+
+/// Prints one `OperationMessage` as a plain-text progress line on stderr, leaving stdout free
+/// for a final result.
+fn print_operation_message_plain(msg: &OperationMessage) {
+    match msg {
+        OperationMessage::Progress(ProgressUpdate::EraseProgress { current, total }) => {
+            eprintln!("Erasing page {}/{}", current, total);
+        }
+        OperationMessage::Progress(ProgressUpdate::FlashProgress { current, total }) => {
+            eprintln!("Flashing page {}/{}", current, total);
+        }
+        OperationMessage::Progress(ProgressUpdate::VerifyProgress { current, total }) => {
+            eprintln!("Verifying page {}/{}", current, total);
+        }
+        OperationMessage::Progress(ProgressUpdate::Message(text)) => eprintln!("{}", text),
+        OperationMessage::DeviceInfo(info) => eprintln!("{}", info),
+        OperationMessage::VerifyResult(mismatches) => {
+            if mismatches.is_empty() {
+                eprintln!("Verify OK: flash matches hex file");
             } else {
-                self.current_screen = Screen::DeviceList;
+                for address in mismatches {
+                    eprintln!("Mismatch at {:#010X}", address);
+                }
             }
         }
+        OperationMessage::Complete => eprintln!("Done"),
+        OperationMessage::Error(err) => eprintln!("Error: {}", err),
+    }
+}
+
+/// Drains `rx` to completion, printing each message, and returns the process exit code: 0 on
+/// `Complete`, 1 on `Error` or if the sender was dropped without either.
+fn drain_headless_operation(rx: Receiver<OperationMessage>, json: bool) -> i32 {
+    for msg in rx.iter() {
+        if json {
+            println!("{}", operation_message_to_json(&msg));
+        } else {
+            print_operation_message_plain(&msg);
+        }
+
+        match msg {
+            OperationMessage::Complete => return 0,
+            OperationMessage::Error(_) => return 1,
+            _ => {}
+        }
+    }
+
+    1
+}
+
+/// Runs `reset`/`erase`/`flash` headlessly, reusing the exact `App::spawn_*` background-thread
+/// logic the TUI drives interactively from `execute_command` so both share one code path.
+fn run_headless_command(
+    subcommand: &str,
+    interface_type: InterfaceType,
+    interface_name: String,
+    node: Option<u8>,
+    hex_file_path: String,
+    json: bool,
+) -> i32 {
+    let app = App::new();
+    let conn_params =
+        headless_conn_params(&interface_type, &interface_name, app.interface_config.serial_baud);
+    let (tx, rx) = channel();
+
+    if interface_type == InterfaceType::Sim {
+        SIMInterface::config_nodes(SIM_NODE_LST.to_vec()).ok();
+    }
+
+    match (interface_type, subcommand) {
+        (InterfaceType::Sim, "reset") => {
+            app.spawn_operation::<SIMInterface>(tx, conn_params, node, None)
+        }
+        (InterfaceType::Sim, "erase") => app.spawn_erase::<SIMInterface>(tx, conn_params, node),
+        (InterfaceType::Sim, "flash") => {
+            app.spawn_flash::<SIMInterface>(tx, conn_params, node, hex_file_path)
+        }
+        (InterfaceType::Serial, "reset") => {
+            app.spawn_operation::<SerialInterface>(tx, conn_params, node, None)
+        }
+        (InterfaceType::Serial, "erase") => {
+            app.spawn_erase::<SerialInterface>(tx, conn_params, node)
+        }
+        (InterfaceType::Serial, "flash") => {
+            app.spawn_flash::<SerialInterface>(tx, conn_params, node, hex_file_path)
+        }
+        (InterfaceType::CAN, "reset") => {
+            app.spawn_operation::<CANInterface>(tx, conn_params, node, None)
+        }
+        (InterfaceType::CAN, "erase") => app.spawn_erase::<CANInterface>(tx, conn_params, node),
+        (InterfaceType::CAN, "flash") => {
+            app.spawn_flash::<CANInterface>(tx, conn_params, node, hex_file_path)
+        }
+        (InterfaceType::Net, "reset") => {
+            app.spawn_operation::<NetInterface>(tx, conn_params, node, None)
+        }
+        (InterfaceType::Net, "erase") => app.spawn_erase::<NetInterface>(tx, conn_params, node),
+        (InterfaceType::Net, "flash") => {
+            app.spawn_flash::<NetInterface>(tx, conn_params, node, hex_file_path)
+        }
+        (_, other) => unreachable!("clap only routes here for reset/erase/flash, got {}", other),
+    }
+
+    drain_headless_operation(rx, json)
+}
+
+/// Lists available interfaces for the headless `list` subcommand, the same sources
+/// `App::discover_interfaces` uses, without needing a running `App`/background probe thread.
+fn print_headless_interface_list() {
+    println!("Serial ports:");
+    for name in App::scan_interface_names(&InterfaceType::Serial) {
+        println!("  {}", name);
+    }
+
+    println!("CAN interfaces:");
+    for name in App::scan_interface_names(&InterfaceType::CAN) {
+        println!("  {}", name);
     }
+
+    println!("Sim nodes:");
+    for node in SIM_NODE_LST {
+        println!("  {}", node);
+    }
+
+    println!("Net: no local enumeration; use --interface net:tcp:host:port or net:udp:host:port");
+}
+
+/// Builds the headless command line. A subcommand makes `main` run it headlessly and skip the
+/// interactive TUI; with no subcommand (no arguments at all, or just `--session`) the TUI starts
+/// as usual.
+fn build_headless_cli() -> ClapCommand {
+    let interface_arg = Arg::new("interface")
+        .long("interface")
+        .help(
+            "Interface spec \"serial:/dev/ttyUSB0\", \"can:can0\", \"net:tcp:host:port\", \
+             \"net:udp:host:port\", or \"sim\"",
+        )
+        .required(true)
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let node_arg = Arg::new("node")
+        .long("node")
+        .help("Node ID for CAN/sim networks")
+        .value_parser(clap::value_parser!(u8).range(0..))
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    let json_arg = Arg::new("json")
+        .long("json")
+        .help("Emit one JSON object per progress/result message on stdout instead of plain text")
+        .action(ArgAction::SetTrue);
+
+    let session_arg = Arg::new("session")
+        .long("session")
+        .help(
+            "Directory for a named-pipe control session (msg_in/progress_out/result_out/\
+             devices_out) a harness can use to drive the interactive TUI instead of a keyboard",
+        )
+        .action(ArgAction::Set)
+        .num_args(1);
+
+    ClapCommand::new("frankly-fw-update-tui")
+        .version("0.1.0")
+        .about("Interactive firmware updater; any subcommand below runs headlessly instead of opening the TUI")
+        .subcommand_required(false)
+        .arg_required_else_help(false)
+        .arg(session_arg)
+        .subcommand(
+            ClapCommand::new("list")
+                .about("List available serial ports, CAN interfaces and sim nodes"),
+        )
+        .subcommand(
+            ClapCommand::new("reset")
+                .about("Reset the device")
+                .arg(interface_arg.clone())
+                .arg(node_arg.clone())
+                .arg(json_arg.clone()),
+        )
+        .subcommand(
+            ClapCommand::new("erase")
+                .about("Erase the device's application flash")
+                .arg(interface_arg.clone())
+                .arg(node_arg.clone())
+                .arg(json_arg.clone()),
+        )
+        .subcommand(
+            ClapCommand::new("flash")
+                .about("Flash a hex file to the device")
+                .arg(
+                    Arg::new("hex")
+                        .help("Path to the firmware hex file")
+                        .required(true)
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(interface_arg)
+                .arg(node_arg)
+                .arg(json_arg),
+        )
+}
+
+/// Runs the headless path for a parsed `reset`/`erase`/`flash`/`list` invocation and exits the
+/// process with the resulting code. Never returns.
+fn run_headless(subcommand: &str, sub_matches: &clap::ArgMatches) -> ! {
+    let exit_code = if subcommand == "list" {
+        print_headless_interface_list();
+        0
+    } else {
+        let interface_spec = sub_matches.get_one::<String>("interface").unwrap();
+        let (interface_type, interface_name) = match parse_interface_spec(interface_spec) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let node = sub_matches.get_one::<u8>("node").copied();
+        let json = sub_matches.get_flag("json");
+        let hex_file_path = sub_matches
+            .get_one::<String>("hex")
+            .cloned()
+            .unwrap_or_default();
+
+        run_headless_command(
+            subcommand,
+            interface_type,
+            interface_name,
+            node,
+            hex_file_path,
+            json,
+        )
+    };
+
+    std::process::exit(exit_code);
 }
 
 // ================================================================================================
@@ -1216,20 +3470,68 @@ impl App {
 /// - Enables raw mode (disables line buffering, echo)
 /// - Switches to alternate screen (preserves main terminal content)
 /// - Enables mouse capture for potential future enhancements
+/// - Enables bracketed paste so a pasted hex file path arrives as one `Event::Paste` instead of a
+///   flood of individual `KeyCode::Char` events
+///
+/// ## Headless Mode
+///
+/// Running with any subcommand (`list`/`reset`/`erase`/`flash`) bypasses `App::new()`/`run_app`
+/// entirely: `run_headless` drives the same `spawn_*` background-thread logic the TUI uses,
+/// prints progress to stderr (or one JSON object per message to stdout with `--json`), and exits
+/// with a non-zero code on any `Error` message — no interactive terminal is touched.
 ///
 /// ## Error Handling
 ///
 /// Ensures terminal is properly restored even if the app panics or returns an error.
+/// Installs a panic hook that restores the terminal (raw mode, alternate screen, mouse capture)
+/// before printing the original panic, so a panic inside `run_app` (e.g. in `execute_command`,
+/// `spawn_search`, or a `draw_*` call) leaves a clean, readable terminal instead of corrupting
+/// it. Chains to whatever hook was previously installed rather than replacing it outright.
+fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        );
+        previous_hook(panic_info);
+    }));
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = build_headless_cli().get_matches();
+    if let Some((subcommand, sub_matches)) = matches.subcommand() {
+        run_headless(subcommand, sub_matches);
+    }
+
     // Setup terminal for TUI mode
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // From here on a panic would otherwise leave the terminal in raw/alternate-screen mode with
+    // no visible cursor; restore it first so the user sees the panic message instead of a
+    // garbled, echo-less shell.
+    install_panic_hook();
+
     // Create app state and run event loop
     let mut app = App::new();
+    if let Some(dir) = matches.get_one::<String>("session") {
+        match session_pipe::SessionPipes::create(Path::new(dir)) {
+            Ok(pipes) => app.session_pipes = Some(pipes),
+            Err(e) => eprintln!("Warning: --session ignored: {}", e),
+        }
+    }
     let res = run_app(&mut terminal, &mut app);
 
     // Restore terminal to original state
@@ -1237,7 +3539,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
@@ -1253,60 +3556,213 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///
 /// Continuously:
 /// 1. Processes messages from background threads (operations and searches)
-/// 2. Redraws the UI based on current app state
-/// 3. Polls for keyboard input with 100ms timeout
+/// 2. Redraws the UI, but only if `app.dirty` was set since the last frame
+/// 3. Waits for either the next terminal event or a fallback animation tick
 /// 4. Dispatches input to appropriate screen handler
 ///
 /// ## Performance
 ///
-/// - Polls at 100ms intervals (10 FPS) for responsive UI
-/// - Non-blocking message processing via `try_recv()`
-/// - Only redraws when state changes or input occurs
+/// Built around crossterm's `EventStream` instead of a fixed `poll()` timeout, so the task
+/// blocks until a terminal event actually arrives instead of waking every 100ms regardless of
+/// activity. `terminal.draw` only runs when `app.dirty` is set (by `process_operation_messages`,
+/// `process_search_messages`, and every input handler below), so an idle screen costs no CPU at
+/// all. Background-thread messages (search/erase/flash progress, hotplug, log capture) still
+/// arrive over plain `std::sync::mpsc` channels, which can't be `.await`ed directly, so a short
+/// fallback tick keeps polling them with `try_recv()` while `Screen::Searching` or
+/// `Screen::Executing` is active (that tick also drives the overlay spinner animation) or while
+/// any of those channels or the `--session` pipe is open; with none of them the task waits on
+/// terminal events only.
 ///
 /// ## Input Handling
 ///
 /// Each screen has its own keyboard handler function. The global 'q' key
-/// quits the application (except when in text input mode).
+/// quits the application (except when in text input mode). The five screens with a clickable
+/// list (`InterfaceTypeSelection`, `InterfaceSelection`, `DeviceList`, `CommandMenu`,
+/// `FileBrowser`) also have a mouse handler: clicking hit-tests the cursor row against that
+/// screen's last-rendered list `Rect` (see `hit_test_list_row`) to select an item, clicking an
+/// already-selected item acts like Enter, and the scroll wheel moves the selection.
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
 ) -> io::Result<()> {
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(run_app_async(terminal, app))
+}
+
+/// Async body of `run_app`; see that function's doc comment for the overall design.
+async fn run_app_async<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> io::Result<()> {
+    let mut events = EventStream::new();
+
     loop {
-        // Poll background threads for progress updates
+        // Poll background threads for progress updates; each sets `app.dirty` itself when it
+        // actually received something.
         app.process_operation_messages();
         app.process_search_messages();
+        app.process_device_hotplug_messages();
+        app.process_interface_events();
+        app.process_probe_messages();
+        app.process_log_messages();
+        app.process_file_browser_events();
+        app.process_session_commands();
+
+        if app.dirty {
+            terminal.draw(|f| ui(f, app))?;
+            app.dirty = false;
+        }
+
+        // Only the Searching/Executing overlays animate without new input. The fallback tick also
+        // runs while any other background channel (or the `--session` pipe) is open, since those
+        // can't wake the task themselves; with none of them, the task blocks on terminal events.
+        let animating = matches!(app.current_screen, Screen::Searching | Screen::Executing);
+        let polling = animating
+            || app.log_receiver.is_some()
+            || app.device_hotplug_receiver.is_some()
+            || app.interface_event_receiver.is_some()
+            || app.probe_receiver.is_some()
+            || app.file_browser_watch_receiver.is_some()
+            || app.session_pipes.is_some();
+
+        tokio::select! {
+            maybe_event = events.next() => {
+                match maybe_event {
+                    Some(Ok(Event::Key(key))) => {
+                        if key.kind == KeyEventKind::Press {
+                            app.dirty = true;
+
+                            // Modal popups (confirmation/error dialogs) sit on top of the screen
+                            // state machine and get first look at every key; only dispatch to the
+                            // screen-specific handler (and the global quit shortcut) below if
+                            // nothing on the stack consumed it.
+                            let consumed_by_popup = compositor::handle_key(app, key.code);
+                            if !consumed_by_popup {
+                                match app.current_screen {
+                                    Screen::InterfaceTypeSelection => {
+                                        handle_interface_type_selection(app, key.code)
+                                    }
+                                    Screen::InterfaceSelection => {
+                                        handle_interface_selection(app, key.code)
+                                    }
+                                    Screen::CanConfig => handle_can_config(app, key.code),
+                                    Screen::NetConfig => handle_net_config(app, key.code),
+                                    Screen::Searching => {} // Blocked during search
+                                    Screen::DeviceList => {
+                                        if app.device_list_filter.is_some() {
+                                            handle_device_list_filter_input(app, key.code);
+                                        } else if key.code == KeyCode::Char('/') {
+                                            app.device_list_filter = Some(String::new());
+                                            app.update_device_list_filter();
+                                        } else {
+                                            let action =
+                                                app.keymap.resolve(Screen::DeviceList, key);
+                                            handle_device_list(app, action);
+                                        }
+                                    }
+                                    Screen::CommandMenu => handle_command_menu(app, key.code),
+                                    Screen::HexFileInput => handle_hex_file_input(app, key.code),
+                                    Screen::FileBrowser => {
+                                        if app.file_browser_bookmark_popup {
+                                            handle_bookmark_popup(app, key.code);
+                                        } else if app.file_browser_filter.is_some() {
+                                            handle_file_browser_filter_input(app, key.code);
+                                        } else {
+                                            handle_file_browser(app, key.code);
+                                        }
+                                    }
+                                    Screen::Executing => {} // Blocked during execution
+                                    Screen::LogView => handle_log_view(app, key.code),
+                                    Screen::Results => handle_results(app, key.code),
+                                }
+                            }
 
-        // Render current screen
-        terminal.draw(|f| ui(f, app))?;
-
-        // Poll for keyboard input with 100ms timeout (keeps UI responsive)
-        if crossterm::event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    // Dispatch to screen-specific handler
-                    match app.current_screen {
-                        Screen::InterfaceTypeSelection => {
-                            handle_interface_type_selection(app, key.code)
+                            // Global quit shortcut (unless in text input mode or a popup ate the
+                            // key), resolved through the keymap so it can be rebound or, via
+                            // `keymap`'s leader support, gated behind a two-key sequence instead
+                            // of firing on a bare key press.
+                            if !consumed_by_popup {
+                                let in_text_mode = app.hex_file_input_mode
+                                    || app.can_config_input_mode
+                                    || app.current_screen == Screen::NetConfig
+                                    || app.device_list_filter.is_some()
+                                    || app.file_browser_filter.is_some();
+
+                                if app.leader_pending {
+                                    app.leader_pending = false;
+                                    if !in_text_mode && app.keymap.resolve_gated(key) == Some(Action::Quit) {
+                                        return Ok(());
+                                    }
+                                } else if !in_text_mode && app.keymap.is_leader(key) {
+                                    app.leader_pending = true;
+                                } else if !in_text_mode
+                                    && app.keymap.resolve(app.current_screen, key) == Some(Action::Quit)
+                                {
+                                    return Ok(());
+                                }
+                            }
                         }
-                        Screen::InterfaceSelection => handle_interface_selection(app, key.code),
-                        Screen::Searching => {} // Blocked during search
-                        Screen::DeviceList => handle_device_list(app, key.code),
-                        Screen::CommandMenu => handle_command_menu(app, key.code),
-                        Screen::HexFileInput => handle_hex_file_input(app, key.code),
-                        Screen::FileBrowser => handle_file_browser(app, key.code),
-                        Screen::Executing => {} // Blocked during execution
-                        Screen::Results => handle_results(app, key.code),
                     }
+                    Some(Ok(Event::Mouse(mouse))) => {
+                        app.dirty = true;
+
+                        // Dispatch to the screens with hit-testable list widgets; other screens
+                        // don't have anything to click and ignore the mouse.
+                        match app.current_screen {
+                            Screen::InterfaceTypeSelection => {
+                                handle_interface_type_selection_mouse(app, mouse)
+                            }
+                            Screen::InterfaceSelection => {
+                                handle_interface_selection_mouse(app, mouse)
+                            }
+                            Screen::DeviceList => handle_device_list_mouse(app, mouse),
+                            Screen::CommandMenu => handle_command_menu_mouse(app, mouse),
+                            Screen::FileBrowser => handle_file_browser_mouse(app, mouse),
+                            _ => {}
+                        }
+                    }
+                    Some(Ok(Event::Paste(text))) => {
+                        app.dirty = true;
+                        if app.current_screen == Screen::HexFileInput {
+                            handle_hex_file_paste(app, &text);
+                        }
+                    }
+                    Some(Ok(Event::Resize(_, _))) => app.dirty = true,
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(()),
+                }
+            }
+            _ = tokio::time::sleep(std::time::Duration::from_millis(100)), if polling => {
+                // No new input; redraw only to animate the spinner/progress overlay (the
+                // `process_*` calls above set `app.dirty` for anything they received).
+                if animating {
+                    app.dirty = true;
+                }
+            }
+        }
+    }
+}
+
+/// Maps a mouse click's row to an item index inside a `List` widget's last-rendered `Rect`,
+/// accounting for the one-row top border ratatui's `List` always draws with `Borders::ALL`.
+/// Returns `None` if the click landed outside the list's rows or the list is empty.
+fn hit_test_list_row(rect: Rect, mouse_row: u16, item_count: usize) -> Option<usize> {
+    if item_count == 0 {
+        return None;
+    }
+
+    let first_row = rect.y + 1;
+    let last_row = rect.y + rect.height.saturating_sub(1);
+    if mouse_row < first_row || mouse_row >= last_row {
+        return None;
+    }
 
-                    // Global quit shortcut (unless in text input mode)
-                    if let KeyCode::Char('q') = key.code {
-                        if !app.hex_file_input_mode {
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-        }
+    let index = (mouse_row - first_row) as usize;
+    if index < item_count {
+        Some(index)
+    } else {
+        None
     }
 }
 
@@ -1329,7 +3785,7 @@ fn handle_interface_type_selection(app: &mut App, key: KeyCode) {
         KeyCode::Down => {
             let i = match app.interface_type_state.selected() {
                 Some(i) => {
-                    if i >= 2 {
+                    if i >= 3 {
                         0
                     } else {
                         i + 1
@@ -1343,7 +3799,7 @@ fn handle_interface_type_selection(app: &mut App, key: KeyCode) {
             let i = match app.interface_type_state.selected() {
                 Some(i) => {
                     if i == 0 {
-                        2
+                        3
                     } else {
                         i - 1
                     }
@@ -1358,10 +3814,23 @@ fn handle_interface_type_selection(app: &mut App, key: KeyCode) {
                 0 => InterfaceType::Sim,
                 1 => InterfaceType::Serial,
                 2 => InterfaceType::CAN,
+                3 => InterfaceType::Net,
                 _ => InterfaceType::Sim,
             });
+
+            // Net has nothing to enumerate locally; go straight to NetConfig for the address
+            // instead of InterfaceSelection.
+            if app.selected_interface_type == Some(InterfaceType::Net) {
+                app.net_address_input = "tcp:".to_string();
+                app.current_screen = Screen::NetConfig;
+                return;
+            }
+
             app.discover_interfaces();
-            if !app.available_interfaces.is_empty() {
+            app.spawn_interface_monitor();
+            // Serial results stream in asynchronously, so move to the selection screen right
+            // away to show the probing overlay instead of waiting for the list to fill in.
+            if app.is_probing_ports || !app.available_interfaces.is_empty() {
                 app.current_screen = Screen::InterfaceSelection;
             }
         }
@@ -1369,7 +3838,67 @@ fn handle_interface_type_selection(app: &mut App, key: KeyCode) {
     }
 }
 
+/// Clicking a row selects it; clicking a row that was already selected acts like Enter, which
+/// also covers a double-click (its first half is the click that selects the row).
+fn handle_interface_type_selection_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_interface_type_selection(app, KeyCode::Down),
+        MouseEventKind::ScrollUp => handle_interface_type_selection(app, KeyCode::Up),
+        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+            let rect = match app.interface_type_list_rect {
+                Some(rect) => rect,
+                None => return,
+            };
+            if let Some(idx) = hit_test_list_row(rect, mouse.row, 4) {
+                let was_selected = app.interface_type_state.selected() == Some(idx);
+                app.interface_type_state.select(Some(idx));
+                if was_selected {
+                    handle_interface_type_selection(app, KeyCode::Enter);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_net_config(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => {
+            match parse_net_address(app.net_address_input.trim()) {
+                Ok(_) => {
+                    app.selected_interface = Some(app.net_address_input.trim().to_string());
+                    app.current_screen = Screen::Searching;
+                    app.spawn_search();
+                }
+                Err(e) => {
+                    app.popups.push(Box::new(ErrorPopup { message: e }));
+                }
+            }
+        }
+        KeyCode::Char(c) => {
+            app.net_address_input.push(c);
+        }
+        KeyCode::Backspace => {
+            app.net_address_input.pop();
+        }
+        KeyCode::Esc => {
+            app.current_screen = Screen::InterfaceTypeSelection;
+        }
+        _ => {}
+    }
+}
+
 fn handle_interface_selection(app: &mut App, key: KeyCode) {
+    // While probing, only Esc (cancel) is meaningful: the list is still filling in.
+    if app.is_probing_ports {
+        if key == KeyCode::Esc {
+            app.cancel_port_probe();
+            app.interface_event_receiver = None;
+            app.current_screen = Screen::InterfaceTypeSelection;
+        }
+        return;
+    }
+
     match key {
         KeyCode::Down => {
             let max_idx = app.available_interfaces.len().saturating_sub(1);
@@ -1403,26 +3932,131 @@ fn handle_interface_selection(app: &mut App, key: KeyCode) {
             if let Some(idx) = app.interface_list_state.selected() {
                 if let Some(interface) = app.available_interfaces.get(idx) {
                     app.selected_interface = Some(interface.clone());
-                    app.current_screen = Screen::Searching;
-                    app.spawn_search();
+
+                    let link = app.can_links.iter().find(|l| &l.name == interface);
+                    let needs_can_config = matches!(
+                        link,
+                        Some(CanLinkInfo {
+                            is_up: false,
+                            is_virtual: false,
+                            ..
+                        })
+                    );
+
+                    if needs_can_config {
+                        app.can_bitrate = None;
+                        app.can_bitrate_input = link
+                            .and_then(|l| l.bitrate)
+                            .map(|b| b.to_string())
+                            .unwrap_or_else(|| "500000".to_string());
+                        app.can_config_input_mode = true;
+                        app.current_screen = Screen::CanConfig;
+                    } else {
+                        app.can_bitrate = link.and_then(|l| l.bitrate);
+                        app.current_screen = Screen::Searching;
+                        app.spawn_search();
+                    }
                 }
             }
         }
         KeyCode::F(5) => {
-            // Refresh interface list (rescan for new serial devices, etc.)
+            // Manual refresh; the udev monitor (or its polling fallback) keeps the list
+            // current on its own, but this stays as an explicit fallback trigger.
             app.discover_interfaces();
         }
         KeyCode::Esc => {
+            app.interface_event_receiver = None;
             app.current_screen = Screen::InterfaceTypeSelection;
         }
         _ => {}
     }
 }
 
-fn handle_device_list(app: &mut App, key: KeyCode) {
+/// See `handle_interface_type_selection_mouse` for the click/double-click convention. Ignored
+/// while `is_probing_ports` is true, same as the keyboard handler.
+fn handle_interface_selection_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.is_probing_ports {
+        return;
+    }
+
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_interface_selection(app, KeyCode::Down),
+        MouseEventKind::ScrollUp => handle_interface_selection(app, KeyCode::Up),
+        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+            let rect = match app.interface_selection_list_rect {
+                Some(rect) => rect,
+                None => return,
+            };
+            let item_count = app.available_interfaces.len();
+            if let Some(idx) = hit_test_list_row(rect, mouse.row, item_count) {
+                let was_selected = app.interface_list_state.selected() == Some(idx);
+                app.interface_list_state.select(Some(idx));
+                if was_selected {
+                    handle_interface_selection(app, KeyCode::Enter);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_can_config(app: &mut App, key: KeyCode) {
     match key {
-        KeyCode::Down => {
-            let max_idx = app.discovered_devices.len().saturating_sub(1);
+        KeyCode::Enter => {
+            let bitrate: u32 = match app.can_bitrate_input.trim().parse() {
+                Ok(b) => b,
+                Err(_) => {
+                    app.popups.push(Box::new(ErrorPopup {
+                        message: format!(
+                            "\"{}\" is not a valid bitrate in bit/s",
+                            app.can_bitrate_input
+                        ),
+                    }));
+                    return;
+                }
+            };
+
+            let interface_name = match &app.selected_interface {
+                Some(name) => name.clone(),
+                None => return,
+            };
+
+            match configure_can_link(&interface_name, bitrate) {
+                Ok(()) => {
+                    app.can_bitrate = Some(bitrate);
+                    app.can_config_input_mode = false;
+                    app.current_screen = Screen::Searching;
+                    app.spawn_search();
+                }
+                Err(e) => {
+                    app.popups.push(Box::new(ErrorPopup {
+                        message: format!("Failed to configure {}: {}", interface_name, e),
+                    }));
+                }
+            }
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            app.can_bitrate_input.push(c);
+        }
+        KeyCode::Backspace => {
+            app.can_bitrate_input.pop();
+        }
+        KeyCode::Esc => {
+            app.can_config_input_mode = false;
+            app.current_screen = Screen::InterfaceSelection;
+        }
+        _ => {}
+    }
+}
+
+/// Unlike the other screen handlers, `DeviceList` is resolved through `keymap` first (see its
+/// call site in `run_app_async`), so it matches on `Action` instead of a raw `KeyCode` — letting
+/// `nav_up`/`nav_down` be rebound (e.g. vim-style `j`/`k`, already a default) without touching
+/// this function.
+fn handle_device_list(app: &mut App, action: Option<Action>) {
+    match action {
+        Some(Action::NavDown) => {
+            let max_idx = app.device_list_len().saturating_sub(1);
             let i = match app.device_list_state.selected() {
                 Some(i) => {
                     if i >= max_idx {
@@ -1437,8 +4071,8 @@ fn handle_device_list(app: &mut App, key: KeyCode) {
             // Clear refresh message when user interacts
             app.device_list_refresh_message = None;
         }
-        KeyCode::Up => {
-            let max_idx = app.discovered_devices.len().saturating_sub(1);
+        Some(Action::NavUp) => {
+            let max_idx = app.device_list_len().saturating_sub(1);
             let i = match app.device_list_state.selected() {
                 Some(i) => {
                     if i == 0 {
@@ -1453,30 +4087,94 @@ fn handle_device_list(app: &mut App, key: KeyCode) {
             // Clear refresh message when user interacts
             app.device_list_refresh_message = None;
         }
-        KeyCode::Enter => {
-            app.selected_device_index = app.device_list_state.selected();
+        Some(Action::Select) => {
+            app.selected_device_index = app
+                .device_list_state
+                .selected()
+                .and_then(|pos| app.device_list_real_index(pos));
             app.device_list_refresh_message = None;
+            app.device_list_filter = None;
             app.current_screen = Screen::CommandMenu;
         }
-        KeyCode::F(5) => {
+        Some(Action::Refresh) => {
             // Refresh device list using async search
             app.is_refresh_search = true;
             app.current_screen = Screen::Searching;
             app.spawn_search();
         }
-        KeyCode::Esc => {
+        Some(Action::Back) => {
             app.current_screen = Screen::InterfaceSelection;
         }
         _ => {}
     }
 }
 
+/// Filter-mode input for `DeviceList`, entered with `/` and active while `device_list_filter` is
+/// `Some`. Unlike `handle_device_list`, this matches on the raw `KeyCode` rather than a resolved
+/// `Action`, the same way `hex_file_input_mode` bypasses the keymap while the user is typing text.
+fn handle_device_list_filter_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Char(c) => {
+            if let Some(filter) = &mut app.device_list_filter {
+                filter.push(c);
+            }
+            app.update_device_list_filter();
+        }
+        KeyCode::Backspace => {
+            if let Some(filter) = &mut app.device_list_filter {
+                filter.pop();
+            }
+            app.update_device_list_filter();
+        }
+        KeyCode::Down => handle_device_list(app, Some(Action::NavDown)),
+        KeyCode::Up => handle_device_list(app, Some(Action::NavUp)),
+        KeyCode::Enter => handle_device_list(app, Some(Action::Select)),
+        KeyCode::Esc => {
+            let real_index = app
+                .device_list_state
+                .selected()
+                .and_then(|pos| app.device_list_real_index(pos));
+            app.device_list_filter = None;
+            app.device_list_filtered_indices.clear();
+            app.device_list_state.select(real_index.or_else(|| {
+                (!app.discovered_devices.is_empty()).then_some(0)
+            }));
+        }
+        _ => {}
+    }
+}
+
+/// See `handle_interface_type_selection_mouse` for the click/double-click convention.
+fn handle_device_list_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_device_list(app, Some(Action::NavDown)),
+        MouseEventKind::ScrollUp => handle_device_list(app, Some(Action::NavUp)),
+        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+            let rect = match app.device_list_list_rect {
+                Some(rect) => rect,
+                None => return,
+            };
+            let item_count = app.discovered_devices.len();
+            if let Some(idx) = hit_test_list_row(rect, mouse.row, item_count) {
+                let was_selected = app.device_list_state.selected() == Some(idx);
+                app.device_list_state.select(Some(idx));
+                app.device_list_refresh_message = None;
+                if was_selected {
+                    handle_device_list(app, Some(Action::Select));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 fn handle_command_menu(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Down => {
+            let last = Command::ALL.len() - 1;
             let i = match app.command_menu_state.selected() {
                 Some(i) => {
-                    if i >= 2 {
+                    if i >= last {
                         0
                     } else {
                         i + 1
@@ -1487,10 +4185,11 @@ fn handle_command_menu(app: &mut App, key: KeyCode) {
             app.command_menu_state.select(Some(i));
         }
         KeyCode::Up => {
+            let last = Command::ALL.len() - 1;
             let i = match app.command_menu_state.selected() {
                 Some(i) => {
                     if i == 0 {
-                        2
+                        last
                     } else {
                         i - 1
                     }
@@ -1501,17 +4200,17 @@ fn handle_command_menu(app: &mut App, key: KeyCode) {
         }
         KeyCode::Enter => {
             let selected = app.command_menu_state.selected().unwrap_or(0);
-            app.selected_command = Some(match selected {
-                0 => Command::Reset,
-                1 => Command::Erase,
-                2 => Command::Flash,
-                _ => Command::Reset,
-            });
+            app.selected_command = Command::ALL.get(selected).cloned();
 
-            // If flash command, ask for hex file first
-            if matches!(app.selected_command, Some(Command::Flash)) {
+            // Flash and Verify both need a hex file to compare against first
+            if matches!(app.selected_command, Some(Command::Flash) | Some(Command::Verify)) {
                 app.current_screen = Screen::HexFileInput;
                 app.hex_file_input_mode = true;
+            } else if app.selected_command == Some(Command::Erase) {
+                app.popups.push(Box::new(ConfirmDialog {
+                    message: "Erase application flash memory?".to_string(),
+                    command: Command::Erase,
+                }));
             } else {
                 app.current_screen = Screen::Executing;
                 app.execute_command();
@@ -1524,6 +4223,28 @@ fn handle_command_menu(app: &mut App, key: KeyCode) {
     }
 }
 
+/// See `handle_interface_type_selection_mouse` for the click/double-click convention.
+fn handle_command_menu_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_command_menu(app, KeyCode::Down),
+        MouseEventKind::ScrollUp => handle_command_menu(app, KeyCode::Up),
+        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+            let rect = match app.command_menu_list_rect {
+                Some(rect) => rect,
+                None => return,
+            };
+            if let Some(idx) = hit_test_list_row(rect, mouse.row, Command::ALL.len()) {
+                let was_selected = app.command_menu_state.selected() == Some(idx);
+                app.command_menu_state.select(Some(idx));
+                if was_selected {
+                    handle_command_menu(app, KeyCode::Enter);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 fn handle_hex_file_input(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Up => {
@@ -1567,8 +4288,7 @@ fn handle_hex_file_input(app: &mut App, key: KeyCode) {
             if !app.hex_file_path.is_empty() {
                 app.hex_file_input_mode = false;
                 app.hex_file_history_index = None;
-                app.current_screen = Screen::Executing;
-                app.execute_command();
+                app.start_selected_command();
             }
         }
         KeyCode::Tab => {
@@ -1598,10 +4318,77 @@ fn handle_hex_file_input(app: &mut App, key: KeyCode) {
     }
 }
 
+/// Appends a bracketed-paste payload to `hex_file_path` in one action instead of the
+/// one-`KeyCode::Char`-per-call flood crossterm would otherwise synthesize for a pasted path;
+/// see `handle_hex_file_input`'s `KeyCode::Char` arm for the single-character equivalent.
+fn handle_hex_file_paste(app: &mut App, text: &str) {
+    app.hex_file_history_index = None;
+    app.hex_file_path.push_str(text);
+}
+
+/// Scans `dir` into the navigable entry list `populate_file_browser` and the Miller-column left
+/// pane both need: `..` first (unless `dir` is the filesystem root), then subdirectories and
+/// `.hex` files, each group sorted alphabetically, hidden entries skipped.
+fn scan_directory_entries(dir: &Path) -> Vec<FileEntry> {
+    let mut entries = Vec::new();
+
+    if let Some(parent) = dir.parent() {
+        entries.push(FileEntry {
+            name: "..".to_string(),
+            path: parent.to_path_buf(),
+            is_dir: true,
+        });
+    }
+
+    if let Ok(read_dir) = fs::read_dir(dir) {
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+
+        for entry in read_dir.flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                // Skip hidden files (starting with .)
+                if name.starts_with('.') {
+                    continue;
+                }
+
+                if metadata.is_dir() {
+                    dirs.push(FileEntry {
+                        name,
+                        path,
+                        is_dir: true,
+                    });
+                } else if metadata.is_file() {
+                    // Only show .hex files
+                    if path.extension().and_then(|s| s.to_str()) == Some("hex") {
+                        files.push(FileEntry {
+                            name,
+                            path,
+                            is_dir: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Sort directories and files alphabetically
+        dirs.sort_by(|a, b| a.name.cmp(&b.name));
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Add directories first, then files
+        entries.extend(dirs);
+        entries.extend(files);
+    }
+
+    entries
+}
+
 fn handle_file_browser(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Down => {
-            let max_idx = app.file_browser_entries.len().saturating_sub(1);
+            let max_idx = app.file_browser_len().saturating_sub(1);
             let i = match app.file_browser_list_state.selected() {
                 Some(i) => {
                     if i >= max_idx {
@@ -1615,7 +4402,7 @@ fn handle_file_browser(app: &mut App, key: KeyCode) {
             app.file_browser_list_state.select(Some(i));
         }
         KeyCode::Up => {
-            let max_idx = app.file_browser_entries.len().saturating_sub(1);
+            let max_idx = app.file_browser_len().saturating_sub(1);
             let i = match app.file_browser_list_state.selected() {
                 Some(i) => {
                     if i == 0 {
@@ -1629,22 +4416,59 @@ fn handle_file_browser(app: &mut App, key: KeyCode) {
             app.file_browser_list_state.select(Some(i));
         }
         KeyCode::Enter => {
-            if let Some(idx) = app.file_browser_list_state.selected() {
+            if let Some(idx) = app
+                .file_browser_list_state
+                .selected()
+                .and_then(|pos| app.file_browser_real_index(pos))
+            {
                 if let Some(entry) = app.file_browser_entries.get(idx).cloned() {
                     if entry.is_dir {
-                        // Navigate into directory
-                        app.file_browser_current_dir = entry.path;
-                        app.populate_file_browser();
+                        app.navigate_file_browser_to(entry.path);
                     } else {
                         // Select file
                         app.hex_file_path = entry.path.to_string_lossy().to_string();
-                        app.current_screen = Screen::Executing;
-                        app.execute_command();
+                        app.start_selected_command();
                     }
                 }
             }
         }
+        // Miller-column ascend/descend, mirroring `h`/`l` in hunter: `..`/Enter-on-directory
+        // still work too, these just give single-key equivalents that match the left/right pane
+        // layout in `draw_file_browser`.
+        KeyCode::Left | KeyCode::Char('h') => {
+            app.file_browser_ascend();
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            app.file_browser_descend();
+        }
+        KeyCode::Char('/') => {
+            app.file_browser_filter = Some(String::new());
+            app.update_file_browser_filter();
+        }
+        // `/` is already the fuzzy-filter trigger above, so the root jump borrowed from fm/hunter
+        // lives on the backtick instead; `~` (home) doesn't collide with anything here.
+        KeyCode::Char('`') => {
+            app.navigate_file_browser_to(PathBuf::from("/"));
+        }
+        KeyCode::Char('~') => {
+            if let Ok(home) = std::env::var("HOME") {
+                app.navigate_file_browser_to(PathBuf::from(home));
+            }
+        }
+        KeyCode::Char('b') => {
+            app.bookmark_list_state
+                .select((!app.bookmarks.is_empty()).then_some(0));
+            app.file_browser_bookmark_popup = true;
+        }
+        KeyCode::Char('m') => {
+            app.add_current_dir_bookmark();
+        }
+        KeyCode::F(5) => {
+            app.refresh_file_browser_entries();
+        }
         KeyCode::Esc => {
+            app.file_browser_watcher = None;
+            app.file_browser_watch_receiver = None;
             app.current_screen = Screen::HexFileInput;
             app.hex_file_input_mode = true;
         }
@@ -1652,6 +4476,132 @@ fn handle_file_browser(app: &mut App, key: KeyCode) {
     }
 }
 
+/// Input for the bookmark popup opened with `b` on `FileBrowser`; see `draw_bookmark_popup`.
+fn handle_bookmark_popup(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Down => {
+            let max_idx = app.bookmarks.len().saturating_sub(1);
+            let i = match app.bookmark_list_state.selected() {
+                Some(i) => {
+                    if i >= max_idx {
+                        0
+                    } else {
+                        i + 1
+                    }
+                }
+                None => 0,
+            };
+            app.bookmark_list_state.select(Some(i));
+        }
+        KeyCode::Up => {
+            let max_idx = app.bookmarks.len().saturating_sub(1);
+            let i = match app.bookmark_list_state.selected() {
+                Some(i) => {
+                    if i == 0 {
+                        max_idx
+                    } else {
+                        i - 1
+                    }
+                }
+                None => 0,
+            };
+            app.bookmark_list_state.select(Some(i));
+        }
+        KeyCode::Enter => {
+            if let Some(dir) = app
+                .bookmark_list_state
+                .selected()
+                .and_then(|i| app.bookmarks.get(i).cloned())
+            {
+                app.navigate_file_browser_to(dir);
+            }
+            app.file_browser_bookmark_popup = false;
+        }
+        KeyCode::Char('b') | KeyCode::Esc => {
+            app.file_browser_bookmark_popup = false;
+        }
+        _ => {}
+    }
+}
+
+/// Filter-mode input for `FileBrowser`; see `handle_device_list_filter_input`.
+fn handle_file_browser_filter_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Char(c) => {
+            if let Some(filter) = &mut app.file_browser_filter {
+                filter.push(c);
+            }
+            app.update_file_browser_filter();
+        }
+        KeyCode::Backspace => {
+            if let Some(filter) = &mut app.file_browser_filter {
+                filter.pop();
+            }
+            app.update_file_browser_filter();
+        }
+        KeyCode::Down => handle_file_browser(app, KeyCode::Down),
+        KeyCode::Up => handle_file_browser(app, KeyCode::Up),
+        KeyCode::Enter => handle_file_browser(app, KeyCode::Enter),
+        KeyCode::Esc => {
+            let real_index = app
+                .file_browser_list_state
+                .selected()
+                .and_then(|pos| app.file_browser_real_index(pos));
+            app.file_browser_filter = None;
+            app.file_browser_filtered_indices.clear();
+            app.file_browser_list_state.select(
+                real_index.or_else(|| (!app.file_browser_entries.is_empty()).then_some(0)),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// See `handle_interface_type_selection_mouse` for the click/double-click convention. Unlike the
+/// other four handlers, acting like Enter here may navigate into a directory rather than confirm
+/// a final selection, exactly as pressing Enter on a `[DIR]` row does.
+fn handle_file_browser_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_file_browser(app, KeyCode::Down),
+        MouseEventKind::ScrollUp => handle_file_browser(app, KeyCode::Up),
+        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+            let rect = match app.file_browser_list_rect {
+                Some(rect) => rect,
+                None => return,
+            };
+            let item_count = app.file_browser_entries.len();
+            if let Some(idx) = hit_test_list_row(rect, mouse.row, item_count) {
+                let was_selected = app.file_browser_list_state.selected() == Some(idx);
+                app.file_browser_list_state.select(Some(idx));
+                if was_selected {
+                    handle_file_browser(app, KeyCode::Enter);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_log_view(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Up => {
+            app.log_scroll = app.log_scroll.saturating_add(1);
+        }
+        KeyCode::Down => {
+            app.log_scroll = app.log_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('s') => match app.save_log_to_file() {
+            Ok(path) => app.log_lines.push(format!("[log] saved to {}", path)),
+            Err(e) => app.log_lines.push(format!("[log] save failed: {}", e)),
+        },
+        KeyCode::Enter | KeyCode::Esc => {
+            app.log_receiver = None;
+            app.current_screen = Screen::Results;
+        }
+        _ => {}
+    }
+}
+
 fn handle_results(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Enter | KeyCode::Esc => {
@@ -1681,12 +4631,19 @@ fn handle_results(app: &mut App, key: KeyCode) {
 // ================================================================================================
 
 /// Main UI dispatcher - renders the appropriate screen based on app state.
-fn ui(f: &mut Frame, app: &App) {
+fn ui(f: &mut Frame, app: &mut App) {
     let size = f.area();
 
     match app.current_screen {
         Screen::InterfaceTypeSelection => draw_interface_type_selection(f, app, size),
-        Screen::InterfaceSelection => draw_interface_selection(f, app, size),
+        Screen::InterfaceSelection => {
+            draw_interface_selection(f, app, size);
+            if app.is_probing_ports {
+                draw_probe_overlay(f);
+            }
+        }
+        Screen::CanConfig => draw_can_config(f, app, size),
+        Screen::NetConfig => draw_net_config(f, app, size),
         Screen::Searching => {
             // Draw device list in background with overlay
             draw_device_list(f, app, size);
@@ -1697,8 +4654,11 @@ fn ui(f: &mut Frame, app: &App) {
         Screen::HexFileInput => draw_hex_file_input(f, app, size),
         Screen::FileBrowser => draw_file_browser(f, app, size),
         Screen::Executing => draw_executing(f, app, size),
+        Screen::LogView => draw_log_view(f, app, size),
         Screen::Results => draw_results(f, app, size),
     }
+
+    compositor::render(app, f);
 }
 
 /// Create a centered rect for popup
@@ -1739,28 +4699,65 @@ fn draw_search_overlay(f: &mut Frame, app: &App) {
     let text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
-            "⏳ Searching for devices...",
+            "⏳ Searching for devices...",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Interface: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{} ({})", interface_name, interface_type)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Please wait, do not interact with the TUI",
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC),
+        )]),
+        Line::from(""),
+    ];
+
+    let block = Block::default()
+        .title(" Device Search ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, area);
+}
+
+/// Draw the serial port probing overlay on top of `InterfaceSelection`
+fn draw_probe_overlay(f: &mut Frame) {
+    let area = centered_rect(60, 30, f.area());
+
+    // Clear the background
+    f.render_widget(Clear, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "⏳ Probing ports...",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Interface: ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{} ({})", interface_name, interface_type)),
-        ]),
-        Line::from(""),
         Line::from(vec![Span::styled(
-            "Please wait, do not interact with the TUI",
-            Style::default()
-                .fg(Color::Gray)
-                .add_modifier(Modifier::ITALIC),
+            "Press Esc to cancel",
+            Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
         )]),
         Line::from(""),
     ];
 
     let block = Block::default()
-        .title(" Device Search ")
+        .title(" Serial Port Probe ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow))
         .style(Style::default().bg(Color::Black));
@@ -1773,7 +4770,105 @@ fn draw_search_overlay(f: &mut Frame, app: &App) {
     f.render_widget(paragraph, area);
 }
 
-fn draw_interface_type_selection(f: &mut Frame, app: &App, area: Rect) {
+/// Confirmation dialog shown before a destructive command (`Erase`/`Flash`) is actually started,
+/// pushed onto `app.popups` instead of jumping straight to `Screen::Executing`.
+struct ConfirmDialog {
+    /// Question shown in the dialog body, e.g. "Erase application flash memory?"
+    message: String,
+    /// Command to run via `execute_command` if the user confirms.
+    command: Command,
+}
+
+impl Component for ConfirmDialog {
+    fn render(&self, f: &mut Frame, _app: &App, area: Rect) {
+        let area = centered_rect(50, 20, area);
+        f.render_widget(Clear, area);
+
+        let text = vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                &self.message,
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "y: confirm | n/Esc: cancel",
+                Style::default().fg(Color::Gray),
+            )]),
+        ];
+
+        let block = Block::default()
+            .title(" Confirm ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .style(Style::default().bg(Color::Black));
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn handle_key(&mut self, app: &mut App, key: KeyCode) -> EventResult {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                app.selected_command = Some(self.command.clone());
+                app.execute_command();
+                EventResult::PushScreen(Screen::Executing)
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => EventResult::Pop,
+            _ => EventResult::Consumed,
+        }
+    }
+}
+
+/// Error popup shown over whichever screen raised `message`, for errors (a bad `NetConfig`
+/// address, a `CanConfig` bitrate that failed to apply) that stay on their current screen rather
+/// than transitioning to `Screen::Results`. Any key dismisses it.
+struct ErrorPopup {
+    message: String,
+}
+
+impl Component for ErrorPopup {
+    fn render(&self, f: &mut Frame, _app: &App, area: Rect) {
+        let area = centered_rect(60, 25, area);
+        f.render_widget(Clear, area);
+
+        let text = vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                &self.message,
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Press any key to dismiss",
+                Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+            )]),
+        ];
+
+        let block = Block::default()
+            .title(" Error ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .style(Style::default().bg(Color::Black));
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn handle_key(&mut self, _app: &mut App, _key: KeyCode) -> EventResult {
+        EventResult::Pop
+    }
+}
+
+fn draw_interface_type_selection(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -1798,6 +4893,7 @@ fn draw_interface_type_selection(f: &mut Frame, app: &App, area: Rect) {
         ListItem::new("SIM (Simulated Device)"),
         ListItem::new("Serial (UART/USB)"),
         ListItem::new("CAN Bus"),
+        ListItem::new("Net (TCP/UDP Bridge)"),
     ];
 
     let list = List::new(items)
@@ -1813,6 +4909,7 @@ fn draw_interface_type_selection(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
+    app.interface_type_list_rect = Some(chunks[1]);
     let mut state = app.interface_type_state.clone();
     f.render_stateful_widget(list, chunks[1], &mut state);
 
@@ -1823,7 +4920,7 @@ fn draw_interface_type_selection(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(help, chunks[2]);
 }
 
-fn draw_interface_selection(f: &mut Frame, app: &App, area: Rect) {
+fn draw_interface_selection(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -1868,6 +4965,7 @@ fn draw_interface_selection(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
+    app.interface_selection_list_rect = Some(chunks[1]);
     let mut state = app.interface_list_state.clone();
     f.render_stateful_widget(list, chunks[1], &mut state);
 
@@ -1880,22 +4978,111 @@ fn draw_interface_selection(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(help, chunks[2]);
 }
 
-fn draw_device_list(f: &mut Frame, app: &App, area: Rect) {
-    // Adjust constraints based on whether we have a refresh message
-    let constraints = if app.device_list_refresh_message.is_some() {
-        vec![
+fn draw_can_config(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
             Constraint::Length(3),
-            Constraint::Length(3), // Refresh message
-            Constraint::Min(10),
             Constraint::Length(3),
-        ]
-    } else {
-        vec![
+            Constraint::Min(5),
             Constraint::Length(3),
-            Constraint::Min(10),
+        ])
+        .split(area);
+
+    let interface_name = app.selected_interface.as_deref().unwrap_or("?");
+    let title = Paragraph::new(format!("Configure {}", interface_name))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let input = Paragraph::new(app.can_bitrate_input.as_str())
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .title("Bitrate (bit/s)")
+                .borders(Borders::ALL),
+        );
+    f.render_widget(input, chunks[1]);
+
+    let help_text = Paragraph::new(format!(
+        "\"{}\" is currently down. Enter its bitrate to set it and bring the link up before searching.\nCommon values: 125000, 250000, 500000, 1000000",
+        interface_name
+    ))
+    .style(Style::default().fg(Color::Gray))
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help_text, chunks[2]);
+
+    let help_final = Paragraph::new("Type bitrate | Enter to apply & search | Esc to go back")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help_final, chunks[3]);
+}
+
+fn draw_net_config(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
             Constraint::Length(3),
-        ]
-    };
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("Configure Net Interface")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let input = Paragraph::new(app.net_address_input.as_str())
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .title("Address (proto:host:port)")
+                .borders(Borders::ALL),
+        );
+    f.render_widget(input, chunks[1]);
+
+    let help_text = Paragraph::new(
+        "Enter the bridge's address, e.g. \"tcp:192.168.1.10:4242\" or \"udp:192.168.1.10:4242\".",
+    )
+    .style(Style::default().fg(Color::Gray))
+    .wrap(Wrap { trim: true })
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help_text, chunks[2]);
+
+    let help_final = Paragraph::new("Type address | Enter to connect & search | Esc to go back")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help_final, chunks[3]);
+}
+
+fn draw_device_list(f: &mut Frame, app: &mut App, area: Rect) {
+    // Adjust constraints based on whether we have a filter query and/or a refresh message
+    let mut constraints = vec![Constraint::Length(3)]; // Title
+    if app.device_list_filter.is_some() {
+        constraints.push(Constraint::Length(3)); // Filter query line
+    }
+    if app.device_list_refresh_message.is_some() {
+        constraints.push(Constraint::Length(3)); // Refresh message
+    }
+    constraints.push(Constraint::Min(10)); // List
+    constraints.push(Constraint::Length(3)); // Help
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -1913,8 +5100,17 @@ fn draw_device_list(f: &mut Frame, app: &App, area: Rect) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
 
-    let (list_chunk, help_chunk) = if let Some(ref refresh_msg) = app.device_list_refresh_message {
-        // Show refresh message
+    let mut next_chunk = 1;
+
+    if let Some(filter) = &app.device_list_filter {
+        let filter_line = Paragraph::new(format!("/{}", filter))
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().title("Filter").borders(Borders::ALL));
+        f.render_widget(filter_line, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+
+    if let Some(ref refresh_msg) = app.device_list_refresh_message {
         let refresh_info = Paragraph::new(refresh_msg.as_str())
             .style(
                 Style::default()
@@ -1923,14 +5119,23 @@ fn draw_device_list(f: &mut Frame, app: &App, area: Rect) {
             )
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
-        f.render_widget(refresh_info, chunks[1]);
-        (2, 3)
-    } else {
-        (1, 2)
+        f.render_widget(refresh_info, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+
+    let list_chunk = next_chunk;
+    let help_chunk = next_chunk + 1;
+
+    let visible: Vec<&DiscoveredDevice> = match &app.device_list_filter {
+        Some(_) => app
+            .device_list_filtered_indices
+            .iter()
+            .filter_map(|&i| app.discovered_devices.get(i))
+            .collect(),
+        None => app.discovered_devices.iter().collect(),
     };
 
-    let items: Vec<ListItem> = app
-        .discovered_devices
+    let items: Vec<ListItem> = visible
         .iter()
         .map(|device| ListItem::new(device.display_name.as_str()))
         .collect();
@@ -1948,19 +5153,23 @@ fn draw_device_list(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
+    app.device_list_list_rect = Some(chunks[list_chunk]);
     let mut state = app.device_list_state.clone();
     f.render_stateful_widget(list, chunks[list_chunk], &mut state);
 
-    let help = Paragraph::new(
-        "↑↓ to navigate | Enter to select | F5 to refresh | Esc to go back | 'q' to quit",
-    )
-    .style(Style::default().fg(Color::Gray))
-    .alignment(Alignment::Center)
-    .block(Block::default().borders(Borders::ALL));
+    let help_text = if app.device_list_filter.is_some() {
+        "Type to filter | ↑↓ to navigate | Enter to select | Esc to clear filter"
+    } else {
+        "↑↓ to navigate | Enter to select | F5 to refresh | '/' to filter | Esc to go back | 'q' to quit"
+    };
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
     f.render_widget(help, chunks[help_chunk]);
 }
 
-fn draw_command_menu(f: &mut Frame, app: &App, area: Rect) {
+fn draw_command_menu(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -1991,11 +5200,7 @@ fn draw_command_menu(f: &mut Frame, app: &App, area: Rect) {
     .block(Block::default().borders(Borders::ALL));
     f.render_widget(title_info, chunks[0]);
 
-    let items: Vec<ListItem> = vec![
-        ListItem::new("Reset Device"),
-        ListItem::new("Erase Application"),
-        ListItem::new("Flash Firmware"),
-    ];
+    let items: Vec<ListItem> = Command::ALL.iter().map(|c| ListItem::new(c.as_str())).collect();
 
     let list = List::new(items)
         .block(Block::default().title("Commands").borders(Borders::ALL))
@@ -2006,6 +5211,7 @@ fn draw_command_menu(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
+    app.command_menu_list_rect = Some(chunks[1]);
     let mut state = app.command_menu_state.clone();
     f.render_stateful_widget(list, chunks[1], &mut state);
 
@@ -2089,16 +5295,18 @@ fn draw_hex_file_input(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(help_final, chunks[3]);
 }
 
-fn draw_file_browser(f: &mut Frame, app: &App, area: Rect) {
+fn draw_file_browser(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut constraints = vec![Constraint::Length(3), Constraint::Length(3)]; // Title, path
+    if app.file_browser_filter.is_some() {
+        constraints.push(Constraint::Length(3)); // Filter query line
+    }
+    constraints.push(Constraint::Min(10)); // List
+    constraints.push(Constraint::Length(3)); // Help
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(10),
-            Constraint::Length(3),
-        ])
+        .constraints(constraints)
         .split(area);
 
     let title = Paragraph::new("Browse for Hex File")
@@ -2121,21 +5329,91 @@ fn draw_file_browser(f: &mut Frame, app: &App, area: Rect) {
         );
     f.render_widget(path_display, chunks[1]);
 
-    let items: Vec<ListItem> = app
-        .file_browser_entries
+    let mut next_chunk = 2;
+
+    if let Some(filter) = &app.file_browser_filter {
+        let filter_line = Paragraph::new(format!("/{}", filter))
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().title("Filter").borders(Borders::ALL));
+        f.render_widget(filter_line, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+
+    let list_chunk = next_chunk;
+    let help_chunk = next_chunk + 1;
+
+    // Miller-column parent pane: the current directory's siblings, with the current directory
+    // itself highlighted, giving spatial context one level up. Omitted at the filesystem root,
+    // where there's no parent to show — the "optional" half of the two-column layout.
+    let (parent_rect, right_area) = if app.file_browser_current_dir.parent().is_some() {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(chunks[list_chunk]);
+        (Some(split[0]), split[1])
+    } else {
+        (None, chunks[list_chunk])
+    };
+
+    if let Some(parent_rect) = parent_rect {
+        draw_file_browser_parent_pane(f, app, parent_rect);
+    }
+
+    // A highlighted `.hex` file gets a preview pane of its memory map alongside the list; a
+    // highlighted directory (or nothing selected) leaves the list at full width.
+    let highlighted_hex_file = app
+        .file_browser_list_state
+        .selected()
+        .and_then(|pos| app.file_browser_real_index(pos))
+        .and_then(|idx| app.file_browser_entries.get(idx))
+        .filter(|entry| !entry.is_dir)
+        .cloned();
+
+    let (list_rect, preview_rect) = if highlighted_hex_file.is_some() {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(right_area);
+        (split[0], Some(split[1]))
+    } else {
+        (right_area, None)
+    };
+
+    let visible: Vec<&FileEntry> = match &app.file_browser_filter {
+        Some(_) => app
+            .file_browser_filtered_indices
+            .iter()
+            .filter_map(|&i| app.file_browser_entries.get(i))
+            .collect(),
+        None => app.file_browser_entries.iter().collect(),
+    };
+
+    let filter_query = app.file_browser_filter.clone().unwrap_or_default();
+    let items: Vec<ListItem> = visible
         .iter()
         .map(|entry| {
-            let display = if entry.is_dir {
-                format!("[DIR]  {}/", entry.name)
-            } else {
-                format!("[FILE] {}", entry.name)
-            };
+            let prefix = if entry.is_dir { "[DIR]  " } else { "[FILE] " };
             let style = if entry.is_dir {
                 Style::default().fg(Color::Blue)
             } else {
                 Style::default().fg(Color::Green)
             };
-            ListItem::new(display).style(style)
+
+            let mut spans = vec![Span::styled(prefix, style)];
+            let matched = fuzzy::highlight_positions(&filter_query, &entry.name);
+            for (pos, c) in entry.name.chars().enumerate() {
+                let char_style = if matched.contains(&pos) {
+                    style.fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                };
+                spans.push(Span::styled(c.to_string(), char_style));
+            }
+            if entry.is_dir {
+                spans.push(Span::styled("/", style));
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -2154,15 +5432,132 @@ fn draw_file_browser(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
+    app.file_browser_list_rect = Some(list_rect);
     let mut state = app.file_browser_list_state.clone();
-    f.render_stateful_widget(list, chunks[2], &mut state);
+    f.render_stateful_widget(list, list_rect, &mut state);
 
-    let help =
-        Paragraph::new("↑↓ to navigate | Enter to select/open | Esc to go back to manual entry")
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
-    f.render_widget(help, chunks[3]);
+    if let (Some(entry), Some(preview_rect)) = (&highlighted_hex_file, preview_rect) {
+        draw_hex_preview(f, app, &entry.path, preview_rect);
+    }
+
+    let help_text = if app.file_browser_filter.is_some() {
+        "Type to filter | ↑↓ to navigate | Enter to select/open | Esc to clear filter"
+    } else {
+        "↑↓/h l nav | Enter/→ open | ← up a level | '/' filter | '~' home | '`' root | 'b' bookmarks | 'm' bookmark dir | F5 refresh | Esc back"
+    };
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help, chunks[help_chunk]);
+
+    if app.file_browser_bookmark_popup {
+        draw_bookmark_popup(f, app, area);
+    }
+}
+
+/// Miller-column left pane: the parent directory's own entries (scanned fresh rather than read
+/// from `file_browser_dir_cache`, which only tracks directories actually visited), with whichever
+/// one matches `file_browser_current_dir` highlighted so the user can see where the right pane's
+/// listing sits among its siblings. Read-only — `↑↓`/Enter only ever act on the right pane.
+fn draw_file_browser_parent_pane(f: &mut Frame, app: &App, area: Rect) {
+    let parent_entries = scan_directory_entries(
+        app.file_browser_current_dir
+            .parent()
+            .expect("caller only renders this pane when a parent exists"),
+    );
+
+    let items: Vec<ListItem> = parent_entries
+        .iter()
+        .map(|entry| {
+            let display = if entry.is_dir {
+                format!("[DIR]  {}/", entry.name)
+            } else {
+                format!("[FILE] {}", entry.name)
+            };
+            let is_current = entry.path == app.file_browser_current_dir;
+            let style = match (entry.is_dir, is_current) {
+                (_, true) => Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                (true, false) => Style::default().fg(Color::Blue),
+                (false, false) => Style::default().fg(Color::Green),
+            };
+            ListItem::new(display).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().title("Parent").borders(Borders::ALL));
+    f.render_widget(list, area);
+}
+
+/// Popup opened with `b` on `FileBrowser`, listing `app.bookmarks` with the same
+/// highlight-symbol list style as `draw_file_browser`'s own entry list. Selecting one with Enter
+/// sets `file_browser_current_dir` and re-scans; see `handle_bookmark_popup`.
+fn draw_bookmark_popup(f: &mut Frame, app: &mut App, area: Rect) {
+    let popup_area = centered_rect(60, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = if app.bookmarks.is_empty() {
+        vec![ListItem::new("No bookmarks yet — press 'm' to add the current directory")]
+    } else {
+        app.bookmarks
+            .iter()
+            .map(|path| ListItem::new(path.to_string_lossy().into_owned()))
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Bookmarks ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().bg(Color::Black))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut state = app.bookmark_list_state.clone();
+    f.render_stateful_widget(list, popup_area, &mut state);
+}
+
+/// Renders the memory-map preview of `path` (a highlighted `.hex` entry) next to the
+/// `FileBrowser` list; see `App::hex_preview_for` for the mtime-keyed cache that keeps this
+/// responsive while scrolling.
+fn draw_hex_preview(f: &mut Frame, app: &mut App, path: &Path, area: Rect) {
+    let preview = app.hex_preview_for(path).clone();
+
+    let paragraph = match preview {
+        Ok(preview) => {
+            let mut lines = vec![format!("{} bytes", preview.total_bytes), String::new()];
+            for (start, end) in &preview.ranges {
+                lines.push(format!("[{:#010X}..{:#010X}]", start, end));
+            }
+            lines.push(String::new());
+            lines.push("checksum: OK".to_string());
+
+            Paragraph::new(lines.join("\n"))
+                .style(Style::default().fg(Color::Green))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().title("Preview").borders(Borders::ALL))
+        }
+        Err(err) => Paragraph::new(err.message)
+            .style(
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .wrap(Wrap { trim: true })
+            .block(Block::default().title("Preview").borders(Borders::ALL)),
+    };
+
+    f.render_widget(paragraph, area);
 }
 
 fn draw_executing(f: &mut Frame, app: &App, area: Rect) {
@@ -2242,6 +5637,55 @@ fn draw_executing(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(info, chunks[1]);
 }
 
+fn draw_log_view(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("Target Log")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let visible_rows = chunks[1].height.saturating_sub(2) as usize;
+    let end = app.log_lines.len().saturating_sub(app.log_scroll.min(app.log_lines.len()));
+    let start = end.saturating_sub(visible_rows);
+
+    let log_lines: Vec<Line> = if app.log_lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "Waiting for target output…",
+            Style::default().fg(Color::Yellow),
+        ))]
+    } else {
+        app.log_lines[start..end]
+            .iter()
+            .map(|line| Line::from(Span::raw(line.clone())))
+            .collect()
+    };
+
+    let log = Paragraph::new(log_lines)
+        .block(Block::default().title("RTT/Serial Output").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(log, chunks[1]);
+
+    let help = Paragraph::new("Up/Down: scroll | s: save log to file | Enter/Esc: continue")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(help, chunks[2]);
+}
+
 fn draw_results(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,145 @@
+//! Named-pipe control session for driving the TUI from an external test harness.
+//!
+//! `--session <dir>` creates four FIFOs under `dir`:
+//! - `msg_in`: one command per line (`flash <path>`, `select-node <id>`, `refresh`), read
+//!   non-blockingly by the main event loop and dispatched the same way the matching keypress
+//!   would be (`flash` skips the confirmation popup).
+//! - `progress_out`: every `ProgressUpdate`/`DeviceInfo` message, as the same JSON line
+//!   `--json` headless mode would print.
+//! - `result_out`: the terminal `Complete`/`Error`/`VerifyResult` of an operation, same format.
+//! - `devices_out`: one JSON line per device found by a search.
+//!
+//! A harness opens the other end of each FIFO itself; this module only creates and owns the
+//! TUI's end. Write errors (nothing reading yet, or the reader went away) are swallowed rather
+//! than surfaced, since a harness that isn't currently listening shouldn't be able to crash the
+//! interactive session it's driving.
+
+use std::ffi::CString;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+extern "C" {
+    fn mkfifo(path: *const std::os::raw::c_char, mode: u32) -> i32;
+}
+
+/// Owner-only FIFOs; a harness running as the same user is the only expected reader/writer.
+const FIFO_MODE: u32 = 0o600;
+
+/// Not exposed by `std::os::unix::fs::OpenOptionsExt`, so the raw Linux/BSD flag value is used
+/// directly, the same way `mkfifo` itself is declared by hand rather than pulling in `libc` for
+/// two constants.
+const O_NONBLOCK: i32 = 0o4000;
+
+/// Creates `path` as a FIFO if it doesn't already exist (e.g. left over from a previous run).
+fn create_fifo(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+
+    let c_path = CString::new(path.to_string_lossy().as_bytes())
+        .map_err(|e| format!("Invalid session path {:?}: {}", path, e))?;
+
+    let result = unsafe { mkfifo(c_path.as_ptr(), FIFO_MODE) };
+    if result != 0 {
+        return Err(format!(
+            "Failed to create FIFO {:?}: {}",
+            path,
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    Ok(())
+}
+
+pub struct SessionPipes {
+    msg_in: BufReader<File>,
+    progress_out: File,
+    result_out: File,
+    devices_out: File,
+}
+
+impl SessionPipes {
+    /// Creates the session directory and its four FIFOs (if missing), then opens all of them.
+    ///
+    /// `msg_in` is opened `O_NONBLOCK` so `read_commands` never stalls the UI waiting for a
+    /// harness to write. The `*_out` pipes are opened read-write rather than write-only, so
+    /// creating the session doesn't itself block waiting for a reader to attach — a harness may
+    /// not start reading until after issuing its first command.
+    pub fn create(dir: &Path) -> Result<SessionPipes, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create session dir {:?}: {}", dir, e))?;
+
+        let msg_in_path = dir.join("msg_in");
+        let progress_out_path = dir.join("progress_out");
+        let result_out_path = dir.join("result_out");
+        let devices_out_path = dir.join("devices_out");
+
+        for path in [&msg_in_path, &progress_out_path, &result_out_path, &devices_out_path] {
+            create_fifo(path)?;
+        }
+
+        let msg_in = OpenOptions::new()
+            .read(true)
+            .custom_flags(O_NONBLOCK)
+            .open(&msg_in_path)
+            .map_err(|e| format!("Failed to open {:?}: {}", msg_in_path, e))?;
+
+        let progress_out = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&progress_out_path)
+            .map_err(|e| format!("Failed to open {:?}: {}", progress_out_path, e))?;
+        let result_out = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&result_out_path)
+            .map_err(|e| format!("Failed to open {:?}: {}", result_out_path, e))?;
+        let devices_out = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&devices_out_path)
+            .map_err(|e| format!("Failed to open {:?}: {}", devices_out_path, e))?;
+
+        Ok(SessionPipes {
+            msg_in: BufReader::new(msg_in),
+            progress_out,
+            result_out,
+            devices_out,
+        })
+    }
+
+    pub fn write_progress(&mut self, line: &str) {
+        writeln!(self.progress_out, "{}", line).ok();
+    }
+
+    pub fn write_result(&mut self, line: &str) {
+        writeln!(self.result_out, "{}", line).ok();
+    }
+
+    pub fn write_device(&mut self, line: &str) {
+        writeln!(self.devices_out, "{}", line).ok();
+    }
+
+    /// Drains every complete line currently buffered on `msg_in`. Returns immediately (possibly
+    /// with nothing) rather than blocking, since `msg_in` was opened `O_NONBLOCK`.
+    pub fn read_commands(&mut self) -> Vec<String> {
+        let mut commands = Vec::new();
+        loop {
+            let mut line = String::new();
+            match self.msg_in.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    let trimmed = line.trim();
+                    if !trimmed.is_empty() {
+                        commands.push(trimmed.to_string());
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(_) => break,
+            }
+        }
+        commands
+    }
+}